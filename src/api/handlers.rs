@@ -1,49 +1,161 @@
 //! HTTP API handlers.
 
-use axum::{extract::State, http::StatusCode, response::IntoResponse, Json};
+use axum::{
+    extract::{Query, State},
+    http::{header, HeaderMap, StatusCode},
+    response::IntoResponse,
+    Json,
+};
 use rust_decimal::Decimal;
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::sync::Arc;
 
-use crate::arbitrage::ExecutorStats;
+use crate::arbitrage::{summarize_history, ExecutorStats, MarketResult};
+use crate::config::RuntimeParams;
+use crate::orderbook::websocket::WsStatus;
+
+/// State of one market being traded.
+#[derive(Debug, Clone, Default)]
+pub struct MarketState {
+    /// Stats of the executor trading it.
+    pub stats: ExecutorStats,
+    /// Whether one of its books appears frozen.
+    pub book_frozen: bool,
+}
 
 /// Application state shared with handlers.
 #[derive(Debug, Clone)]
 pub struct AppState {
-    /// Whether the bot is ready to trade.
-    pub ready: Arc<std::sync::atomic::AtomicBool>,
-    /// Current market slug.
-    pub market_slug: Arc<tokio::sync::RwLock<Option<String>>>,
     /// Executor stats.
     pub stats: Arc<tokio::sync::RwLock<ExecutorStats>>,
+    /// Set when a circuit breaker reset has been requested.
+    pub resume_requested: Arc<std::sync::atomic::AtomicBool>,
+    /// Closed-market results, oldest first.
+    pub history: Arc<tokio::sync::RwLock<Vec<MarketResult>>>,
+    /// Local minus CLOB server clock in seconds, once measured.
+    pub clock_skew_seconds: Arc<tokio::sync::RwLock<Option<i64>>>,
+    /// Latest market WebSocket snapshot, when running on the WebSocket feed.
+    pub websocket: Arc<tokio::sync::RwLock<Option<WsStatus>>>,
+    /// Markets being traded, by slug; readiness and frozen books derive from these.
+    pub markets: Arc<tokio::sync::RwLock<BTreeMap<String, MarketState>>>,
+    /// Trading thresholds, read by the scan loops before each check.
+    pub params: Arc<tokio::sync::RwLock<RuntimeParams>>,
+    /// Bearer token for changing the thresholds (changes disabled when unset).
+    pub params_token: Option<Arc<str>>,
 }
 
 impl AppState {
     /// Create new app state.
     pub fn new() -> Self {
         Self {
-            ready: Arc::new(std::sync::atomic::AtomicBool::new(false)),
-            market_slug: Arc::new(tokio::sync::RwLock::new(None)),
-            stats: Arc::new(tokio::sync::RwLock::new(ExecutorStats {
-                trades_executed: 0,
-                opportunities_found: 0,
-                total_invested: Decimal::ZERO,
-                total_shares_bought: Decimal::ZERO,
-                sim_balance: Decimal::ZERO,
-                sim_start_balance: Decimal::ZERO,
-            })),
+            stats: Arc::new(tokio::sync::RwLock::new(ExecutorStats::default())),
+            resume_requested: Arc::new(std::sync::atomic::AtomicBool::new(false)),
+            history: Arc::new(tokio::sync::RwLock::new(Vec::new())),
+            clock_skew_seconds: Arc::new(tokio::sync::RwLock::new(None)),
+            websocket: Arc::new(tokio::sync::RwLock::new(None)),
+            markets: Arc::new(tokio::sync::RwLock::new(BTreeMap::new())),
+            params: Arc::new(tokio::sync::RwLock::new(RuntimeParams::default())),
+            params_token: None,
+        }
+    }
+
+    /// Allow `POST /api/v1/params` for requests bearing `token`.
+    pub fn with_params_token(mut self, token: Option<String>) -> Self {
+        self.params_token = token.filter(|t| !t.is_empty()).map(Arc::from);
+        self
+    }
+
+    /// Whether any market is being traded.
+    pub async fn is_ready(&self) -> bool {
+        !self.markets.read().await.is_empty()
+    }
+
+    /// Slug of the earliest market being traded.
+    pub async fn market_slug(&self) -> Option<String> {
+        self.markets.read().await.keys().next().cloned()
+    }
+
+    /// Set whether a book of the market `slug` appears frozen.
+    pub async fn set_book_frozen(&self, slug: &str, frozen: bool) {
+        if let Some(entry) = self.markets.write().await.get_mut(slug) {
+            entry.book_frozen = frozen;
+        }
+    }
+
+    /// Whether a book of any market being traded appears frozen.
+    pub async fn is_book_frozen(&self) -> bool {
+        self.markets.read().await.values().any(|market| market.book_frozen)
+    }
+
+    /// Merge an executor's closed-market results into the shared history.
+    ///
+    /// Concurrent market tasks each keep their own history, so results replace
+    /// the entry for the same market (its profit changes once it settles) or
+    /// are appended, keeping the most recent `limit`.
+    pub async fn merge_history(&self, results: &[MarketResult], limit: usize) {
+        let mut history = self.history.write().await;
+        for result in results {
+            match history.iter_mut().find(|r| r.slug == result.slug) {
+                Some(entry) => *entry = result.clone(),
+                None => history.push(result.clone()),
+            }
         }
+        let excess = history.len().saturating_sub(limit);
+        history.drain(..excess);
     }
 
-    /// Set ready state.
-    pub fn set_ready(&self, ready: bool) {
-        self.ready
-            .store(ready, std::sync::atomic::Ordering::SeqCst);
+    /// Request a circuit breaker reset.
+    pub fn request_resume(&self) {
+        self.resume_requested
+            .store(true, std::sync::atomic::Ordering::SeqCst);
     }
 
-    /// Check if ready.
-    pub fn is_ready(&self) -> bool {
-        self.ready.load(std::sync::atomic::Ordering::SeqCst)
+    /// Take a pending resume request, clearing it.
+    pub fn take_resume_request(&self) -> bool {
+        self.resume_requested
+            .swap(false, std::sync::atomic::Ordering::SeqCst)
+    }
+
+    /// Mark a market as traded; false if another task is already trading it.
+    pub async fn claim_market(&self, slug: &str) -> bool {
+        let mut markets = self.markets.write().await;
+        if markets.contains_key(slug) {
+            return false;
+        }
+        markets.insert(slug.to_string(), MarketState::default());
+        true
+    }
+
+    /// Publish the stats of the executor trading `slug`.
+    pub async fn update_market_stats(&self, slug: &str, stats: ExecutorStats) {
+        if let Some(entry) = self.markets.write().await.get_mut(slug) {
+            entry.stats = stats.clone();
+        }
+        *self.stats.write().await = stats;
+    }
+
+    /// Stop reporting a market once it has closed.
+    pub async fn release_market(&self, slug: &str) {
+        self.markets.write().await.remove(slug);
+    }
+
+    /// Why the bot can't trade right now; `None` when it is ready.
+    ///
+    /// Between windows takes precedence: a feed left over from the last
+    /// market or a tripped breaker doesn't matter until a market is open.
+    pub async fn not_ready_reason(&self) -> Option<NotReadyReason> {
+        if !self.is_ready().await {
+            return Some(NotReadyReason::BetweenWindows);
+        }
+        if matches!(*self.websocket.read().await, Some(ws) if !ws.connected) {
+            return Some(NotReadyReason::NoConnection);
+        }
+        let markets = self.markets.read().await;
+        if markets.values().any(|m| m.stats.circuit_open || m.book_frozen) {
+            return Some(NotReadyReason::Paused);
+        }
+        None
     }
 }
 
@@ -60,13 +172,36 @@ pub struct HealthResponse {
     pub status: &'static str,
 }
 
+/// Why the bot is not ready to trade.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NotReadyReason {
+    /// No market is open; normal between 15-minute windows.
+    BetweenWindows,
+    /// The market WebSocket feed is disconnected.
+    NoConnection,
+    /// New entries are paused by the circuit breaker or a frozen book.
+    Paused,
+}
+
 /// Readiness check response.
 #[derive(Debug, Serialize)]
 pub struct ReadyResponse {
     /// Whether service is ready.
     pub ready: bool,
-    /// Current market slug if available.
+    /// Earliest market being traded, if any.
     pub market: Option<String>,
+    /// Why the bot is not ready (null when ready).
+    pub reason: Option<NotReadyReason>,
+}
+
+/// Liveness check response.
+#[derive(Debug, Serialize)]
+pub struct LiveResponse {
+    /// Status: "ok" or "unhealthy".
+    pub status: &'static str,
+    /// Why the bot is not ready, which is only unhealthy for `no_connection`.
+    pub reason: Option<NotReadyReason>,
 }
 
 /// Status response.
@@ -74,12 +209,60 @@ pub struct ReadyResponse {
 pub struct StatusResponse {
     /// Service status.
     pub status: &'static str,
-    /// Current market slug.
+    /// Earliest market being traded, if any.
     pub market: Option<String>,
+    /// Local minus CLOB server clock in seconds, if measured.
+    pub clock_skew_seconds: Option<i64>,
+    /// Market WebSocket health, absent in polling mode.
+    pub websocket: Option<WebSocketResponse>,
+    /// Whether new entries are paused because a book's mid has stopped moving in any market.
+    pub book_frozen: bool,
+    /// Markets being traded, each with its executor's statistics.
+    pub markets: Vec<ActiveMarketResponse>,
+    /// Trading thresholds in effect.
+    pub params: ParamsResponse,
     /// Statistics.
     pub stats: StatsResponse,
 }
 
+/// Market being traded in status response.
+#[derive(Debug, Serialize)]
+pub struct ActiveMarketResponse {
+    /// Market slug.
+    pub market: String,
+    /// Whether one of its books appears frozen.
+    pub book_frozen: bool,
+    /// Statistics of the executor trading it.
+    pub stats: StatsResponse,
+}
+
+/// Market WebSocket health in status response.
+#[derive(Debug, Serialize)]
+pub struct WebSocketResponse {
+    /// Connection state: disconnected, connecting, connected, or reconnecting.
+    pub state: &'static str,
+    /// Whether currently connected.
+    pub connected: bool,
+    /// Reconnection attempts so far.
+    pub reconnect_attempts: u64,
+    /// Whether the feed appears stale.
+    pub stale: bool,
+    /// Milliseconds since the last message, if any has arrived.
+    pub last_message_age_ms: Option<u64>,
+}
+
+impl From<WsStatus> for WebSocketResponse {
+    fn from(status: WsStatus) -> Self {
+        Self {
+            state: status.state.as_str(),
+            connected: status.connected,
+            reconnect_attempts: status.reconnect_attempts,
+            stale: status.stale,
+            last_message_age_ms: status.last_message_age.map(|age| age.as_millis() as u64),
+        }
+    }
+}
+
 /// Statistics in status response.
 #[derive(Debug, Serialize)]
 pub struct StatsResponse {
@@ -91,6 +274,128 @@ pub struct StatsResponse {
     pub total_invested: String,
     /// Total shares bought.
     pub total_shares_bought: String,
+    /// Trading fees paid.
+    pub total_fees: String,
+    /// Gas paid for redemptions, in POL.
+    pub total_gas: String,
+    /// Redemption gas in USDC at `POL_PRICE_USDC`, as netted from profit.
+    pub gas_usdc: String,
+    /// Realized profit from settled markets, if any have settled.
+    pub realized_profit: Option<String>,
+    /// Whether the circuit breaker is open.
+    pub circuit_open: bool,
+    /// Rolling fraction of recent legs that filled this market, once any were submitted.
+    pub fill_rate: Option<String>,
+    /// Settled markets whose redemption is queued, in flight, or being retried.
+    pub pending_redemptions: usize,
+    /// Settled markets left for manual redemption: unsupported, or out of attempts.
+    pub failed_redemptions: u64,
+}
+
+impl From<&ExecutorStats> for StatsResponse {
+    fn from(stats: &ExecutorStats) -> Self {
+        Self {
+            trades_executed: stats.trades_executed,
+            opportunities_found: stats.opportunities_found,
+            total_invested: stats.total_invested.to_string(),
+            total_shares_bought: stats.total_shares_bought.to_string(),
+            total_fees: stats.total_fees.to_string(),
+            total_gas: stats.total_gas.to_string(),
+            gas_usdc: stats.gas_usdc.to_string(),
+            realized_profit: stats.realized_profit.map(|p| p.to_string()),
+            circuit_open: stats.circuit_open,
+            fill_rate: stats.fill_rate.map(|r| r.normalize().to_string()),
+            pending_redemptions: stats.pending_redemptions,
+            failed_redemptions: stats.failed_redemptions,
+        }
+    }
+}
+
+/// Resume response.
+#[derive(Debug, Serialize)]
+pub struct ResumeResponse {
+    /// Status: "resume_requested".
+    pub status: &'static str,
+}
+
+/// Params request; unset fields keep their current value.
+#[derive(Debug, Deserialize)]
+pub struct ParamsRequest {
+    /// New maximum combined cost to trigger arbitrage.
+    pub target_pair_cost: Option<Decimal>,
+    /// New number of shares per trade.
+    pub order_size: Option<Decimal>,
+}
+
+/// Trading thresholds in params and status responses.
+#[derive(Debug, Serialize)]
+pub struct ParamsResponse {
+    /// Maximum combined cost to trigger arbitrage.
+    pub target_pair_cost: String,
+    /// Number of shares per trade.
+    pub order_size: String,
+}
+
+impl From<&RuntimeParams> for ParamsResponse {
+    fn from(params: &RuntimeParams) -> Self {
+        Self {
+            target_pair_cost: params.target_pair_cost.to_string(),
+            order_size: params.order_size.to_string(),
+        }
+    }
+}
+
+/// Error response.
+#[derive(Debug, Serialize)]
+pub struct ErrorResponse {
+    /// What was wrong with the request.
+    pub error: String,
+}
+
+/// History query parameters.
+#[derive(Debug, Deserialize)]
+pub struct HistoryQuery {
+    /// Number of most recent markets to return (all retained if unset).
+    pub limit: Option<usize>,
+}
+
+/// History response.
+#[derive(Debug, Serialize)]
+pub struct HistoryResponse {
+    /// Per-market results, oldest first.
+    pub markets: Vec<MarketResultResponse>,
+    /// Aggregates over the returned markets.
+    pub summary: HistorySummaryResponse,
+}
+
+/// Single market result in history response.
+#[derive(Debug, Serialize)]
+pub struct MarketResultResponse {
+    /// Market slug.
+    pub slug: String,
+    /// Amount invested.
+    pub invested: String,
+    /// Profit (realized if settled, otherwise expected).
+    pub profit: String,
+    /// Trades executed.
+    pub trades: u64,
+}
+
+/// Aggregates in history response.
+#[derive(Debug, Serialize)]
+pub struct HistorySummaryResponse {
+    /// Markets summarized.
+    pub markets: usize,
+    /// Markets with at least one trade.
+    pub traded_markets: usize,
+    /// Total invested.
+    pub total_invested: String,
+    /// Total profit.
+    pub total_profit: String,
+    /// Total profit over total invested.
+    pub roi: Option<String>,
+    /// Fraction of traded markets that were net-positive.
+    pub win_rate: Option<String>,
 }
 
 /// Health check handler - always returns 200.
@@ -98,55 +403,212 @@ pub async fn health() -> impl IntoResponse {
     Json(HealthResponse { status: "ok" })
 }
 
-/// Readiness check handler - returns 200 if ready, 503 otherwise.
+/// Readiness check handler - returns 200 if ready to trade, 503 with a reason otherwise.
 pub async fn ready(State(state): State<AppState>) -> impl IntoResponse {
-    let is_ready = state.is_ready();
-    let market = state.market_slug.read().await.clone();
+    let reason = state.not_ready_reason().await;
+    let market = state.market_slug().await;
 
     let response = ReadyResponse {
-        ready: is_ready,
+        ready: reason.is_none(),
         market,
+        reason,
     };
 
-    if is_ready {
+    if reason.is_none() {
         (StatusCode::OK, Json(response))
     } else {
         (StatusCode::SERVICE_UNAVAILABLE, Json(response))
     }
 }
 
+/// Liveness check handler - returns 503 only when the bot is broken.
+///
+/// Waiting between windows or paused entries are normal operation; a lost
+/// market feed is not.
+pub async fn live(State(state): State<AppState>) -> impl IntoResponse {
+    let reason = state.not_ready_reason().await;
+
+    if reason == Some(NotReadyReason::NoConnection) {
+        let response = LiveResponse { status: "unhealthy", reason };
+        (StatusCode::SERVICE_UNAVAILABLE, Json(response))
+    } else {
+        (StatusCode::OK, Json(LiveResponse { status: "ok", reason }))
+    }
+}
+
 /// Status handler - returns bot status and statistics.
 pub async fn status(State(state): State<AppState>) -> impl IntoResponse {
-    let market = state.market_slug.read().await.clone();
+    let market = state.market_slug().await;
+    let status = if state.is_ready().await { "running" } else { "starting" };
+    let book_frozen = state.is_book_frozen().await;
     let stats = state.stats.read().await;
-
-    let status = if state.is_ready() { "running" } else { "starting" };
+    let markets = state.markets.read().await;
 
     Json(StatusResponse {
         status,
         market,
-        stats: StatsResponse {
-            trades_executed: stats.trades_executed,
-            opportunities_found: stats.opportunities_found,
-            total_invested: stats.total_invested.to_string(),
-            total_shares_bought: stats.total_shares_bought.to_string(),
+        clock_skew_seconds: *state.clock_skew_seconds.read().await,
+        websocket: state.websocket.read().await.map(WebSocketResponse::from),
+        book_frozen,
+        markets: markets
+            .iter()
+            .map(|(slug, market)| ActiveMarketResponse {
+                market: slug.clone(),
+                book_frozen: market.book_frozen,
+                stats: StatsResponse::from(&market.stats),
+            })
+            .collect(),
+        params: ParamsResponse::from(&*state.params.read().await),
+        stats: StatsResponse::from(&*stats),
+    })
+}
+
+/// History handler - returns recent per-market results with aggregate ROI and win rate.
+pub async fn history(
+    State(state): State<AppState>,
+    Query(query): Query<HistoryQuery>,
+) -> impl IntoResponse {
+    let history = state.history.read().await;
+    let start = query
+        .limit
+        .map_or(0, |limit| history.len().saturating_sub(limit));
+    let recent = &history[start..];
+    let summary = summarize_history(recent);
+
+    Json(HistoryResponse {
+        markets: recent
+            .iter()
+            .map(|r| MarketResultResponse {
+                slug: r.slug.clone(),
+                invested: r.invested.to_string(),
+                profit: r.profit.to_string(),
+                trades: r.trades,
+            })
+            .collect(),
+        summary: HistorySummaryResponse {
+            markets: summary.markets,
+            traded_markets: summary.traded_markets,
+            total_invested: summary.total_invested.to_string(),
+            total_profit: summary.total_profit.to_string(),
+            roi: summary.roi.map(|r| r.round_dp(6).normalize().to_string()),
+            win_rate: summary.win_rate.map(|w| w.round_dp(4).normalize().to_string()),
         },
     })
 }
 
+/// Resume handler - requests a circuit breaker reset, applied before the next execution.
+pub async fn resume(State(state): State<AppState>) -> impl IntoResponse {
+    state.request_resume();
+    (
+        StatusCode::ACCEPTED,
+        Json(ResumeResponse {
+            status: "resume_requested",
+        }),
+    )
+}
+
+/// Params handler - validates and applies new trading thresholds from the next scan on.
+///
+/// Requires `Authorization: Bearer <PARAMS_API_TOKEN>`; without a configured
+/// token every request is refused.
+pub async fn params(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Json(request): Json<ParamsRequest>,
+) -> impl IntoResponse {
+    let Some(token) = state.params_token.as_deref() else {
+        let error = "runtime params are disabled; set PARAMS_API_TOKEN to enable".to_string();
+        return (StatusCode::FORBIDDEN, Json(ErrorResponse { error })).into_response();
+    };
+    let bearer = headers
+        .get(header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "));
+    if bearer != Some(token) {
+        let error = "missing or invalid bearer token".to_string();
+        return (StatusCode::UNAUTHORIZED, Json(ErrorResponse { error })).into_response();
+    }
+
+    let mut params = state.params.write().await;
+    let mut updated = *params;
+    if let Some(target_pair_cost) = request.target_pair_cost {
+        updated.target_pair_cost = target_pair_cost;
+    }
+    if let Some(order_size) = request.order_size {
+        updated.order_size = order_size;
+    }
+
+    if let Err(error) = updated.validate() {
+        return (StatusCode::BAD_REQUEST, Json(ErrorResponse { error })).into_response();
+    }
+
+    *params = updated;
+    tracing::info!(
+        target_pair_cost = %updated.target_pair_cost,
+        order_size = %updated.order_size,
+        "Trading params updated"
+    );
+    (StatusCode::OK, Json(ParamsResponse::from(&updated))).into_response()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    #[test]
-    fn app_state_ready_toggle() {
+    #[tokio::test]
+    async fn app_state_ready_while_any_market_is_traded() {
         let state = AppState::new();
-        assert!(!state.is_ready());
+        assert!(!state.is_ready().await);
+
+        state.claim_market("btc-updown-15m-1765302300").await;
+        state.claim_market("btc-updown-15m-1765301400").await;
+        assert!(state.is_ready().await);
+        assert_eq!(state.market_slug().await.as_deref(), Some("btc-updown-15m-1765301400"));
+
+        // One market closing leaves the other's state untouched
+        state.set_book_frozen("btc-updown-15m-1765302300", true).await;
+        state.release_market("btc-updown-15m-1765301400").await;
+        assert!(state.is_ready().await);
+        assert!(state.is_book_frozen().await);
+
+        state.release_market("btc-updown-15m-1765302300").await;
+        assert!(!state.is_ready().await);
+        assert!(!state.is_book_frozen().await);
+    }
+
+    #[tokio::test]
+    async fn merged_history_keeps_every_task_and_updates_settled_markets() {
+        use rust_decimal_macros::dec;
 
-        state.set_ready(true);
-        assert!(state.is_ready());
+        let result = |slug: &str, profit| MarketResult {
+            slug: slug.to_string(),
+            invested: dec!(10),
+            profit,
+            trades: 1,
+        };
+        let state = AppState::new();
+        state.merge_history(&[result("a1", dec!(0.2))], 3).await;
+        state.merge_history(&[result("b1", dec!(0.1))], 3).await;
+        // Task A's market settled below its expected profit, then A closed another
+        state.merge_history(&[result("a1", dec!(-0.5)), result("a2", dec!(0.3))], 3).await;
+
+        let history = state.history.read().await.clone();
+        assert_eq!(
+            history,
+            vec![result("a1", dec!(-0.5)), result("b1", dec!(0.1)), result("a2", dec!(0.3))]
+        );
+
+        state.merge_history(&[result("b2", dec!(0.1))], 3).await;
+        assert_eq!(state.history.read().await[0].slug, "b1");
+    }
+
+    #[test]
+    fn app_state_resume_request_is_taken_once() {
+        let state = AppState::new();
+        assert!(!state.take_resume_request());
 
-        state.set_ready(false);
-        assert!(!state.is_ready());
+        state.request_resume();
+        assert!(state.take_resume_request());
+        assert!(!state.take_resume_request());
     }
 }
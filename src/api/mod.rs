@@ -3,5 +3,5 @@
 pub mod handlers;
 pub mod routes;
 
-pub use handlers::AppState;
+pub use handlers::{AppState, MarketState};
 pub use routes::create_router;
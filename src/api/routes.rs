@@ -1,8 +1,11 @@
 //! HTTP API route definitions.
 
-use axum::{routing::get, Router};
+use axum::{
+    routing::{get, post},
+    Router,
+};
 
-use super::handlers::{health, ready, status, AppState};
+use super::handlers::{health, history, live, params, ready, resume, status, AppState};
 
 /// Create the API router.
 pub fn create_router(state: AppState) -> Router {
@@ -10,8 +13,15 @@ pub fn create_router(state: AppState) -> Router {
         // Health endpoints
         .route("/health", get(health))
         .route("/ready", get(ready))
+        .route("/live", get(live))
         // Status endpoint
         .route("/api/v1/status", get(status))
+        // Per-market history
+        .route("/api/v1/history", get(history))
+        // Circuit breaker reset
+        .route("/api/v1/resume", post(resume))
+        // Runtime trading thresholds
+        .route("/api/v1/params", post(params))
         // TODO: Add metrics endpoint
         // .route("/metrics", get(metrics))
         .with_state(state)
@@ -58,7 +68,7 @@ mod tests {
     #[tokio::test]
     async fn ready_endpoint_returns_200_when_ready() {
         let state = AppState::new();
-        state.set_ready(true);
+        state.claim_market("btc-updown-15m-1765301400").await;
         let app = create_router(state);
 
         let response = app
@@ -67,5 +77,337 @@ mod tests {
             .unwrap();
 
         assert_eq!(response.status(), StatusCode::OK);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert!(json["reason"].is_null());
+    }
+
+    /// GET `uri` and return the status with the JSON body.
+    async fn get_json(state: &AppState, uri: &str) -> (StatusCode, serde_json::Value) {
+        let response = create_router(state.clone())
+            .oneshot(Request::builder().uri(uri).body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        let status = response.status();
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        (status, serde_json::from_slice(&body).unwrap())
+    }
+
+    #[tokio::test]
+    async fn between_windows_is_not_ready_but_live() {
+        let state = AppState::new();
+
+        let (status, json) = get_json(&state, "/ready").await;
+        assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(json["reason"], "between_windows");
+
+        let (status, json) = get_json(&state, "/live").await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(json["status"], "ok");
+        assert_eq!(json["reason"], "between_windows");
+    }
+
+    #[tokio::test]
+    async fn lost_feed_is_neither_ready_nor_live() {
+        use crate::orderbook::websocket::{ConnectionState, WsStatus};
+
+        let state = AppState::new();
+        state.claim_market("btc-updown-15m-1765301400").await;
+        *state.websocket.write().await = Some(WsStatus {
+            state: ConnectionState::Reconnecting,
+            connected: false,
+            reconnect_attempts: 2,
+            stale: false,
+            last_message_age: None,
+        });
+
+        let (status, json) = get_json(&state, "/ready").await;
+        assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(json["reason"], "no_connection");
+
+        let (status, json) = get_json(&state, "/live").await;
+        assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(json["status"], "unhealthy");
+    }
+
+    #[tokio::test]
+    async fn paused_entries_are_not_ready_but_live() {
+        use crate::arbitrage::ExecutorStats;
+
+        let state = AppState::new();
+        state.claim_market("btc-updown-15m-1765301400").await;
+        let stats = ExecutorStats {
+            circuit_open: true,
+            ..Default::default()
+        };
+        state.update_market_stats("btc-updown-15m-1765301400", stats).await;
+
+        let (status, json) = get_json(&state, "/ready").await;
+        assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(json["reason"], "paused");
+        assert_eq!(get_json(&state, "/live").await.0, StatusCode::OK);
+
+        // A frozen book pauses entries too
+        state.update_market_stats("btc-updown-15m-1765301400", ExecutorStats::default()).await;
+        assert_eq!(get_json(&state, "/ready").await.0, StatusCode::OK);
+        state.set_book_frozen("btc-updown-15m-1765301400", true).await;
+        assert_eq!(get_json(&state, "/ready").await.1["reason"], "paused");
+    }
+
+    #[tokio::test]
+    async fn resume_endpoint_requests_resume() {
+        let state = AppState::new();
+        let app = create_router(state.clone());
+
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .method("POST")
+                    .uri("/api/v1/resume")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::ACCEPTED);
+        assert!(state.take_resume_request());
+    }
+
+    #[tokio::test]
+    async fn history_endpoint_returns_last_n_with_summary() {
+        use crate::arbitrage::MarketResult;
+        use rust_decimal_macros::dec;
+
+        let state = AppState::new();
+        *state.history.write().await = vec![
+            MarketResult {
+                slug: "m1".to_string(),
+                invested: dec!(10),
+                profit: dec!(-1),
+                trades: 1,
+            },
+            MarketResult {
+                slug: "m2".to_string(),
+                invested: dec!(10),
+                profit: dec!(0.5),
+                trades: 1,
+            },
+            MarketResult {
+                slug: "m3".to_string(),
+                invested: dec!(10),
+                profit: dec!(0.5),
+                trades: 1,
+            },
+        ];
+        let app = create_router(state);
+
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .uri("/api/v1/history?limit=2")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(json["markets"].as_array().unwrap().len(), 2);
+        assert_eq!(json["markets"][0]["slug"], "m2");
+        assert_eq!(json["summary"]["total_profit"], "1.0");
+        assert_eq!(json["summary"]["roi"], "0.05");
+        assert_eq!(json["summary"]["win_rate"], "1");
+    }
+
+    #[tokio::test]
+    async fn status_endpoint_renders_websocket_state() {
+        use crate::orderbook::websocket::{ConnectionState, WsStatus};
+        use std::time::Duration;
+
+        let state = AppState::new();
+        *state.websocket.write().await = Some(WsStatus {
+            state: ConnectionState::Reconnecting,
+            connected: false,
+            reconnect_attempts: 4,
+            stale: true,
+            last_message_age: Some(Duration::from_millis(12_500)),
+        });
+        state.claim_market("btc-updown-15m-1765301400").await;
+        state.set_book_frozen("btc-updown-15m-1765301400", true).await;
+        let app = create_router(state);
+
+        let response = app
+            .oneshot(Request::builder().uri("/api/v1/status").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        let websocket = &json["websocket"];
+        assert_eq!(websocket["state"], "reconnecting");
+        assert_eq!(websocket["connected"], false);
+        assert_eq!(websocket["reconnect_attempts"], 4);
+        assert_eq!(websocket["stale"], true);
+        assert_eq!(websocket["last_message_age_ms"], 12_500);
+        assert_eq!(json["book_frozen"], true);
+        assert_eq!(json["markets"][0]["book_frozen"], true);
+    }
+
+    #[tokio::test]
+    async fn status_endpoint_omits_websocket_in_polling_mode() {
+        let app = create_router(AppState::new());
+
+        let response = app
+            .oneshot(Request::builder().uri("/api/v1/status").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert!(json["websocket"].is_null());
+        assert_eq!(json["book_frozen"], false);
+    }
+
+    #[tokio::test]
+    async fn status_endpoint_lists_active_markets() {
+        use crate::arbitrage::ExecutorStats;
+
+        let state = AppState::new();
+        assert!(state.claim_market("btc-updown-15m-1765301400").await);
+        assert!(state.claim_market("btc-updown-15m-1765302300").await);
+        assert!(!state.claim_market("btc-updown-15m-1765302300").await);
+        let stats = ExecutorStats {
+            trades_executed: 2,
+            ..Default::default()
+        };
+        state.update_market_stats("btc-updown-15m-1765302300", stats).await;
+
+        let (_, json) = get_json(&state, "/api/v1/status").await;
+        let markets = json["markets"].as_array().unwrap();
+        assert_eq!(markets.len(), 2);
+        assert_eq!(markets[0]["market"], "btc-updown-15m-1765301400");
+        assert_eq!(markets[0]["stats"]["trades_executed"], 0);
+        assert_eq!(markets[1]["stats"]["trades_executed"], 2);
+
+        state.release_market("btc-updown-15m-1765301400").await;
+        assert!(state.claim_market("btc-updown-15m-1765301400").await);
+    }
+
+    /// Token the params tests enable the endpoint with.
+    const PARAMS_TOKEN: &str = "test-token";
+
+    fn post_params_with_token(body: &str, token: &str) -> Request<Body> {
+        Request::builder()
+            .method("POST")
+            .uri("/api/v1/params")
+            .header("content-type", "application/json")
+            .header("authorization", format!("Bearer {}", token))
+            .body(Body::from(body.to_string()))
+            .unwrap()
+    }
+
+    fn post_params(body: &str) -> Request<Body> {
+        post_params_with_token(body, PARAMS_TOKEN)
+    }
+
+    /// App state accepting params changes with [`PARAMS_TOKEN`].
+    fn params_state() -> AppState {
+        AppState::new().with_params_token(Some(PARAMS_TOKEN.to_string()))
+    }
+
+    #[tokio::test]
+    async fn params_endpoint_updates_status() {
+        let state = params_state();
+        let app = create_router(state.clone());
+
+        let response = app
+            .clone()
+            .oneshot(post_params(r#"{"target_pair_cost": "0.97"}"#))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let response = app
+            .oneshot(Request::builder().uri("/api/v1/status").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(json["params"]["target_pair_cost"], "0.97");
+        // Unset fields keep their value
+        assert_eq!(json["params"]["order_size"], "5");
+    }
+
+    #[tokio::test]
+    async fn params_endpoint_rejects_invalid_values() {
+        use rust_decimal_macros::dec;
+
+        let state = params_state();
+        state.params.write().await.fee_rate_bps = dec!(200);
+        let app = create_router(state.clone());
+
+        for body in [
+            r#"{"order_size": "4"}"#,
+            r#"{"target_pair_cost": "1.0"}"#,
+            r#"{"target_pair_cost": "0.985"}"#,
+            r#"{"target_pair_cost": "0"}"#,
+            r#"{"target_pair_cost": "-0.5"}"#,
+        ] {
+            let response = app.clone().oneshot(post_params(body)).await.unwrap();
+            assert_eq!(response.status(), StatusCode::BAD_REQUEST, "{}", body);
+        }
+
+        let params = *state.params.read().await;
+        assert_eq!(params.target_pair_cost, dec!(0.991));
+        assert_eq!(params.order_size, dec!(5));
+    }
+
+    #[tokio::test]
+    async fn params_endpoint_rejects_order_size_above_max() {
+        use rust_decimal_macros::dec;
+
+        let state = params_state();
+        state.params.write().await.max_order_size = dec!(50);
+        let app = create_router(state.clone());
+
+        let response = app.clone().oneshot(post_params(r#"{"order_size": "5000"}"#)).await.unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        assert_eq!(state.params.read().await.order_size, dec!(5));
+
+        let response = app.oneshot(post_params(r#"{"order_size": "50"}"#)).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(state.params.read().await.order_size, dec!(50));
+    }
+
+    #[tokio::test]
+    async fn params_endpoint_requires_configured_token() {
+        use crate::config::RuntimeParams;
+
+        let body = r#"{"target_pair_cost": "0.97"}"#;
+
+        // Disabled unless a token is configured
+        let state = AppState::new();
+        let response = create_router(state.clone()).oneshot(post_params(body)).await.unwrap();
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+
+        let state = params_state();
+        let app = create_router(state.clone());
+        let response =
+            app.clone().oneshot(post_params_with_token(body, "wrong-token")).await.unwrap();
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+        let unauthenticated = Request::builder()
+            .method("POST")
+            .uri("/api/v1/params")
+            .header("content-type", "application/json")
+            .body(Body::from(body))
+            .unwrap();
+        let response = app.oneshot(unauthenticated).await.unwrap();
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+        assert_eq!(*state.params.read().await, RuntimeParams::default());
     }
 }
@@ -0,0 +1,222 @@
+//! Detect-to-submit latency benchmark.
+//!
+//! Times the hot path from a book update to both orders being posted:
+//! `check_arbitrage`, auth headers, order construction, signing, and submission
+//! to the mock client. Books are synthetic and nothing touches the network, so the result
+//! is a regression number for the bot's own code rather than the exchange.
+
+use std::time::{Duration, Instant};
+
+use rust_decimal::Decimal;
+use time::OffsetDateTime;
+
+use super::detector::check_arbitrage;
+use super::executor::order_params;
+use crate::config::Config;
+use crate::error::TradingError;
+use crate::market::{Market, MockPolymarketClient, Outcome, PolymarketClient};
+use crate::orderbook::{OutcomeBook, PriceLevel};
+use crate::signing::generate_auth_headers;
+use crate::trading::execution::build_order_request;
+use crate::trading::OrderParams;
+
+/// Latency percentiles over a benchmark run.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LatencySummary {
+    /// Iterations measured.
+    pub iterations: usize,
+    /// Fastest iteration.
+    pub min: Duration,
+    /// Median latency.
+    pub p50: Duration,
+    /// 95th percentile latency.
+    pub p95: Duration,
+    /// Slowest iteration.
+    pub max: Duration,
+}
+
+impl LatencySummary {
+    /// Summarize per-iteration latencies; `None` when there are none.
+    pub fn from_samples(mut samples: Vec<Duration>) -> Option<Self> {
+        if samples.is_empty() {
+            return None;
+        }
+        samples.sort_unstable();
+        let percentile = |pct: usize| samples[(samples.len() - 1) * pct / 100];
+
+        Some(Self {
+            iterations: samples.len(),
+            min: samples[0],
+            p50: percentile(50),
+            p95: percentile(95),
+            max: samples[samples.len() - 1],
+        })
+    }
+}
+
+/// Auth-header latency for the two legs of one execution.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AuthHeaderLatency {
+    /// Each leg signing its own headers.
+    pub per_leg: LatencySummary,
+    /// Both legs sharing the client's cached headers.
+    pub shared: LatencySummary,
+}
+
+/// Synthetic market and books priced at 0.48 + 0.51, deep enough for any order size.
+pub(super) fn synthetic_books() -> (Market, OutcomeBook, OutcomeBook) {
+    let start_timestamp = OffsetDateTime::now_utc().unix_timestamp();
+    let market = Market {
+        slug: format!("btc-updown-15m-{}", start_timestamp),
+        id: "benchmark".to_string(),
+        up_token_id: "benchmark-up".to_string(),
+        down_token_id: "benchmark-down".to_string(),
+        start_timestamp,
+        end_timestamp: start_timestamp + Market::WINDOW_SECONDS,
+        question: None,
+        neg_risk: None,
+        condition_id: None,
+        accepting_orders_until: None,
+        tick_size: None,
+    };
+
+    let book = |token_id: &str, outcome: Outcome, ask: Decimal| OutcomeBook {
+        token_id: token_id.to_string(),
+        outcome,
+        bids: vec![PriceLevel::new(ask - Decimal::new(1, 2), Decimal::new(10_000, 0))],
+        asks: vec![PriceLevel::new(ask, Decimal::new(10_000, 0))],
+        updated_at: OffsetDateTime::now_utc(),
+        as_of: None,
+    };
+    let up = book(&market.up_token_id, Outcome::Up, Decimal::new(48, 2));
+    let down = book(&market.down_token_id, Outcome::Down, Decimal::new(51, 2));
+
+    (market, up, down)
+}
+
+/// Submit one leg the way `submit_order` does, but to the mock client.
+pub(super) async fn submit_leg(
+    client: &PolymarketClient,
+    mock: &MockPolymarketClient,
+    params: &OrderParams,
+) -> Result<String, TradingError> {
+    let _auth_headers = client.auth_headers().await?;
+    let order = build_order_request(client, params).await?;
+    mock.submit_order(&order).await
+}
+
+/// Time detection, order construction, signing, and mock submission of both legs.
+///
+/// Signs with the configured private key; orders go to a mock client only.
+/// Each iteration signs fresh auth headers, as an execution seconds after the last would.
+pub async fn detect_to_submit_latency(
+    config: &Config,
+    iterations: usize,
+) -> Result<Option<LatencySummary>, TradingError> {
+    let client = PolymarketClient::new(config);
+    let mock = MockPolymarketClient::new();
+    let (market, up_book, down_book) = synthetic_books();
+    let mut samples = Vec::with_capacity(iterations);
+
+    for _ in 0..iterations {
+        client.invalidate_auth_headers().await;
+        let start = Instant::now();
+
+        let opportunity = check_arbitrage(&market, &up_book, &down_book, config)
+            .map_err(|e| TradingError::InvalidParams(e.to_string()))?
+            .ok_or_else(|| {
+                TradingError::InvalidParams(
+                    "synthetic book is not an opportunity at TARGET_PAIR_COST".to_string(),
+                )
+            })?;
+        let (up_params, down_params) = order_params(&opportunity, config)?;
+        tokio::try_join!(
+            submit_leg(&client, &mock, &up_params),
+            submit_leg(&client, &mock, &down_params),
+        )?;
+
+        samples.push(start.elapsed());
+    }
+
+    Ok(LatencySummary::from_samples(samples))
+}
+
+/// Time auth headers for both legs: signed per leg versus shared through the client.
+pub async fn paired_auth_header_latency(
+    config: &Config,
+    iterations: usize,
+) -> Result<Option<AuthHeaderLatency>, TradingError> {
+    let client = PolymarketClient::new(config);
+    let key = client.private_key();
+    let signature_type = client.signature_type();
+    let mut per_leg = Vec::with_capacity(iterations);
+    let mut shared = Vec::with_capacity(iterations);
+
+    for _ in 0..iterations {
+        let start = Instant::now();
+        tokio::try_join!(
+            generate_auth_headers(key, signature_type),
+            generate_auth_headers(key, signature_type),
+        )?;
+        per_leg.push(start.elapsed());
+
+        client.invalidate_auth_headers().await;
+        let start = Instant::now();
+        tokio::try_join!(client.auth_headers(), client.auth_headers())?;
+        shared.push(start.elapsed());
+    }
+
+    Ok(LatencySummary::from_samples(per_leg)
+        .zip(LatencySummary::from_samples(shared))
+        .map(|(per_leg, shared)| AuthHeaderLatency { per_leg, shared }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn summary_percentiles() {
+        let samples = (1..=100).rev().map(Duration::from_millis).collect();
+
+        let summary = LatencySummary::from_samples(samples).unwrap();
+
+        assert_eq!(summary.iterations, 100);
+        assert_eq!(summary.min, Duration::from_millis(1));
+        assert_eq!(summary.p50, Duration::from_millis(50));
+        assert_eq!(summary.p95, Duration::from_millis(95));
+        assert_eq!(summary.max, Duration::from_millis(100));
+        assert!(LatencySummary::from_samples(Vec::new()).is_none());
+    }
+
+    #[tokio::test]
+    async fn detect_to_submit_benchmark_completes() {
+        let config: Config = serde_json::from_value(serde_json::json!({
+            "polymarket_private_key":
+                "0x0123456789abcdef0123456789abcdef0123456789abcdef0123456789abcdef",
+        }))
+        .unwrap();
+
+        let summary = detect_to_submit_latency(&config, 50).await.unwrap().unwrap();
+
+        assert_eq!(summary.iterations, 50);
+        assert!(summary.min <= summary.p50);
+        assert!(summary.p50 <= summary.p95);
+        assert!(summary.p95 <= summary.max);
+    }
+
+    #[tokio::test]
+    async fn shared_auth_headers_sign_once_per_execution() {
+        let config: Config = serde_json::from_value(serde_json::json!({
+            "polymarket_private_key":
+                "0x0123456789abcdef0123456789abcdef0123456789abcdef0123456789abcdef",
+        }))
+        .unwrap();
+
+        let latency = paired_auth_header_latency(&config, 5).await.unwrap().unwrap();
+
+        assert_eq!(latency.per_leg.iterations, 5);
+        assert_eq!(latency.shared.iterations, 5);
+        assert!(paired_auth_header_latency(&config, 0).await.unwrap().is_none());
+    }
+}
@@ -3,8 +3,17 @@
 use rust_decimal::Decimal;
 use time::OffsetDateTime;
 
+use crate::config::{CalcRounding, OrderSizeUnit};
 use crate::market::Market;
-use crate::orderbook::{calculate_fill_price, OutcomeBook};
+use crate::orderbook::aggregator::cumulative_depth_up_to;
+use crate::orderbook::{calculate_fill_price, OutcomeBook, PriceLevel};
+use crate::trading::order::{OrderPrecision, Side};
+
+/// Basis points per unit.
+pub(crate) const BPS: Decimal = Decimal::from_parts(10_000, 0, 0, false, 0);
+
+/// Depth beyond this multiple of the order size adds nothing to an opportunity's score.
+const SCORE_DEPTH_CAP: Decimal = Decimal::from_parts(5, 0, 0, false, 0);
 
 /// Detected arbitrage opportunity.
 #[derive(Debug, Clone)]
@@ -29,6 +38,9 @@ pub struct ArbitrageOpportunity {
     pub expected_payout: Decimal,
     /// Expected profit (expected_payout - total_investment).
     pub expected_profit: Decimal,
+    /// Highest pair cost the opportunity was accepted under: its matched ladder
+    /// tier's, or the flat threshold. Entry prices are clamped to it.
+    pub max_pair_cost: Decimal,
     /// Best ask price for UP.
     pub best_ask_up: Option<Decimal>,
     /// Best ask price for DOWN.
@@ -37,6 +49,14 @@ pub struct ArbitrageOpportunity {
     pub vwap_up: Option<Decimal>,
     /// VWAP for DOWN fill.
     pub vwap_down: Option<Decimal>,
+    /// UP ask levels the fill consumes at detection (used by the dry-run fill model).
+    pub up_asks: Vec<PriceLevel>,
+    /// DOWN ask levels the fill consumes at detection (used by the dry-run fill model).
+    pub down_asks: Vec<PriceLevel>,
+    /// Full UP book at detection (recorded in book snapshots).
+    pub up_book: OutcomeBook,
+    /// Full DOWN book at detection (recorded in book snapshots).
+    pub down_book: OutcomeBook,
     /// Timestamp when opportunity was detected.
     pub detected_at: OffsetDateTime,
 }
@@ -50,30 +70,145 @@ impl ArbitrageOpportunity {
             (self.expected_profit / self.total_investment) * Decimal::ONE_HUNDRED
         }
     }
+
+    /// Executable depth on the thinner side, as a multiple of the order size.
+    ///
+    /// Counts ask size at or below each leg's limit price, capped at
+    /// [`SCORE_DEPTH_CAP`].
+    pub fn depth_ratio(&self) -> Decimal {
+        if self.order_size.is_zero() {
+            return Decimal::ZERO;
+        }
+        let up_depth = cumulative_depth_up_to(&self.up_asks, self.up_price);
+        let down_depth = cumulative_depth_up_to(&self.down_asks, self.down_price);
+        (up_depth.min(down_depth) / self.order_size).min(SCORE_DEPTH_CAP)
+    }
+
+    /// Comparable quality score: profit percentage weighted by executable depth.
+    ///
+    /// A 1% edge with exactly enough depth for the order scores 1; thin books
+    /// or small edges score lower.
+    pub fn score(&self) -> Decimal {
+        (self.profit_pct * self.depth_ratio()).round_dp(4)
+    }
+
+    /// Round computed VWAPs, costs and percentages with [`round_calc`].
+    ///
+    /// The leg limit prices are book levels already on the exchange's tick, so
+    /// they are left as is: rounding them could lift a BUY limit.
+    pub fn rounded(mut self, scale: u32, rounding: CalcRounding) -> Self {
+        let round = |d| round_calc(d, scale, rounding);
+        self.total_cost = round(self.total_cost);
+        self.profit_per_share = round(self.profit_per_share);
+        self.profit_pct = round(self.profit_pct);
+        self.total_investment = round(self.total_investment);
+        self.expected_profit = round(self.expected_profit);
+        self.vwap_up = self.vwap_up.map(round);
+        self.vwap_down = self.vwap_down.map(round);
+        self
+    }
+
+    /// Worst-case loss if only one leg fills, in USDC.
+    ///
+    /// Assumes the costlier leg is the one left unhedged. With an `unwind_bid`
+    /// the position is sold there; without one it is held and settles against
+    /// us, losing its full cost.
+    pub fn max_partial_loss(&self, unwind_bid: Option<Decimal>) -> Decimal {
+        let leg_price = self.up_price.max(self.down_price);
+        let exit_price = unwind_bid.unwrap_or(Decimal::ZERO).clamp(Decimal::ZERO, leg_price);
+        (leg_price - exit_price) * self.order_size
+    }
 }
 
-/// Calculate arbitrage metrics from order books.
-pub fn calculate_opportunity(
-    market: &Market,
-    up_book: &OutcomeBook,
-    down_book: &OutcomeBook,
+/// One leg of a multi-outcome opportunity.
+#[derive(Debug, Clone)]
+pub struct OpportunityLeg {
+    /// Token ID to buy.
+    pub token_id: String,
+    /// Limit price (worst fill price).
+    pub price: Decimal,
+    /// Best ask price.
+    pub best_ask: Option<Decimal>,
+    /// VWAP for the fill.
+    pub vwap: Decimal,
+    /// Ask levels the fill consumes at detection (used by the dry-run fill model).
+    pub asks: Vec<PriceLevel>,
+}
+
+/// Opportunity across N mutually exclusive outcomes (e.g. a neg-risk event group).
+///
+/// Exactly one outcome resolves to $1, so buying one share of every outcome
+/// pays $1 per share set regardless of the result.
+#[derive(Debug, Clone)]
+pub struct MultiLegOpportunity {
+    /// One leg per outcome.
+    pub legs: Vec<OpportunityLeg>,
+    /// Total cost per share set (sum of leg prices).
+    pub total_cost: Decimal,
+    /// Profit per share set (1.0 - total_cost).
+    pub profit_per_share: Decimal,
+    /// Profit percentage ((profit / cost) * 100).
+    pub profit_pct: Decimal,
+    /// Number of shares to trade on each leg.
+    pub order_size: Decimal,
+    /// Total investment (total_cost * order_size).
+    pub total_investment: Decimal,
+    /// Expected payout at close (order_size * 1.0).
+    pub expected_payout: Decimal,
+    /// Expected profit (expected_payout - total_investment).
+    pub expected_profit: Decimal,
+    /// Timestamp when opportunity was detected.
+    pub detected_at: OffsetDateTime,
+}
+
+/// Calculate a multi-leg opportunity across mutually exclusive outcome books.
+///
+/// Sums each outcome's worst fill price for `target_size`; returns `None` if
+/// fewer than two outcomes are given, any book is too thin, or the total
+/// exceeds `threshold`.
+pub fn calculate_multi_opportunity(
+    market_outcomes: &[OutcomeBook],
     target_size: Decimal,
     threshold: Decimal,
-) -> Option<ArbitrageOpportunity> {
-    // Get fill prices for both sides
-    let up_fill = calculate_fill_price(&up_book.asks, target_size).ok()?;
-    let down_fill = calculate_fill_price(&down_book.asks, target_size).ok()?;
+) -> Option<MultiLegOpportunity> {
+    multi_leg_opportunity(market_outcomes, target_size, threshold)
+}
 
+/// Shared N-leg calculation over borrowed books.
+fn multi_leg_opportunity<'a>(
+    books: impl IntoIterator<Item = &'a OutcomeBook>,
+    target_size: Decimal,
+    threshold: Decimal,
+) -> Option<MultiLegOpportunity> {
     // Use worst-case prices for guaranteed arbitrage
-    let up_price = up_fill.worst_price;
-    let down_price = down_fill.worst_price;
-    let total_cost = up_price + down_price;
+    let fills = books
+        .into_iter()
+        .map(|book| Some((book, calculate_fill_price(&book.asks, target_size).ok()?)))
+        .collect::<Option<Vec<_>>>()?;
+
+    if fills.len() < 2 {
+        return None;
+    }
+
+    let total_cost: Decimal = fills.iter().map(|(_, fill)| fill.worst_price).sum();
 
     // Check if profitable
     if total_cost > threshold {
         return None;
     }
 
+    // Only profitable scans copy ask levels, and only those the fill consumes
+    let legs = fills
+        .into_iter()
+        .map(|(book, fill)| OpportunityLeg {
+            token_id: book.token_id.clone(),
+            price: fill.worst_price,
+            best_ask: fill.best_price,
+            vwap: fill.vwap,
+            asks: consumed_levels(&book.asks, fill.worst_price),
+        })
+        .collect::<Vec<_>>();
+
     let profit_per_share = Decimal::ONE - total_cost;
     let profit_pct = if total_cost > Decimal::ZERO {
         (profit_per_share / total_cost) * Decimal::ONE_HUNDRED
@@ -82,13 +217,11 @@ pub fn calculate_opportunity(
     };
 
     let total_investment = total_cost * target_size;
-    let expected_payout = target_size; // $1.00 per share at settlement
+    let expected_payout = target_size; // $1.00 per share set at settlement
     let expected_profit = expected_payout - total_investment;
 
-    Some(ArbitrageOpportunity {
-        market: market.clone(),
-        up_price,
-        down_price,
+    Some(MultiLegOpportunity {
+        legs,
         total_cost,
         profit_per_share,
         profit_pct,
@@ -96,14 +229,149 @@ pub fn calculate_opportunity(
         total_investment,
         expected_payout,
         expected_profit,
-        best_ask_up: up_fill.best_price,
-        best_ask_down: down_fill.best_price,
-        vwap_up: Some(up_fill.vwap),
-        vwap_down: Some(down_fill.vwap),
         detected_at: OffsetDateTime::now_utc(),
     })
 }
 
+/// Ask levels up to and including `worst_price`, the ones a fill walks through.
+fn consumed_levels(asks: &[PriceLevel], worst_price: Decimal) -> Vec<PriceLevel> {
+    asks.iter()
+        .take_while(|level| level.price <= worst_price)
+        .cloned()
+        .collect()
+}
+
+/// Calculate arbitrage metrics from order books.
+///
+/// The two-outcome case of [`calculate_multi_opportunity`].
+pub fn calculate_opportunity(
+    market: &Market,
+    up_book: &OutcomeBook,
+    down_book: &OutcomeBook,
+    target_size: Decimal,
+    threshold: Decimal,
+) -> Option<ArbitrageOpportunity> {
+    let multi = multi_leg_opportunity([up_book, down_book], target_size, threshold)?;
+    let [up, down]: [OpportunityLeg; 2] = multi.legs.try_into().ok()?;
+
+    Some(ArbitrageOpportunity {
+        market: market.clone(),
+        up_price: up.price,
+        down_price: down.price,
+        total_cost: multi.total_cost,
+        profit_per_share: multi.profit_per_share,
+        profit_pct: multi.profit_pct,
+        order_size: multi.order_size,
+        total_investment: multi.total_investment,
+        expected_payout: multi.expected_payout,
+        expected_profit: multi.expected_profit,
+        max_pair_cost: threshold,
+        best_ask_up: up.best_ask,
+        best_ask_down: down.best_ask,
+        vwap_up: Some(up.vwap),
+        vwap_down: Some(down.vwap),
+        up_asks: up.asks,
+        down_asks: down.asks,
+        up_book: up_book.clone(),
+        down_book: down_book.clone(),
+        detected_at: multi.detected_at,
+    })
+}
+
+/// Calculate an opportunity for `size` counted in `unit`.
+///
+/// A USDC size is first converted to shares at the best-ask pair cost, then
+/// again at the worst-fill pair cost of that many shares. Fewer shares never
+/// fill at worse prices, so the second count's investment stays within the
+/// notional, and every price and depth check runs at the share count traded.
+/// Returns `None` when the notional buys fewer than the 5-share minimum.
+pub fn calculate_sized_opportunity(
+    market: &Market,
+    up_book: &OutcomeBook,
+    down_book: &OutcomeBook,
+    size: Decimal,
+    unit: OrderSizeUnit,
+    threshold: Decimal,
+) -> Option<ArbitrageOpportunity> {
+    if unit == OrderSizeUnit::Shares {
+        return calculate_opportunity(market, up_book, down_book, size, threshold);
+    }
+
+    let size_dp = OrderPrecision::SIZE_DECIMALS;
+    let best_cost = up_book.best_ask()? + down_book.best_ask()?;
+    let estimate = unit.shares(size, best_cost, size_dp)?;
+    let at_estimate = calculate_opportunity(market, up_book, down_book, estimate, Decimal::MAX)?;
+    let shares = unit.shares(size, at_estimate.total_cost, size_dp)?;
+    calculate_opportunity(market, up_book, down_book, shares, threshold)
+}
+
+/// Calculate an opportunity sized by a (max pair cost, size) ladder.
+///
+/// Tiers are tried from the largest size down, so the biggest tier whose cost
+/// threshold the books satisfy at that size's worst-fill prices wins; a tier
+/// the books are too thin to fill is skipped. If no tier matches (or the
+/// ladder is empty), falls through to the flat `target_size` / `threshold` check.
+/// Sizes are counted in `unit`, as in [`calculate_sized_opportunity`].
+pub fn calculate_laddered_opportunity(
+    market: &Market,
+    up_book: &OutcomeBook,
+    down_book: &OutcomeBook,
+    ladder: &[(Decimal, Decimal)],
+    target_size: Decimal,
+    unit: OrderSizeUnit,
+    threshold: Decimal,
+) -> Option<ArbitrageOpportunity> {
+    let mut tiers = ladder.to_vec();
+    tiers.sort_by_key(|&(_, size)| std::cmp::Reverse(size));
+
+    let sized = |size, max_cost| {
+        calculate_sized_opportunity(market, up_book, down_book, size, unit, max_cost)
+    };
+    tiers
+        .into_iter()
+        .find_map(|(max_cost, size)| sized(size, max_cost))
+        .or_else(|| sized(target_size, threshold))
+}
+
+/// Round a computed value to `scale` decimal places.
+///
+/// Division leaves VWAPs and percentages with up to 28 digits; rounding them
+/// once, by one rule, keeps logged, compared and submitted values consistent.
+pub fn round_calc(d: Decimal, scale: u32, rounding: CalcRounding) -> Decimal {
+    d.round_dp_with_strategy(scale, rounding.strategy())
+}
+
+/// Shift entry limit prices by `offset_bps` from the worst-fill prices.
+///
+/// Positive offsets post inside the book (more edge, lower fill probability);
+/// negative offsets pay up for fill certainty. Both prices are floored onto
+/// the `tick_size` grid first, and the pair is then clamped so it never costs
+/// more than `threshold`. Returns `None` when either leg would fall below one
+/// tick, since no valid pair of orders exists then.
+pub fn entry_prices(
+    up_price: Decimal,
+    down_price: Decimal,
+    offset_bps: Decimal,
+    threshold: Decimal,
+    tick_size: Decimal,
+) -> Option<(Decimal, Decimal)> {
+    let precision = OrderPrecision::from_tick_size(tick_size);
+    let floor = |price| Some(precision.round_price(price, Side::Buy)).filter(|p| *p >= tick_size);
+    let factor = Decimal::ONE - offset_bps / BPS;
+    let up = floor(up_price * factor)?;
+    let down = floor(down_price * factor)?;
+
+    let total = up + down;
+    if total <= threshold {
+        return Some((up, down));
+    }
+
+    // Clamp to the threshold, keeping the legs' proportions; flooring the
+    // clamped prices keeps the rounded pair within it
+    let up = floor(up * threshold / total)?;
+    Some((up, floor(threshold - up)?))
+}
+
 /// Calculate the break-even threshold (cost at which profit = 0).
 pub fn break_even_cost() -> Decimal {
     Decimal::ONE
@@ -139,6 +407,10 @@ mod tests {
             start_timestamp: 0,
             end_timestamp: 900,
             question: None,
+            neg_risk: None,
+            condition_id: None,
+            accepting_orders_until: None,
+            tick_size: None,
         }
     }
 
@@ -149,6 +421,7 @@ mod tests {
             bids: vec![],
             asks: vec![PriceLevel::new(ask_price, ask_size)],
             updated_at: OffsetDateTime::now_utc(),
+            as_of: None,
         }
     }
 
@@ -169,6 +442,33 @@ mod tests {
         assert_eq!(opp.expected_profit, dec!(0.1));
     }
 
+    #[test]
+    fn deep_high_edge_opportunity_scores_above_thin_marginal_one() {
+        let market = test_market();
+        let deep = calculate_opportunity(
+            &market,
+            &test_book(Outcome::Up, dec!(0.47), dec!(100)),
+            &test_book(Outcome::Down, dec!(0.50), dec!(100)),
+            dec!(10),
+            dec!(0.991),
+        )
+        .unwrap();
+        let thin = calculate_opportunity(
+            &market,
+            &test_book(Outcome::Up, dec!(0.48), dec!(10)),
+            &test_book(Outcome::Down, dec!(0.51), dec!(12)),
+            dec!(10),
+            dec!(0.991),
+        )
+        .unwrap();
+
+        // Depth is capped at 5x the order size
+        assert_eq!(deep.depth_ratio(), dec!(5));
+        assert_eq!(thin.depth_ratio(), dec!(1));
+        assert!(deep.score() > thin.score());
+        assert!(thin.score() > Decimal::ZERO);
+    }
+
     #[test]
     fn no_arbitrage_when_cost_exceeds_threshold() {
         let market = test_market();
@@ -180,6 +480,45 @@ mod tests {
         assert!(opp.is_none()); // 0.50 + 0.51 = 1.01 > 0.99
     }
 
+    fn outcome_books(asks: &[Decimal]) -> Vec<OutcomeBook> {
+        asks.iter()
+            .enumerate()
+            .map(|(i, &price)| OutcomeBook {
+                token_id: format!("outcome-{}", i),
+                ..test_book(Outcome::Up, price, dec!(100))
+            })
+            .collect()
+    }
+
+    #[test]
+    fn detect_three_outcome_arbitrage_below_threshold() {
+        let books = outcome_books(&[dec!(0.30), dec!(0.33), dec!(0.32)]);
+
+        let opp = calculate_multi_opportunity(&books, dec!(10), dec!(0.99)).unwrap();
+
+        assert_eq!(opp.legs.len(), 3);
+        assert_eq!(opp.legs[2].token_id, "outcome-2");
+        assert_eq!(opp.total_cost, dec!(0.95));
+        assert_eq!(opp.profit_per_share, dec!(0.05));
+        assert_eq!(opp.total_investment, dec!(9.5));
+        assert_eq!(opp.expected_profit, dec!(0.5));
+    }
+
+    #[test]
+    fn no_three_outcome_arbitrage_above_threshold() {
+        let books = outcome_books(&[dec!(0.34), dec!(0.33), dec!(0.34)]);
+
+        // 0.34 + 0.33 + 0.34 = 1.01 > 0.99
+        assert!(calculate_multi_opportunity(&books, dec!(10), dec!(0.99)).is_none());
+    }
+
+    #[test]
+    fn multi_opportunity_needs_two_outcomes() {
+        let books = outcome_books(&[dec!(0.40)]);
+
+        assert!(calculate_multi_opportunity(&books, dec!(10), dec!(0.99)).is_none());
+    }
+
     #[test]
     fn opportunity_roi_calculation() {
         let opp = ArbitrageOpportunity {
@@ -193,10 +532,15 @@ mod tests {
             total_investment: dec!(99),
             expected_payout: dec!(100),
             expected_profit: dec!(1),
+            max_pair_cost: dec!(0.99),
             best_ask_up: Some(dec!(0.48)),
             best_ask_down: Some(dec!(0.51)),
             vwap_up: Some(dec!(0.48)),
             vwap_down: Some(dec!(0.51)),
+            up_asks: vec![PriceLevel::new(dec!(0.48), dec!(100))],
+            down_asks: vec![PriceLevel::new(dec!(0.51), dec!(100))],
+            up_book: OutcomeBook::default(),
+            down_book: OutcomeBook::default(),
             detected_at: OffsetDateTime::now_utc(),
         };
 
@@ -204,6 +548,205 @@ mod tests {
         assert!(opp.roi() > dec!(1) && opp.roi() < dec!(1.02));
     }
 
+    #[test]
+    fn partial_loss_is_full_leg_cost_without_unwind_bid() {
+        let market = test_market();
+        let up_book = test_book(Outcome::Up, dec!(0.48), dec!(100));
+        let down_book = test_book(Outcome::Down, dec!(0.51), dec!(100));
+        let opp =
+            calculate_opportunity(&market, &up_book, &down_book, dec!(10), dec!(0.991)).unwrap();
+
+        // The 0.51 DOWN leg settling worthless
+        assert_eq!(opp.max_partial_loss(None), dec!(5.1));
+    }
+
+    #[test]
+    fn partial_loss_is_reduced_by_unwind_bid() {
+        let market = test_market();
+        let up_book = test_book(Outcome::Up, dec!(0.48), dec!(100));
+        let down_book = test_book(Outcome::Down, dec!(0.51), dec!(100));
+        let opp =
+            calculate_opportunity(&market, &up_book, &down_book, dec!(10), dec!(0.991)).unwrap();
+
+        assert_eq!(opp.max_partial_loss(Some(dec!(0.45))), dec!(0.6));
+        // A bid above cost is no loss, not a gain
+        assert_eq!(opp.max_partial_loss(Some(dec!(0.60))), dec!(0));
+    }
+
+    #[test]
+    fn opportunity_keeps_only_consumed_ask_levels() {
+        let market = test_market();
+        let mut up_book = test_book(Outcome::Up, dec!(0.47), dec!(4));
+        up_book.asks.push(PriceLevel::new(dec!(0.48), dec!(10)));
+        up_book.asks.push(PriceLevel::new(dec!(0.60), dec!(100)));
+        let down_book = test_book(Outcome::Down, dec!(0.51), dec!(100));
+
+        let opp =
+            calculate_opportunity(&market, &up_book, &down_book, dec!(10), dec!(0.991)).unwrap();
+
+        let up_prices: Vec<Decimal> = opp.up_asks.iter().map(|level| level.price).collect();
+        assert_eq!(up_prices, [dec!(0.47), dec!(0.48)]);
+        assert_eq!(opp.down_asks.len(), 1);
+    }
+
+    #[test]
+    fn rounded_opportunity_uses_configured_scale_and_rule() {
+        let market = test_market();
+        // 1 share at 0.48 and 2 at 0.49: VWAP 0.48666..
+        let mut up_book = test_book(Outcome::Up, dec!(0.48), dec!(1));
+        up_book.asks.push(PriceLevel::new(dec!(0.49), dec!(2)));
+        let down_book = test_book(Outcome::Down, dec!(0.50), dec!(100));
+        let opp =
+            calculate_opportunity(&market, &up_book, &down_book, dec!(3), dec!(0.991)).unwrap();
+
+        // profit_pct = 0.01 / 0.99 * 100 = 1.0101..
+        let rounded = opp.clone().rounded(6, CalcRounding::MidpointAwayFromZero);
+        assert_eq!(rounded.vwap_up, Some(dec!(0.486667)));
+        assert_eq!(rounded.profit_pct, dec!(1.010101));
+        assert_eq!(rounded.total_cost, dec!(0.99));
+
+        let truncated = opp.rounded(4, CalcRounding::ToZero);
+        assert_eq!(truncated.vwap_up, Some(dec!(0.4866)));
+        assert_eq!(truncated.profit_pct, dec!(1.0101));
+    }
+
+    #[test]
+    fn rounding_never_moves_leg_limit_prices() {
+        let market = test_market();
+        // A 0.001-tick book: rounding 0.485 to 2 places would lift the limit
+        let up_book = test_book(Outcome::Up, dec!(0.485), dec!(100));
+        let down_book = test_book(Outcome::Down, dec!(0.50), dec!(100));
+        let opp =
+            calculate_opportunity(&market, &up_book, &down_book, dec!(10), dec!(0.991)).unwrap();
+
+        let rounded = opp.rounded(2, CalcRounding::MidpointAwayFromZero);
+        assert_eq!(rounded.up_price, dec!(0.485));
+        assert_eq!(rounded.down_price, dec!(0.50));
+    }
+
+    #[test]
+    fn round_calc_midpoint_rules() {
+        let round = |d, rounding| round_calc(d, 6, rounding);
+
+        assert_eq!(round(dec!(0.0000125), CalcRounding::MidpointAwayFromZero), dec!(0.000013));
+        assert_eq!(round(dec!(0.0000125), CalcRounding::MidpointNearestEven), dec!(0.000012));
+        assert_eq!(round(dec!(0.0000125), CalcRounding::MidpointTowardZero), dec!(0.000012));
+        assert_eq!(round(dec!(0.0000135), CalcRounding::ToZero), dec!(0.000013));
+    }
+
+    /// Laddered opportunity on books with 10 shares at 0.48 UP / 0.49 DOWN and
+    /// 100 more at 0.49 / 0.50.
+    fn laddered(
+        ladder: &[(Decimal, Decimal)],
+        target_size: Decimal,
+        threshold: Decimal,
+    ) -> Option<ArbitrageOpportunity> {
+        let book = |outcome, best: Decimal, deep: Decimal| OutcomeBook {
+            asks: vec![PriceLevel::new(best, dec!(10)), PriceLevel::new(deep, dec!(100))],
+            ..test_book(outcome, best, dec!(10))
+        };
+        let up = book(Outcome::Up, dec!(0.48), dec!(0.49));
+        let down = book(Outcome::Down, dec!(0.49), dec!(0.50));
+
+        calculate_laddered_opportunity(
+            &test_market(),
+            &up,
+            &down,
+            ladder,
+            target_size,
+            OrderSizeUnit::Shares,
+            threshold,
+        )
+    }
+
+    #[test]
+    fn ladder_picks_largest_satisfied_tier() {
+        // 50 shares fill at 0.49 + 0.50 = 0.99, inside the 50-share tier
+        let opp = laddered(&[(dec!(0.995), dec!(5)), (dec!(0.99), dec!(50))], dec!(5), dec!(0.991))
+            .unwrap();
+
+        assert_eq!(opp.order_size, dec!(50));
+        assert_eq!(opp.total_cost, dec!(0.99));
+        assert_eq!(opp.max_pair_cost, dec!(0.99));
+    }
+
+    #[test]
+    fn ladder_falls_back_to_smaller_tier() {
+        // 50 shares cost 0.99 > 0.98; 5 shares cost 0.97 <= 0.975
+        let opp = laddered(&[(dec!(0.975), dec!(5)), (dec!(0.98), dec!(50))], dec!(5), dec!(0.96))
+            .unwrap();
+
+        assert_eq!(opp.order_size, dec!(5));
+        assert_eq!(opp.total_cost, dec!(0.97));
+    }
+
+    #[test]
+    fn ladder_skips_tier_without_liquidity() {
+        // Only 110 shares are offered, so the 500-share tier can't fill
+        let ladder = [(dec!(0.995), dec!(500)), (dec!(0.99), dec!(20))];
+
+        let opp = laddered(&ladder, dec!(5), dec!(0.991)).unwrap();
+
+        assert_eq!(opp.order_size, dec!(20));
+    }
+
+    #[test]
+    fn ladder_falls_through_to_flat_threshold() {
+        let ladder = [(dec!(0.95), dec!(5)), (dec!(0.96), dec!(50))];
+
+        // No tier matches; the flat 10-share check at 0.991 does
+        let opp = laddered(&ladder, dec!(10), dec!(0.991)).unwrap();
+        assert_eq!(opp.order_size, dec!(10));
+        assert_eq!(opp.max_pair_cost, dec!(0.991));
+
+        assert!(laddered(&ladder, dec!(10), dec!(0.96)).is_none());
+    }
+
+    #[test]
+    fn positive_entry_offset_stays_profitable() {
+        let (up, down) =
+            entry_prices(dec!(0.48), dec!(0.51), dec!(100), dec!(0.991), dec!(0.01)).unwrap();
+
+        // 1% inside each worst-fill price (0.4752, 0.5049), floored to the tick
+        assert_eq!(up, dec!(0.47));
+        assert_eq!(down, dec!(0.50));
+        assert!(up + down < dec!(0.991));
+    }
+
+    #[test]
+    fn excessive_entry_offset_is_clamped() {
+        // Paying up 5% would cost 1.03 per pair on the tick; clamp to the threshold
+        let (up, down) =
+            entry_prices(dec!(0.48), dec!(0.51), dec!(-500), dec!(0.991), dec!(0.01)).unwrap();
+        assert_eq!((up, down), (dec!(0.48), dec!(0.51)));
+        assert!(up + down <= dec!(0.991));
+
+        // The clamp is applied after rounding, so an off-tick threshold holds
+        let (up, down) =
+            entry_prices(dec!(0.48), dec!(0.51), dec!(-500), dec!(0.985), dec!(0.01)).unwrap();
+        assert_eq!((up, down), (dec!(0.47), dec!(0.51)));
+    }
+
+    #[test]
+    fn entry_price_below_one_tick_is_skipped() {
+        // Posting far inside would price both legs under the minimum tick
+        assert_eq!(entry_prices(dec!(0.48), dec!(0.51), dec!(9999), dec!(0.991), dec!(0.01)), None);
+
+        // Clamping to 0.019 floors UP to 0.01, leaving less than a tick for DOWN
+        assert_eq!(entry_prices(dec!(0.98), dec!(0.02), dec!(0), dec!(0.019), dec!(0.01)), None);
+
+        // Exactly one tick is still a valid price
+        let prices = entry_prices(dec!(0.98), dec!(0.01), dec!(0), dec!(0.99), dec!(0.01));
+        assert_eq!(prices, Some((dec!(0.98), dec!(0.01))));
+    }
+
+    #[test]
+    fn zero_entry_offset_keeps_worst_fill_prices() {
+        let prices = entry_prices(dec!(0.48), dec!(0.51), dec!(0), dec!(0.991), dec!(0.01));
+
+        assert_eq!(prices, Some((dec!(0.48), dec!(0.51))));
+    }
+
     #[test]
     fn effective_spread_calculation() {
         let up_book = test_book(Outcome::Up, dec!(0.48), dec!(100));
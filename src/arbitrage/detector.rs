@@ -1,13 +1,16 @@
 //! Arbitrage opportunity detection.
 
 use rust_decimal::Decimal;
+use time::OffsetDateTime;
 use tracing::{debug, info, instrument, warn};
 
-use super::calculator::{calculate_opportunity, ArbitrageOpportunity};
+use super::calculator::{calculate_laddered_opportunity, entry_prices, ArbitrageOpportunity};
 use crate::config::Config;
 use crate::error::ArbitrageError;
 use crate::market::Market;
-use crate::orderbook::OutcomeBook;
+use crate::orderbook::aggregator::cumulative_depth_up_to;
+use crate::orderbook::{OutcomeBook, PriceLevel};
+use crate::trading::order::OrderPrecision;
 
 /// Check for arbitrage opportunity given order books.
 #[instrument(skip(up_book, down_book, config), fields(market = %market.slug))]
@@ -17,11 +20,38 @@ pub fn check_arbitrage(
     down_book: &OutcomeBook,
     config: &Config,
 ) -> Result<Option<ArbitrageOpportunity>, ArbitrageError> {
+    // Guard against books left over from a previous market
+    if config.verify_book_tokens && !books_match_market(market, up_book, down_book) {
+        warn!(
+            up_token = %up_book.token_id,
+            down_token = %down_book.token_id,
+            "Order books do not belong to current market, skipping"
+        );
+        return Ok(None);
+    }
+
+    // Avoid entering when one leg might not fill before the book freezes; once
+    // the market stops accepting orders every order would be rejected
+    if let Some(reason) = settlement_window_reason(market, config.min_seconds_to_close) {
+        if config.stop_new_entries_before_close || market.stopped_accepting_orders() {
+            info!(reason = %reason, "Opportunity rejected by settlement window");
+            return Ok(None);
+        }
+        warn!(reason = %reason, "Evaluating inside settlement window (not enforced)");
+    }
+
+    // Don't act on prices the exchange served from an old snapshot
+    let now = OffsetDateTime::now_utc();
+    if let Some(reason) = stale_book_reason(up_book, down_book, config.max_book_age_ms, now) {
+        info!(reason = %reason, "Opportunity rejected by stale book");
+        return Ok(None);
+    }
+
     // Validate books are not inverted
     if up_book.is_inverted() {
         let best_ask = up_book.best_ask().unwrap_or_default();
         let best_bid = up_book.best_bid().unwrap_or_default();
-        warn!(
+        debug!(
             side = "UP",
             best_ask = %best_ask,
             best_bid = %best_bid,
@@ -37,7 +67,7 @@ pub fn check_arbitrage(
     if down_book.is_inverted() {
         let best_ask = down_book.best_ask().unwrap_or_default();
         let best_bid = down_book.best_bid().unwrap_or_default();
-        warn!(
+        debug!(
             side = "DOWN",
             best_ask = %best_ask,
             best_bid = %best_bid,
@@ -56,20 +86,88 @@ pub fn check_arbitrage(
         return Ok(None);
     }
 
-    // Calculate opportunity
-    let opportunity = calculate_opportunity(
+    // A USDC order size is sized in shares at the best-ask pair cost for the depth gate
+    let Some(order_shares) = order_shares_estimate(up_book, down_book, config) else {
+        info!(
+            order_size = %config.order_size,
+            "Order size buys fewer than 5 shares at this pair cost, skipping"
+        );
+        return Ok(None);
+    };
+
+    // A lone thin level at window open is noise, not a price
+    if let Some(reason) =
+        shallow_book_reason(up_book, down_book, config.min_book_levels, order_shares)
+    {
+        info!(reason = %reason, "Opportunity rejected by book depth levels");
+        return Ok(None);
+    }
+
+    // Calculate opportunity, sized by the ladder when one is configured
+    let opportunity = calculate_laddered_opportunity(
         market,
         up_book,
         down_book,
+        &config.size_ladder,
         config.order_size,
+        config.order_size_unit,
         config.target_pair_cost,
-    );
+    )
+    .map(|opp| opp.rounded(config.calc_decimals, config.calc_rounding));
+
+    // Reject thin books that may not hold the quoted price
+    if let Some(ref opp) = opportunity {
+        if let Some(reason) = thin_book_reason(
+            up_book,
+            down_book,
+            opp.up_price,
+            opp.down_price,
+            config.min_top_of_book_size,
+        ) {
+            info!(reason = %reason, "Opportunity rejected by depth gate");
+            return Ok(None);
+        }
+    }
+
+    // Enforce the bps floor on top of the cost ceiling
+    if let Some(ref opp) = opportunity {
+        let edge_bps = config.net_edge_bps(opp.profit_per_share);
+        if edge_bps < config.min_edge_bps {
+            info!(
+                edge_bps = %edge_bps,
+                min_edge_bps = %config.min_edge_bps,
+                "Opportunity rejected by minimum edge"
+            );
+            return Ok(None);
+        }
+    }
+
+    // No valid orders exist when the entry offset prices a leg below one tick
+    if let Some(ref opp) = opportunity {
+        let tick_size = market.tick_size_or(config.tick_size);
+        let prices = entry_prices(
+            opp.up_price,
+            opp.down_price,
+            config.entry_price_offset_bps,
+            opp.max_pair_cost,
+            tick_size,
+        );
+        if prices.is_none() {
+            info!(
+                entry_price_offset_bps = %config.entry_price_offset_bps,
+                tick_size = %tick_size,
+                "Opportunity rejected: entry price below one tick"
+            );
+            return Ok(None);
+        }
+    }
 
     if let Some(ref opp) = opportunity {
         info!(
             total_cost = %opp.total_cost,
             profit_per_share = %opp.profit_per_share,
             profit_pct = %opp.profit_pct,
+            score = %opp.score(),
             "Arbitrage opportunity detected"
         );
     } else {
@@ -85,6 +183,127 @@ pub fn check_arbitrage(
     Ok(opportunity)
 }
 
+/// Why either book's exchange timestamp is too old to trade on; `None` when fresh.
+///
+/// Books without a timestamp pass, as does everything when `max_age_ms` is 0.
+pub fn stale_book_reason(
+    up_book: &OutcomeBook,
+    down_book: &OutcomeBook,
+    max_age_ms: u64,
+    now: OffsetDateTime,
+) -> Option<String> {
+    if max_age_ms == 0 {
+        return None;
+    }
+    let max_age_ms = i64::try_from(max_age_ms).unwrap_or(i64::MAX);
+    [("UP", up_book), ("DOWN", down_book)].into_iter().find_map(|(side, book)| {
+        let age_ms = book.server_age_ms(now)?;
+        (age_ms > max_age_ms)
+            .then(|| format!("{} book is {}ms old (max {}ms)", side, age_ms, max_age_ms))
+    })
+}
+
+/// Check that both books belong to the given market.
+pub fn books_match_market(market: &Market, up_book: &OutcomeBook, down_book: &OutcomeBook) -> bool {
+    up_book.token_id == market.up_token_id && down_book.token_id == market.down_token_id
+}
+
+/// Check the market has at least `min_seconds` left before it stops accepting orders.
+///
+/// Returns a rejection reason when inside the window (0 disables), or once
+/// the market no longer accepts orders.
+pub fn settlement_window_reason(market: &Market, min_seconds: u64) -> Option<String> {
+    if market.stopped_accepting_orders() {
+        return Some("market no longer accepting orders".to_string());
+    }
+    if min_seconds == 0 {
+        return None;
+    }
+
+    let remaining = market.entry_time_remaining().map_or(0, |d| d.as_secs());
+    if remaining < min_seconds {
+        Some(format!(
+            "too close to settlement: {}s left < min {}s",
+            remaining, min_seconds
+        ))
+    } else {
+        None
+    }
+}
+
+/// Check both sides have enough ask depth at or better than their fill price.
+///
+/// Returns a rejection reason when either side is below `min_size` (0 disables).
+pub fn thin_book_reason(
+    up_book: &OutcomeBook,
+    down_book: &OutcomeBook,
+    up_price: Decimal,
+    down_price: Decimal,
+    min_size: Decimal,
+) -> Option<String> {
+    if min_size <= Decimal::ZERO {
+        return None;
+    }
+
+    let up_depth = cumulative_depth_up_to(&up_book.asks, up_price);
+    let down_depth = cumulative_depth_up_to(&down_book.asks, down_price);
+
+    if up_depth < min_size || down_depth < min_size {
+        Some(format!(
+            "thin book: UP depth {} / DOWN depth {} < min {}",
+            up_depth, down_depth, min_size
+        ))
+    } else {
+        None
+    }
+}
+
+/// Check both sides have at least `min_levels` ask levels.
+///
+/// A side with fewer levels still passes when its total ask depth covers
+/// `min_levels` orders of `order_size`. Returns a rejection reason otherwise
+/// (0 disables).
+pub fn shallow_book_reason(
+    up_book: &OutcomeBook,
+    down_book: &OutcomeBook,
+    min_levels: usize,
+    order_size: Decimal,
+) -> Option<String> {
+    if min_levels == 0 {
+        return None;
+    }
+
+    let min_depth = order_size * Decimal::from(min_levels);
+    let levels = |book: &OutcomeBook| book.asks.iter().filter(|l| l.size > Decimal::ZERO).count();
+    let shallow =
+        |book: &OutcomeBook| levels(book) < min_levels && book.total_ask_liquidity() < min_depth;
+
+    if shallow(up_book) || shallow(down_book) {
+        Some(format!(
+            "book too shallow: UP {} / DOWN {} ask levels < min {}",
+            levels(up_book),
+            levels(down_book),
+            min_levels
+        ))
+    } else {
+        None
+    }
+}
+
+/// `ORDER_SIZE` in shares at the books' best-ask pair cost, or `None` when it
+/// buys fewer than the 5-share minimum.
+fn order_shares_estimate(
+    up_book: &OutcomeBook,
+    down_book: &OutcomeBook,
+    config: &Config,
+) -> Option<Decimal> {
+    let best_cost =
+        up_book.best_ask().unwrap_or_default() + down_book.best_ask().unwrap_or_default();
+    config
+        .order_size_unit
+        .shares(config.order_size, best_cost, OrderPrecision::SIZE_DECIMALS)
+}
+
 /// Quick check if books might have an opportunity (without full calculation).
 pub fn quick_opportunity_check(
     up_book: &OutcomeBook,
@@ -97,13 +316,29 @@ pub fn quick_opportunity_check(
     }
 }
 
+/// Prescreen from the cheap `/price` buy quotes before fetching full books.
+///
+/// Returns false when the combined price is more than `margin` above `threshold`,
+/// i.e. clearly no opportunity, so the `/book` fetches can be skipped.
+pub fn prescreen(
+    up_price: Decimal,
+    down_price: Decimal,
+    threshold: Decimal,
+    margin: Decimal,
+) -> bool {
+    up_price + down_price <= threshold + margin
+}
+
 /// Get diagnostic information about why there's no opportunity.
 pub fn diagnose_no_opportunity(
+    market: &Market,
     up_book: &OutcomeBook,
     down_book: &OutcomeBook,
-    target_size: Decimal,
-    threshold: Decimal,
+    config: &Config,
 ) -> NoOpportunityDiagnosis {
+    let target_size =
+        order_shares_estimate(up_book, down_book, config).unwrap_or(config.order_size);
+    let threshold = config.target_pair_cost;
     let best_ask_up = up_book.best_ask();
     let best_ask_down = down_book.best_ask();
 
@@ -117,15 +352,27 @@ pub fn diagnose_no_opportunity(
     let has_sufficient_liquidity = up_liquidity >= target_size && down_liquidity >= target_size;
 
     // Calculate fill-based total if possible
-    let fill_total = {
+    let fills = {
         use crate::orderbook::calculate_fill_price;
         let up_fill = calculate_fill_price(&up_book.asks, target_size).ok();
         let down_fill = calculate_fill_price(&down_book.asks, target_size).ok();
         match (up_fill, down_fill) {
-            (Some(u), Some(d)) => Some(u.worst_price + d.worst_price),
+            (Some(u), Some(d)) => Some((u.worst_price, d.worst_price)),
             _ => None,
         }
     };
+    let fill_total = fills.map(|(up, down)| up + down);
+
+    // Explain a rejection by the settlement window, by too few levels, or by
+    // the depth gate when the cost test passes
+    let rejection_reason = settlement_window_reason(market, config.entry_cutoff_seconds())
+        .or_else(|| shallow_book_reason(up_book, down_book, config.min_book_levels, target_size))
+        .or_else(|| match fills {
+            Some((up, down)) if up + down <= threshold => {
+                thin_book_reason(up_book, down_book, up, down, config.min_top_of_book_size)
+            }
+            _ => None,
+        });
 
     NoOpportunityDiagnosis {
         best_ask_up,
@@ -136,9 +383,83 @@ pub fn diagnose_no_opportunity(
         up_liquidity,
         down_liquidity,
         has_sufficient_liquidity,
+        rejection_reason,
+    }
+}
+
+/// Tolerates transiently inverted books across consecutive scans.
+///
+/// Thin books cross briefly, so an inversion counts as no opportunity until it
+/// persists past the tolerance; after that the `BookInverted` error is passed on.
+#[derive(Debug, Default)]
+pub struct InversionTracker {
+    /// Consecutive scans with an inverted book.
+    consecutive: u32,
+    /// Inverted scans tolerated before erroring.
+    tolerance: u32,
+}
+
+impl InversionTracker {
+    /// Create a tracker tolerating `tolerance` consecutive inverted scans.
+    pub fn new(tolerance: u32) -> Self {
+        Self {
+            consecutive: 0,
+            tolerance,
+        }
+    }
+
+    /// Consecutive scans that have seen an inverted book.
+    pub fn consecutive(&self) -> u32 {
+        self.consecutive
+    }
+
+    /// Filter a `check_arbitrage` result, downgrading tolerated inversions to `Ok(None)`.
+    pub fn filter(
+        &mut self,
+        result: Result<Option<ArbitrageOpportunity>, ArbitrageError>,
+    ) -> Result<Option<ArbitrageOpportunity>, ArbitrageError> {
+        let Err(e @ ArbitrageError::BookInverted { .. }) = result else {
+            self.consecutive = 0;
+            return result;
+        };
+
+        self.consecutive = self.consecutive.saturating_add(1);
+        if self.consecutive > self.tolerance {
+            return Err(e);
+        }
+        // Log once per streak
+        if self.consecutive == 1 {
+            debug!(error = %e, tolerance = self.tolerance, "Tolerating inverted book");
+        }
+        Ok(None)
     }
 }
 
+/// Format the top `depth` bid/ask levels of both books as a compact table.
+pub fn format_top_of_book(up_book: &OutcomeBook, down_book: &OutcomeBook, depth: usize) -> String {
+    let cell = |levels: &[PriceLevel], i: usize| {
+        levels
+            .get(i)
+            .map_or_else(|| "-".to_string(), |l| format!("{} x {}", l.price, l.size))
+    };
+
+    let mut table = format!(
+        "{:<2} {:>14} {:>14} {:>14} {:>14}",
+        "#", "UP bid", "UP ask", "DOWN bid", "DOWN ask"
+    );
+    for i in 0..depth {
+        table.push_str(&format!(
+            "\n{:<2} {:>14} {:>14} {:>14} {:>14}",
+            i + 1,
+            cell(&up_book.bids, i),
+            cell(&up_book.asks, i),
+            cell(&down_book.bids, i),
+            cell(&down_book.asks, i),
+        ));
+    }
+    table
+}
+
 /// Diagnostic information for debugging.
 #[derive(Debug, Clone)]
 pub struct NoOpportunityDiagnosis {
@@ -158,6 +479,8 @@ pub struct NoOpportunityDiagnosis {
     pub down_liquidity: Decimal,
     /// Whether there's enough liquidity for target size.
     pub has_sufficient_liquidity: bool,
+    /// Why a cost-passing opportunity was rejected, if it was.
+    pub rejection_reason: Option<String>,
 }
 
 impl std::fmt::Display for NoOpportunityDiagnosis {
@@ -172,48 +495,29 @@ impl std::fmt::Display for NoOpportunityDiagnosis {
             self.fill_total.map(|d| d.to_string()).unwrap_or_else(|| "N/A".to_string()),
             self.up_liquidity,
             self.down_liquidity,
-        )
+        )?;
+        if let Some(reason) = &self.rejection_reason {
+            write!(f, " | rejected: {}", reason)?;
+        }
+        Ok(())
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::config::OrderSizeUnit;
     use crate::market::Outcome;
-    use crate::orderbook::PriceLevel;
     use rust_decimal_macros::dec;
     use time::OffsetDateTime;
 
     fn test_config() -> Config {
         Config {
             polymarket_private_key: "0x1234".to_string(),
-            polymarket_api_key: None,
-            polymarket_api_secret: None,
-            polymarket_api_passphrase: None,
-            polymarket_signature_type: 0,
-            polymarket_funder: None,
-            target_pair_cost: dec!(0.991),
             order_size: dec!(10),
-            order_type: "FOK".to_string(),
-            balance_margin: dec!(1.2),
-            dry_run: true,
-            sim_balance: dec!(100),
-            cooldown_seconds: 10,
-            polymarket_market_slug: None,
-            use_wss: false,
             polymarket_ws_url: "wss://test".to_string(),
             polymarket_clob_url: "https://test".to_string(),
-            port: 8080,
-            rust_log: "info".to_string(),
-            verbose: false,
-            http_timeout_ms: 2000,
-            http_pool_size: 10,
-            order_timeout_ms: 500,
-            order_poll_interval_ms: 50,
-            ws_reconnect_max_delay_s: 30,
-            ws_heartbeat_interval_s: 30,
-            metrics_enabled: true,
-            metrics_port: 9090,
+            ..Config::test_default()
         }
     }
 
@@ -226,16 +530,25 @@ mod tests {
             start_timestamp: 0,
             end_timestamp: 900,
             question: None,
+            neg_risk: None,
+            condition_id: None,
+            accepting_orders_until: None,
+            tick_size: None,
         }
     }
 
     fn test_book(outcome: Outcome, asks: Vec<(Decimal, Decimal)>) -> OutcomeBook {
+        let token_id = match outcome {
+            Outcome::Up => "up-token",
+            Outcome::Down => "down-token",
+        };
         OutcomeBook {
-            token_id: "token".to_string(),
+            token_id: token_id.to_string(),
             outcome,
             bids: vec![],
             asks: asks.into_iter().map(|(p, s)| PriceLevel::new(p, s)).collect(),
             updated_at: OffsetDateTime::now_utc(),
+            as_of: None,
         }
     }
 
@@ -251,6 +564,47 @@ mod tests {
         assert!(result.is_some());
     }
 
+    #[test]
+    fn usdc_order_size_is_sized_in_shares_before_detection() {
+        let market = test_market();
+        let mut config = test_config();
+        config.order_size_unit = OrderSizeUnit::Usdc;
+        config.order_size = dec!(100);
+        // $100 at the 0.90 best-ask pair is 111.11 shares, which reach UP's 0.47 level
+        let up_book =
+            test_book(Outcome::Up, vec![(dec!(0.45), dec!(100)), (dec!(0.47), dec!(100))]);
+        let down_book = test_book(Outcome::Down, vec![(dec!(0.45), dec!(200))]);
+
+        let opp = check_arbitrage(&market, &up_book, &down_book, &config).unwrap().unwrap();
+
+        // Re-sized at the 0.92 worst-fill pair, the trade stays within $100
+        assert_eq!(opp.order_size, dec!(108.69));
+        assert_eq!(opp.total_cost, dec!(0.92));
+        assert!(opp.total_investment <= dec!(100));
+
+        // A notional below 5 shares is skipped rather than raised over budget
+        config.order_size = dec!(4);
+        assert!(check_arbitrage(&market, &up_book, &down_book, &config).unwrap().is_none());
+    }
+
+    #[test]
+    fn higher_ladder_tier_prices_orders_to_its_own_max_cost() {
+        let market = test_market();
+        let mut config = test_config();
+        config.target_pair_cost = dec!(0.98);
+        config.size_ladder = vec![(dec!(0.995), dec!(5))];
+        let up_book = test_book(Outcome::Up, vec![(dec!(0.48), dec!(100))]);
+        let down_book = test_book(Outcome::Down, vec![(dec!(0.51), dec!(100))]);
+
+        // 0.99 is over the flat 0.98 target; only the 0.995 tier accepts it
+        let opp = check_arbitrage(&market, &up_book, &down_book, &config).unwrap().unwrap();
+        assert_eq!(opp.max_pair_cost, dec!(0.995));
+
+        // The legs go out at the prices the tier allowed, not clamped to 0.98
+        let (up, down) = crate::arbitrage::executor::order_params(&opp, &config).unwrap();
+        assert_eq!((up.price, down.price), (dec!(0.48), dec!(0.51)));
+    }
+
     #[test]
     fn check_arbitrage_returns_none_when_costly() {
         let market = test_market();
@@ -263,6 +617,284 @@ mod tests {
         assert!(result.is_none());
     }
 
+    #[test]
+    fn check_arbitrage_enforces_min_edge_below_cost_ceiling() {
+        let market = test_market();
+        let mut config = test_config();
+        // 0.99 clears the 0.991 ceiling with 100 bps of edge
+        let up_book = test_book(Outcome::Up, vec![(dec!(0.48), dec!(100))]);
+        let down_book = test_book(Outcome::Down, vec![(dec!(0.51), dec!(100))]);
+
+        config.min_edge_bps = dec!(150);
+        assert!(check_arbitrage(&market, &up_book, &down_book, &config).unwrap().is_none());
+
+        config.min_edge_bps = dec!(100);
+        assert!(check_arbitrage(&market, &up_book, &down_book, &config).unwrap().is_some());
+
+        // Fees count against the floor
+        config.fee_rate_bps = dec!(10);
+        assert!(check_arbitrage(&market, &up_book, &down_book, &config).unwrap().is_none());
+    }
+
+    #[test]
+    fn check_arbitrage_skips_entry_prices_below_one_tick() {
+        let market = test_market();
+        let mut config = test_config();
+        let up_book = test_book(Outcome::Up, vec![(dec!(0.48), dec!(100))]);
+        let down_book = test_book(Outcome::Down, vec![(dec!(0.51), dec!(100))]);
+
+        // 99% inside the asks prices both legs under the 0.01 tick
+        config.entry_price_offset_bps = dec!(9900);
+        assert!(check_arbitrage(&market, &up_book, &down_book, &config).unwrap().is_none());
+
+        config.entry_price_offset_bps = dec!(100);
+        assert!(check_arbitrage(&market, &up_book, &down_book, &config).unwrap().is_some());
+    }
+
+    #[test]
+    fn check_arbitrage_skips_books_from_other_market() {
+        let market = test_market();
+        let config = test_config();
+        let up_book = test_book(Outcome::Up, vec![(dec!(0.48), dec!(100))]);
+        let mut down_book = test_book(Outcome::Down, vec![(dec!(0.51), dec!(100))]);
+        down_book.token_id = "previous-market-down".to_string();
+
+        let result = check_arbitrage(&market, &up_book, &down_book, &config).unwrap();
+
+        assert!(result.is_none());
+    }
+
+    fn inverted_books() -> (OutcomeBook, OutcomeBook) {
+        let mut up_book = test_book(Outcome::Up, vec![(dec!(0.48), dec!(100))]);
+        up_book.bids = vec![PriceLevel::new(dec!(0.50), dec!(100))];
+        let down_book = test_book(Outcome::Down, vec![(dec!(0.51), dec!(100))]);
+        (up_book, down_book)
+    }
+
+    #[test]
+    fn transient_inversion_is_tolerated() {
+        let market = test_market();
+        let config = test_config();
+        let (up_inverted, down_book) = inverted_books();
+        let up_book = test_book(Outcome::Up, vec![(dec!(0.48), dec!(100))]);
+        let mut tracker = InversionTracker::new(2);
+
+        for _ in 0..2 {
+            let result = check_arbitrage(&market, &up_inverted, &down_book, &config);
+            assert!(tracker.filter(result).unwrap().is_none());
+        }
+
+        // A clean scan resets the streak
+        let result = check_arbitrage(&market, &up_book, &down_book, &config);
+        assert!(tracker.filter(result).unwrap().is_some());
+        assert_eq!(tracker.consecutive(), 0);
+
+        let result = check_arbitrage(&market, &up_inverted, &down_book, &config);
+        assert!(tracker.filter(result).unwrap().is_none());
+    }
+
+    #[test]
+    fn persistent_inversion_errors() {
+        let market = test_market();
+        let config = test_config();
+        let (up_book, down_book) = inverted_books();
+        let mut tracker = InversionTracker::new(2);
+
+        for _ in 0..2 {
+            let result = check_arbitrage(&market, &up_book, &down_book, &config);
+            assert!(tracker.filter(result).is_ok());
+        }
+
+        let result = check_arbitrage(&market, &up_book, &down_book, &config);
+        assert!(matches!(
+            tracker.filter(result),
+            Err(ArbitrageError::BookInverted { ref side, .. }) if side == "UP"
+        ));
+        assert_eq!(tracker.consecutive(), 3);
+    }
+
+    #[test]
+    fn check_arbitrage_rejects_thin_book() {
+        let market = test_market();
+        let mut config = test_config();
+        config.min_top_of_book_size = dec!(50);
+        // Cheap enough to pass the cost test, but only 20 shares on the DOWN side
+        let up_book = test_book(Outcome::Up, vec![(dec!(0.48), dec!(100))]);
+        let down_book = test_book(Outcome::Down, vec![(dec!(0.51), dec!(20))]);
+
+        let result = check_arbitrage(&market, &up_book, &down_book, &config).unwrap();
+        assert!(result.is_none());
+
+        let diagnosis = diagnose_no_opportunity(&market, &up_book, &down_book, &config);
+        assert!(diagnosis.rejection_reason.unwrap().contains("thin book"));
+    }
+
+    #[test]
+    fn check_arbitrage_rejects_single_thin_level() {
+        let market = test_market();
+        let mut config = test_config();
+        config.min_book_levels = 3;
+        // A lone 10-share level on UP at window open
+        let up_book = test_book(Outcome::Up, vec![(dec!(0.40), dec!(10))]);
+        let down_book = test_book(
+            Outcome::Down,
+            vec![(dec!(0.51), dec!(100)), (dec!(0.52), dec!(100)), (dec!(0.53), dec!(100))],
+        );
+
+        let result = check_arbitrage(&market, &up_book, &down_book, &config).unwrap();
+        assert!(result.is_none());
+
+        let diagnosis = diagnose_no_opportunity(&market, &up_book, &down_book, &config);
+        assert!(diagnosis.rejection_reason.unwrap().contains("book too shallow"));
+    }
+
+    #[test]
+    fn check_arbitrage_accepts_multi_level_or_deep_book() {
+        let market = test_market();
+        let mut config = test_config();
+        config.min_book_levels = 3;
+        let up_book = test_book(
+            Outcome::Up,
+            vec![(dec!(0.48), dec!(100)), (dec!(0.49), dec!(100)), (dec!(0.50), dec!(100))],
+        );
+        let down_book = test_book(
+            Outcome::Down,
+            vec![(dec!(0.51), dec!(100)), (dec!(0.52), dec!(100)), (dec!(0.53), dec!(100))],
+        );
+        assert!(check_arbitrage(&market, &up_book, &down_book, &config).unwrap().is_some());
+
+        // One level deep enough for three orders is trusted too
+        let up_book = test_book(Outcome::Up, vec![(dec!(0.48), config.order_size * dec!(3))]);
+        assert!(check_arbitrage(&market, &up_book, &down_book, &config).unwrap().is_some());
+    }
+
+    #[test]
+    fn check_arbitrage_rejects_stale_server_book() {
+        let market = test_market();
+        let mut config = test_config();
+        config.max_book_age_ms = 2000;
+        let up_book = test_book(Outcome::Up, vec![(dec!(0.48), dec!(100))]);
+        let mut down_book = test_book(Outcome::Down, vec![(dec!(0.51), dec!(100))]);
+        down_book.as_of = Some(OffsetDateTime::now_utc() - time::Duration::seconds(5));
+
+        let result = check_arbitrage(&market, &up_book, &down_book, &config).unwrap();
+        assert!(result.is_none());
+        let reason =
+            stale_book_reason(&up_book, &down_book, 2000, OffsetDateTime::now_utc()).unwrap();
+        assert!(reason.starts_with("DOWN book"), "{}", reason);
+
+        // The same snapshot trades when the bound is disabled
+        config.max_book_age_ms = 0;
+        assert!(check_arbitrage(&market, &up_book, &down_book, &config).unwrap().is_some());
+
+        // A fresh timestamp passes the bound
+        config.max_book_age_ms = 2000;
+        down_book.as_of = Some(OffsetDateTime::now_utc());
+        assert!(check_arbitrage(&market, &up_book, &down_book, &config).unwrap().is_some());
+    }
+
+    fn market_closing_in(seconds: i64) -> Market {
+        let now = OffsetDateTime::now_utc().unix_timestamp();
+        Market {
+            start_timestamp: now - 900 + seconds,
+            end_timestamp: now + seconds,
+            ..test_market()
+        }
+    }
+
+    #[test]
+    fn check_arbitrage_rejects_inside_settlement_window() {
+        let market = market_closing_in(10);
+        let mut config = test_config();
+        config.min_seconds_to_close = 30;
+        let up_book = test_book(Outcome::Up, vec![(dec!(0.48), dec!(100))]);
+        let down_book = test_book(Outcome::Down, vec![(dec!(0.51), dec!(100))]);
+
+        let result = check_arbitrage(&market, &up_book, &down_book, &config).unwrap();
+        assert!(result.is_none());
+
+        let diagnosis = diagnose_no_opportunity(&market, &up_book, &down_book, &config);
+        assert!(diagnosis
+            .rejection_reason
+            .unwrap()
+            .contains("too close to settlement"));
+    }
+
+    #[test]
+    fn check_arbitrage_accepts_outside_settlement_window() {
+        let market = market_closing_in(120);
+        let mut config = test_config();
+        config.min_seconds_to_close = 30;
+        let up_book = test_book(Outcome::Up, vec![(dec!(0.48), dec!(100))]);
+        let down_book = test_book(Outcome::Down, vec![(dec!(0.51), dec!(100))]);
+
+        let result = check_arbitrage(&market, &up_book, &down_book, &config).unwrap();
+
+        assert!(result.is_some());
+    }
+
+    #[test]
+    fn settlement_window_not_enforced_when_entries_allowed() {
+        let market = market_closing_in(10);
+        let mut config = test_config();
+        config.min_seconds_to_close = 30;
+        config.stop_new_entries_before_close = false;
+        let up_book = test_book(Outcome::Up, vec![(dec!(0.48), dec!(100))]);
+        let down_book = test_book(Outcome::Down, vec![(dec!(0.51), dec!(100))]);
+
+        let result = check_arbitrage(&market, &up_book, &down_book, &config).unwrap();
+
+        assert!(result.is_some());
+        assert_eq!(config.entry_cutoff_seconds(), 0);
+    }
+
+    #[test]
+    fn entries_stop_when_market_stops_accepting_orders() {
+        let now = OffsetDateTime::now_utc().unix_timestamp();
+        let mut config = test_config();
+        config.min_seconds_to_close = 30;
+        let up_book = test_book(Outcome::Up, vec![(dec!(0.48), dec!(100))]);
+        let down_book = test_book(Outcome::Down, vec![(dec!(0.51), dec!(100))]);
+
+        // 120s to close but orders are refused in 20s: inside the 30s margin
+        let market = Market {
+            accepting_orders_until: Some(now + 20),
+            tick_size: None,
+            ..market_closing_in(120)
+        };
+        assert!(check_arbitrage(&market, &up_book, &down_book, &config).unwrap().is_none());
+
+        // Past the cutoff, entries stop even with the margin disabled
+        config.min_seconds_to_close = 0;
+        config.stop_new_entries_before_close = false;
+        let market = Market {
+            accepting_orders_until: Some(now - 1),
+            tick_size: None,
+            ..market_closing_in(120)
+        };
+        assert!(check_arbitrage(&market, &up_book, &down_book, &config).unwrap().is_none());
+        let diagnosis = diagnose_no_opportunity(&market, &up_book, &down_book, &config);
+        assert!(diagnosis.rejection_reason.unwrap().contains("no longer accepting orders"));
+
+        // Without a cutoff the close is the deadline
+        assert!(check_arbitrage(&market_closing_in(120), &up_book, &down_book, &config)
+            .unwrap()
+            .is_some());
+    }
+
+    #[test]
+    fn check_arbitrage_passes_depth_gate_with_deep_book() {
+        let market = test_market();
+        let mut config = test_config();
+        config.min_top_of_book_size = dec!(50);
+        let up_book = test_book(Outcome::Up, vec![(dec!(0.48), dec!(100))]);
+        let down_book = test_book(Outcome::Down, vec![(dec!(0.51), dec!(100))]);
+
+        let result = check_arbitrage(&market, &up_book, &down_book, &config).unwrap();
+
+        assert!(result.is_some());
+    }
+
     #[test]
     fn quick_opportunity_check_works() {
         let up_book = test_book(Outcome::Up, vec![(dec!(0.48), dec!(100))]);
@@ -271,4 +903,34 @@ mod tests {
         assert!(quick_opportunity_check(&up_book, &down_book, dec!(0.991)));
         assert!(!quick_opportunity_check(&up_book, &down_book, dec!(0.98)));
     }
+
+    #[test]
+    fn prescreen_skips_only_clearly_priced_out_pairs() {
+        assert!(prescreen(dec!(0.48), dec!(0.51), dec!(0.991), dec!(0.02)));
+        // 1.005 is within the margin of 0.991, so the books are still worth a look
+        assert!(prescreen(dec!(0.50), dec!(0.505), dec!(0.991), dec!(0.02)));
+        assert!(!prescreen(dec!(0.52), dec!(0.51), dec!(0.991), dec!(0.02)));
+    }
+
+    #[test]
+    fn format_top_of_book_renders_three_levels() {
+        let up_asks = vec![(dec!(0.48), dec!(50)), (dec!(0.49), dec!(100))];
+        let mut up_book = test_book(Outcome::Up, up_asks);
+        up_book.bids = vec![PriceLevel::new(dec!(0.47), dec!(100))];
+        let mut down_book = test_book(Outcome::Down, vec![(dec!(0.52), dec!(40))]);
+        down_book.bids = vec![
+            PriceLevel::new(dec!(0.50), dec!(20)),
+            PriceLevel::new(dec!(0.49), dec!(10)),
+        ];
+
+        let table = format_top_of_book(&up_book, &down_book, 3);
+
+        let expected = [
+            "#          UP bid         UP ask       DOWN bid       DOWN ask",
+            "1      0.47 x 100      0.48 x 50      0.50 x 20      0.52 x 40",
+            "2               -     0.49 x 100      0.49 x 10              -",
+            "3               -              -              -              -",
+        ];
+        assert_eq!(table, expected.join("\n"));
+    }
 }
@@ -1,25 +1,52 @@
 //! Arbitrage trade execution logic.
 
-use std::time::Instant;
-
+use std::collections::{HashMap, VecDeque};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use rust_decimal::prelude::ToPrimitive;
 use rust_decimal::Decimal;
-use tracing::{error, info, instrument, warn};
-
-use super::calculator::ArbitrageOpportunity;
-use crate::config::Config;
+use serde::Serialize;
+use tracing::{debug, error, info, instrument, warn, Span};
+
+use super::calculator::{entry_prices, ArbitrageOpportunity};
+use super::exposure::ExposureGuard;
+use super::fill_stats::FillStats;
+use super::intent::{trade_intent_key, TradeIntentLog};
+use super::journal::{JournalRow, TradeJournal};
+use super::settlement::{PendingSettlement, SettlementQueue, SETTLEMENT_MAX_ATTEMPTS};
+use super::simulator::{simulate_leg_fill, simulated_unwind_proceeds, SimulatedFill};
+use super::snapshot::SnapshotWriter;
+use super::summary::SummaryLog;
+use crate::config::{Config, DryRunMode, FirstLegPolicy, LegMode, MakerLeg};
 use crate::error::TradingError;
-use crate::market::{Outcome, PolymarketClient};
+use crate::market::client::PositionResponse;
+use crate::market::{Market, Outcome, PolymarketClient};
+use crate::metrics;
+use crate::orderbook::aggregator::{calculate_fill_price, cumulative_depth_up_to};
+use crate::orderbook::PriceLevel;
 use crate::trading::execution::{
-    cancel_orders, submit_order, wait_for_terminal_order, DEFAULT_ORDER_TIMEOUT,
-    DEFAULT_POLL_INTERVAL,
+    build_order_request, cancel_orders, not_found_grace_from_config, order_timeout_from_config,
+    poll_interval_from_config, submit_order, wait_for_terminal_order, OrderRequest,
 };
-use crate::trading::order::{OrderParams, Side, TimeInForce};
+use crate::trading::order::{OrderParams, OrderState, Side, TimeInForce};
+use crate::trading::Redemption;
+use crate::trading::user_ws::UserWebSocket;
+use crate::utils::alert::{AlertEvent, AlertNotifier};
 
 /// Result of attempting to execute an arbitrage.
-#[derive(Debug)]
+///
+/// Serializes tagged by `result`, with decimals as strings.
+#[derive(Debug, Serialize)]
+#[serde(tag = "result", rename_all = "snake_case")]
 pub enum ExecutionResult {
     /// Both legs filled successfully.
     BothFilled {
+        /// Execution ID shared by both legs' log lines.
+        exec_id: u64,
         /// UP order ID.
         up_order_id: String,
         /// DOWN order ID.
@@ -33,6 +60,8 @@ pub enum ExecutionResult {
     },
     /// Only one leg filled; attempted unwind.
     PartialFill {
+        /// Execution ID shared by both legs' log lines.
+        exec_id: u64,
         /// Which leg filled.
         filled_leg: Outcome,
         /// Filled size.
@@ -43,19 +72,63 @@ pub enum ExecutionResult {
         unwind_result: Option<String>,
     },
     /// Neither leg filled.
-    NeitherFilled,
+    NeitherFilled {
+        /// Execution ID shared by both legs' log lines.
+        exec_id: u64,
+    },
     /// Simulation mode - no real orders.
     Simulated {
+        /// Execution ID shared by both legs' log lines.
+        exec_id: u64,
+        /// Pairs filled (may be less than the order size).
+        filled_size: Decimal,
         /// Would invest this amount.
         would_invest: Decimal,
         /// Would profit this amount.
         would_profit: Decimal,
     },
+    /// Sign-only dry run - orders built and signed but not submitted.
+    Signed {
+        /// Execution ID shared by both legs' log lines.
+        exec_id: u64,
+        /// Signed UP order, serialized without its signature.
+        #[serde(serialize_with = "serialize_redacted")]
+        up_order: Box<OrderRequest>,
+        /// Signed DOWN order, serialized without its signature.
+        #[serde(serialize_with = "serialize_redacted")]
+        down_order: Box<OrderRequest>,
+    },
     /// Skipped due to cooldown.
     CooldownActive {
         /// Remaining seconds.
         remaining_seconds: u64,
     },
+    /// Skipped because the circuit breaker is open.
+    CircuitOpen {
+        /// Consecutive failures that tripped the breaker.
+        consecutive_failures: u32,
+        /// Seconds until the breaker allows a retry.
+        remaining_seconds: u64,
+    },
+    /// Skipped because a balanced position is already held.
+    AlreadyPositioned {
+        /// Balanced UP/DOWN pairs already held.
+        held_pairs: Decimal,
+    },
+    /// Skipped because this exact opportunity was already acted on.
+    DuplicateOpportunity,
+    /// Skipped because this market's pair already filled this window.
+    AlreadyExecuted {
+        /// Trade intent key.
+        intent: String,
+    },
+    /// Skipped because the trade would exceed the total exposure cap.
+    ExposureLimitReached {
+        /// USDC invested in markets not yet settled.
+        current: Decimal,
+        /// Configured cap.
+        cap: Decimal,
+    },
     /// Skipped due to insufficient balance.
     InsufficientBalance {
         /// Required balance.
@@ -65,6 +138,112 @@ pub enum ExecutionResult {
     },
 }
 
+impl ExecutionResult {
+    /// Execution ID of an attempt that reached order handling; `None` for skips.
+    pub fn exec_id(&self) -> Option<u64> {
+        match self {
+            Self::BothFilled { exec_id, .. }
+            | Self::PartialFill { exec_id, .. }
+            | Self::NeitherFilled { exec_id }
+            | Self::Simulated { exec_id, .. }
+            | Self::Signed { exec_id, .. } => Some(*exec_id),
+            _ => None,
+        }
+    }
+
+    /// Whether at least one leg filled, for real or in simulation.
+    pub fn has_fill(&self) -> bool {
+        matches!(
+            self,
+            Self::BothFilled { .. } | Self::PartialFill { .. } | Self::Simulated { .. }
+        )
+    }
+}
+
+impl std::fmt::Display for ExecutionResult {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::BothFilled {
+                exec_id,
+                up_order_id,
+                down_order_id,
+                up_filled_size,
+                down_filled_size,
+                actual_investment,
+            } => write!(
+                f,
+                "#{} both filled: UP {} + DOWN {} for ${} (orders {}, {})",
+                exec_id,
+                up_filled_size,
+                down_filled_size,
+                actual_investment,
+                up_order_id,
+                down_order_id
+            ),
+            Self::PartialFill {
+                exec_id,
+                filled_leg,
+                filled_size,
+                unwind_attempted,
+                unwind_result,
+            } => {
+                write!(f, "#{} partial fill: {} {} filled", exec_id, filled_leg, filled_size)?;
+                match unwind_result {
+                    Some(unwind) => write!(f, ", unwind: {}", unwind),
+                    None if *unwind_attempted => write!(f, ", unwind attempted"),
+                    None => write!(f, ", not unwound"),
+                }
+            }
+            Self::NeitherFilled { exec_id } => write!(f, "#{} neither leg filled", exec_id),
+            Self::Simulated {
+                exec_id,
+                filled_size,
+                would_invest,
+                would_profit,
+            } => write!(
+                f,
+                "#{} simulated: {} pairs for ${}, profit ${}",
+                exec_id, filled_size, would_invest, would_profit
+            ),
+            Self::Signed { exec_id, .. } => write!(f, "#{} signed, not submitted", exec_id),
+            Self::CooldownActive { remaining_seconds } => {
+                write!(f, "skipped: cooldown, {}s remaining", remaining_seconds)
+            }
+            Self::CircuitOpen {
+                consecutive_failures,
+                remaining_seconds,
+            } => write!(
+                f,
+                "skipped: circuit open after {} failures, {}s remaining",
+                consecutive_failures, remaining_seconds
+            ),
+            Self::AlreadyPositioned { held_pairs } => {
+                write!(f, "skipped: already holding {} pairs", held_pairs)
+            }
+            Self::DuplicateOpportunity => write!(f, "skipped: duplicate opportunity"),
+            Self::AlreadyExecuted { intent } => write!(f, "skipped: already executed {}", intent),
+            Self::ExposureLimitReached { current, cap } => {
+                write!(f, "skipped: exposure ${} at cap ${}", current, cap)
+            }
+            Self::InsufficientBalance {
+                required,
+                available,
+            } => write!(f, "skipped: balance ${} below required ${}", available, required),
+        }
+    }
+}
+
+/// Serialize a signed order with its signature redacted.
+fn serialize_redacted<S: serde::Serializer>(
+    order: &impl AsRef<OrderRequest>,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    order.as_ref().redacted().serialize(serializer)
+}
+
+/// Redemption attempts per settled market before it is left for manual redemption.
+const MAX_REDEMPTION_ATTEMPTS: u32 = 5;
+
 /// Executor state for tracking cooldowns and stats.
 #[derive(Debug)]
 pub struct ArbitrageExecutor {
@@ -80,10 +259,92 @@ pub struct ArbitrageExecutor {
     pub total_invested: Decimal,
     /// Total shares bought.
     pub total_shares_bought: Decimal,
+    /// Trading fees charged on fills, in USDC.
+    pub total_fees: Decimal,
+    /// Gas paid for redemptions, in POL.
+    pub total_gas: Decimal,
+    /// POL price in USDC that gas is netted from profit at.
+    pol_price_usdc: Decimal,
     /// Simulation balance.
     pub sim_balance: Decimal,
     /// Starting simulation balance.
     pub sim_start_balance: Decimal,
+    /// Realized profit from settled markets (None until a market settles).
+    pub realized_profit: Option<Decimal>,
+    /// Unhedged shares left after a failed unwind in the current market.
+    pub open_exposure: Decimal,
+    /// USDC invested per market that has not settled yet, by slug.
+    at_risk: HashMap<String, Decimal>,
+    /// Capital at risk across all executors in the process, checked against the cap.
+    exposure: ExposureGuard,
+    /// Consecutive execution failures.
+    consecutive_failures: u32,
+    /// Failures before the circuit breaker trips.
+    max_consecutive_failures: u32,
+    /// When the circuit breaker tripped.
+    circuit_opened_at: Option<Instant>,
+    /// Circuit breaker cooldown in seconds.
+    circuit_cooldown_seconds: u64,
+    /// Probability that each simulated leg is not rejected.
+    sim_fill_probability: f64,
+    /// Probability that one simulated leg loses the race while the other fills.
+    sim_leg_failure_rate: f64,
+    /// Simulated slippage in basis points.
+    sim_slippage_bps: Decimal,
+    /// RNG for all simulation randomness (seeded from `sim_seed` when set).
+    rng: StdRng,
+    /// User-channel WebSocket for fill notifications (polling when unset).
+    user_ws: Option<Arc<UserWebSocket>>,
+    /// Results of closed markets, oldest first.
+    history: VecDeque<MarketResult>,
+    /// Maximum closed-market results kept.
+    history_limit: usize,
+    /// Trade intents already filled, persisted across restarts.
+    intents: TradeIntentLog,
+    /// Prices and window of the last opportunity that filled at least one leg.
+    last_opportunity: Option<OpportunityKey>,
+    /// Filled vs rejected legs in the current market session.
+    fill_stats: FillStats,
+    /// ID of the most recent execution attempt, shared with the other executors
+    /// in the process so IDs increase from 1 and never repeat across markets.
+    last_exec_id: Arc<AtomicU64>,
+    /// Webhook for fill and failure alerts (none when not configured).
+    alerts: Option<AlertNotifier>,
+    /// Writer for book snapshots of executions (none when not configured).
+    snapshots: Option<SnapshotWriter>,
+    /// CSV journal of fills and settlements (none when not configured).
+    journal: Option<TradeJournal>,
+    /// Daily files of closed-market results (none when not configured).
+    summaries: Option<SummaryLog>,
+    /// Closed markets whose realized profit waits on resolution.
+    settlements: SettlementQueue,
+    /// Settled markets whose winning shares have not been redeemed yet.
+    redemptions: SettlementQueue,
+    /// Redemptions taken from the queue whose outcome is not back yet.
+    redeeming: usize,
+    /// Settled markets left for manual redemption.
+    failed_redemptions: u64,
+}
+
+/// Drop the cached balance when the exchange rejected a leg for lack of funds,
+/// so the next balance check sees the real figure instead of retrying blindly.
+fn refresh_balance_if_short(client: &PolymarketClient, error: &TradingError) {
+    if let TradingError::InsufficientFunds { reason } = error {
+        warn!(reason = %reason, "Exchange reports insufficient balance or allowance");
+        client.invalidate_balance_cache();
+    }
+}
+
+/// Identity of an opportunity: both leg prices within a market window.
+type OpportunityKey = (Decimal, Decimal, i64);
+
+/// Key an opportunity so repeated book updates at the same prices match.
+fn opportunity_key(opportunity: &ArbitrageOpportunity) -> OpportunityKey {
+    (
+        opportunity.up_price,
+        opportunity.down_price,
+        opportunity.market.window_start(),
+    )
 }
 
 impl ArbitrageExecutor {
@@ -96,9 +357,131 @@ impl ArbitrageExecutor {
             opportunities_found: 0,
             total_invested: Decimal::ZERO,
             total_shares_bought: Decimal::ZERO,
+            total_fees: Decimal::ZERO,
+            total_gas: Decimal::ZERO,
+            pol_price_usdc: config.pol_price_usdc,
             sim_balance: config.sim_balance,
             sim_start_balance: config.sim_balance,
+            realized_profit: None,
+            open_exposure: Decimal::ZERO,
+            at_risk: HashMap::new(),
+            exposure: ExposureGuard::new(),
+            consecutive_failures: 0,
+            max_consecutive_failures: config.max_consecutive_failures,
+            circuit_opened_at: None,
+            circuit_cooldown_seconds: config.circuit_breaker_cooldown_seconds,
+            sim_fill_probability: config.sim_fill_probability.to_f64().unwrap_or(1.0),
+            sim_leg_failure_rate: config.sim_leg_failure_rate.to_f64().unwrap_or(0.0),
+            sim_slippage_bps: config.sim_slippage_bps,
+            rng: match config.sim_seed {
+                Some(seed) => StdRng::seed_from_u64(seed),
+                None => StdRng::from_entropy(),
+            },
+            user_ws: None,
+            history: VecDeque::new(),
+            history_limit: config.history_limit,
+            intents: TradeIntentLog::load(&config.state_file),
+            last_opportunity: None,
+            fill_stats: FillStats::default(),
+            last_exec_id: Arc::new(AtomicU64::new(0)),
+            alerts: AlertNotifier::from_url(config.alert_webhook_url.as_deref()),
+            snapshots: SnapshotWriter::from_dir(&config.snapshot_dir),
+            journal: TradeJournal::from_path(&config.journal_csv),
+            summaries: SummaryLog::from_dir(&config.summary_dir),
+            settlements: SettlementQueue::default(),
+            redemptions: SettlementQueue::default(),
+            redeeming: 0,
+            failed_redemptions: 0,
+        }
+    }
+
+    /// Await fills via the user WebSocket instead of polling order status.
+    pub fn with_user_ws(mut self, user_ws: Arc<UserWebSocket>) -> Self {
+        self.user_ws = Some(user_ws);
+        self
+    }
+
+    /// Enforce the exposure cap against a guard shared with other executors.
+    pub fn with_exposure_guard(mut self, exposure: ExposureGuard) -> Self {
+        self.exposure = exposure;
+        self
+    }
+
+    /// Draw execution IDs from a counter shared with other executors.
+    pub fn with_exec_ids(mut self, last_exec_id: Arc<AtomicU64>) -> Self {
+        self.last_exec_id = last_exec_id;
+        self
+    }
+
+    /// Check if the circuit breaker is open (tripped and still cooling down).
+    pub fn is_circuit_open(&self) -> bool {
+        self.circuit_remaining() > 0
+    }
+
+    /// Get remaining circuit breaker cooldown seconds.
+    pub fn circuit_remaining(&self) -> u64 {
+        match self.circuit_opened_at {
+            Some(opened) => self
+                .circuit_cooldown_seconds
+                .saturating_sub(opened.elapsed().as_secs()),
+            None => 0,
+        }
+    }
+
+    /// Record a failed execution, tripping the breaker at the limit.
+    pub fn record_failure(&mut self) {
+        self.consecutive_failures += 1;
+
+        if self.consecutive_failures >= self.max_consecutive_failures {
+            self.trip_circuit_breaker();
+        }
+    }
+
+    /// Open the circuit breaker now, alerting unless it was already open.
+    fn trip_circuit_breaker(&mut self) {
+        let was_open = self.is_circuit_open();
+        self.circuit_opened_at = Some(Instant::now());
+        error!(
+            consecutive_failures = self.consecutive_failures,
+            cooldown_seconds = self.circuit_cooldown_seconds,
+            "Circuit breaker tripped"
+        );
+        if !was_open {
+            self.alert(AlertEvent::CircuitBreakerTripped {
+                consecutive_failures: self.consecutive_failures,
+                cooldown_seconds: self.circuit_cooldown_seconds,
+            });
+        }
+    }
+
+    /// Send an alert to the webhook, if one is configured.
+    fn alert(&self, event: AlertEvent) {
+        if let Some(alerts) = &self.alerts {
+            alerts.notify(event);
+        }
+    }
+
+    /// Alert on a failed order submission.
+    fn alert_failure(&self, opportunity: &ArbitrageOpportunity, exec_id: u64, error: String) {
+        self.alert(AlertEvent::ExecutionFailed {
+            market: opportunity.market.slug.clone(),
+            exec_id,
+            error,
+        });
+    }
+
+    /// Record a successful execution, resetting the failure counter.
+    pub fn record_success(&mut self) {
+        self.consecutive_failures = 0;
+        self.circuit_opened_at = None;
+    }
+
+    /// Manually close the circuit breaker.
+    pub fn resume(&mut self) {
+        if self.circuit_opened_at.is_some() {
+            info!("Circuit breaker reset manually");
         }
+        self.record_success();
     }
 
     /// Check if cooldown is active.
@@ -125,7 +508,13 @@ impl ArbitrageExecutor {
     }
 
     /// Execute an arbitrage opportunity.
-    #[instrument(skip(self, client, config), fields(market = %opportunity.market.slug))]
+    ///
+    /// Each attempt past the skip checks gets an `exec_id`, recorded on this span
+    /// so submission, fill-wait, and unwind logs for both legs can be filtered by it.
+    #[instrument(
+        skip(self, client, config),
+        fields(market = %opportunity.market.slug, exec_id = tracing::field::Empty)
+    )]
     pub async fn execute(
         &mut self,
         client: &PolymarketClient,
@@ -135,6 +524,30 @@ impl ArbitrageExecutor {
         // Track opportunity
         self.opportunities_found += 1;
 
+        // Buying two tokens that aren't one market's pair would not be a hedge
+        if config.verify_token_pair && !opportunity.market.has_token_pair() {
+            error!(
+                market = %opportunity.market.slug,
+                up_token = %opportunity.market.up_token_id,
+                down_token = %opportunity.market.down_token_id,
+                "UP and DOWN tokens are not a distinct pair, refusing to execute"
+            );
+            return Err(TradingError::InvalidParams(format!(
+                "{} has no distinct UP/DOWN token pair",
+                opportunity.market.slug
+            )));
+        }
+
+        // Check circuit breaker
+        if self.is_circuit_open() {
+            let remaining = self.circuit_remaining();
+            warn!(remaining_seconds = remaining, "Circuit breaker open, skipping");
+            return Ok(ExecutionResult::CircuitOpen {
+                consecutive_failures: self.consecutive_failures,
+                remaining_seconds: remaining,
+            });
+        }
+
         // Check cooldown
         if self.is_cooldown_active() {
             let remaining = self.cooldown_remaining();
@@ -144,20 +557,155 @@ impl ArbitrageExecutor {
             });
         }
 
+        // Skip a market whose legs already filled this window, even across restarts
+        let intent = trade_intent_key(&opportunity.market);
+        if self.intents.contains(&intent) {
+            info!(intent = %intent, "Trade intent already executed this window, skipping");
+            return Ok(ExecutionResult::AlreadyExecuted { intent });
+        }
+
+        // Cap capital committed to markets that have not settled, holding the
+        // headroom until this execution finishes
+        let cap = config.max_total_exposure;
+        let _reservation = if cap.is_zero() {
+            None
+        } else {
+            match self.exposure.try_reserve(opportunity.total_investment, cap) {
+                Ok(reservation) => Some(reservation),
+                Err(current) => {
+                    warn!(
+                        current = %current,
+                        required = %opportunity.total_investment,
+                        cap = %cap,
+                        "Exposure limit reached, skipping"
+                    );
+                    return Ok(ExecutionResult::ExposureLimitReached { current, cap });
+                }
+            }
+        };
+
+        // Book updates that leave prices unchanged re-detect the same opportunity
+        let key = opportunity_key(opportunity);
+        if self.last_opportunity == Some(key) {
+            debug!("Opportunity unchanged since last execution, skipping");
+            return Ok(ExecutionResult::DuplicateOpportunity);
+        }
+
         // Update last execution time
         self.last_execution = Some(Instant::now());
+        let exec_id = self.last_exec_id.fetch_add(1, Ordering::SeqCst) + 1;
+        Span::current().record("exec_id", exec_id);
 
-        // Log opportunity details
+        // Log opportunity details and keep the books it was decided on
         self.log_opportunity(opportunity);
+        if let Some(snapshots) = &self.snapshots {
+            snapshots.record(exec_id, opportunity);
+        }
+
+        // Only a fill makes the same prices a duplicate; a skip or miss may retry them
+        let result = self.execute_attempt(client, opportunity, config, exec_id, intent).await;
+        if matches!(&result, Ok(result) if result.has_fill()) {
+            self.last_opportunity = Some(key);
+        }
+        result
+    }
 
+    /// Run one numbered attempt: simulate it in dry-run, otherwise check the
+    /// wallet and submit both legs.
+    async fn execute_attempt(
+        &mut self,
+        client: &PolymarketClient,
+        opportunity: &ArbitrageOpportunity,
+        config: &Config,
+        exec_id: u64,
+        intent: String,
+    ) -> Result<ExecutionResult, TradingError> {
         // Handle simulation mode
         if config.dry_run {
-            return self.execute_simulated(opportunity);
+            return match config.dry_run_mode {
+                DryRunMode::Full => self.execute_simulated(opportunity, config, exec_id),
+                DryRunMode::Sign => self.execute_signed(client, opportunity, config, exec_id).await,
+                DryRunMode::BalanceOnly => {
+                    match self.check_balance(client, opportunity, config).await? {
+                        Some(insufficient) => Ok(insufficient),
+                        None => self.execute_simulated(opportunity, config, exec_id),
+                    }
+                }
+            };
+        }
+
+        // Skip if a balanced position is already held
+        if config.skip_if_already_positioned {
+            let market = &opportunity.market;
+            let token_ids = vec![market.up_token_id.clone(), market.down_token_id.clone()];
+            match client.get_positions(&token_ids).await {
+                Ok(positions) => {
+                    let held_pairs =
+                        balanced_pairs(&positions, &market.up_token_id, &market.down_token_id);
+                    if held_pairs >= opportunity.order_size {
+                        info!(held_pairs = %held_pairs, "Already positioned, skipping");
+                        return Ok(ExecutionResult::AlreadyPositioned { held_pairs });
+                    }
+                }
+                Err(e) => warn!(error = %e, "Failed to fetch positions, proceeding"),
+            }
         }
 
         // Check balance
+        if let Some(insufficient) = self.check_balance(client, opportunity, config).await? {
+            return Ok(insufficient);
+        }
+
+        // Execute real trades, remembering any fill so a restart won't repeat it
+        let (mut up_params, mut down_params) = order_params(opportunity, config)?;
+        if !config.strict_hedge {
+            self.adapt_to_fill_rate(&mut up_params, &mut down_params, config.min_fill_rate);
+        }
+        let fees_before = self.total_fees;
+        let result = self
+            .execute_real(client, opportunity, &mut up_params, &mut down_params, config, exec_id)
+            .await?;
+        if let Some(journal) = &self.journal {
+            let fees = self.total_fees - fees_before;
+            let row = JournalRow::fill(&result, opportunity, &up_params, &down_params, fees);
+            if let Some(row) = row {
+                journal.record(&row);
+            }
+        }
+        match &result {
+            ExecutionResult::BothFilled {
+                actual_investment, ..
+            } => {
+                client.debit_cached_balance(*actual_investment);
+                self.intents.record(intent);
+            }
+            ExecutionResult::PartialFill { .. } => {
+                // Unwind proceeds are unknown locally, so re-fetch next time
+                client.invalidate_balance_cache();
+                self.intents.record(intent);
+            }
+            _ => {}
+        }
+        Ok(result)
+    }
+
+    /// Check the wallet covers the investment plus margin.
+    ///
+    /// Returns `Some(InsufficientBalance)` when it does not.
+    async fn check_balance(
+        &mut self,
+        client: &PolymarketClient,
+        opportunity: &ArbitrageOpportunity,
+        config: &Config,
+    ) -> Result<Option<ExecutionResult>, TradingError> {
         let required = opportunity.total_investment * config.balance_margin;
-        let balance = client.get_balance().await?;
+        let balance = match client.get_balance_cached().await {
+            Ok(balance) => balance,
+            Err(e) => {
+                self.record_failure();
+                return Err(e);
+            }
+        };
 
         if balance < required {
             warn!(
@@ -165,20 +713,92 @@ impl ArbitrageExecutor {
                 available = %balance,
                 "Insufficient balance"
             );
-            return Ok(ExecutionResult::InsufficientBalance {
+            return Ok(Some(ExecutionResult::InsufficientBalance {
                 required,
                 available: balance,
-            });
+            }));
+        }
+
+        // The exchange locks each order at its own limit price, and a filled leg
+        // leaves less for the other, so fund the legs in submission order
+        let (up_params, down_params) = order_params(opportunity, config)?;
+        let up_cost = up_params.price * up_params.size * config.balance_margin;
+        let down_cost = down_params.price * down_params.size * config.balance_margin;
+        let down_first = config.leg_mode == LegMode::Sequential
+            && first_leg(config.first_leg_policy, opportunity) == Outcome::Down;
+        let legs = if down_first {
+            [(Outcome::Down, down_cost), (Outcome::Up, up_cost)]
+        } else {
+            [(Outcome::Up, up_cost), (Outcome::Down, down_cost)]
+        };
+        if let Some((leg, leg_required, leg_available)) = underfunded_leg(balance, legs) {
+            warn!(
+                leg = ?leg,
+                required = %leg_required,
+                available = %leg_available,
+                "Insufficient balance for leg after funding the other"
+            );
+            return Ok(Some(ExecutionResult::InsufficientBalance {
+                required: up_cost + down_cost,
+                available: balance,
+            }));
         }
 
-        // Execute real trades
-        self.execute_real(client, opportunity, config).await
+        Ok(None)
+    }
+
+    /// Build and sign both orders without submitting them.
+    async fn execute_signed(
+        &mut self,
+        client: &PolymarketClient,
+        opportunity: &ArbitrageOpportunity,
+        config: &Config,
+        exec_id: u64,
+    ) -> Result<ExecutionResult, TradingError> {
+        info!("SIGN-ONLY DRY RUN - Orders will be signed but not submitted");
+
+        let (up_params, down_params) = order_params(opportunity, config)?;
+        up_params.validate().map_err(TradingError::InvalidParams)?;
+        down_params.validate().map_err(TradingError::InvalidParams)?;
+
+        let signed = tokio::try_join!(
+            build_order_request(client, &up_params),
+            build_order_request(client, &down_params),
+        );
+        let (up_order, down_order) = match signed {
+            Ok(orders) => orders,
+            Err(e) => {
+                error!(error = %e, "Order signing failed");
+                self.record_failure();
+                return Err(e);
+            }
+        };
+
+        self.record_success();
+        info!(
+            up_order = ?up_order.redacted(),
+            down_order = ?down_order.redacted(),
+            "Signed orders (not submitted)"
+        );
+
+        Ok(ExecutionResult::Signed {
+            exec_id,
+            up_order: Box::new(up_order),
+            down_order: Box::new(down_order),
+        })
     }
 
     /// Execute in simulation mode.
+    ///
+    /// Each leg may be rejected (`sim_fill_probability`) or partially filled
+    /// against the ask depth captured at detection, with slippage applied, and
+    /// one leg may lose the race outright (`sim_leg_failure_rate`).
+    /// Unmatched shares are unwound at a slippage loss, as live trading would.
     fn execute_simulated(
         &mut self,
         opportunity: &ArbitrageOpportunity,
+        config: &Config,
+        exec_id: u64,
     ) -> Result<ExecutionResult, TradingError> {
         info!("SIMULATION MODE - No real orders will be executed");
 
@@ -195,120 +815,280 @@ impl ArbitrageExecutor {
             });
         }
 
+        let (mut up_params, mut down_params) = order_params(opportunity, config)?;
+        self.record_success();
+
+        if !config.strict_hedge {
+            self.adapt_to_fill_rate(&mut up_params, &mut down_params, config.min_fill_rate);
+        }
+
+        // Roll for rejection, then fill each leg against the book
+        let mut up_fill = self.simulate_leg(&opportunity.up_asks, &up_params);
+        let mut down_fill = self.simulate_leg(&opportunity.down_asks, &down_params);
+        // Another taker may empty one side between detection and submission
+        if self.sim_leg_failure_rate > 0.0
+            && self.rng.gen_bool(self.sim_leg_failure_rate.clamp(0.0, 1.0))
+        {
+            if self.rng.gen_bool(0.5) {
+                up_fill = SimulatedFill::NONE;
+            } else {
+                down_fill = SimulatedFill::NONE;
+            }
+        }
+        self.fill_stats.record(up_fill.is_filled());
+        self.fill_stats.record(down_fill.is_filled());
+
+        let matched = up_fill.filled_size.min(down_fill.filled_size);
+        if matched.is_zero() {
+            let (filled_leg, fill) = match (up_fill.is_filled(), down_fill.is_filled()) {
+                (true, _) => (Outcome::Up, up_fill),
+                (false, true) => (Outcome::Down, down_fill),
+                (false, false) => {
+                    info!("Simulated orders did not fill");
+                    return Ok(ExecutionResult::NeitherFilled { exec_id });
+                }
+            };
+
+            let loss = fill.cost() - simulated_unwind_proceeds(&fill, self.sim_slippage_bps);
+            self.sim_balance -= loss;
+            warn!(
+                leg = ?filled_leg,
+                filled = %fill.filled_size,
+                loss = %loss,
+                "Simulated partial fill"
+            );
+
+            // Strict hedging sells the leg at once; the exit is modelled the same way
+            let exit = if config.strict_hedge { "liquidation" } else { "unwind" };
+            return Ok(ExecutionResult::PartialFill {
+                exec_id,
+                filled_leg,
+                filled_size: fill.filled_size,
+                unwind_attempted: true,
+                unwind_result: Some(format!("simulated {}, lost ${}", exit, loss)),
+            });
+        }
+
+        // Unwind any shares on the larger leg beyond the matched pairs
+        let excess = if up_fill.filled_size > matched { up_fill } else { down_fill };
+        let excess = SimulatedFill {
+            filled_size: excess.filled_size - matched,
+            ..excess
+        };
+        let unwind_loss = excess.cost() - simulated_unwind_proceeds(&excess, self.sim_slippage_bps);
+
+        let investment = matched * (up_fill.avg_price + down_fill.avg_price);
+        let profit = matched - investment - unwind_loss;
+
         // Deduct from simulated balance
-        self.sim_balance -= opportunity.total_investment;
-        self.total_invested += opportunity.total_investment;
-        self.total_shares_bought += opportunity.order_size * Decimal::TWO;
+        self.sim_balance -= investment + unwind_loss;
+        self.total_invested += investment;
+        self.commit_at_risk(&opportunity.market.slug, investment);
+        self.total_shares_bought += matched * Decimal::TWO;
         self.trades_executed += 1;
 
         info!(
             sim_balance = %self.sim_balance,
-            deducted = %opportunity.total_investment,
+            filled = %matched,
+            deducted = %(investment + unwind_loss),
             "Simulated trade executed"
         );
 
         Ok(ExecutionResult::Simulated {
-            would_invest: opportunity.total_investment,
-            would_profit: opportunity.expected_profit,
+            exec_id,
+            filled_size: matched,
+            would_invest: investment,
+            would_profit: profit,
         })
     }
 
+    /// Fall back from FOK to FAK while the recent fill rate is below `min_fill_rate`.
+    ///
+    /// A FAK leg keeps whatever depth is left instead of being rejected outright;
+    /// uneven legs are unwound as usual. A resting maker leg is left as is.
+    fn adapt_to_fill_rate(
+        &self,
+        up_params: &mut OrderParams,
+        down_params: &mut OrderParams,
+        min_fill_rate: Decimal,
+    ) {
+        let any_fok = [&*up_params, &*down_params].iter().any(|p| p.tif == TimeInForce::FOK);
+        if !any_fok || !self.fill_stats.is_below(min_fill_rate) {
+            return;
+        }
+        debug!(
+            fill_rate = ?self.fill_stats.fill_rate(),
+            min_fill_rate = %min_fill_rate,
+            "Low fill rate, submitting FAK instead of FOK"
+        );
+        for params in [up_params, down_params] {
+            if params.tif == TimeInForce::FOK {
+                params.tif = TimeInForce::FAK;
+            }
+        }
+    }
+
+    /// Simulate one leg: a rejection roll, then a fill against the asks.
+    fn simulate_leg(&mut self, asks: &[PriceLevel], params: &OrderParams) -> SimulatedFill {
+        if !self.rng.gen_bool(self.sim_fill_probability.clamp(0.0, 1.0)) {
+            return SimulatedFill::NONE;
+        }
+        simulate_leg_fill(asks, params.price, params.size, params.tif, self.sim_slippage_bps)
+    }
+
     /// Execute real trades with concurrent order submission.
+    ///
+    /// Leaves each leg's params at the price it was last submitted at.
     async fn execute_real(
         &mut self,
         client: &PolymarketClient,
         opportunity: &ArbitrageOpportunity,
+        up_params: &mut OrderParams,
+        down_params: &mut OrderParams,
         config: &Config,
+        exec_id: u64,
     ) -> Result<ExecutionResult, TradingError> {
         info!("Executing REAL arbitrage trade");
-
-        // Parse time-in-force from config
-        let tif = match config.order_type.to_uppercase().as_str() {
-            "FOK" => TimeInForce::FOK,
-            "FAK" => TimeInForce::FAK,
-            "GTC" => TimeInForce::GTC,
-            _ => TimeInForce::FOK,
-        };
-
-        // Create UP order parameters
-        let up_params = OrderParams {
-            token_id: opportunity.market.up_token_id.clone(),
-            side: Side::Buy,
-            price: opportunity.up_price,
-            size: opportunity.order_size,
-            tif,
-        };
-
-        // Create DOWN order parameters
-        let down_params = OrderParams {
-            token_id: opportunity.market.down_token_id.clone(),
-            side: Side::Buy,
-            price: opportunity.down_price,
-            size: opportunity.order_size,
-            tif,
-        };
+        let fees_before = self.total_fees;
 
         info!(
             up_token = %opportunity.market.up_token_id,
             down_token = %opportunity.market.down_token_id,
-            up_price = %opportunity.up_price,
-            down_price = %opportunity.down_price,
+            up_price = %up_params.price,
+            down_price = %down_params.price,
             size = %opportunity.order_size,
             "Submitting paired orders"
         );
 
-        // Submit both orders concurrently
-        let (up_result, down_result) = tokio::join!(
-            submit_order(client, &up_params),
-            submit_order(client, &down_params),
-        );
+        // Submit both legs; sequential mode sends the second only once the first is
+        // accepted. Parallel legs are never repriced: each retry would be checked
+        // against the other leg's unconfirmed price
+        let (up_result, down_result) = match config.leg_mode {
+            LegMode::Parallel => {
+                tokio::join!(submit_order(client, up_params), submit_order(client, down_params))
+            }
+            LegMode::Sequential => {
+                let first = first_leg(config.first_leg_policy, opportunity);
+                let (first_params, second_params) = match first {
+                    Outcome::Up => (&mut *up_params, &mut *down_params),
+                    Outcome::Down => (&mut *down_params, &mut *up_params),
+                };
+                let second_price = second_params.price;
+                let first_submission = submit_leg_with_repricing(
+                    client,
+                    first_params,
+                    second_price,
+                    opportunity,
+                    config,
+                );
+                let first_order_id = match first_submission.await {
+                    Ok(order_id) => order_id,
+                    Err(e @ TradingError::PostOnlyCrossed { .. }) => {
+                        info!(
+                            leg = ?first,
+                            error = %e,
+                            "First leg would have crossed as maker, second leg not submitted"
+                        );
+                        self.fill_stats.record(false);
+                        return Ok(ExecutionResult::NeitherFilled { exec_id });
+                    }
+                    Err(e) => {
+                        error!(
+                            leg = ?first,
+                            error = %e,
+                            "First leg submission failed, second leg not submitted"
+                        );
+                        refresh_balance_if_short(client, &e);
+                        self.alert_failure(opportunity, exec_id, e.to_string());
+                        self.record_failure();
+                        self.fill_stats.record(false);
+                        return Ok(ExecutionResult::NeitherFilled { exec_id });
+                    }
+                };
+                // The first leg's price is now the one it was accepted at
+                let first_price = first_params.price;
+                let second_result = submit_leg_with_repricing(
+                    client,
+                    second_params,
+                    first_price,
+                    opportunity,
+                    config,
+                )
+                .await;
+                match first {
+                    Outcome::Up => (Ok(first_order_id), second_result),
+                    Outcome::Down => (second_result, Ok(first_order_id)),
+                }
+            }
+        };
+
+        // A maker leg that would have crossed never rested; it is unfilled, not failed
+        let up_result = placed_unless_crossed(up_result);
+        let down_result = placed_unless_crossed(down_result);
 
         // Analyze results
         match (up_result, down_result) {
             // Both orders submitted successfully
             (Ok(up_order_id), Ok(down_order_id)) => {
+                self.record_success();
                 info!(
-                    up_order_id = %up_order_id,
-                    down_order_id = %down_order_id,
+                    up_order_id = ?up_order_id,
+                    down_order_id = ?down_order_id,
                     "Both orders submitted, waiting for fills"
                 );
 
                 // Wait for both orders to reach terminal state
+                let size = opportunity.order_size;
                 let (up_state, down_state) = tokio::join!(
-                    wait_for_terminal_order(
-                        client,
-                        &up_order_id,
-                        opportunity.order_size,
-                        DEFAULT_ORDER_TIMEOUT,
-                        DEFAULT_POLL_INTERVAL,
-                    ),
-                    wait_for_terminal_order(
-                        client,
-                        &down_order_id,
-                        opportunity.order_size,
-                        DEFAULT_ORDER_TIMEOUT,
-                        DEFAULT_POLL_INTERVAL,
-                    ),
+                    self.await_leg(client, up_order_id.as_deref(), size, config),
+                    self.await_leg(client, down_order_id.as_deref(), size, config),
                 );
-
-                // Check fill status
-                let up_filled = up_state.is_filled;
-                let down_filled = down_state.is_filled;
-
-                match (up_filled, down_filled) {
+                // Only placed legs can be cancelled
+                let up_open: Vec<String> = up_order_id.iter().cloned().collect();
+                let down_open: Vec<String> = down_order_id.iter().cloned().collect();
+                self.record_fee(&up_state);
+                self.record_fee(&down_state);
+
+                // Check fill status; a terminal FAK leg keeps whatever it matched
+                let up_fill = leg_fill_size(&up_state, up_params.tif, opportunity.order_size);
+                let down_fill =
+                    leg_fill_size(&down_state, down_params.tif, opportunity.order_size);
+                self.fill_stats.record(up_fill.is_some());
+                self.fill_stats.record(down_fill.is_some());
+
+                match (up_fill, down_fill) {
                     // Both filled - success!
-                    (true, true) => {
-                        let up_filled_size = up_state.filled_size.unwrap_or(opportunity.order_size);
-                        let down_filled_size =
-                            down_state.filled_size.unwrap_or(opportunity.order_size);
+                    (Some(up_filled_size), Some(down_filled_size)) => {
+                        // FAK legs can match different sizes; unwind the unhedged excess
+                        if up_filled_size != down_filled_size {
+                            let outcome = if up_filled_size > down_filled_size {
+                                Outcome::Up
+                            } else {
+                                Outcome::Down
+                            };
+                            let excess = (up_filled_size - down_filled_size).abs();
+                            warn!(
+                                up_filled = %up_filled_size,
+                                down_filled = %down_filled_size,
+                                "Legs filled unevenly, unwinding excess"
+                            );
+                            self.attempt_unwind(
+                                client,
+                                &opportunity.market,
+                                outcome,
+                                excess,
+                                config,
+                            )
+                            .await;
+                        }
 
-                        // Calculate actual investment
-                        let actual_investment =
-                            (up_filled_size * opportunity.up_price) +
-                            (down_filled_size * opportunity.down_price);
+                        // Investment at the limit prices the legs were accepted at
+                        let actual_investment = (up_filled_size * up_params.price)
+                            + (down_filled_size * down_params.price);
 
                         self.trades_executed += 1;
                         self.total_invested += actual_investment;
+                        self.commit_at_risk(&opportunity.market.slug, actual_investment);
                         self.total_shares_bought += up_filled_size + down_filled_size;
 
                         info!(
@@ -317,95 +1097,139 @@ impl ArbitrageExecutor {
                             investment = %actual_investment,
                             "ARBITRAGE EXECUTED SUCCESSFULLY"
                         );
+                        // Net of every fee this execution paid, as the journal records it
+                        let fees = self.total_fees - fees_before;
+                        let pairs = up_filled_size.min(down_filled_size);
+                        let gross = (Decimal::ONE - up_params.price - down_params.price) * pairs;
+                        self.alert(AlertEvent::BothFilled {
+                            market: opportunity.market.slug.clone(),
+                            exec_id,
+                            up_filled_size,
+                            down_filled_size,
+                            investment: actual_investment,
+                            expected_profit: gross - fees,
+                        });
 
                         Ok(ExecutionResult::BothFilled {
-                            up_order_id,
-                            down_order_id,
+                            exec_id,
+                            // Both filled, so both were placed
+                            up_order_id: up_order_id.unwrap_or_default(),
+                            down_order_id: down_order_id.unwrap_or_default(),
                             up_filled_size,
                             down_filled_size,
                             actual_investment,
                         })
                     }
                     // Only UP filled - need to handle partial
-                    (true, false) => {
-                        let filled_size = up_state.filled_size.unwrap_or(opportunity.order_size);
+                    (Some(filled_size), None) => {
                         warn!(
                             up_filled = %filled_size,
                             "Partial fill: only UP leg filled"
                         );
 
                         // Cancel the DOWN order if still open
-                        let _ = cancel_orders(client, &[down_order_id]).await;
+                        let _ = cancel_orders(client, &down_open).await;
 
                         // Attempt to unwind by selling the UP position
-                        let unwind_result =
-                            self.attempt_unwind(client, Outcome::Up, &opportunity.market.up_token_id, filled_size, config)
-                                .await;
-
-                        Ok(ExecutionResult::PartialFill {
-                            filled_leg: Outcome::Up,
-                            filled_size,
-                            unwind_attempted: true,
-                            unwind_result,
-                        })
+                        Ok(self
+                            .unwind_partial(
+                                client,
+                                opportunity,
+                                Outcome::Up,
+                                filled_size,
+                                exec_id,
+                                config,
+                            )
+                            .await)
                     }
                     // Only DOWN filled - need to handle partial
-                    (false, true) => {
-                        let filled_size = down_state.filled_size.unwrap_or(opportunity.order_size);
+                    (None, Some(filled_size)) => {
                         warn!(
                             down_filled = %filled_size,
                             "Partial fill: only DOWN leg filled"
                         );
 
                         // Cancel the UP order if still open
-                        let _ = cancel_orders(client, &[up_order_id]).await;
+                        let _ = cancel_orders(client, &up_open).await;
 
                         // Attempt to unwind by selling the DOWN position
-                        let unwind_result = self
-                            .attempt_unwind(
+                        Ok(self
+                            .unwind_partial(
                                 client,
+                                opportunity,
                                 Outcome::Down,
-                                &opportunity.market.down_token_id,
                                 filled_size,
+                                exec_id,
                                 config,
                             )
-                            .await;
-
-                        Ok(ExecutionResult::PartialFill {
-                            filled_leg: Outcome::Down,
-                            filled_size,
-                            unwind_attempted: true,
-                            unwind_result,
-                        })
+                            .await)
                     }
                     // Neither filled
-                    (false, false) => {
+                    (None, None) => {
                         warn!("Neither order filled");
 
                         // Cancel any remaining orders
-                        let _ = cancel_orders(client, &[up_order_id, down_order_id]).await;
+                        let _ = cancel_orders(client, &[up_open, down_open].concat()).await;
 
-                        Ok(ExecutionResult::NeitherFilled)
+                        Ok(ExecutionResult::NeitherFilled { exec_id })
                     }
                 }
             }
             // Only UP order submitted
             (Ok(up_order_id), Err(down_err)) => {
                 error!(error = %down_err, "DOWN order submission failed");
+                refresh_balance_if_short(client, &down_err);
+                self.alert_failure(opportunity, exec_id, down_err.to_string());
+                self.record_failure();
+                self.fill_stats.record(false);
 
                 // Cancel the UP order
-                let _ = cancel_orders(client, &[up_order_id]).await;
-
-                Ok(ExecutionResult::NeitherFilled)
+                let _ = cancel_orders(client, &Vec::from_iter(up_order_id.clone())).await;
+
+                if config.strict_hedge {
+                    if let Some(result) = self
+                        .liquidate_orphan(
+                            client,
+                            opportunity,
+                            Outcome::Up,
+                            up_order_id.as_deref(),
+                            exec_id,
+                            config,
+                        )
+                        .await
+                    {
+                        return Ok(result);
+                    }
+                }
+                Ok(ExecutionResult::NeitherFilled { exec_id })
             }
             // Only DOWN order submitted
             (Err(up_err), Ok(down_order_id)) => {
                 error!(error = %up_err, "UP order submission failed");
+                refresh_balance_if_short(client, &up_err);
+                self.alert_failure(opportunity, exec_id, up_err.to_string());
+                self.record_failure();
+                self.fill_stats.record(false);
 
                 // Cancel the DOWN order
-                let _ = cancel_orders(client, &[down_order_id]).await;
-
-                Ok(ExecutionResult::NeitherFilled)
+                let _ = cancel_orders(client, &Vec::from_iter(down_order_id.clone())).await;
+
+                if config.strict_hedge {
+                    if let Some(result) = self
+                        .liquidate_orphan(
+                            client,
+                            opportunity,
+                            Outcome::Down,
+                            down_order_id.as_deref(),
+                            exec_id,
+                            config,
+                        )
+                        .await
+                    {
+                        return Ok(result);
+                    }
+                }
+                Ok(ExecutionResult::NeitherFilled { exec_id })
             }
             // Both failed
             (Err(up_err), Err(down_err)) => {
@@ -414,64 +1238,293 @@ impl ArbitrageExecutor {
                     down_error = %down_err,
                     "Both orders failed to submit"
                 );
-
-                Ok(ExecutionResult::NeitherFilled)
+                refresh_balance_if_short(client, &up_err);
+                refresh_balance_if_short(client, &down_err);
+                let error = format!("UP: {}; DOWN: {}", up_err, down_err);
+                self.alert_failure(opportunity, exec_id, error);
+                self.record_failure();
+                self.fill_stats.record(false);
+                self.fill_stats.record(false);
+
+                Ok(ExecutionResult::NeitherFilled { exec_id })
             }
         }
     }
 
-    /// Attempt to unwind a partial fill by selling the filled position.
-    async fn attempt_unwind(
+    /// Wait up to `ORDER_TIMEOUT_MS` for an order to fill or reach a terminal state.
+    ///
+    /// Uses user-channel fill events when connected, with a single REST status
+    /// check if no event arrives in time; otherwise polls every
+    /// `ORDER_POLL_INTERVAL_MS`.
+    async fn await_order(
         &self,
         client: &PolymarketClient,
-        outcome: Outcome,
-        token_id: &str,
+        order_id: &str,
         size: Decimal,
-        _config: &Config,
-    ) -> Option<String> {
-        info!(
-            outcome = ?outcome,
-            token_id = %token_id,
-            size = %size,
-            "Attempting to unwind partial fill"
-        );
+        config: &Config,
+    ) -> OrderState {
+        let timeout = order_timeout_from_config(config);
+        self.await_order_within(client, order_id, size, timeout, config).await
+    }
 
-        // Get the current order book to find a sell price
-        match client.get_order_book(token_id).await {
-            Ok(book) => {
-                // Use best bid as sell price (minus small buffer for fill probability)
-                if let Some(best_bid) = book.best_bid() {
-                    let sell_price = best_bid - Decimal::new(1, 2); // $0.01 below best bid
+    /// Wait up to `timeout` for an order to reach a terminal state.
+    async fn await_order_within(
+        &self,
+        client: &PolymarketClient,
+        order_id: &str,
+        size: Decimal,
+        timeout: Duration,
+        config: &Config,
+    ) -> OrderState {
+        let poll_interval = poll_interval_from_config(config);
+        let grace = not_found_grace_from_config(config);
+        if let Some(user_ws) = self.user_ws.as_ref().filter(|ws| ws.is_connected()) {
+            if let Some(state) = user_ws.wait_for_terminal(order_id, size, timeout).await {
+                return state;
+            }
 
-                    let sell_params = OrderParams {
-                        token_id: token_id.to_string(),
-                        side: Side::Sell,
-                        price: sell_price,
-                        size,
-                        tif: TimeInForce::GTC, // Use GTC for unwind
-                    };
+            warn!(order_id = %order_id, "No user-channel update before timeout, checking status");
+            return wait_for_terminal_order(
+                client,
+                order_id,
+                size,
+                poll_interval,
+                poll_interval,
+                grace,
+            )
+            .await;
+        }
 
-                    match submit_order(client, &sell_params).await {
-                        Ok(order_id) => {
-                            info!(
-                                order_id = %order_id,
-                                price = %sell_price,
-                                "Unwind sell order submitted"
-                            );
-                            Some(format!("Unwind order submitted: {}", order_id))
-                        }
-                        Err(e) => {
+        wait_for_terminal_order(client, order_id, size, timeout, poll_interval, grace).await
+    }
+
+    /// Wait for a submitted leg; a leg never placed (`None`) is terminal and unfilled.
+    async fn await_leg(
+        &self,
+        client: &PolymarketClient,
+        order_id: Option<&str>,
+        size: Decimal,
+        config: &Config,
+    ) -> OrderState {
+        match order_id {
+            Some(order_id) => self.await_order(client, order_id, size, config).await,
+            None => OrderState {
+                is_terminal: true,
+                ..Default::default()
+            },
+        }
+    }
+
+    /// Unwind a leg that filled without its pair, alert, and report the partial fill.
+    async fn unwind_partial(
+        &mut self,
+        client: &PolymarketClient,
+        opportunity: &ArbitrageOpportunity,
+        filled_leg: Outcome,
+        filled_size: Decimal,
+        exec_id: u64,
+        config: &Config,
+    ) -> ExecutionResult {
+        let unwind_result = self
+            .attempt_unwind(client, &opportunity.market, filled_leg, filled_size, config)
+            .await;
+        self.alert(AlertEvent::PartialFill {
+            market: opportunity.market.slug.clone(),
+            exec_id,
+            filled_leg,
+            filled_size,
+            unwind_result: unwind_result.clone(),
+        });
+
+        ExecutionResult::PartialFill {
+            exec_id,
+            filled_leg,
+            filled_size,
+            unwind_attempted: true,
+            unwind_result,
+        }
+    }
+
+    /// Strict hedge: a leg placed without its pair may have filled before it was
+    /// cancelled, so check it and liquidate whatever it bought.
+    ///
+    /// Returns `None` when the leg bought nothing.
+    async fn liquidate_orphan(
+        &mut self,
+        client: &PolymarketClient,
+        opportunity: &ArbitrageOpportunity,
+        outcome: Outcome,
+        order_id: Option<&str>,
+        exec_id: u64,
+        config: &Config,
+    ) -> Option<ExecutionResult> {
+        let state = self.await_leg(client, order_id, opportunity.order_size, config).await;
+        self.record_fee(&state);
+        let filled_size = state.filled_size.filter(|size| *size > Decimal::ZERO)?;
+        warn!(leg = ?outcome, filled = %filled_size, "Leg filled without its pair");
+        Some(
+            self.unwind_partial(client, opportunity, outcome, filled_size, exec_id, config)
+                .await,
+        )
+    }
+
+    /// Strict hedge: sell an unhedged leg FAK at the minimum tick, crossing every bid.
+    ///
+    /// Whatever is still held once `STRICT_HEDGE_DEADLINE_MS` has passed is left
+    /// as open exposure and trips the circuit breaker.
+    async fn liquidate(
+        &mut self,
+        client: &PolymarketClient,
+        market: &Market,
+        outcome: Outcome,
+        size: Decimal,
+        config: &Config,
+    ) -> Option<String> {
+        let started = Instant::now();
+        let deadline = config.strict_hedge_deadline();
+        let token_id = market.token_id(outcome);
+        warn!(
+            outcome = ?outcome,
+            token_id = %token_id,
+            size = %size,
+            "Strict hedge: liquidating unhedged leg"
+        );
+
+        let sell_params = OrderParams {
+            token_id: token_id.to_string(),
+            side: Side::Sell,
+            price: market.tick_size_or(config.tick_size),
+            size,
+            tif: TimeInForce::FAK,
+            post_only: false,
+            neg_risk: market.neg_risk_or(config.neg_risk_default),
+            expiration: 0,
+            tick_size: market.tick_size,
+        };
+
+        let sold = match submit_order(client, &sell_params).await {
+            Ok(order_id) => {
+                let remaining = deadline.saturating_sub(started.elapsed());
+                let state = wait_for_terminal_order(
+                    client,
+                    &order_id,
+                    size,
+                    remaining,
+                    poll_interval_from_config(config),
+                    not_found_grace_from_config(config),
+                )
+                .await;
+                self.record_fee(&state);
+                if !state.is_terminal {
+                    let _ = cancel_orders(client, &[order_id]).await;
+                }
+                leg_fill_size(&state, TimeInForce::FAK, size).unwrap_or_default()
+            }
+            Err(e) => {
+                error!(error = %e, "Failed to submit liquidation order");
+                Decimal::ZERO
+            }
+        };
+
+        let unsold = (size - sold).max(Decimal::ZERO);
+        if unsold.is_zero() {
+            info!(sold = %sold, "Unhedged leg liquidated");
+            return Some(format!("Liquidated {} at {}", sold, sell_params.price));
+        }
+
+        error!(
+            sold = %sold,
+            unsold = %unsold,
+            deadline_ms = config.strict_hedge_deadline_ms,
+            "Liquidation incomplete by the deadline, tripping circuit breaker"
+        );
+        self.open_exposure += unsold;
+        self.trip_circuit_breaker();
+        Some(format!("Liquidation left {} unsold", unsold))
+    }
+
+    /// Attempt to unwind a partial fill by selling the filled position.
+    ///
+    /// Strict-hedge mode liquidates instead.
+    async fn attempt_unwind(
+        &mut self,
+        client: &PolymarketClient,
+        market: &Market,
+        outcome: Outcome,
+        size: Decimal,
+        config: &Config,
+    ) -> Option<String> {
+        if config.strict_hedge {
+            return self.liquidate(client, market, outcome, size, config).await;
+        }
+
+        let started = Instant::now();
+        let token_id = market.token_id(outcome);
+        info!(
+            outcome = ?outcome,
+            token_id = %token_id,
+            size = %size,
+            "Attempting to unwind partial fill"
+        );
+
+        // Get the current order book to find a sell price
+        match client.get_order_book(token_id).await {
+            Ok(book) => {
+                // Use best bid as sell price (minus small buffer for fill probability)
+                if let Some(best_bid) = book.best_bid() {
+                    // One tick below best bid
+                    let sell_price = best_bid - market.tick_size_or(config.tick_size);
+
+                    // The unwind rests if unmatched, until shortly before the close
+                    let (tif, expiration) = TimeInForce::resting(
+                        market.end_timestamp,
+                        config.default_order_ttl_seconds,
+                        chrono::Utc::now().timestamp(),
+                    );
+                    let sell_params = OrderParams {
+                        token_id: token_id.to_string(),
+                        side: Side::Sell,
+                        price: sell_price,
+                        size,
+                        tif,
+                        post_only: false,
+                        neg_risk: market.neg_risk_or(config.neg_risk_default),
+                        expiration,
+                        tick_size: market.tick_size,
+                    };
+
+                    match submit_order(client, &sell_params).await {
+                        Ok(order_id) => {
+                            info!(
+                                order_id = %order_id,
+                                price = %sell_price,
+                                "Unwind sell order submitted"
+                            );
+                            // The order rests if unmatched; charge what it filled by the timeout
+                            let remaining = order_timeout_from_config(config)
+                                .saturating_sub(started.elapsed())
+                                .max(poll_interval_from_config(config));
+                            let state = self
+                                .await_order_within(client, &order_id, size, remaining, config)
+                                .await;
+                            self.record_fee(&state);
+                            Some(format!("Unwind order submitted: {}", order_id))
+                        }
+                        Err(e) => {
                             error!(error = %e, "Failed to submit unwind order");
+                            self.open_exposure += size;
                             Some(format!("Unwind failed: {}", e))
                         }
                     }
                 } else {
                     warn!("No bids available for unwind");
+                    self.open_exposure += size;
                     Some("No bids available for unwind".to_string())
                 }
             }
             Err(e) => {
                 error!(error = %e, "Failed to get order book for unwind");
+                self.open_exposure += size;
                 Some(format!("Failed to get order book: {}", e))
             }
         }
@@ -491,14 +1544,250 @@ impl ArbitrageExecutor {
         info!("Total cost:           ${}", opportunity.total_cost);
         info!("Profit per share:     ${}", opportunity.profit_per_share);
         info!("Profit %:             {}%", opportunity.profit_pct);
+        info!("Score:                {}", opportunity.score());
         info!("----------------------------------------");
         info!("Order size:           {} shares each side", opportunity.order_size);
         info!("Total investment:     ${}", opportunity.total_investment);
         info!("Expected payout:      ${}", opportunity.expected_payout);
         info!("EXPECTED PROFIT:      ${}", opportunity.expected_profit);
+        info!("Worst partial loss:   ${}", opportunity.max_partial_loss(None));
         info!("========================================");
     }
 
+    /// Queue a closed market to record its realized profit once it resolves.
+    ///
+    /// Markets that nothing was invested in have nothing to settle.
+    pub fn queue_settlement(&mut self, market: &Market, market_invested: Decimal) {
+        if !market_invested.is_zero() {
+            self.settlements.push(market, market_invested, Instant::now());
+        }
+    }
+
+    /// USDC invested in markets that have not settled yet.
+    pub fn capital_at_risk(&self) -> Decimal {
+        self.at_risk.values().copied().sum()
+    }
+
+    /// Count a fill's investment against its market until the market settles.
+    fn commit_at_risk(&mut self, market: &str, investment: Decimal) {
+        *self.at_risk.entry(market.to_string()).or_default() += investment;
+        self.exposure.commit(investment);
+    }
+
+    /// Stop counting a settled market's investment toward the exposure cap.
+    fn release_at_risk(&mut self, market: &str) {
+        if let Some(invested) = self.at_risk.remove(market) {
+            self.exposure.release(invested);
+        }
+    }
+
+    /// Closed markets still waiting to resolve.
+    pub fn pending_settlements(&self) -> usize {
+        self.settlements.len()
+    }
+
+    /// Queue a settled market for redemption, due at once.
+    pub fn queue_redemption(&mut self, market: &Market) {
+        // Nothing is invested in a redemption; the queue is only for its backoff
+        self.redemptions.push(market, Decimal::ZERO, Instant::now());
+    }
+
+    /// Remove and return the queued redemptions due for an attempt at `now`.
+    ///
+    /// Each stays counted as pending until `finish_redemption` records its outcome.
+    pub fn take_due_redemptions(&mut self, now: Instant) -> Vec<PendingSettlement> {
+        let due = self.redemptions.take_due(now);
+        self.redeeming += due.len();
+        due
+    }
+
+    /// Record the outcome of a redemption taken with `take_due_redemptions`.
+    ///
+    /// Gas is recorded on success. A market that can never be redeemed here
+    /// (neg-risk, or shares held by a proxy wallet) is left for manual
+    /// redemption at once; any other failure is retried with backoff, up to
+    /// `MAX_REDEMPTION_ATTEMPTS` attempts. Either way a market given up on is
+    /// counted in `failed_redemptions`. Returns whether a payout was received.
+    pub fn finish_redemption(
+        &mut self,
+        pending: PendingSettlement,
+        result: Result<Option<Redemption>, TradingError>,
+    ) -> bool {
+        self.redeeming = self.redeeming.saturating_sub(1);
+        let market = pending.market.slug.clone();
+        let attempts = pending.attempts + 1;
+        let error = match result {
+            Ok(Some(redemption)) => {
+                self.record_gas(redemption.gas_cost);
+                return true;
+            }
+            Ok(None) => return false,
+            Err(e) => e,
+        };
+
+        let permanent = matches!(error, TradingError::RedemptionUnsupported(_));
+        if permanent || attempts >= MAX_REDEMPTION_ATTEMPTS {
+            self.failed_redemptions += 1;
+            error!(
+                market = %market, attempts, error = %error,
+                "Redemption failed, redeem manually"
+            );
+        } else {
+            warn!(market = %market, attempts, error = %error, "Redemption failed, retrying");
+            self.redemptions.retry(pending, Instant::now());
+        }
+        false
+    }
+
+    /// Settled markets still waiting to be redeemed, including any in flight.
+    pub fn pending_redemptions(&self) -> usize {
+        self.redemptions.len() + self.redeeming
+    }
+
+    /// Check the queued markets that are due and record those that resolved.
+    ///
+    /// A market with no positions left (fully unwound, or already redeemed)
+    /// settles at zero. Unresolved markets, and markets whose positions could
+    /// not be fetched, are checked again after a backoff. Returns the markets
+    /// settled with winning shares to redeem.
+    pub async fn settle_pending(&mut self, client: &PolymarketClient) -> Vec<Market> {
+        let mut settled = Vec::new();
+        for pending in self.settlements.take_due(Instant::now()) {
+            let value = match client.get_positions(&pending.token_ids()).await {
+                Ok(positions) if positions.is_empty() => Some(Decimal::ZERO),
+                Ok(positions) => settled_value(&positions),
+                Err(e) => {
+                    let market = &pending.market.slug;
+                    warn!(market = %market, error = %e, "Failed to fetch settled positions");
+                    None
+                }
+            };
+
+            match value {
+                Some(value) => {
+                    self.record_settlement(&pending.market.slug, value, pending.invested);
+                    if !value.is_zero() {
+                        settled.push(pending.market);
+                    }
+                }
+                None => {
+                    info!(
+                        market = %pending.market.slug,
+                        attempts = pending.attempts + 1,
+                        "Market not yet resolved, realized profit pending"
+                    );
+                    self.retry_settlement(pending);
+                }
+            }
+        }
+        settled
+    }
+
+    /// Re-queue an unresolved market with backoff.
+    ///
+    /// After `SETTLEMENT_MAX_ATTEMPTS` checks the market is dropped without a
+    /// realized profit, and stops counting toward the exposure cap.
+    fn retry_settlement(&mut self, pending: PendingSettlement) {
+        if pending.attempts + 1 >= SETTLEMENT_MAX_ATTEMPTS {
+            warn!(
+                market = %pending.market.slug,
+                attempts = pending.attempts + 1,
+                "Market never resolved, giving up on its realized profit"
+            );
+            self.release_at_risk(&pending.market.slug);
+            return;
+        }
+        self.settlements.retry(pending, Instant::now());
+    }
+
+    /// Record the settled value of a closed market against what was invested in it.
+    pub fn record_settlement(
+        &mut self,
+        market: &str,
+        settled_value: Decimal,
+        market_invested: Decimal,
+    ) {
+        let profit = settled_value - market_invested;
+        self.realized_profit = Some(self.realized_profit.unwrap_or(Decimal::ZERO) + profit);
+        self.release_at_risk(market);
+        // The market closed with its expected profit; replace it with the realized one
+        if let Some(result) = self.history.iter_mut().rev().find(|r| r.slug == market) {
+            result.profit = profit;
+            // A later row for the same market supersedes its closing row
+            if let Some(summaries) = &self.summaries {
+                summaries.record(result);
+            }
+        }
+        if let Some(journal) = &self.journal {
+            journal.record(&JournalRow::settlement(market, market_invested, settled_value));
+        }
+
+        info!(
+            settled_value = %settled_value,
+            invested = %market_invested,
+            realized_profit = %profit,
+            "Recorded market settlement"
+        );
+    }
+
+    /// Record a closed market against stats captured when it opened.
+    ///
+    /// Always uses the expected profit of the pairs bought; `record_settlement`
+    /// replaces it once the market resolves. Other markets settling while this
+    /// one traded do not count toward it. A market queued for settlement keeps
+    /// counting toward the exposure cap until it settles; any other market (a
+    /// dry run's, or one with nothing invested) is released now.
+    pub fn record_market_result(&mut self, slug: &str, before: &ExecutorStats) -> MarketResult {
+        let after = self.stats();
+        let result = MarketResult {
+            slug: slug.to_string(),
+            invested: after.total_invested - before.total_invested,
+            profit: after.expected_profit() - before.expected_profit(),
+            trades: after.trades_executed - before.trades_executed,
+        };
+
+        self.open_exposure = Decimal::ZERO;
+        self.last_opportunity = None;
+        self.fill_stats.reset();
+        if !self.settlements.contains(slug) {
+            self.release_at_risk(slug);
+        }
+
+        if let Some(summaries) = &self.summaries {
+            summaries.record(&result);
+        }
+        self.history.push_back(result.clone());
+        while self.history.len() > self.history_limit {
+            self.history.pop_front();
+        }
+
+        result
+    }
+
+    /// Get closed-market results, oldest first.
+    pub fn history(&self) -> Vec<MarketResult> {
+        self.history.iter().cloned().collect()
+    }
+
+    /// Record gas paid for a redemption, in POL.
+    pub fn record_gas(&mut self, gas: Decimal) {
+        self.total_gas += gas;
+    }
+
+    /// Add the fees charged on an order's fills.
+    fn record_fee(&mut self, state: &OrderState) {
+        self.total_fees += state.fee.unwrap_or_default();
+    }
+
+    /// Publish balance and exposure gauges.
+    ///
+    /// The live balance comes from the client's cache, so it reflects the last
+    /// fetch plus any fills deducted since.
+    pub fn update_gauges(&self, client: &PolymarketClient) {
+        metrics::set_balance_gauge(client.cached_balance().map(|b| b.value), self.sim_balance);
+        metrics::set_exposure_gauge(self.open_exposure);
+    }
+
     /// Get statistics summary.
     pub fn stats(&self) -> ExecutorStats {
         ExecutorStats {
@@ -506,14 +1795,23 @@ impl ArbitrageExecutor {
             opportunities_found: self.opportunities_found,
             total_invested: self.total_invested,
             total_shares_bought: self.total_shares_bought,
+            total_fees: self.total_fees,
+            total_gas: self.total_gas,
+            gas_usdc: self.total_gas * self.pol_price_usdc,
             sim_balance: self.sim_balance,
             sim_start_balance: self.sim_start_balance,
+            realized_profit: self.realized_profit,
+            open_exposure: self.open_exposure,
+            circuit_open: self.is_circuit_open(),
+            fill_rate: self.fill_stats.fill_rate(),
+            pending_redemptions: self.pending_redemptions(),
+            failed_redemptions: self.failed_redemptions,
         }
     }
 }
 
 /// Executor statistics.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default)]
 pub struct ExecutorStats {
     /// Total trades executed.
     pub trades_executed: u64,
@@ -523,64 +1821,391 @@ pub struct ExecutorStats {
     pub total_invested: Decimal,
     /// Total shares bought.
     pub total_shares_bought: Decimal,
+    /// Trading fees charged on fills.
+    pub total_fees: Decimal,
+    /// Gas paid for redemptions, in POL.
+    pub total_gas: Decimal,
+    /// Redemption gas in USDC at `POL_PRICE_USDC` (zero when no price is set).
+    pub gas_usdc: Decimal,
     /// Current simulation balance.
     pub sim_balance: Decimal,
     /// Starting simulation balance.
     pub sim_start_balance: Decimal,
+    /// Realized profit from settled markets.
+    pub realized_profit: Option<Decimal>,
+    /// Unhedged shares left after a failed unwind.
+    pub open_exposure: Decimal,
+    /// Whether the circuit breaker is open.
+    pub circuit_open: bool,
+    /// Rolling fraction of recent legs that filled this market.
+    pub fill_rate: Option<Decimal>,
+    /// Settled markets whose redemption is queued, in flight, or being retried.
+    pub pending_redemptions: usize,
+    /// Settled markets left for manual redemption: unsupported, or out of attempts.
+    pub failed_redemptions: u64,
 }
 
 impl ExecutorStats {
-    /// Calculate expected profit at settlement.
+    /// Calculate expected profit at settlement, net of fees and redemption gas.
     pub fn expected_profit(&self) -> Decimal {
         // Each pair of shares (UP + DOWN) pays $1.00 at settlement
         let pairs = self.total_shares_bought / Decimal::TWO;
-        pairs - self.total_invested
+        pairs - self.total_invested - self.total_fees - self.gas_usdc
     }
 
-    /// Calculate simulation ending balance (after claiming).
+    /// Calculate simulation ending balance (after claiming), net of fees and gas.
     pub fn sim_ending_balance(&self) -> Decimal {
         let pairs = self.total_shares_bought / Decimal::TWO;
-        self.sim_balance + pairs
+        self.sim_balance + pairs - self.total_fees - self.gas_usdc
+    }
+}
+
+/// Outcome of a single closed market.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MarketResult {
+    /// Market slug.
+    pub slug: String,
+    /// Amount invested in this market.
+    pub invested: Decimal,
+    /// Profit from this market (realized if settled, otherwise expected).
+    pub profit: Decimal,
+    /// Trades executed in this market.
+    pub trades: u64,
+}
+
+/// Aggregate statistics over closed-market results.
+#[derive(Debug, Clone, PartialEq)]
+pub struct HistorySummary {
+    /// Markets summarized.
+    pub markets: usize,
+    /// Markets with at least one trade.
+    pub traded_markets: usize,
+    /// Total invested across markets.
+    pub total_invested: Decimal,
+    /// Total profit across markets.
+    pub total_profit: Decimal,
+    /// Total profit over total invested (None if nothing was invested).
+    pub roi: Option<Decimal>,
+    /// Fraction of traded markets that were net-positive (None if none traded).
+    pub win_rate: Option<Decimal>,
+}
+
+/// Summarize closed-market results into aggregate ROI and win rate.
+pub fn summarize_history(results: &[MarketResult]) -> HistorySummary {
+    let total_invested: Decimal = results.iter().map(|r| r.invested).sum();
+    let total_profit: Decimal = results.iter().map(|r| r.profit).sum();
+    let traded: Vec<&MarketResult> = results.iter().filter(|r| r.trades > 0).collect();
+    let winners = traded.iter().filter(|r| r.profit > Decimal::ZERO).count();
+
+    HistorySummary {
+        markets: results.len(),
+        traded_markets: traded.len(),
+        total_invested,
+        total_profit,
+        roi: (!total_invested.is_zero()).then(|| total_profit / total_invested),
+        win_rate: (!traded.is_empty())
+            .then(|| Decimal::from(winners) / Decimal::from(traded.len())),
+    }
+}
+
+/// Shares a leg ended up holding, or `None` if it did not fill.
+///
+/// FOK and GTC legs count only once fully filled. A FAK leg is terminal as soon
+/// as its remainder is cancelled, so any matched size counts as a fill.
+fn leg_fill_size(state: &OrderState, tif: TimeInForce, requested: Decimal) -> Option<Decimal> {
+    if state.is_filled {
+        return Some(state.filled_size.unwrap_or(requested));
+    }
+    if tif == TimeInForce::FAK && state.is_terminal {
+        return state.filled_size.filter(|size| *size > Decimal::ZERO);
+    }
+    None
+}
+
+/// Submit a sequential leg, retrying once at a fresh worst-fill price if the book moved.
+///
+/// On a `PriceMoved` rejection the leg's book is re-fetched and the pair is
+/// re-checked with the other leg's limit against the opportunity's
+/// `max_pair_cost`, `MIN_EDGE_BPS`, and the investment the balance check
+/// cleared (the opportunity's times `BALANCE_MARGIN`). The rejection stands
+/// when the pair no longer passes or the fresh price is the one just rejected;
+/// there is never more than one retry. On a retry `params.price` is updated
+/// to the price submitted.
+async fn submit_leg_with_repricing(
+    client: &PolymarketClient,
+    params: &mut OrderParams,
+    other_leg_price: Decimal,
+    opportunity: &ArbitrageOpportunity,
+    config: &Config,
+) -> Result<String, TradingError> {
+    let rejection = match submit_order(client, params).await {
+        Err(rejection @ TradingError::PriceMoved { .. }) => rejection,
+        result => return result,
+    };
+
+    let fresh_price = match client.get_order_book(&params.token_id).await {
+        Ok(book) => calculate_fill_price(&book.asks, params.size)
+            .ok()
+            .map(|fill| fill.worst_price),
+        Err(e) => {
+            warn!(error = %e, "Failed to re-fetch book after price rejection");
+            None
+        }
+    };
+    let Some(fresh_price) = fresh_price else {
+        info!(token_id = %params.token_id, "No fill price on the fresh book, not retrying");
+        return Err(rejection);
+    };
+
+    let pair_cost = fresh_price + other_leg_price;
+    let edge_bps = config.net_edge_bps(Decimal::ONE - pair_cost);
+    // A retry may not cost more than the balance check cleared
+    let budget = opportunity.total_investment * config.balance_margin;
+    if fresh_price == params.price
+        || pair_cost > opportunity.max_pair_cost
+        || edge_bps < config.min_edge_bps
+        || pair_cost * params.size > budget
+    {
+        info!(
+            token_id = %params.token_id,
+            rejected_price = %params.price,
+            fresh_price = %fresh_price,
+            pair_cost = %pair_cost,
+            max_pair_cost = %opportunity.max_pair_cost,
+            edge_bps = %edge_bps,
+            min_edge_bps = %config.min_edge_bps,
+            budget = %budget,
+            "Book moved, pair no longer worth a retry"
+        );
+        return Err(rejection);
+    }
+
+    info!(
+        token_id = %params.token_id,
+        rejected_price = %params.price,
+        fresh_price = %fresh_price,
+        pair_cost = %pair_cost,
+        "Book moved, retrying once at the fresh price"
+    );
+    params.price = fresh_price;
+    submit_order(client, params).await
+}
+
+/// Order ID of a placed leg, or `None` for a post-only leg that would have crossed.
+fn placed_unless_crossed(
+    result: Result<String, TradingError>,
+) -> Result<Option<String>, TradingError> {
+    match result {
+        Ok(order_id) => Ok(Some(order_id)),
+        Err(e @ TradingError::PostOnlyCrossed { .. }) => {
+            info!(error = %e, "Maker leg would have crossed, treating it as unfilled");
+            Ok(None)
+        }
+        Err(e) => Err(e),
+    }
+}
+
+/// First leg `balance` cannot fund once the legs before it are, with its cost and what was left.
+///
+/// Legs are `(outcome, reserved USDC)` in submission order.
+fn underfunded_leg(
+    balance: Decimal,
+    legs: [(Outcome, Decimal); 2],
+) -> Option<(Outcome, Decimal, Decimal)> {
+    let mut available = balance;
+    for (leg, cost) in legs {
+        if cost > available {
+            return Some((leg, cost, available));
+        }
+        available -= cost;
+    }
+    None
+}
+
+/// Which leg to submit first in sequential leg mode.
+///
+/// `ThinnerFirst` picks the side with less ask depth at or better than its fill
+/// price, since it is the likelier to be rejected; ties go to UP.
+pub fn first_leg(policy: FirstLegPolicy, opportunity: &ArbitrageOpportunity) -> Outcome {
+    match policy {
+        FirstLegPolicy::UpFirst => Outcome::Up,
+        FirstLegPolicy::DownFirst => Outcome::Down,
+        FirstLegPolicy::ThinnerFirst => {
+            let up_depth = cumulative_depth_up_to(&opportunity.up_asks, opportunity.up_price);
+            let down_depth =
+                cumulative_depth_up_to(&opportunity.down_asks, opportunity.down_price);
+            if down_depth < up_depth {
+                Outcome::Down
+            } else {
+                Outcome::Up
+            }
+        }
+    }
+}
+
+/// Build the paired BUY order parameters for an opportunity.
+///
+/// Fails when the entry offset or the clamp to target would price a leg below one tick.
+pub(super) fn order_params(
+    opportunity: &ArbitrageOpportunity,
+    config: &Config,
+) -> Result<(OrderParams, OrderParams), TradingError> {
+    // Parse time-in-force from config; strict hedging only sends all-or-nothing legs
+    let tif = match config.order_type.to_uppercase().as_str() {
+        _ if config.strict_hedge => TimeInForce::FOK,
+        "FOK" => TimeInForce::FOK,
+        "FAK" => TimeInForce::FAK,
+        "GTC" => TimeInForce::GTC,
+        _ => TimeInForce::FOK,
+    };
+
+    // Shift entry prices by the configured offset from the worst-fill prices
+    let tick_size = opportunity.market.tick_size_or(config.tick_size);
+    let (up_price, down_price) = entry_prices(
+        opportunity.up_price,
+        opportunity.down_price,
+        config.entry_price_offset_bps,
+        opportunity.max_pair_cost,
+        tick_size,
+    )
+    .ok_or_else(|| {
+        TradingError::InvalidParams(format!(
+            "entry prices for {} fall below one tick ({})",
+            opportunity.market.slug, tick_size
+        ))
+    })?;
+    let neg_risk = opportunity.market.neg_risk_or(config.neg_risk_default);
+    // Resting legs go out as GTD so they are off the book before the close
+    let (tif, expiration) = match tif {
+        TimeInForce::GTC => TimeInForce::resting(
+            opportunity.market.end_timestamp,
+            config.default_order_ttl_seconds,
+            chrono::Utc::now().timestamp(),
+        ),
+        tif => (tif, 0),
+    };
+
+    // Create UP order parameters
+    let mut up_params = OrderParams {
+        token_id: opportunity.market.up_token_id.clone(),
+        side: Side::Buy,
+        price: up_price,
+        size: opportunity.order_size,
+        tif,
+        neg_risk,
+        expiration,
+        post_only: false,
+        tick_size: opportunity.market.tick_size,
+    };
+
+    // Create DOWN order parameters
+    let mut down_params = OrderParams {
+        token_id: opportunity.market.down_token_id.clone(),
+        side: Side::Buy,
+        price: down_price,
+        size: opportunity.order_size,
+        tif,
+        neg_risk,
+        expiration,
+        post_only: false,
+        tick_size: opportunity.market.tick_size,
+    };
+
+    // The maker leg rests instead of crossing, avoiding the taker fee
+    let market_end = opportunity.market.end_timestamp;
+    let maker_leg = if config.strict_hedge { MakerLeg::None } else { config.maker_leg };
+    match maker_leg {
+        MakerLeg::None => {}
+        MakerLeg::Up => {
+            rest_as_maker(&mut up_params, &opportunity.up_asks, market_end, tick_size, config)
+        }
+        MakerLeg::Down => {
+            rest_as_maker(&mut down_params, &opportunity.down_asks, market_end, tick_size, config)
+        }
     }
+
+    Ok((up_params, down_params))
+}
+
+/// Turn a leg into a post-only resting order one tick inside its best ask.
+///
+/// The price never rises above the leg's entry price, so the pair cost stays
+/// within target. The order is a GTD off the book before the close, per
+/// [`TimeInForce::resting`].
+fn rest_as_maker(
+    params: &mut OrderParams,
+    asks: &[PriceLevel],
+    market_end: i64,
+    tick_size: Decimal,
+    config: &Config,
+) {
+    let best_ask = asks.iter().map(|level| level.price).min().unwrap_or(params.price);
+    params.price = (best_ask - tick_size).max(tick_size).min(params.price);
+    params.post_only = true;
+    (params.tif, params.expiration) = TimeInForce::resting(
+        market_end,
+        config.default_order_ttl_seconds,
+        chrono::Utc::now().timestamp(),
+    );
+}
+
+/// Balanced UP/DOWN pairs held (the smaller of the two leg sizes).
+pub fn balanced_pairs(
+    positions: &[PositionResponse],
+    up_token_id: &str,
+    down_token_id: &str,
+) -> Decimal {
+    let size_of = |token_id: &str| -> Decimal {
+        positions
+            .iter()
+            .filter(|p| {
+                p.token_id.as_deref() == Some(token_id)
+                    || p.asset.as_ref().and_then(|a| a.token_id.as_deref()) == Some(token_id)
+            })
+            .filter_map(|p| p.size.as_deref()?.parse::<Decimal>().ok())
+            .sum()
+    };
+
+    size_of(up_token_id).min(size_of(down_token_id))
+}
+
+/// Calculate the settled value of positions once their market has resolved.
+///
+/// Each share is worth its resolved price (1 for the winning side, 0 for the
+/// losing side). Returns `None` if any position is not yet resolved.
+pub fn settled_value(positions: &[PositionResponse]) -> Option<Decimal> {
+    let mut value = Decimal::ZERO;
+
+    for position in positions {
+        let size: Decimal = position.size.as_deref()?.parse().ok()?;
+        let price: Decimal = position.cur_price.as_deref()?.parse().ok()?;
+
+        if price != Decimal::ZERO && price != Decimal::ONE {
+            return None;
+        }
+
+        value += size * price;
+    }
+
+    Some(value)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::market::Market;
+    use crate::arbitrage::snapshot::BookSnapshot;
+    use crate::arbitrage::summary::load_summaries;
+    use crate::orderbook::OutcomeBook;
+    use crate::trading::order::{OrderStatus, GTD_SECURITY_BUFFER_SECS};
     use rust_decimal_macros::dec;
     use time::OffsetDateTime;
 
     fn test_config() -> Config {
         Config {
-            polymarket_private_key: "0x0123456789abcdef0123456789abcdef0123456789abcdef0123456789abcdef".to_string(),
-            polymarket_api_key: None,
-            polymarket_api_secret: None,
-            polymarket_api_passphrase: None,
-            polymarket_signature_type: 0,
-            polymarket_funder: None,
-            target_pair_cost: dec!(0.991),
             order_size: dec!(10),
-            order_type: "FOK".to_string(),
-            balance_margin: dec!(1.2),
-            dry_run: true,
-            sim_balance: dec!(100),
-            cooldown_seconds: 10,
-            polymarket_market_slug: None,
-            use_wss: false,
             polymarket_ws_url: "wss://test".to_string(),
             polymarket_clob_url: "https://test".to_string(),
-            port: 8080,
-            rust_log: "info".to_string(),
-            verbose: false,
-            http_timeout_ms: 2000,
-            http_pool_size: 10,
-            order_timeout_ms: 500,
-            order_poll_interval_ms: 50,
-            ws_reconnect_max_delay_s: 30,
-            ws_heartbeat_interval_s: 30,
-            metrics_enabled: true,
-            metrics_port: 9090,
+            ..Config::test_default()
         }
     }
 
@@ -594,6 +2219,10 @@ mod tests {
                 start_timestamp: 0,
                 end_timestamp: 900,
                 question: None,
+                neg_risk: None,
+                condition_id: None,
+                accepting_orders_until: None,
+                tick_size: None,
             },
             up_price: dec!(0.48),
             down_price: dec!(0.51),
@@ -604,10 +2233,15 @@ mod tests {
             total_investment: dec!(9.9),
             expected_payout: dec!(10),
             expected_profit: dec!(0.1),
+            max_pair_cost: dec!(0.991),
             best_ask_up: Some(dec!(0.48)),
             best_ask_down: Some(dec!(0.51)),
             vwap_up: Some(dec!(0.48)),
             vwap_down: Some(dec!(0.51)),
+            up_asks: vec![PriceLevel::new(dec!(0.48), dec!(100))],
+            down_asks: vec![PriceLevel::new(dec!(0.51), dec!(100))],
+            up_book: OutcomeBook::default(),
+            down_book: OutcomeBook::default(),
             detected_at: OffsetDateTime::now_utc(),
         }
     }
@@ -640,11 +2274,1002 @@ mod tests {
             opportunities_found: 5,
             total_invested: dec!(29.7), // 3 trades * 9.9
             total_shares_bought: dec!(60), // 3 trades * 20 shares
+            total_fees: Decimal::ZERO,
+            total_gas: Decimal::ZERO,
+            gas_usdc: Decimal::ZERO,
             sim_balance: dec!(70.3),
             sim_start_balance: dec!(100),
+            realized_profit: None,
+            open_exposure: Decimal::ZERO,
+            circuit_open: false,
+            fill_rate: None,
+            pending_redemptions: 0,
+            failed_redemptions: 0,
         };
 
         // 30 pairs * $1 - $29.7 = $0.3 profit
         assert_eq!(stats.expected_profit(), dec!(0.3));
     }
+
+    #[test]
+    fn fees_reduce_net_profit() {
+        let stats = ExecutorStats {
+            trades_executed: 3,
+            opportunities_found: 3,
+            total_invested: dec!(29.7),
+            total_shares_bought: dec!(60),
+            total_fees: dec!(0.15),
+            total_gas: Decimal::ZERO,
+            gas_usdc: Decimal::ZERO,
+            sim_balance: dec!(70.3),
+            sim_start_balance: dec!(100),
+            realized_profit: None,
+            open_exposure: Decimal::ZERO,
+            circuit_open: false,
+            fill_rate: None,
+            pending_redemptions: 0,
+            failed_redemptions: 0,
+        };
+
+        // $0.3 gross less $0.15 in fees
+        assert_eq!(stats.expected_profit(), dec!(0.15));
+        assert_eq!(stats.sim_ending_balance(), dec!(100.15));
+    }
+
+    #[test]
+    fn redemption_gas_is_tracked_apart_from_usdc_profit() {
+        let mut executor = ArbitrageExecutor::new(&test_config());
+        let expected = executor.stats().expected_profit();
+
+        executor.record_gas(dec!(0.006));
+        executor.record_gas(dec!(0.004));
+
+        let stats = executor.stats();
+        assert_eq!(stats.total_gas, dec!(0.01));
+        assert_eq!(stats.expected_profit(), expected);
+    }
+
+    #[test]
+    fn redemption_gas_is_netted_at_the_pol_price() {
+        let config = Config {
+            pol_price_usdc: dec!(0.5),
+            ..test_config()
+        };
+        let mut executor = ArbitrageExecutor::new(&config);
+        let expected = executor.stats().expected_profit();
+
+        executor.record_gas(dec!(0.01));
+
+        let stats = executor.stats();
+        assert_eq!(stats.gas_usdc, dec!(0.005));
+        assert_eq!(stats.expected_profit(), expected - dec!(0.005));
+        assert_eq!(stats.sim_ending_balance(), config.sim_balance - dec!(0.005));
+    }
+
+    #[test]
+    fn failed_redemption_is_retried_then_left_for_manual_redemption() {
+        let mut executor = ArbitrageExecutor::new(&test_config());
+        executor.queue_redemption(&test_opportunity().market);
+        let failed = || Err(TradingError::RedemptionFailed("no receipt".to_string()));
+
+        let mut pending = executor.take_due_redemptions(Instant::now()).pop().unwrap();
+        for _ in 1..MAX_REDEMPTION_ATTEMPTS {
+            // In flight, then re-queued: pending either way
+            assert_eq!(executor.stats().pending_redemptions, 1);
+            assert!(!executor.finish_redemption(pending, failed()));
+            assert_eq!(executor.stats().pending_redemptions, 1);
+            // Backed off, so not due again straight away
+            assert!(executor.take_due_redemptions(Instant::now()).is_empty());
+            let later = Instant::now() + Duration::from_secs(3600);
+            pending = executor.take_due_redemptions(later).pop().unwrap();
+        }
+
+        executor.finish_redemption(pending, failed());
+        let stats = executor.stats();
+        assert_eq!((stats.pending_redemptions, stats.failed_redemptions), (0, 1));
+    }
+
+    #[test]
+    fn unsupported_redemption_is_not_retried() {
+        let mut executor = ArbitrageExecutor::new(&test_config());
+        executor.queue_redemption(&test_opportunity().market);
+        let pending = executor.take_due_redemptions(Instant::now()).pop().unwrap();
+
+        let neg_risk = TradingError::RedemptionUnsupported("neg-risk market".to_string());
+        executor.finish_redemption(pending, Err(neg_risk));
+
+        let stats = executor.stats();
+        assert_eq!((stats.pending_redemptions, stats.failed_redemptions), (0, 1));
+        let later = Instant::now() + Duration::from_secs(3600);
+        assert!(executor.take_due_redemptions(later).is_empty());
+    }
+
+    #[test]
+    fn successful_redemption_records_its_gas() {
+        let mut executor = ArbitrageExecutor::new(&test_config());
+        executor.queue_redemption(&test_opportunity().market);
+        let pending = executor.take_due_redemptions(Instant::now()).pop().unwrap();
+
+        let redemption = Redemption {
+            tx_hash: "0xabc".to_string(),
+            usdc_received: dec!(10),
+            gas_cost: dec!(0.006),
+        };
+        assert!(executor.finish_redemption(pending, Ok(Some(redemption))));
+
+        let stats = executor.stats();
+        assert_eq!(stats.total_gas, dec!(0.006));
+        assert_eq!(stats.pending_redemptions, 0);
+    }
+
+    fn settled_position(token_id: &str, size: &str, cur_price: &str) -> PositionResponse {
+        PositionResponse {
+            token_id: Some(token_id.to_string()),
+            asset: None,
+            size: Some(size.to_string()),
+            avg_price: Some("0.5".to_string()),
+            cur_price: Some(cur_price.to_string()),
+        }
+    }
+
+    #[test]
+    fn realized_profit_with_unbalanced_leg() {
+        let config = test_config();
+        let mut executor = ArbitrageExecutor::new(&config);
+
+        // 10 UP (won) + 8 DOWN (lost) after a partial unwind, $8.90 invested
+        let positions = vec![
+            settled_position("up-token", "10", "1"),
+            settled_position("down-token", "8", "0"),
+        ];
+        let value = settled_value(&positions).unwrap();
+        assert_eq!(value, dec!(10));
+
+        executor.record_settlement("btc-updown-15m-0", value, dec!(8.9));
+        assert_eq!(executor.stats().realized_profit, Some(dec!(1.1)));
+    }
+
+    #[test]
+    fn settlement_after_close_updates_history() {
+        let config = test_config();
+        let mut executor = ArbitrageExecutor::new(&config);
+        let before = executor.stats();
+        executor.total_invested = dec!(9.6);
+        executor.total_shares_bought = dec!(20);
+        let result = executor.record_market_result("btc-updown-15m-0", &before);
+        assert_eq!(result.profit, dec!(0.4));
+
+        executor.record_settlement("btc-updown-15m-0", dec!(9.5), dec!(9.6));
+
+        assert_eq!(executor.history()[0].profit, dec!(-0.1));
+    }
+
+    #[test]
+    fn settlement_of_another_market_is_not_counted_in_the_closing_one() {
+        let mut executor = ArbitrageExecutor::new(&test_config());
+
+        // Market A closes with 0.4 expected, then B opens
+        let before_a = executor.stats();
+        executor.total_invested = dec!(9.6);
+        executor.total_shares_bought = dec!(20);
+        executor.queue_settlement(&test_opportunity().market, dec!(9.6));
+        executor.record_market_result("btc-updown-15m-123", &before_a);
+        let before_b = executor.stats();
+
+        // A resolves while B is still trading; B buys 10 pairs at 0.99
+        executor.record_settlement("btc-updown-15m-123", dec!(10), dec!(9.6));
+        executor.total_invested += dec!(9.9);
+        executor.total_shares_bought += dec!(20);
+        let result_b = executor.record_market_result("btc-updown-15m-1023", &before_b);
+
+        assert_eq!(result_b.profit, dec!(0.1));
+        let profits: Vec<Decimal> = executor.history().iter().map(|r| r.profit).collect();
+        assert_eq!(profits, vec![dec!(0.4), dec!(0.1)]);
+        assert_eq!(executor.stats().realized_profit, Some(dec!(0.4)));
+    }
+
+    #[test]
+    fn settled_market_summary_carries_realized_profit() {
+        let dir = std::env::temp_dir().join(format!("settled-summaries-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        let mut config = test_config();
+        config.summary_dir = dir.to_str().unwrap().to_string();
+        let mut executor = ArbitrageExecutor::new(&config);
+
+        // Closes with 0.4 expected from 10 pairs at 0.96, then only one leg pays out
+        let before = executor.stats();
+        executor.total_invested = dec!(9.6);
+        executor.total_shares_bought = dec!(20);
+        executor.trades_executed = 1;
+        executor.queue_settlement(&test_opportunity().market, dec!(9.6));
+        executor.record_market_result("btc-updown-15m-123", &before);
+        executor.record_settlement("btc-updown-15m-123", dec!(9), dec!(9.6));
+
+        let results = load_summaries(&dir).unwrap();
+        let _ = std::fs::remove_dir_all(&dir);
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].profit, dec!(-0.6));
+        assert_eq!((results[0].invested, results[0].trades), (dec!(9.6), 1));
+    }
+
+    #[test]
+    fn unresolved_market_is_given_up_after_max_attempts() {
+        let mut executor = ArbitrageExecutor::new(&test_config());
+        let market = test_opportunity().market;
+        executor.commit_at_risk(&market.slug, dec!(9.6));
+        executor.queue_settlement(&market, dec!(9.6));
+
+        let later = Instant::now() + Duration::from_secs(3600);
+        for _ in 0..SETTLEMENT_MAX_ATTEMPTS {
+            let pending = executor.settlements.take_due(later).pop().unwrap();
+            assert_eq!(executor.capital_at_risk(), dec!(9.6));
+            executor.retry_settlement(pending);
+        }
+
+        assert_eq!(executor.pending_settlements(), 0);
+        assert!(executor.capital_at_risk().is_zero());
+        assert_eq!(executor.stats().realized_profit, None);
+    }
+
+    #[test]
+    fn settled_value_is_none_before_resolution() {
+        let positions = vec![
+            settled_position("up-token", "10", "0.62"),
+            settled_position("down-token", "10", "0.38"),
+        ];
+
+        assert_eq!(settled_value(&positions), None);
+    }
+
+    async fn run_simulation(config: &Config, rounds: usize) -> ExecutorStats {
+        let client = PolymarketClient::new(config);
+        let mut executor = ArbitrageExecutor::new(config);
+
+        // One opportunity per window so rounds aren't deduplicated
+        for round in 0..rounds as i64 {
+            let mut opportunity = test_opportunity();
+            opportunity.market.start_timestamp = round * Market::WINDOW_SECONDS;
+            executor.execute(&client, &opportunity, config).await.unwrap();
+        }
+
+        executor.stats()
+    }
+
+    #[tokio::test]
+    async fn execution_writes_book_snapshot() {
+        let dir = std::env::temp_dir().join(format!("book-snapshots-{}", std::process::id()));
+        let mut config = test_config();
+        config.snapshot_dir = dir.to_str().unwrap().to_string();
+        let mut executor = ArbitrageExecutor::new(&config);
+        let client = PolymarketClient::new(&config);
+        let opportunity = test_opportunity();
+
+        let result = executor.execute(&client, &opportunity, &config).await.unwrap();
+        let exec_id = result.exec_id().unwrap();
+
+        // Snapshots are written in the background
+        let path = dir.join(format!("book-snapshots-{}.jsonl", opportunity.detected_at.date()));
+        let mut text = String::new();
+        for _ in 0..100 {
+            text = std::fs::read_to_string(&path).unwrap_or_default();
+            if !text.is_empty() {
+                break;
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+        }
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        let snapshot: BookSnapshot = serde_json::from_str(text.lines().next().unwrap()).unwrap();
+        assert_eq!(snapshot.exec_id, exec_id);
+        assert_eq!(snapshot.market, opportunity.market.slug);
+        assert_eq!(snapshot.total_cost, dec!(0.99));
+        assert_eq!(snapshot.up_asks, opportunity.up_book.asks);
+        assert_eq!(snapshot.down_bids, opportunity.down_book.bids);
+    }
+
+    #[tokio::test]
+    async fn executors_sharing_exec_ids_never_repeat_one() {
+        let config = test_config();
+        let exec_ids = Arc::new(AtomicU64::new(0));
+        let mut first = ArbitrageExecutor::new(&config).with_exec_ids(exec_ids.clone());
+        let mut second = ArbitrageExecutor::new(&config).with_exec_ids(exec_ids);
+        let client = PolymarketClient::new(&config);
+        let opportunity = test_opportunity();
+
+        let first_id = first.execute(&client, &opportunity, &config).await.unwrap().exec_id();
+        let second_id = second.execute(&client, &opportunity, &config).await.unwrap().exec_id();
+
+        assert_eq!(first_id, Some(1));
+        assert_eq!(second_id, Some(2));
+    }
+
+    #[tokio::test]
+    async fn same_sim_seed_gives_identical_stats() {
+        let mut config = test_config();
+        config.cooldown_seconds = 0;
+        config.sim_fill_probability = dec!(0.5);
+        config.sim_balance = dec!(1000);
+        config.sim_seed = Some(42);
+
+        let first = run_simulation(&config, 20).await;
+        let second = run_simulation(&config, 20).await;
+
+        assert!(first.trades_executed > 0 && first.trades_executed < 20);
+        assert_eq!(first.trades_executed, second.trades_executed);
+        assert_eq!(first.total_invested, second.total_invested);
+        assert_eq!(first.total_shares_bought, second.total_shares_bought);
+        assert_eq!(first.sim_balance, second.sim_balance);
+    }
+
+    #[tokio::test]
+    async fn seeded_leg_failures_give_reproducible_partials() {
+        let mut config = test_config();
+        config.cooldown_seconds = 0;
+        config.sim_balance = dec!(1000);
+        config.sim_seed = Some(7);
+        config.sim_slippage_bps = dec!(50);
+
+        let partials = |rate| {
+            let mut config = config.clone();
+            config.sim_leg_failure_rate = rate;
+            async move {
+                let client = PolymarketClient::new(&config);
+                let mut executor = ArbitrageExecutor::new(&config);
+                let mut partials = 0;
+                for round in 0..50 {
+                    let mut opportunity = test_opportunity();
+                    opportunity.market.start_timestamp = round * Market::WINDOW_SECONDS;
+                    let result = executor.execute(&client, &opportunity, &config).await.unwrap();
+                    if matches!(result, ExecutionResult::PartialFill { .. }) {
+                        partials += 1;
+                    }
+                }
+                (partials, executor.stats())
+            }
+        };
+
+        let (none, _) = partials(Decimal::ZERO).await;
+        let (all, all_stats) = partials(Decimal::ONE).await;
+        let (some, stats) = partials(dec!(0.2)).await;
+        let (again, stats_again) = partials(dec!(0.2)).await;
+
+        assert_eq!(none, 0);
+        assert_eq!(all, 50);
+        assert_eq!(all_stats.trades_executed, 0);
+        // Each partial is unwound at a slippage loss
+        assert!(all_stats.sim_balance < dec!(1000));
+        // Seed 7 loses a leg in 12 of 50 rounds at a 20% rate
+        assert_eq!(some, 12);
+        assert_eq!(some, again);
+        assert_eq!(stats.sim_balance, stats_again.sim_balance);
+    }
+
+    #[tokio::test]
+    async fn shallow_book_gives_partial_simulated_fill() {
+        let mut config = test_config();
+        config.order_type = "FAK".to_string();
+        config.sim_slippage_bps = dec!(10);
+        let client = PolymarketClient::new(&config);
+        let mut executor = ArbitrageExecutor::new(&config);
+        let mut opportunity = test_opportunity();
+        // Only 4 UP and 6 DOWN shares rest at or below the limit prices
+        opportunity.up_asks = vec![PriceLevel::new(dec!(0.48), dec!(4))];
+        opportunity.down_asks = vec![PriceLevel::new(dec!(0.51), dec!(6))];
+
+        let result = executor.execute(&client, &opportunity, &config).await.unwrap();
+
+        let ExecutionResult::Simulated {
+            filled_size,
+            would_invest,
+            would_profit,
+            ..
+        } = result
+        else {
+            panic!("expected simulated fill, got {:?}", result);
+        };
+        assert_eq!(filled_size, dec!(4));
+        // 4 * (0.48 + 0.51) * 1.001
+        assert_eq!(would_invest, dec!(3.963960));
+        assert!(would_profit < dec!(4) - would_invest);
+        assert_eq!(executor.stats().total_shares_bought, dec!(8));
+        assert!(executor.sim_balance < config.sim_balance - would_invest);
+    }
+
+    #[tokio::test]
+    async fn low_fill_rate_switches_fok_to_fak() {
+        let mut config = test_config();
+        config.cooldown_seconds = 0;
+        config.min_fill_rate = dec!(0.5);
+        let client = PolymarketClient::new(&config);
+        let mut executor = ArbitrageExecutor::new(&config);
+        // Only 4 UP shares rest at the limit, so FOK rejects and FAK partially fills
+        let shallow = |start_timestamp| {
+            let mut opportunity = test_opportunity();
+            opportunity.market.start_timestamp = start_timestamp;
+            opportunity.up_asks = vec![PriceLevel::new(dec!(0.48), dec!(4))];
+            opportunity
+        };
+
+        let result = executor.execute(&client, &shallow(0), &config).await.unwrap();
+        assert!(matches!(result, ExecutionResult::PartialFill { .. }), "got {:?}", result);
+        assert_eq!(executor.stats().fill_rate, Some(dec!(0.5)));
+
+        // A third rejected leg drops the rate below the minimum
+        executor.fill_stats.record(false);
+        let opportunity = shallow(Market::WINDOW_SECONDS);
+        let result = executor.execute(&client, &opportunity, &config).await.unwrap();
+        let ExecutionResult::Simulated { filled_size, .. } = result else {
+            panic!("expected FAK partial fill, got {:?}", result);
+        };
+        assert_eq!(filled_size, dec!(4));
+    }
+
+    #[tokio::test]
+    async fn mismatched_token_pair_is_refused() {
+        let config = test_config();
+        let client = PolymarketClient::new(&config);
+        let mut executor = ArbitrageExecutor::new(&config);
+        let mut opportunity = test_opportunity();
+        opportunity.market.down_token_id = opportunity.market.up_token_id.clone();
+
+        let result = executor.execute(&client, &opportunity, &config).await;
+
+        assert!(matches!(result, Err(TradingError::InvalidParams(_))), "got {:?}", result);
+        assert_eq!(executor.stats().trades_executed, 0);
+    }
+
+    #[tokio::test]
+    async fn duplicate_opportunity_executes_once() {
+        let mut config = test_config();
+        config.cooldown_seconds = 0;
+        let client = PolymarketClient::new(&config);
+        let mut executor = ArbitrageExecutor::new(&config);
+
+        for _ in 0..3 {
+            executor.execute(&client, &test_opportunity(), &config).await.unwrap();
+        }
+        assert_eq!(executor.trades_executed, 1);
+
+        // A price move is a new opportunity
+        let mut moved = test_opportunity();
+        moved.up_price = dec!(0.47);
+        moved.up_asks = vec![PriceLevel::new(dec!(0.47), dec!(100))];
+        let result = executor.execute(&client, &moved, &config).await.unwrap();
+        assert!(matches!(result, ExecutionResult::Simulated { .. }), "got {:?}", result);
+
+        let result = executor.execute(&client, &moved, &config).await.unwrap();
+        assert!(matches!(result, ExecutionResult::DuplicateOpportunity));
+        assert_eq!(executor.trades_executed, 2);
+    }
+
+    #[tokio::test]
+    async fn unfilled_opportunity_can_be_retried() {
+        let mut config = test_config();
+        config.cooldown_seconds = 0;
+        let client = PolymarketClient::new(&config);
+        let mut executor = ArbitrageExecutor::new(&config);
+        executor.sim_balance = dec!(1);
+
+        let result = executor.execute(&client, &test_opportunity(), &config).await.unwrap();
+        assert!(matches!(result, ExecutionResult::InsufficientBalance { .. }), "got {:?}", result);
+
+        // Nothing filled, so the same prices are not a duplicate once funded
+        executor.sim_balance = dec!(1000);
+        let result = executor.execute(&client, &test_opportunity(), &config).await.unwrap();
+        assert!(matches!(result, ExecutionResult::Simulated { .. }), "got {:?}", result);
+    }
+
+    #[tokio::test]
+    async fn exposure_cap_blocks_until_market_closes() {
+        let mut config = test_config();
+        config.cooldown_seconds = 0;
+        config.max_total_exposure = dec!(15);
+        let client = PolymarketClient::new(&config);
+        let mut executor = ArbitrageExecutor::new(&config);
+        let stats_before = executor.stats();
+
+        let first = executor.execute(&client, &test_opportunity(), &config).await.unwrap();
+        assert!(matches!(first, ExecutionResult::Simulated { .. }), "got {:?}", first);
+        assert_eq!(executor.capital_at_risk(), dec!(9.9));
+
+        // 9.9 + 9.9 would exceed the 15 USDC cap
+        let second = executor.execute(&client, &test_opportunity(), &config).await.unwrap();
+        assert!(matches!(
+            second,
+            ExecutionResult::ExposureLimitReached { current, cap }
+                if current == dec!(9.9) && cap == dec!(15)
+        ));
+
+        executor.record_market_result("btc-updown-15m-123", &stats_before);
+        assert!(executor.capital_at_risk().is_zero());
+
+        let third = executor.execute(&client, &test_opportunity(), &config).await.unwrap();
+        assert!(matches!(third, ExecutionResult::Simulated { .. }), "got {:?}", third);
+    }
+
+    #[tokio::test]
+    async fn closed_unsettled_market_still_counts_toward_exposure_cap() {
+        let mut config = test_config();
+        config.cooldown_seconds = 0;
+        config.max_total_exposure = dec!(15);
+        let client = PolymarketClient::new(&config);
+        let mut executor = ArbitrageExecutor::new(&config);
+        let stats_before = executor.stats();
+        let opportunity = test_opportunity();
+
+        executor.execute(&client, &opportunity, &config).await.unwrap();
+
+        // The market closes but has not resolved yet
+        executor.queue_settlement(&opportunity.market, dec!(9.9));
+        executor.record_market_result(&opportunity.market.slug, &stats_before);
+        assert_eq!(executor.capital_at_risk(), dec!(9.9));
+
+        let mut next = test_opportunity();
+        next.market.slug = "btc-updown-15m-1023".to_string();
+        next.market.start_timestamp = 900;
+        next.market.end_timestamp = 1800;
+        let blocked = executor.execute(&client, &next, &config).await.unwrap();
+        assert!(matches!(
+            blocked,
+            ExecutionResult::ExposureLimitReached { current, .. } if current == dec!(9.9)
+        ));
+
+        // Settlement frees the headroom
+        executor.record_settlement(&opportunity.market.slug, dec!(10), dec!(9.9));
+        assert!(executor.capital_at_risk().is_zero());
+        let entered = executor.execute(&client, &next, &config).await.unwrap();
+        assert!(matches!(entered, ExecutionResult::Simulated { .. }), "got {:?}", entered);
+    }
+
+    #[tokio::test]
+    async fn simulated_trade_updates_balance_gauges() {
+        let config = test_config();
+        let client = PolymarketClient::new(&config);
+        let mut executor = ArbitrageExecutor::new(&config);
+
+        executor.execute(&client, &test_opportunity(), &config).await.unwrap();
+
+        let recorder = metrics_exporter_prometheus::PrometheusBuilder::new().build_recorder();
+        let handle = recorder.handle();
+        ::metrics::with_local_recorder(&recorder, || executor.update_gauges(&client));
+
+        let rendered = handle.render();
+        assert!(executor.sim_balance < config.sim_balance);
+        assert!(
+            rendered.contains(&format!("sim_balance_usdc {}", executor.sim_balance.normalize())),
+            "{}",
+            rendered
+        );
+        assert!(rendered.contains("open_exposure_shares 0"), "{}", rendered);
+        // No live balance fetched in simulation
+        assert!(!rendered.contains("current_balance_usdc"), "{}", rendered);
+    }
+
+    #[tokio::test]
+    async fn shallow_book_rejects_simulated_fok() {
+        let config = test_config();
+        let client = PolymarketClient::new(&config);
+        let mut executor = ArbitrageExecutor::new(&config);
+        let mut opportunity = test_opportunity();
+        opportunity.up_asks = vec![PriceLevel::new(dec!(0.48), dec!(4))];
+
+        let result = executor.execute(&client, &opportunity, &config).await.unwrap();
+
+        assert!(matches!(
+            result,
+            ExecutionResult::PartialFill {
+                filled_leg: Outcome::Down,
+                ..
+            }
+        ));
+        assert_eq!(executor.trades_executed, 0);
+        assert_eq!(executor.sim_balance, config.sim_balance);
+    }
+
+    #[tokio::test]
+    async fn executed_intent_suppresses_same_window_only() {
+        let config = test_config();
+        let client = PolymarketClient::new(&config);
+        let mut executor = ArbitrageExecutor::new(&config);
+        let opportunity = test_opportunity();
+        executor.intents.record(trade_intent_key(&opportunity.market));
+
+        let result = executor.execute(&client, &opportunity, &config).await.unwrap();
+        assert!(matches!(result, ExecutionResult::AlreadyExecuted { .. }));
+
+        let mut next_window = test_opportunity();
+        next_window.market.start_timestamp = 900;
+        next_window.market.end_timestamp = 1800;
+        let result = executor.execute(&client, &next_window, &config).await.unwrap();
+        assert!(matches!(result, ExecutionResult::Simulated { .. }));
+    }
+
+    #[tokio::test]
+    async fn sign_mode_signs_orders_without_posting() {
+        let mut config = test_config();
+        config.dry_run_mode = DryRunMode::Sign;
+        let client = PolymarketClient::new(&config);
+        let mut executor = ArbitrageExecutor::new(&config);
+
+        let result = executor
+            .execute(&client, &test_opportunity(), &config)
+            .await
+            .unwrap();
+
+        // The reported result never carries a signature
+        let json = serde_json::to_value(&result).unwrap();
+        assert_eq!(json["up_order"]["token_id"], "up-token");
+        assert_eq!(json["up_order"]["signature"], "<redacted>");
+        assert_eq!(json["down_order"]["signature"], "<redacted>");
+
+        let ExecutionResult::Signed { up_order, down_order, .. } = result else {
+            panic!("expected signed orders, got {:?}", result);
+        };
+        assert_eq!(up_order.token_id, "up-token");
+        assert_eq!(down_order.token_id, "down-token");
+        assert_eq!(up_order.maker, client.get_address().unwrap());
+        assert!(up_order.signature.len() > 2);
+        assert!(down_order.signature.len() > 2);
+        assert_eq!(executor.sim_balance, config.sim_balance);
+    }
+
+    #[tokio::test]
+    async fn market_neg_risk_flows_into_signed_orders() {
+        let mut config = test_config();
+        config.dry_run_mode = DryRunMode::Sign;
+        let client = PolymarketClient::new(&config);
+
+        for (market_flag, expected) in [(Some(false), false), (Some(true), true), (None, true)] {
+            let mut opportunity = test_opportunity();
+            opportunity.market.neg_risk = market_flag;
+            let mut executor = ArbitrageExecutor::new(&config);
+
+            let result = executor.execute(&client, &opportunity, &config).await.unwrap();
+
+            let ExecutionResult::Signed { up_order, down_order, .. } = result else {
+                panic!("expected signed orders, got {:?}", result);
+            };
+            assert_eq!(up_order.neg_risk, expected);
+            assert_eq!(down_order.neg_risk, expected);
+        }
+    }
+
+    fn market_result(slug: &str, invested: Decimal, profit: Decimal, trades: u64) -> MarketResult {
+        MarketResult {
+            slug: slug.to_string(),
+            invested,
+            profit,
+            trades,
+        }
+    }
+
+    #[test]
+    fn history_summary_aggregates_roi_and_win_rate() {
+        let results = vec![
+            market_result("m1", dec!(10), dec!(0.5), 1),
+            market_result("m2", dec!(20), dec!(-1), 2),
+            market_result("m3", dec!(0), dec!(0), 0),
+            market_result("m4", dec!(10), dec!(1.5), 1),
+        ];
+
+        let summary = summarize_history(&results);
+
+        assert_eq!(summary.markets, 4);
+        assert_eq!(summary.traded_markets, 3);
+        assert_eq!(summary.total_invested, dec!(40));
+        assert_eq!(summary.total_profit, dec!(1));
+        // 1 / 40
+        assert_eq!(summary.roi, Some(dec!(0.025)));
+        // m1 and m4 of three traded markets
+        assert_eq!(summary.win_rate.unwrap().round_dp(4), dec!(0.6667));
+    }
+
+    #[test]
+    fn history_summary_empty() {
+        let summary = summarize_history(&[]);
+
+        assert_eq!(summary.markets, 0);
+        assert_eq!(summary.roi, None);
+        assert_eq!(summary.win_rate, None);
+    }
+
+    #[test]
+    fn market_history_is_bounded() {
+        let mut config = test_config();
+        config.history_limit = 2;
+        let mut executor = ArbitrageExecutor::new(&config);
+
+        for slug in ["m1", "m2", "m3"] {
+            let before = executor.stats();
+            executor.total_invested += dec!(9.9);
+            executor.total_shares_bought += dec!(20);
+            executor.trades_executed += 1;
+            let result = executor.record_market_result(slug, &before);
+            assert_eq!(result.invested, dec!(9.9));
+            assert_eq!(result.profit, dec!(0.1));
+            assert_eq!(result.trades, 1);
+        }
+
+        let slugs: Vec<String> = executor.history().into_iter().map(|r| r.slug).collect();
+        assert_eq!(slugs, vec!["m2", "m3"]);
+    }
+
+    #[tokio::test]
+    async fn balanced_pairs_detects_existing_position() {
+        use crate::market::{MockPolymarketClient, MockPosition};
+
+        let client = MockPolymarketClient::new();
+        for (token_id, size) in [("up-token", dec!(12)), ("down-token", dec!(10))] {
+            client.add_position(MockPosition {
+                token_id: token_id.to_string(),
+                size,
+                avg_price: dec!(0.5),
+            });
+        }
+
+        let positions = client.get_positions(&[]).await.unwrap();
+        let held = balanced_pairs(&positions, "up-token", "down-token");
+
+        assert_eq!(held, dec!(10));
+        assert!(held >= test_opportunity().order_size);
+    }
+
+    #[tokio::test]
+    async fn balanced_pairs_ignores_one_sided_position() {
+        use crate::market::{MockPolymarketClient, MockPosition};
+
+        let client = MockPolymarketClient::new();
+        client.add_position(MockPosition {
+            token_id: "up-token".to_string(),
+            size: dec!(20),
+            avg_price: dec!(0.5),
+        });
+
+        let positions = client.get_positions(&[]).await.unwrap();
+        let held = balanced_pairs(&positions, "up-token", "down-token");
+
+        assert_eq!(held, Decimal::ZERO);
+        assert!(held < test_opportunity().order_size);
+    }
+
+    #[test]
+    fn circuit_breaker_trips_after_max_failures() {
+        let config = test_config();
+        let mut executor = ArbitrageExecutor::new(&config);
+
+        for _ in 0..config.max_consecutive_failures - 1 {
+            executor.record_failure();
+        }
+        assert!(!executor.is_circuit_open());
+
+        executor.record_failure();
+        assert!(executor.is_circuit_open());
+        assert!(executor.stats().circuit_open);
+
+        executor.resume();
+        assert!(!executor.is_circuit_open());
+    }
+
+    #[test]
+    fn circuit_breaker_success_resets_failures() {
+        let config = test_config();
+        let mut executor = ArbitrageExecutor::new(&config);
+
+        for _ in 0..config.max_consecutive_failures - 1 {
+            executor.record_failure();
+        }
+        executor.record_success();
+        executor.record_failure();
+
+        assert!(!executor.is_circuit_open());
+    }
+
+    #[test]
+    fn order_params_apply_entry_offset() {
+        let mut config = test_config();
+        config.entry_price_offset_bps = dec!(100);
+        let opportunity = test_opportunity();
+
+        let (up, down) = order_params(&opportunity, &config).unwrap();
+
+        // 1% inside 0.48 and 0.51, floored to the 0.01 tick
+        assert_eq!(up.price, dec!(0.47));
+        assert_eq!(down.price, dec!(0.50));
+    }
+
+    #[test]
+    fn resting_order_params_are_gtd_before_market_close() {
+        let mut config = test_config();
+        config.order_type = "GTC".to_string();
+        let mut opportunity = test_opportunity();
+        opportunity.market.end_timestamp = chrono::Utc::now().timestamp() + 600;
+        let expires = opportunity.market.end_timestamp - GTD_SECURITY_BUFFER_SECS;
+
+        let (up, down) = order_params(&opportunity, &config).unwrap();
+
+        assert_eq!((up.tif, up.expiration), (TimeInForce::GTD, expires));
+        assert_eq!((down.tif, down.expiration), (TimeInForce::GTD, expires));
+        assert!(up.validate().is_ok());
+
+        // Immediate legs never carry an expiration, even with a TTL
+        config.order_type = "FAK".to_string();
+        config.default_order_ttl_seconds = 30;
+        let (fak, _) = order_params(&opportunity, &config).unwrap();
+        assert_eq!((fak.tif, fak.expiration), (TimeInForce::FAK, 0));
+        assert_eq!(order_params(&opportunity, &test_config()).unwrap().0.expiration, 0);
+    }
+
+    #[test]
+    fn maker_leg_rests_one_tick_inside_best_ask() {
+        let mut config = test_config();
+        config.maker_leg = MakerLeg::Down;
+        let mut opportunity = test_opportunity();
+        opportunity.market.end_timestamp = chrono::Utc::now().timestamp() + 600;
+
+        let (up, down) = order_params(&opportunity, &config).unwrap();
+
+        assert_eq!(down.price, dec!(0.50));
+        assert!(down.post_only);
+        // The maker leg is a GTD off the book one buffer before the close
+        assert_eq!(down.tif, TimeInForce::GTD);
+        assert_eq!(down.expiration, opportunity.market.end_timestamp - GTD_SECURITY_BUFFER_SECS);
+        assert!(down.validate().is_ok());
+        assert_eq!(up.price, opportunity.up_price);
+        assert_eq!((up.tif, up.expiration), (TimeInForce::FOK, 0));
+        assert!(!up.post_only);
+
+        // Too near the close for a GTD, it rests as GTC without an expiration
+        opportunity.market.end_timestamp = chrono::Utc::now().timestamp() + 90;
+        let (_, down) = order_params(&opportunity, &config).unwrap();
+        assert_eq!((down.tif, down.expiration), (TimeInForce::GTC, 0));
+        assert!(down.validate().is_ok());
+    }
+
+    #[test]
+    fn legs_are_funded_in_submission_order() {
+        let legs = [(Outcome::Down, dec!(6)), (Outcome::Up, dec!(5))];
+
+        assert_eq!(underfunded_leg(dec!(11), legs), None);
+        assert_eq!(underfunded_leg(dec!(10), legs), Some((Outcome::Up, dec!(5), dec!(4))));
+        assert_eq!(underfunded_leg(dec!(5), legs), Some((Outcome::Down, dec!(6), dec!(5))));
+    }
+
+    #[test]
+    fn thinner_first_picks_side_with_less_depth() {
+        let mut opportunity = test_opportunity();
+        // DOWN depth beyond its 0.51 fill price does not count
+        opportunity.up_asks = vec![PriceLevel::new(dec!(0.48), dec!(40))];
+        opportunity.down_asks =
+            vec![PriceLevel::new(dec!(0.51), dec!(25)), PriceLevel::new(dec!(0.52), dec!(500))];
+
+        assert_eq!(first_leg(FirstLegPolicy::ThinnerFirst, &opportunity), Outcome::Down);
+        assert_eq!(first_leg(FirstLegPolicy::UpFirst, &opportunity), Outcome::Up);
+
+        opportunity.up_asks = vec![PriceLevel::new(dec!(0.48), dec!(10))];
+        assert_eq!(first_leg(FirstLegPolicy::ThinnerFirst, &opportunity), Outcome::Up);
+        assert_eq!(first_leg(FirstLegPolicy::DownFirst, &opportunity), Outcome::Down);
+    }
+
+    fn terminal_state(status: OrderStatus, filled: Decimal) -> OrderState {
+        OrderState {
+            order_id: "0xorder".to_string(),
+            status: Some(status),
+            filled_size: Some(filled),
+            is_terminal: true,
+            is_filled: status.is_filled(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn partially_filled_fak_counts_as_fill() {
+        let state = terminal_state(OrderStatus::Canceled, dec!(4));
+
+        assert_eq!(leg_fill_size(&state, TimeInForce::FAK, dec!(10)), Some(dec!(4)));
+        assert_eq!(leg_fill_size(&state, TimeInForce::FOK, dec!(10)), None);
+    }
+
+    #[test]
+    fn unfilled_fak_is_not_a_fill() {
+        let state = terminal_state(OrderStatus::Canceled, dec!(0));
+
+        assert_eq!(leg_fill_size(&state, TimeInForce::FAK, dec!(10)), None);
+    }
+
+    #[tokio::test]
+    async fn execute_returns_circuit_open_when_tripped() {
+        let config = test_config();
+        let client = PolymarketClient::new(&config);
+        let mut executor = ArbitrageExecutor::new(&config);
+
+        for _ in 0..config.max_consecutive_failures {
+            executor.record_failure();
+        }
+
+        let result = executor
+            .execute(&client, &test_opportunity(), &config)
+            .await
+            .unwrap();
+
+        assert!(matches!(result, ExecutionResult::CircuitOpen { consecutive_failures: 5, .. }));
+    }
+
+    #[tokio::test]
+    async fn strict_hedge_liquidates_simulated_partial_fill() {
+        let mut config = test_config();
+        config.strict_hedge = true;
+        config.order_type = "FAK".to_string();
+        config.maker_leg = MakerLeg::Up;
+        config.sim_seed = Some(7);
+        config.sim_leg_failure_rate = Decimal::ONE;
+        let client = PolymarketClient::new(&config);
+        let mut executor = ArbitrageExecutor::new(&config);
+
+        // Both legs go FOK whatever ORDER_TYPE and MAKER_LEG say
+        let (up_params, down_params) = order_params(&test_opportunity(), &config).unwrap();
+        assert_eq!((up_params.tif, down_params.tif), (TimeInForce::FOK, TimeInForce::FOK));
+        assert!(!up_params.post_only);
+
+        let result = executor.execute(&client, &test_opportunity(), &config).await.unwrap();
+        match result {
+            ExecutionResult::PartialFill {
+                unwind_result: Some(unwind),
+                ..
+            } => assert!(unwind.starts_with("simulated liquidation"), "{}", unwind),
+            other => panic!("expected a liquidated partial fill, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn both_filled_serializes_tagged_with_string_decimals() {
+        let result = ExecutionResult::BothFilled {
+            exec_id: 7,
+            up_order_id: "0xup".to_string(),
+            down_order_id: "0xdown".to_string(),
+            up_filled_size: dec!(5),
+            down_filled_size: dec!(5),
+            actual_investment: dec!(4.950),
+        };
+
+        assert_eq!(
+            serde_json::to_value(&result).unwrap(),
+            serde_json::json!({
+                "result": "both_filled",
+                "exec_id": 7,
+                "up_order_id": "0xup",
+                "down_order_id": "0xdown",
+                "up_filled_size": "5",
+                "down_filled_size": "5",
+                "actual_investment": "4.950",
+            })
+        );
+        assert_eq!(
+            result.to_string(),
+            "#7 both filled: UP 5 + DOWN 5 for $4.950 (orders 0xup, 0xdown)"
+        );
+    }
+
+    #[test]
+    fn partial_fill_serializes_filled_leg_and_unwind() {
+        let result = ExecutionResult::PartialFill {
+            exec_id: 8,
+            filled_leg: Outcome::Down,
+            filled_size: dec!(2.5),
+            unwind_attempted: true,
+            unwind_result: Some("sold 2.5 @ 0.47".to_string()),
+        };
+
+        assert_eq!(
+            serde_json::to_value(&result).unwrap(),
+            serde_json::json!({
+                "result": "partial_fill",
+                "exec_id": 8,
+                "filled_leg": "down",
+                "filled_size": "2.5",
+                "unwind_attempted": true,
+                "unwind_result": "sold 2.5 @ 0.47",
+            })
+        );
+        assert_eq!(result.to_string(), "#8 partial fill: DOWN 2.5 filled, unwind: sold 2.5 @ 0.47");
+    }
 }
@@ -0,0 +1,96 @@
+//! Capital at risk shared by executors trading in one process.
+//!
+//! With several markets traded concurrently, each executor enforces
+//! `MAX_TOTAL_EXPOSURE` against the combined total rather than its own, and
+//! reserves the investment for the duration of an execution so two markets
+//! cannot both pass the check on the same headroom.
+
+use std::sync::{Arc, Mutex};
+
+use rust_decimal::Decimal;
+
+/// USDC invested in markets that have not settled yet, across executors.
+#[derive(Debug, Clone, Default)]
+pub struct ExposureGuard {
+    /// Committed plus reserved USDC.
+    total: Arc<Mutex<Decimal>>,
+}
+
+/// Headroom held for an execution in flight; released on drop.
+#[derive(Debug)]
+pub struct ExposureReservation {
+    /// Guard the amount was reserved from.
+    guard: ExposureGuard,
+    /// Reserved USDC.
+    amount: Decimal,
+}
+
+impl Drop for ExposureReservation {
+    fn drop(&mut self) {
+        self.guard.release(self.amount);
+    }
+}
+
+impl ExposureGuard {
+    /// Create a guard with nothing at risk.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Committed plus reserved USDC.
+    pub fn total(&self) -> Decimal {
+        *self.total.lock().expect("exposure lock")
+    }
+
+    /// Reserve `amount` if it fits under `cap`; otherwise return the current total.
+    pub fn try_reserve(
+        &self,
+        amount: Decimal,
+        cap: Decimal,
+    ) -> Result<ExposureReservation, Decimal> {
+        let mut total = self.total.lock().expect("exposure lock");
+        if *total + amount > cap {
+            return Err(*total);
+        }
+        *total += amount;
+        Ok(ExposureReservation {
+            guard: self.clone(),
+            amount,
+        })
+    }
+
+    /// Record USDC invested by a fill.
+    pub fn commit(&self, amount: Decimal) {
+        *self.total.lock().expect("exposure lock") += amount;
+    }
+
+    /// Release USDC whose market has settled.
+    pub fn release(&self, amount: Decimal) {
+        let mut total = self.total.lock().expect("exposure lock");
+        *total = (*total - amount).max(Decimal::ZERO);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    #[test]
+    fn reservations_share_headroom_across_clones() {
+        let guard = ExposureGuard::new();
+        let other_market = guard.clone();
+
+        let reservation = guard.try_reserve(dec!(9.9), dec!(15)).unwrap();
+        assert_eq!(other_market.try_reserve(dec!(9.9), dec!(15)).unwrap_err(), dec!(9.9));
+
+        // The fill commits the investment, then the reservation is released
+        guard.commit(dec!(9.9));
+        drop(reservation);
+        assert_eq!(other_market.total(), dec!(9.9));
+
+        guard.release(dec!(9.9));
+        assert!(other_market.try_reserve(dec!(9.9), dec!(15)).is_ok());
+        assert!(guard.total().is_zero());
+    }
+}
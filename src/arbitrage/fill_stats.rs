@@ -0,0 +1,117 @@
+//! Rolling fill rate of submitted legs.
+//!
+//! Legs that keep getting rejected mean FOK entries are racing other takers
+//! for depth that is gone by the time the order lands. Tracking the recent fill
+//! rate lets the executor fall back to FAK and keep whatever part still matches.
+
+use std::collections::VecDeque;
+
+use rust_decimal::Decimal;
+
+/// Leg outcomes kept for the rolling rate.
+pub const FILL_STATS_WINDOW: usize = 20;
+
+/// Filled/rejected counts for legs submitted in the current market session.
+#[derive(Debug, Clone)]
+pub struct FillStats {
+    /// Most recent leg outcomes (true = filled), oldest first.
+    recent: VecDeque<bool>,
+    /// Outcomes kept for the rolling rate.
+    window: usize,
+    /// Legs filled this session.
+    filled: u64,
+    /// Legs rejected or unfilled this session.
+    rejected: u64,
+}
+
+impl Default for FillStats {
+    fn default() -> Self {
+        Self::new(FILL_STATS_WINDOW)
+    }
+}
+
+impl FillStats {
+    /// Create a tracker computing the rate over the last `window` legs.
+    pub fn new(window: usize) -> Self {
+        Self {
+            recent: VecDeque::with_capacity(window),
+            window: window.max(1),
+            filled: 0,
+            rejected: 0,
+        }
+    }
+
+    /// Record whether a submitted leg filled.
+    pub fn record(&mut self, filled: bool) {
+        if filled {
+            self.filled += 1;
+        } else {
+            self.rejected += 1;
+        }
+        self.recent.push_back(filled);
+        while self.recent.len() > self.window {
+            self.recent.pop_front();
+        }
+    }
+
+    /// Legs filled this session.
+    pub fn filled(&self) -> u64 {
+        self.filled
+    }
+
+    /// Legs rejected or left unfilled this session.
+    pub fn rejected(&self) -> u64 {
+        self.rejected
+    }
+
+    /// Fraction of the last `window` legs that filled; `None` before any leg.
+    pub fn fill_rate(&self) -> Option<Decimal> {
+        if self.recent.is_empty() {
+            return None;
+        }
+        let filled = self.recent.iter().filter(|&&f| f).count();
+        Some(Decimal::from(filled) / Decimal::from(self.recent.len()))
+    }
+
+    /// Whether the rolling rate is below `min_rate` (never, with no data or a zero minimum).
+    pub fn is_below(&self, min_rate: Decimal) -> bool {
+        !min_rate.is_zero() && self.fill_rate().is_some_and(|rate| rate < min_rate)
+    }
+
+    /// Start a new market session.
+    pub fn reset(&mut self) {
+        self.recent.clear();
+        self.filled = 0;
+        self.rejected = 0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    #[test]
+    fn rolling_rate_covers_recent_legs() {
+        let mut stats = FillStats::new(4);
+        assert_eq!(stats.fill_rate(), None);
+        assert!(!stats.is_below(dec!(0.5)));
+
+        for filled in [true, true, false, true] {
+            stats.record(filled);
+        }
+        assert_eq!(stats.fill_rate(), Some(dec!(0.75)));
+
+        // Two more rejections push the oldest fills out of the window
+        stats.record(false);
+        stats.record(false);
+        assert_eq!(stats.fill_rate(), Some(dec!(0.25)));
+        assert_eq!((stats.filled(), stats.rejected()), (3, 3));
+        assert!(stats.is_below(dec!(0.5)));
+        assert!(!stats.is_below(Decimal::ZERO));
+
+        stats.reset();
+        assert_eq!(stats.fill_rate(), None);
+        assert_eq!((stats.filled(), stats.rejected()), (0, 0));
+    }
+}
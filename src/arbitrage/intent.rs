@@ -0,0 +1,150 @@
+//! Cross-run deduplication of executed trades.
+//!
+//! A trade intent is a stable key for "buy this market's pair in this window".
+//! It carries no nonce or timestamp beyond the window, so a bot restarted
+//! mid-window derives the same key and can see the pair was already filled.
+
+use std::collections::VecDeque;
+use std::path::PathBuf;
+
+use alloy::primitives::keccak256;
+use serde::{Deserialize, Serialize};
+use tracing::warn;
+
+use crate::market::Market;
+
+/// Executed intents kept on disk (one day of 15-minute markets).
+const MAX_INTENTS: usize = 96;
+
+/// Derive the trade intent key for a market.
+///
+/// Hashes the slug, the start of the market's window, and both token IDs.
+pub fn trade_intent_key(market: &Market) -> String {
+    let preimage = format!(
+        "{}:{}:{}:{}",
+        market.slug,
+        market.window_start(),
+        market.up_token_id,
+        market.down_token_id
+    );
+    format!("0x{}", hex::encode(keccak256(preimage.as_bytes())))
+}
+
+/// On-disk state file layout.
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct StateFile {
+    /// Executed intent keys, oldest first.
+    #[serde(default)]
+    executed_intents: VecDeque<String>,
+}
+
+/// Executed trade intents, persisted to the state file.
+#[derive(Debug, Default)]
+pub struct TradeIntentLog {
+    /// State file path (in memory only when unset).
+    path: Option<PathBuf>,
+    /// Executed intent keys, oldest first.
+    executed: VecDeque<String>,
+}
+
+impl TradeIntentLog {
+    /// Load executed intents from `path`; an empty path keeps them in memory only.
+    ///
+    /// A missing or unreadable file starts an empty log.
+    pub fn load(path: &str) -> Self {
+        if path.is_empty() {
+            return Self::default();
+        }
+
+        let path = PathBuf::from(path);
+        let state = match std::fs::read_to_string(&path) {
+            Ok(text) => serde_json::from_str::<StateFile>(&text).unwrap_or_else(|e| {
+                warn!(path = %path.display(), error = %e, "Ignoring unreadable state file");
+                StateFile::default()
+            }),
+            Err(_) => StateFile::default(),
+        };
+
+        Self {
+            path: Some(path),
+            executed: state.executed_intents,
+        }
+    }
+
+    /// Check whether an intent has already been executed.
+    pub fn contains(&self, key: &str) -> bool {
+        self.executed.iter().any(|k| k == key)
+    }
+
+    /// Record an executed intent and write the state file.
+    pub fn record(&mut self, key: String) {
+        if self.contains(&key) {
+            return;
+        }
+        self.executed.push_back(key);
+        while self.executed.len() > MAX_INTENTS {
+            self.executed.pop_front();
+        }
+
+        let Some(path) = &self.path else {
+            return;
+        };
+        let state = StateFile {
+            executed_intents: self.executed.clone(),
+        };
+        let written = serde_json::to_string_pretty(&state)
+            .map_err(|e| e.to_string())
+            .and_then(|json| std::fs::write(path, json).map_err(|e| e.to_string()));
+        if let Err(e) = written {
+            warn!(path = %path.display(), error = %e, "Failed to write state file");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn market(start_timestamp: i64) -> Market {
+        Market {
+            slug: format!("btc-updown-15m-{}", start_timestamp),
+            id: "1".to_string(),
+            up_token_id: "up".to_string(),
+            down_token_id: "down".to_string(),
+            start_timestamp,
+            end_timestamp: start_timestamp + Market::WINDOW_SECONDS,
+            question: None,
+            neg_risk: None,
+            condition_id: None,
+            accepting_orders_until: None,
+            tick_size: None,
+        }
+    }
+
+    #[test]
+    fn intent_key_is_stable_within_window() {
+        let a = market(1765301400);
+        let mut b = a.clone();
+        b.start_timestamp += 30;
+
+        assert_eq!(trade_intent_key(&a), trade_intent_key(&a));
+        assert_eq!(trade_intent_key(&a), trade_intent_key(&b));
+        assert_ne!(trade_intent_key(&a), trade_intent_key(&market(1765302300)));
+    }
+
+    #[test]
+    fn recorded_intents_survive_reload() {
+        let path = std::env::temp_dir().join(format!("intent-log-{}.json", std::process::id()));
+        let path_str = path.to_str().unwrap();
+        let key = trade_intent_key(&market(1765301400));
+
+        let mut log = TradeIntentLog::load(path_str);
+        assert!(!log.contains(&key));
+        log.record(key.clone());
+
+        let reloaded = TradeIntentLog::load(path_str);
+        std::fs::remove_file(&path).unwrap();
+        assert!(reloaded.contains(&key));
+        assert!(!reloaded.contains(&trade_intent_key(&market(1765302300))));
+    }
+}
@@ -0,0 +1,240 @@
+//! CSV journal of executed trades and settlements for accounting export.
+//!
+//! Each live fill and each market settlement appends one row to a single
+//! file, flushed as it is written so a crash never loses a recorded trade.
+//! The header row is written when the file is new or empty, so a journal can
+//! be appended to across restarts and opened directly in a spreadsheet.
+
+use std::fs::OpenOptions;
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use time::format_description::well_known::Rfc3339;
+use time::OffsetDateTime;
+use tracing::warn;
+
+use super::calculator::ArbitrageOpportunity;
+use super::executor::ExecutionResult;
+use crate::market::Outcome;
+use crate::trading::OrderParams;
+
+/// What a journal row records.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum JournalEvent {
+    /// Both legs filled.
+    BothFilled,
+    /// One leg filled and was unwound.
+    PartialFill,
+    /// A closed market paid out.
+    Settlement,
+}
+
+/// One row of the trade journal.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct JournalRow {
+    /// When the row was written, RFC 3339 in UTC.
+    pub timestamp: String,
+    /// What the row records.
+    pub event: JournalEvent,
+    /// Execution ID (empty for settlements).
+    pub exec_id: Option<u64>,
+    /// Market slug.
+    pub market: String,
+    /// UP limit price (empty for settlements).
+    pub up_price: Option<Decimal>,
+    /// DOWN limit price (empty for settlements).
+    pub down_price: Option<Decimal>,
+    /// UP shares filled (empty for settlements).
+    pub up_size: Option<Decimal>,
+    /// DOWN shares filled (empty for settlements).
+    pub down_size: Option<Decimal>,
+    /// USDC invested by the fill, or in the market for a settlement.
+    pub investment: Decimal,
+    /// Trading fees charged on the fill (empty for settlements).
+    pub fees: Option<Decimal>,
+    /// USDC the market paid out (settlements only).
+    pub settled_value: Option<Decimal>,
+    /// Settled value less investment (settlements only).
+    pub realized_profit: Option<Decimal>,
+}
+
+impl JournalRow {
+    /// Row for a live fill; `None` for results that bought nothing.
+    ///
+    /// Prices are those the legs were last submitted at, which can differ from
+    /// the opportunity's for a resting maker leg or a repriced leg.
+    pub fn fill(
+        result: &ExecutionResult,
+        opportunity: &ArbitrageOpportunity,
+        up_params: &OrderParams,
+        down_params: &OrderParams,
+        fees: Decimal,
+    ) -> Option<Self> {
+        let (event, exec_id, up_size, down_size) = match result {
+            ExecutionResult::BothFilled {
+                exec_id,
+                up_filled_size,
+                down_filled_size,
+                ..
+            } => (JournalEvent::BothFilled, *exec_id, *up_filled_size, *down_filled_size),
+            ExecutionResult::PartialFill {
+                exec_id,
+                filled_leg,
+                filled_size,
+                ..
+            } => match filled_leg {
+                Outcome::Up => (JournalEvent::PartialFill, *exec_id, *filled_size, Decimal::ZERO),
+                Outcome::Down => {
+                    (JournalEvent::PartialFill, *exec_id, Decimal::ZERO, *filled_size)
+                }
+            },
+            _ => return None,
+        };
+        // Match the investment debited for a pair; a lone leg is at its submitted price
+        let investment = match result {
+            ExecutionResult::BothFilled {
+                actual_investment, ..
+            } => *actual_investment,
+            _ => up_size * up_params.price + down_size * down_params.price,
+        };
+
+        Some(Self {
+            timestamp: now_rfc3339(),
+            event,
+            exec_id: Some(exec_id),
+            market: opportunity.market.slug.clone(),
+            up_price: Some(up_params.price),
+            down_price: Some(down_params.price),
+            up_size: Some(up_size),
+            down_size: Some(down_size),
+            investment,
+            fees: Some(fees),
+            settled_value: None,
+            realized_profit: None,
+        })
+    }
+
+    /// Row for a settled market.
+    pub fn settlement(market: &str, invested: Decimal, settled_value: Decimal) -> Self {
+        Self {
+            timestamp: now_rfc3339(),
+            event: JournalEvent::Settlement,
+            exec_id: None,
+            market: market.to_string(),
+            up_price: None,
+            down_price: None,
+            up_size: None,
+            down_size: None,
+            investment: invested,
+            fees: None,
+            settled_value: Some(settled_value),
+            realized_profit: Some(settled_value - invested),
+        }
+    }
+}
+
+/// Current time as RFC 3339 in UTC.
+fn now_rfc3339() -> String {
+    OffsetDateTime::now_utc().format(&Rfc3339).unwrap_or_default()
+}
+
+/// Held while appending, so journals of concurrent market tasks sharing a file
+/// cannot both see it empty and write the header twice.
+static APPEND_LOCK: Mutex<()> = Mutex::new(());
+
+/// Appends [`JournalRow`]s to a CSV file.
+#[derive(Debug, Clone)]
+pub struct TradeJournal {
+    /// Journal file path.
+    path: PathBuf,
+}
+
+impl TradeJournal {
+    /// Journal appending to `path`; the file is created on the first row.
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+
+    /// Journal for the configured path; `None` when empty.
+    pub fn from_path(path: &str) -> Option<Self> {
+        (!path.is_empty()).then(|| Self::new(path))
+    }
+
+    /// Append a row and flush it, logging rather than failing on IO errors.
+    pub fn record(&self, row: &JournalRow) {
+        if let Err(e) = self.append(row) {
+            warn!(path = %self.path.display(), error = %e, "Failed to write trade journal row");
+        }
+    }
+
+    /// Append a row, with the header first when the file is empty.
+    fn append(&self, row: &JournalRow) -> Result<(), csv::Error> {
+        let _lock = APPEND_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+        let file = OpenOptions::new().create(true).append(true).open(&self.path)?;
+        let is_empty = file.metadata()?.len() == 0;
+        let mut writer = csv::WriterBuilder::new()
+            .has_headers(is_empty)
+            .from_writer(file);
+        writer.serialize(row)?;
+        writer.flush()?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    #[test]
+    fn header_is_written_once_across_reopens() {
+        let path = std::env::temp_dir().join(format!("trade-journal-{}.csv", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+
+        TradeJournal::new(&path).record(&JournalRow::settlement("market-a", dec!(9.6), dec!(10)));
+        TradeJournal::new(&path).record(&JournalRow::settlement("market-b", dec!(9.6), dec!(10)));
+
+        let text = std::fs::read_to_string(&path).unwrap();
+        let _ = std::fs::remove_file(&path);
+        assert_eq!(text.lines().filter(|line| line.starts_with("timestamp,")).count(), 1);
+
+        let rows: Vec<JournalRow> = csv::Reader::from_reader(text.as_bytes())
+            .deserialize()
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[1].market, "market-b");
+        assert_eq!(rows[1].event, JournalEvent::Settlement);
+        assert_eq!(rows[1].realized_profit, Some(dec!(0.4)));
+        assert_eq!(rows[1].up_price, None);
+    }
+
+    #[test]
+    fn concurrent_journals_on_one_file_write_one_header() {
+        let path = std::env::temp_dir()
+            .join(format!("trade-journal-concurrent-{}.csv", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+
+        // One journal per market task, all appending to the same file
+        let writers: Vec<_> = (0..8)
+            .map(|i| {
+                let journal = TradeJournal::new(&path);
+                std::thread::spawn(move || {
+                    let market = format!("market-{}", i);
+                    journal.record(&JournalRow::settlement(&market, dec!(9.6), dec!(10)));
+                })
+            })
+            .collect();
+        for writer in writers {
+            writer.join().unwrap();
+        }
+
+        let text = std::fs::read_to_string(&path).unwrap();
+        let _ = std::fs::remove_file(&path);
+        assert_eq!(text.lines().filter(|line| line.starts_with("timestamp,")).count(), 1);
+        assert_eq!(text.lines().count(), 9);
+    }
+}
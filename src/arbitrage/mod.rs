@@ -4,11 +4,51 @@
 //! - Opportunity detection from order books
 //! - Profit/cost calculations
 //! - Trade execution with verification
+//! - Cross-run deduplication of executed trades
+//! - Book-driven fill simulation for dry runs
+//! - Rolling fill rate of submitted legs
+//! - Capital at risk shared across concurrently traded markets
+//! - Order-book snapshots of executed opportunities
+//! - CSV journal of fills and settlements for accounting
+//! - Closed markets re-checked until they resolve
+//! - Daily summary files of closed-market results
+//! - Detect-to-submit latency benchmark
+//! - Scan loop against the mock client with injected latency
 
+pub mod benchmark;
 pub mod calculator;
 pub mod detector;
 pub mod executor;
+pub mod exposure;
+pub mod fill_stats;
+pub mod intent;
+pub mod journal;
+pub mod settlement;
+pub mod sim_loop;
+pub mod simulator;
+pub mod snapshot;
+pub mod summary;
 
-pub use calculator::{calculate_opportunity, ArbitrageOpportunity};
-pub use detector::{check_arbitrage, diagnose_no_opportunity, quick_opportunity_check};
-pub use executor::{ArbitrageExecutor, ExecutionResult, ExecutorStats};
+pub use calculator::{
+    calculate_laddered_opportunity, calculate_multi_opportunity, calculate_opportunity,
+    calculate_sized_opportunity, round_calc, ArbitrageOpportunity, MultiLegOpportunity,
+    OpportunityLeg,
+};
+pub use detector::{
+    books_match_market, check_arbitrage, diagnose_no_opportunity, format_top_of_book,
+    prescreen, quick_opportunity_check, settlement_window_reason, thin_book_reason,
+    InversionTracker,
+};
+pub use executor::{
+    balanced_pairs, first_leg, settled_value, summarize_history,
+    ArbitrageExecutor, ExecutionResult, ExecutorStats, HistorySummary, MarketResult,
+};
+pub use exposure::{ExposureGuard, ExposureReservation};
+pub use fill_stats::FillStats;
+pub use intent::{trade_intent_key, TradeIntentLog};
+pub use journal::{JournalEvent, JournalRow, TradeJournal};
+pub use settlement::{PendingSettlement, SettlementQueue};
+pub use sim_loop::{run_sim_loop, SimLoopOptions, SimLoopReport};
+pub use simulator::{simulate_leg_fill, SimulatedFill};
+pub use snapshot::{BookSnapshot, SnapshotWriter};
+pub use summary::{load_summaries, SummaryLog, SummaryRow};
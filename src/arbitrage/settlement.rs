@@ -0,0 +1,157 @@
+//! Closed markets waiting for resolution before realized profit is recorded.
+//!
+//! A market stops trading when its window ends, but its outcome is reported
+//! by the oracle some minutes later. Until then positions still carry their
+//! last traded price, so each closed market is queued here and re-checked with
+//! exponential backoff until its positions are worth exactly 0 or 1, or until
+//! [`SETTLEMENT_MAX_ATTEMPTS`] checks have been made.
+
+use std::time::{Duration, Instant};
+
+use rust_decimal::Decimal;
+
+use crate::market::Market;
+
+/// Delay before re-checking a market that had not resolved.
+pub const SETTLEMENT_INITIAL_DELAY: Duration = Duration::from_secs(30);
+
+/// Longest delay between checks of one market.
+pub const SETTLEMENT_MAX_DELAY: Duration = Duration::from_secs(600);
+
+/// Checks of one market before it is given up on (about two and a half hours).
+pub const SETTLEMENT_MAX_ATTEMPTS: u32 = 20;
+
+/// A closed market whose realized profit has not been recorded yet.
+#[derive(Debug, Clone)]
+pub struct PendingSettlement {
+    /// The closed market.
+    pub market: Market,
+    /// USDC invested in the market.
+    pub invested: Decimal,
+    /// Checks made so far.
+    pub attempts: u32,
+    /// Earliest time of the next check.
+    next_check: Instant,
+}
+
+impl PendingSettlement {
+    /// Token IDs whose positions settle the market.
+    pub fn token_ids(&self) -> Vec<String> {
+        vec![self.market.up_token_id.clone(), self.market.down_token_id.clone()]
+    }
+}
+
+/// Closed markets to re-check, each on its own backoff schedule.
+#[derive(Debug, Clone)]
+pub struct SettlementQueue {
+    /// Markets not yet settled, in the order they closed.
+    pending: Vec<PendingSettlement>,
+    /// Delay after the first unresolved check.
+    initial_delay: Duration,
+    /// Cap on the delay between checks.
+    max_delay: Duration,
+}
+
+impl Default for SettlementQueue {
+    fn default() -> Self {
+        Self::new(SETTLEMENT_INITIAL_DELAY, SETTLEMENT_MAX_DELAY)
+    }
+}
+
+impl SettlementQueue {
+    /// Create a queue backing off from `initial_delay` up to `max_delay`.
+    pub fn new(initial_delay: Duration, max_delay: Duration) -> Self {
+        Self {
+            pending: Vec::new(),
+            initial_delay,
+            max_delay: max_delay.max(initial_delay),
+        }
+    }
+
+    /// Queue a closed market, due for its first check immediately.
+    pub fn push(&mut self, market: &Market, invested: Decimal, now: Instant) {
+        self.pending.push(PendingSettlement {
+            market: market.clone(),
+            invested,
+            attempts: 0,
+            next_check: now,
+        });
+    }
+
+    /// Remove and return the markets due for a check at `now`.
+    pub fn take_due(&mut self, now: Instant) -> Vec<PendingSettlement> {
+        let (due, waiting) = self.pending.drain(..).partition(|p| p.next_check <= now);
+        self.pending = waiting;
+        due
+    }
+
+    /// Re-queue a market that had not resolved, doubling its delay up to the cap.
+    pub fn retry(&mut self, mut pending: PendingSettlement, now: Instant) {
+        let delay = self
+            .initial_delay
+            .saturating_mul(2u32.saturating_pow(pending.attempts))
+            .min(self.max_delay);
+        pending.attempts += 1;
+        pending.next_check = now + delay;
+        self.pending.push(pending);
+    }
+
+    /// Whether the market with `slug` is waiting to settle.
+    pub fn contains(&self, slug: &str) -> bool {
+        self.pending.iter().any(|p| p.market.slug == slug)
+    }
+
+    /// Markets still waiting to settle.
+    pub fn len(&self) -> usize {
+        self.pending.len()
+    }
+
+    /// Whether every queued market has settled.
+    pub fn is_empty(&self) -> bool {
+        self.pending.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    fn market() -> Market {
+        Market {
+            slug: "btc-updown-15m-0".to_string(),
+            id: "market-id".to_string(),
+            up_token_id: "up-token".to_string(),
+            down_token_id: "down-token".to_string(),
+            start_timestamp: 0,
+            end_timestamp: 900,
+            question: None,
+            neg_risk: None,
+            condition_id: None,
+            accepting_orders_until: None,
+            tick_size: None,
+        }
+    }
+
+    #[test]
+    fn unresolved_markets_back_off_up_to_the_cap() {
+        let mut queue = SettlementQueue::new(Duration::from_secs(30), Duration::from_secs(100));
+        let start = Instant::now();
+        queue.push(&market(), dec!(9.5), start);
+
+        // First check is immediate
+        let mut pending = queue.take_due(start).pop().unwrap();
+        assert!(queue.is_empty());
+
+        let mut now = start;
+        for delay in [30, 60, 100, 100] {
+            queue.retry(pending, now);
+            assert!(queue.take_due(now + Duration::from_secs(delay - 1)).is_empty());
+            now += Duration::from_secs(delay);
+            pending = queue.take_due(now).pop().unwrap();
+        }
+        assert_eq!(pending.attempts, 4);
+        assert_eq!(pending.invested, dec!(9.5));
+        assert_eq!(pending.token_ids(), vec!["up-token", "down-token"]);
+    }
+}
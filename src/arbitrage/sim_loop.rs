@@ -0,0 +1,199 @@
+//! Scan loop against the mock client with injected latency, for chaos testing.
+//!
+//! Each scan fetches synthetic books from [`MockPolymarketClient`], runs them
+//! through `check_arbitrage` and a dry-run [`ArbitrageExecutor`], and mirrors
+//! the paired order submission of each execution to the mock. Opportunities
+//! the executor skips post nothing. The mock's `latency_ms` delays
+//! every book fetch and submission, so a slow network shows up in
+//! detect-to-execute time and in how cooldowns and fill stats play out.
+
+use std::time::Instant;
+
+use tracing::{debug, warn};
+
+use super::benchmark::{submit_leg, synthetic_books, LatencySummary};
+use super::detector::check_arbitrage;
+use super::executor::{order_params, ArbitrageExecutor};
+use crate::config::Config;
+use crate::error::TradingError;
+use crate::market::mock::MockConfig;
+use crate::market::{Market, MockOrderBook, MockPolymarketClient, Outcome, PolymarketClient};
+
+/// Settings for a simulated run.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SimLoopOptions {
+    /// Scans to run; each is a fresh market window.
+    pub scans: usize,
+    /// Delay the mock adds to every book fetch and order submission.
+    pub latency_ms: u64,
+}
+
+/// What a simulated run observed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SimLoopReport {
+    /// Scans run.
+    pub scans: usize,
+    /// Scans where `check_arbitrage` found an opportunity.
+    pub opportunities: usize,
+    /// Opportunities the executor acted on.
+    pub executed: usize,
+    /// Opportunities the executor skipped (cooldown, circuit breaker, balance...).
+    pub skipped: usize,
+    /// Orders posted to the mock, two per execution.
+    pub mock_orders: u32,
+    /// Detection to both legs posted, over executed opportunities.
+    pub detect_to_execute: Option<LatencySummary>,
+}
+
+/// Run `options.scans` scans against the mock, always in dry-run.
+///
+/// State files, snapshots and alerts are disabled so a simulated run never
+/// touches the live bot's files or webhook.
+pub async fn run_sim_loop(
+    config: &Config,
+    options: SimLoopOptions,
+) -> Result<SimLoopReport, TradingError> {
+    let mut config = config.clone();
+    config.dry_run = true;
+    config.state_file = String::new();
+    config.snapshot_dir = String::new();
+    config.alert_webhook_url = None;
+
+    let client = PolymarketClient::new(&config);
+    let mock = MockPolymarketClient::with_config(MockConfig {
+        latency_ms: options.latency_ms,
+        ..Default::default()
+    });
+    let mut executor = ArbitrageExecutor::new(&config);
+
+    let (template, up_book, down_book) = synthetic_books();
+    for book in [&up_book, &down_book] {
+        mock.set_order_book(MockOrderBook {
+            token_id: book.token_id.clone(),
+            bids: book.bids.clone(),
+            asks: book.asks.clone(),
+        });
+    }
+
+    let mut report = SimLoopReport {
+        scans: options.scans,
+        opportunities: 0,
+        executed: 0,
+        skipped: 0,
+        mock_orders: 0,
+        detect_to_execute: None,
+    };
+    let mut samples = Vec::new();
+
+    for scan in 0..options.scans {
+        // A fresh window per scan, so executions aren't deduplicated
+        let offset = Market::WINDOW_SECONDS * scan as i64;
+        let market = Market {
+            slug: format!("btc-updown-15m-{}", template.start_timestamp + offset),
+            start_timestamp: template.start_timestamp + offset,
+            end_timestamp: template.end_timestamp + offset,
+            ..template.clone()
+        };
+
+        let (up, down) = tokio::join!(
+            mock.get_order_book(&market.up_token_id),
+            mock.get_order_book(&market.down_token_id),
+        );
+        let (up, mut down) = match (up, down) {
+            (Ok(up), Ok(down)) => (up, down),
+            (Err(e), _) | (_, Err(e)) => {
+                warn!(scan, error = %e, "Simulated book fetch failed");
+                continue;
+            }
+        };
+        down.outcome = Outcome::Down;
+
+        let opportunity = match check_arbitrage(&market, &up, &down, &config) {
+            Ok(Some(opportunity)) => opportunity,
+            Ok(None) => continue,
+            Err(e) => {
+                warn!(scan, error = %e, "Simulated arbitrage check failed");
+                continue;
+            }
+        };
+        report.opportunities += 1;
+        let detected = Instant::now();
+
+        let result = executor.execute(&client, &opportunity, &config).await?;
+        debug!(scan, result = %result, "Simulated execution");
+        if result.exec_id().is_none() {
+            report.skipped += 1;
+            continue;
+        }
+
+        // The dry-run executor never posts, so the orders it would send go to the mock
+        let (up_params, down_params) = order_params(&opportunity, &config)?;
+        tokio::try_join!(
+            submit_leg(&client, &mock, &up_params),
+            submit_leg(&client, &mock, &down_params),
+        )?;
+        report.executed += 1;
+        samples.push(detected.elapsed());
+    }
+
+    report.mock_orders = mock.orders_submitted();
+    report.detect_to_execute = LatencySummary::from_samples(samples);
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sim_config() -> Config {
+        serde_json::from_value(serde_json::json!({
+            "polymarket_private_key":
+                "0x0123456789abcdef0123456789abcdef0123456789abcdef0123456789abcdef",
+            "cooldown_seconds": 0,
+        }))
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn higher_latency_slows_detect_to_execute() {
+        let config = sim_config();
+        let run = |latency_ms| run_sim_loop(&config, SimLoopOptions { scans: 3, latency_ms });
+
+        let fast = run(0).await.unwrap();
+        let slow = run(25).await.unwrap();
+
+        assert_eq!(fast.opportunities, 3);
+        assert_eq!(slow.executed, 3);
+        let fast = fast.detect_to_execute.unwrap();
+        let slow = slow.detect_to_execute.unwrap();
+        assert!(slow.min >= std::time::Duration::from_millis(25), "{:?}", slow);
+        assert!(slow.p50 > fast.p50, "slow {:?}, fast {:?}", slow, fast);
+    }
+
+    #[tokio::test]
+    async fn cooldown_skips_back_to_back_opportunities() {
+        let mut config = sim_config();
+        config.cooldown_seconds = 60;
+
+        let report = run_sim_loop(&config, SimLoopOptions { scans: 3, latency_ms: 0 })
+            .await
+            .unwrap();
+
+        assert_eq!(report.executed, 1);
+        assert_eq!(report.skipped, 2);
+    }
+
+    #[tokio::test]
+    async fn skipped_opportunities_place_no_mock_orders() {
+        let mut config = sim_config();
+        config.cooldown_seconds = 60;
+
+        let report = run_sim_loop(&config, SimLoopOptions { scans: 3, latency_ms: 0 })
+            .await
+            .unwrap();
+
+        // Only the first opportunity clears the cooldown, so only its two legs post
+        assert_eq!(report.skipped, 2);
+        assert_eq!(report.mock_orders, 2);
+    }
+}
@@ -0,0 +1,127 @@
+//! Book-driven fill model for dry-run execution.
+
+use rust_decimal::Decimal;
+
+use super::calculator::BPS;
+use crate::orderbook::PriceLevel;
+use crate::trading::order::TimeInForce;
+
+/// Simulated fill of a single BUY leg.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SimulatedFill {
+    /// Shares filled.
+    pub filled_size: Decimal,
+    /// Average price paid per share, including slippage.
+    pub avg_price: Decimal,
+}
+
+impl SimulatedFill {
+    /// An unfilled leg.
+    pub const NONE: Self = Self {
+        filled_size: Decimal::ZERO,
+        avg_price: Decimal::ZERO,
+    };
+
+    /// Total cost of the fill.
+    pub fn cost(&self) -> Decimal {
+        self.filled_size * self.avg_price
+    }
+
+    /// Whether any shares filled.
+    pub fn is_filled(&self) -> bool {
+        self.filled_size > Decimal::ZERO
+    }
+}
+
+/// Simulate a BUY of `size` against resting asks at or below `limit_price`.
+///
+/// FOK orders fill completely or not at all; FAK and GTC take whatever depth
+/// is available. `slippage_bps` is added to the average fill price.
+pub fn simulate_leg_fill(
+    asks: &[PriceLevel],
+    limit_price: Decimal,
+    size: Decimal,
+    tif: TimeInForce,
+    slippage_bps: Decimal,
+) -> SimulatedFill {
+    let mut remaining = size;
+    let mut cost = Decimal::ZERO;
+
+    for level in asks.iter().filter(|l| l.price <= limit_price) {
+        if remaining.is_zero() {
+            break;
+        }
+        let take = remaining.min(level.size);
+        cost += take * level.price;
+        remaining -= take;
+    }
+
+    let filled_size = size - remaining;
+    if filled_size.is_zero() || (tif == TimeInForce::FOK && !remaining.is_zero()) {
+        return SimulatedFill::NONE;
+    }
+
+    SimulatedFill {
+        filled_size,
+        avg_price: cost / filled_size * (Decimal::ONE + slippage_bps / BPS),
+    }
+}
+
+/// Proceeds from unwinding a simulated fill, losing `slippage_bps` on the exit.
+pub fn simulated_unwind_proceeds(fill: &SimulatedFill, slippage_bps: Decimal) -> Decimal {
+    fill.cost() * (Decimal::ONE - slippage_bps / BPS)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    fn asks(levels: &[(Decimal, Decimal)]) -> Vec<PriceLevel> {
+        levels.iter().map(|&(p, s)| PriceLevel::new(p, s)).collect()
+    }
+
+    #[test]
+    fn deep_book_fills_completely() {
+        let book = asks(&[(dec!(0.48), dec!(100))]);
+
+        let fill = simulate_leg_fill(&book, dec!(0.48), dec!(10), TimeInForce::FOK, dec!(0));
+
+        assert_eq!(fill.filled_size, dec!(10));
+        assert_eq!(fill.avg_price, dec!(0.48));
+        assert_eq!(fill.cost(), dec!(4.8));
+    }
+
+    #[test]
+    fn shallow_book_partially_fills_fak() {
+        // Only 6 shares at or below the limit; the 0.55 level is out of range
+        let book = asks(&[(dec!(0.47), dec!(2)), (dec!(0.48), dec!(4)), (dec!(0.55), dec!(100))]);
+
+        let fill = simulate_leg_fill(&book, dec!(0.48), dec!(10), TimeInForce::FAK, dec!(0));
+
+        assert_eq!(fill.filled_size, dec!(6));
+        // (2 * 0.47 + 4 * 0.48) / 6
+        assert_eq!(fill.cost().round_dp(8), dec!(2.86));
+    }
+
+    #[test]
+    fn shallow_book_rejects_fok() {
+        let book = asks(&[(dec!(0.48), dec!(6))]);
+
+        let fill = simulate_leg_fill(&book, dec!(0.48), dec!(10), TimeInForce::FOK, dec!(0));
+
+        assert!(!fill.is_filled());
+    }
+
+    #[test]
+    fn slippage_raises_fill_price() {
+        let book = asks(&[(dec!(0.50), dec!(100))]);
+
+        let fill = simulate_leg_fill(&book, dec!(0.50), dec!(10), TimeInForce::FOK, dec!(20));
+
+        // 0.50 * (1 + 20 / 10000)
+        assert_eq!(fill.avg_price, dec!(0.501));
+        // 5.01 * (1 - 20 / 10000)
+        assert_eq!(simulated_unwind_proceeds(&fill, dec!(20)), dec!(4.99998));
+    }
+}
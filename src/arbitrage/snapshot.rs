@@ -0,0 +1,186 @@
+//! Order-book snapshots of executed opportunities for post-mortem analysis.
+//!
+//! Each execution appends one JSON line with the bid and ask levels of both
+//! outcome books and the prices it was decided on. Lines go to a background
+//! thread over a channel, so file IO never delays an order, and files rotate
+//! daily.
+
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::PathBuf;
+use std::sync::mpsc::{self, Sender};
+
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use tracing::warn;
+
+use super::calculator::ArbitrageOpportunity;
+use crate::orderbook::PriceLevel;
+
+/// Levels kept per side of each book.
+pub const SNAPSHOT_LEVELS: usize = 10;
+
+/// Book state and prices behind one execution.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BookSnapshot {
+    /// Execution ID.
+    pub exec_id: u64,
+    /// Market slug.
+    pub market: String,
+    /// When the opportunity was detected, in Unix milliseconds.
+    pub detected_at_ms: i64,
+    /// UP limit price.
+    pub up_price: Decimal,
+    /// DOWN limit price.
+    pub down_price: Decimal,
+    /// Combined pair cost.
+    pub total_cost: Decimal,
+    /// Shares per leg.
+    pub order_size: Decimal,
+    /// Best UP bid levels at detection.
+    pub up_bids: Vec<PriceLevel>,
+    /// Best UP ask levels at detection.
+    pub up_asks: Vec<PriceLevel>,
+    /// Best DOWN bid levels at detection.
+    pub down_bids: Vec<PriceLevel>,
+    /// Best DOWN ask levels at detection.
+    pub down_asks: Vec<PriceLevel>,
+}
+
+impl BookSnapshot {
+    /// Snapshot an opportunity's prices and the top [`SNAPSHOT_LEVELS`] bids and
+    /// asks of both outcome books.
+    pub fn new(exec_id: u64, opportunity: &ArbitrageOpportunity) -> Self {
+        let (up_book, down_book) = (&opportunity.up_book, &opportunity.down_book);
+        let top = |levels: &[PriceLevel]| levels.iter().take(SNAPSHOT_LEVELS).copied().collect();
+        Self {
+            exec_id,
+            market: opportunity.market.slug.clone(),
+            detected_at_ms: (opportunity.detected_at.unix_timestamp_nanos() / 1_000_000) as i64,
+            up_price: opportunity.up_price,
+            down_price: opportunity.down_price,
+            total_cost: opportunity.total_cost,
+            order_size: opportunity.order_size,
+            up_bids: top(&up_book.bids),
+            up_asks: top(&up_book.asks),
+            down_bids: top(&down_book.bids),
+            down_asks: top(&down_book.asks),
+        }
+    }
+}
+
+/// Appends snapshots to `book-snapshots-<date>.jsonl` files in a directory.
+#[derive(Debug, Clone)]
+pub struct SnapshotWriter {
+    /// Channel to the writer thread.
+    sender: Sender<(String, String)>,
+}
+
+impl SnapshotWriter {
+    /// Start a writer thread for `dir`, creating the directory if needed.
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        let dir = dir.into();
+        let (sender, receiver) = mpsc::channel::<(String, String)>();
+        std::thread::spawn(move || {
+            if let Err(e) = fs::create_dir_all(&dir) {
+                warn!(dir = %dir.display(), error = %e, "Failed to create snapshot directory");
+            }
+            for (date, line) in receiver {
+                let path = dir.join(format!("book-snapshots-{}.jsonl", date));
+                let written = OpenOptions::new()
+                    .create(true)
+                    .append(true)
+                    .open(&path)
+                    .and_then(|mut file| writeln!(file, "{}", line));
+                if let Err(e) = written {
+                    warn!(path = %path.display(), error = %e, "Failed to write book snapshot");
+                }
+            }
+        });
+        Self { sender }
+    }
+
+    /// Writer for the configured directory; `None` when empty.
+    pub fn from_dir(dir: &str) -> Option<Self> {
+        (!dir.is_empty()).then(|| Self::new(dir))
+    }
+
+    /// Queue a snapshot of an executed opportunity.
+    pub fn record(&self, exec_id: u64, opportunity: &ArbitrageOpportunity) {
+        let snapshot = BookSnapshot::new(exec_id, opportunity);
+        let line = match serde_json::to_string(&snapshot) {
+            Ok(line) => line,
+            Err(e) => {
+                warn!(error = %e, "Failed to serialize book snapshot");
+                return;
+            }
+        };
+        let date = opportunity.detected_at.date().to_string();
+        if self.sender.send((date, line)).is_err() {
+            warn!("Snapshot writer stopped, dropping book snapshot");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::arbitrage::calculator::calculate_opportunity;
+    use crate::market::{Market, Outcome};
+    use crate::orderbook::OutcomeBook;
+    use rust_decimal_macros::dec;
+    use time::OffsetDateTime;
+
+    /// Book with `levels` bids stepping down from `best_bid` and asks up from `best_ask`.
+    fn deep_book(
+        outcome: Outcome,
+        best_bid: Decimal,
+        best_ask: Decimal,
+        levels: u32,
+    ) -> OutcomeBook {
+        let tick = dec!(0.01);
+        OutcomeBook {
+            token_id: format!("{:?}-token", outcome),
+            outcome,
+            bids: (0..levels)
+                .map(|i| PriceLevel::new(best_bid - tick * Decimal::from(i), dec!(100)))
+                .collect(),
+            asks: (0..levels)
+                .map(|i| PriceLevel::new(best_ask + tick * Decimal::from(i), dec!(100)))
+                .collect(),
+            updated_at: OffsetDateTime::now_utc(),
+            as_of: None,
+        }
+    }
+
+    #[test]
+    fn snapshot_keeps_top_bids_and_asks_of_both_books() {
+        let market = Market {
+            slug: "btc-updown-15m-0".to_string(),
+            id: "market-id".to_string(),
+            up_token_id: "Up-token".to_string(),
+            down_token_id: "Down-token".to_string(),
+            start_timestamp: 0,
+            end_timestamp: 900,
+            question: None,
+            neg_risk: None,
+            condition_id: None,
+            accepting_orders_until: None,
+            tick_size: None,
+        };
+        let up = deep_book(Outcome::Up, dec!(0.47), dec!(0.48), 15);
+        let down = deep_book(Outcome::Down, dec!(0.50), dec!(0.51), 15);
+        let opportunity =
+            calculate_opportunity(&market, &up, &down, dec!(10), dec!(0.995)).unwrap();
+
+        let snapshot = BookSnapshot::new(7, &opportunity);
+
+        assert_eq!(snapshot.up_bids, up.bids[..SNAPSHOT_LEVELS]);
+        assert_eq!(snapshot.up_asks, up.asks[..SNAPSHOT_LEVELS]);
+        assert_eq!(snapshot.down_bids, down.bids[..SNAPSHOT_LEVELS]);
+        assert_eq!(snapshot.down_asks, down.asks[..SNAPSHOT_LEVELS]);
+        // Levels beyond the consumed asks are kept too
+        assert!(opportunity.up_asks.len() < SNAPSHOT_LEVELS);
+        assert_eq!(snapshot.total_cost, dec!(0.99));
+    }
+}
@@ -0,0 +1,196 @@
+//! Daily summary files of closed-market results, for stats across restarts.
+//!
+//! Each closed market appends one row (market, invested, profit, trades) to
+//! `summary-<date>.csv` in the summary directory, with its expected profit.
+//! Once it resolves, a second row with the realized profit is appended, and
+//! [`load_summaries`] keeps only the latest row per market. The in-memory
+//! history is capped and lost on restart; these files are not, so
+//! [`load_summaries`] can aggregate every day ever recorded.
+
+use std::collections::HashMap;
+use std::fs::{self, OpenOptions};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use time::format_description::well_known::Rfc3339;
+use time::{Date, OffsetDateTime};
+use tracing::warn;
+
+use super::executor::MarketResult;
+
+/// File name prefix of daily summary files.
+const SUMMARY_PREFIX: &str = "summary-";
+
+/// One closed market in a summary file.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SummaryRow {
+    /// When the market closed, RFC 3339 in UTC.
+    pub timestamp: String,
+    /// Market slug.
+    pub market: String,
+    /// USDC invested in the market.
+    pub invested: Decimal,
+    /// Profit from the market: expected when it closed, realized in a settlement row.
+    pub profit: Decimal,
+    /// Trades executed in the market.
+    pub trades: u64,
+}
+
+impl SummaryRow {
+    /// Row for a closed market, stamped now.
+    pub fn new(result: &MarketResult) -> Self {
+        Self {
+            timestamp: OffsetDateTime::now_utc().format(&Rfc3339).unwrap_or_default(),
+            market: result.slug.clone(),
+            invested: result.invested,
+            profit: result.profit,
+            trades: result.trades,
+        }
+    }
+}
+
+impl From<SummaryRow> for MarketResult {
+    fn from(row: SummaryRow) -> Self {
+        Self {
+            slug: row.market,
+            invested: row.invested,
+            profit: row.profit,
+            trades: row.trades,
+        }
+    }
+}
+
+/// Held while appending, so market tasks closing at once cannot both see a
+/// new day's file empty and write the header twice.
+static APPEND_LOCK: Mutex<()> = Mutex::new(());
+
+/// Appends closed-market results to daily `summary-<date>.csv` files.
+#[derive(Debug, Clone)]
+pub struct SummaryLog {
+    /// Directory holding the daily files.
+    dir: PathBuf,
+}
+
+impl SummaryLog {
+    /// Log writing to `dir`; the directory is created on the first row.
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    /// Log for the configured directory; `None` when empty.
+    pub fn from_dir(dir: &str) -> Option<Self> {
+        (!dir.is_empty()).then(|| Self::new(dir))
+    }
+
+    /// Append a closed market to today's file, logging rather than failing on IO errors.
+    pub fn record(&self, result: &MarketResult) {
+        let today = OffsetDateTime::now_utc().date();
+        if let Err(e) = self.append(today, &SummaryRow::new(result)) {
+            warn!(dir = %self.dir.display(), error = %e, "Failed to write market summary");
+        }
+    }
+
+    /// Append a row to `date`'s file, with the header first when the file is empty.
+    fn append(&self, date: Date, row: &SummaryRow) -> Result<(), csv::Error> {
+        let _lock = APPEND_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+        fs::create_dir_all(&self.dir)?;
+        let path = self.dir.join(format!("{}{}.csv", SUMMARY_PREFIX, date));
+        let file = OpenOptions::new().create(true).append(true).open(path)?;
+        let is_empty = file.metadata()?.len() == 0;
+        let mut writer = csv::WriterBuilder::new()
+            .has_headers(is_empty)
+            .from_writer(file);
+        writer.serialize(row)?;
+        writer.flush()?;
+        Ok(())
+    }
+}
+
+/// Read every daily summary file in `dir`, oldest day first.
+///
+/// A market with several rows (its closing row, then its settlement) appears
+/// once, where it first closed, with the values of its latest row.
+pub fn load_summaries(dir: &Path) -> Result<Vec<MarketResult>, csv::Error> {
+    let mut paths = Vec::new();
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        let is_summary = path.file_name().and_then(|name| name.to_str()).is_some_and(|name| {
+            name.starts_with(SUMMARY_PREFIX) && name.ends_with(".csv")
+        });
+        if is_summary {
+            paths.push(path);
+        }
+    }
+    // ISO dates in the file names sort chronologically
+    paths.sort();
+
+    let mut results: Vec<MarketResult> = Vec::new();
+    let mut positions: HashMap<String, usize> = HashMap::new();
+    for path in paths {
+        for row in csv::Reader::from_path(&path)?.deserialize::<SummaryRow>() {
+            let result: MarketResult = row?.into();
+            match positions.get(&result.slug) {
+                Some(&i) => results[i] = result,
+                None => {
+                    positions.insert(result.slug.clone(), results.len());
+                    results.push(result);
+                }
+            }
+        }
+    }
+    Ok(results)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::arbitrage::summarize_history;
+    use rust_decimal_macros::dec;
+    use time::Month;
+
+    fn result(slug: &str, invested: Decimal, profit: Decimal, trades: u64) -> MarketResult {
+        MarketResult {
+            slug: slug.to_string(),
+            invested,
+            profit,
+            trades,
+        }
+    }
+
+    #[test]
+    fn two_days_of_summaries_aggregate() {
+        let dir = std::env::temp_dir().join(format!("market-summaries-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        let log = SummaryLog::new(&dir);
+        let day = |d| Date::from_calendar_date(2026, Month::March, d).unwrap();
+
+        let first_day = [
+            result("btc-updown-15m-1", dec!(9.8), dec!(0.2), 2),
+            result("btc-updown-15m-2", Decimal::ZERO, Decimal::ZERO, 0),
+        ];
+        for market in &first_day {
+            log.append(day(1), &SummaryRow::new(market)).unwrap();
+        }
+        let second_day = result("btc-updown-15m-3", dec!(4.9), dec!(-0.3), 1);
+        log.append(day(2), &SummaryRow::new(&second_day)).unwrap();
+        // The first market settles a day later, below its expected profit
+        let settled = result("btc-updown-15m-1", dec!(9.8), dec!(0.1), 2);
+        log.append(day(2), &SummaryRow::new(&settled)).unwrap();
+        // Files outside the naming scheme are ignored
+        fs::write(dir.join("notes.txt"), "not a summary").unwrap();
+
+        let results = load_summaries(&dir).unwrap();
+        let _ = fs::remove_dir_all(&dir);
+
+        let slugs: Vec<&str> = results.iter().map(|r| r.slug.as_str()).collect();
+        assert_eq!(slugs, ["btc-updown-15m-1", "btc-updown-15m-2", "btc-updown-15m-3"]);
+        let summary = summarize_history(&results);
+        assert_eq!(summary.markets, 3);
+        assert_eq!(summary.traded_markets, 2);
+        assert_eq!(summary.total_invested, dec!(14.7));
+        assert_eq!(summary.total_profit, dec!(-0.2));
+        assert_eq!(summary.win_rate, Some(dec!(0.5)));
+    }
+}
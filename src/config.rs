@@ -1,7 +1,168 @@
 //! Application configuration loaded from environment variables.
 
-use rust_decimal::Decimal;
+use rust_decimal::{Decimal, RoundingStrategy};
 use serde::Deserialize;
+use tracing::warn;
+
+use crate::arbitrage::calculator::BPS;
+use crate::utils::LogFormat;
+
+/// User-Agent sent on every HTTP request unless `HTTP_USER_AGENT` overrides it.
+pub const DEFAULT_HTTP_USER_AGENT: &str = concat!("polymarket-arb/", env!("CARGO_PKG_VERSION"));
+
+/// How much of the live pipeline a dry run exercises.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, clap::ValueEnum)]
+#[serde(rename_all = "kebab-case")]
+pub enum DryRunMode {
+    /// Simulated fills against the simulation balance.
+    #[default]
+    Full,
+    /// Build and sign real orders but never POST them.
+    Sign,
+    /// Check the real wallet balance, then simulate the fill.
+    BalanceOnly,
+}
+
+/// How the two legs of a pair are submitted.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum LegMode {
+    /// Submit both legs at once; cancel the survivor if one is rejected.
+    #[default]
+    Parallel,
+    /// Submit the second leg only once the first is accepted, avoiding one-sided exposure.
+    Sequential,
+}
+
+/// Which leg goes first in sequential leg mode.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum FirstLegPolicy {
+    /// The side with less ask depth at its price, so the likelier failure costs nothing.
+    #[default]
+    ThinnerFirst,
+    /// Always UP first.
+    UpFirst,
+    /// Always DOWN first.
+    DownFirst,
+}
+
+/// What `ORDER_SIZE` and size ladder sizes count.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum OrderSizeUnit {
+    /// Shares bought on each leg.
+    #[default]
+    Shares,
+    /// USDC spent on a pair, converted to shares at the pair cost.
+    Usdc,
+}
+
+impl OrderSizeUnit {
+    /// Shares per leg for `order_size` at `pair_cost`, or `None` below the
+    /// 5-share minimum.
+    ///
+    /// A USDC notional becomes `order_size / pair_cost` shares, rounded down to
+    /// `size_dp` decimals. It is never raised to the minimum, which would spend
+    /// more than the notional.
+    pub fn shares(self, order_size: Decimal, pair_cost: Decimal, size_dp: u32) -> Option<Decimal> {
+        let shares = match self {
+            Self::Shares => order_size,
+            Self::Usdc if pair_cost > Decimal::ZERO => {
+                (order_size / pair_cost).round_dp_with_strategy(size_dp, RoundingStrategy::ToZero)
+            }
+            Self::Usdc => return None,
+        };
+        (shares >= Decimal::new(5, 0)).then_some(shares)
+    }
+
+    /// `order_size` with its unit, for logs.
+    pub fn describe(self, order_size: Decimal) -> String {
+        match self {
+            Self::Shares => format!("{} shares", order_size),
+            Self::Usdc => format!("${} USDC per pair", order_size),
+        }
+    }
+}
+
+/// How computed prices, VWAPs and percentages are rounded to `CALC_DECIMALS`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum CalcRounding {
+    /// Halves round away from zero (0.0000125 -> 0.000013).
+    #[default]
+    MidpointAwayFromZero,
+    /// Halves round to the even neighbour (0.0000125 -> 0.000012).
+    MidpointNearestEven,
+    /// Halves round toward zero.
+    MidpointTowardZero,
+    /// Everything rounds toward zero.
+    ToZero,
+}
+
+impl CalcRounding {
+    /// The matching `rust_decimal` strategy.
+    pub fn strategy(self) -> RoundingStrategy {
+        match self {
+            Self::MidpointAwayFromZero => RoundingStrategy::MidpointAwayFromZero,
+            Self::MidpointNearestEven => RoundingStrategy::MidpointNearestEven,
+            Self::MidpointTowardZero => RoundingStrategy::MidpointTowardZero,
+            Self::ToZero => RoundingStrategy::ToZero,
+        }
+    }
+}
+
+/// Which leg, if any, rests on the book as a post-only maker order.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum MakerLeg {
+    /// Both legs cross as takers.
+    #[default]
+    None,
+    /// UP rests one tick inside its best ask; DOWN crosses.
+    Up,
+    /// DOWN rests one tick inside its best ask; UP crosses.
+    Down,
+}
+
+/// A market stream traded by its own task alongside others in one process.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MarketSpec {
+    /// Name used in logs and the per-task state file.
+    pub name: String,
+    /// Window offset for this task's discovery, replacing `WINDOW_OFFSET_SECONDS`.
+    pub window_offset_seconds: i64,
+}
+
+/// Settings that market discovery reads on its own, without the credentials a
+/// full `Config` requires.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct DiscoveryConfig {
+    /// Seconds added to the local clock when computing window slugs.
+    #[serde(default)]
+    pub window_offset_seconds: i64,
+}
+
+impl DiscoveryConfig {
+    /// Load discovery settings from environment variables and `.env`.
+    pub fn load() -> Result<Self, envy::Error> {
+        dotenvy::dotenv().ok();
+        envy::from_env()
+    }
+
+    /// Check the settings are valid.
+    pub fn validate(&self) -> Result<(), String> {
+        validate_window_offset(self.window_offset_seconds)
+    }
+}
+
+/// `WINDOW_OFFSET_SECONDS` must stay within one 15-minute window.
+fn validate_window_offset(window_offset_seconds: i64) -> Result<(), String> {
+    if window_offset_seconds.abs() >= 900 {
+        return Err("WINDOW_OFFSET_SECONDS must be within one 15-minute window".to_string());
+    }
+    Ok(())
+}
 
 /// Application configuration loaded from environment variables.
 #[derive(Debug, Clone, Deserialize)]
@@ -36,41 +197,242 @@ pub struct Config {
     #[serde(default = "default_target_cost")]
     pub target_pair_cost: Decimal,
 
-    /// Number of shares per trade (minimum 5).
+    /// Exchange fee rate in basis points, used only for the fee break-even and
+    /// net-edge maths; orders are signed with a zero fee rate.
+    #[serde(default)]
+    pub fee_rate_bps: Decimal,
+
+    /// Minimum edge per pair in basis points, net of fees (0 disables).
+    ///
+    /// Applies alongside `target_pair_cost`; whichever is stricter wins.
+    #[serde(default)]
+    pub min_edge_bps: Decimal,
+
+    /// Number of shares per trade (minimum 5), or USDC per pair with `order_size_unit`;
+    /// a USDC size is converted to shares during detection.
     #[serde(default = "default_order_size")]
     pub order_size: Decimal,
 
+    /// Whether `order_size` and size ladder sizes are shares or USDC.
+    #[serde(default)]
+    pub order_size_unit: OrderSizeUnit,
+
+    /// Size tiers as (max pair cost, size), e.g. `0.995:5,0.98:50`; the largest
+    /// tier the opportunity qualifies for is traded.
+    #[serde(default, deserialize_with = "deserialize_size_ladder")]
+    pub size_ladder: Vec<(Decimal, Decimal)>,
+
     /// Order type: FOK, FAK, or GTC.
     #[serde(default = "default_order_type")]
     pub order_type: String,
 
+    /// Basis points to shift entry prices below the worst-fill price (negative pays up).
+    #[serde(default)]
+    pub entry_price_offset_bps: Decimal,
+
+    /// Fallback price tick size for markets whose metadata omits it.
+    #[serde(default = "default_tick_size")]
+    pub tick_size: Decimal,
+
+    /// Neg-risk flag for markets whose metadata omits `negRisk`.
+    #[serde(default = "default_true")]
+    pub neg_risk_default: bool,
+
+    /// How the two legs are submitted (parallel or sequential).
+    #[serde(default)]
+    pub leg_mode: LegMode,
+
+    /// Which leg is submitted first in sequential leg mode.
+    #[serde(default)]
+    pub first_leg_policy: FirstLegPolicy,
+
+    /// Leg posted as a post-only GTD maker order instead of crossing.
+    #[serde(default)]
+    pub maker_leg: MakerLeg,
+
+    /// Refuse to execute unless the market's UP and DOWN tokens are a distinct pair.
+    #[serde(default = "default_true")]
+    pub verify_token_pair: bool,
+
+    /// Submit FAK instead of FOK while the rolling leg fill rate is below this (0 disables).
+    #[serde(default)]
+    pub min_fill_rate: Decimal,
+
+    /// Force FOK legs and liquidate any unhedged fill at a price crossing every bid.
+    #[serde(default)]
+    pub strict_hedge: bool,
+
+    /// Milliseconds a strict-hedge liquidation may take before the circuit breaker trips;
+    /// also bounds how long an ordinary unwind sell is watched for fills.
+    #[serde(default = "default_strict_hedge_deadline_ms")]
+    pub strict_hedge_deadline_ms: u64,
+
+    /// Allow GTC entry orders to rest on the book (passive-posting mode).
+    #[serde(default)]
+    pub passive_posting: bool,
+
     /// Balance safety margin (1.2 = 20% extra).
     #[serde(default = "default_balance_margin")]
     pub balance_margin: Decimal,
 
+    /// Decimals of the collateral token's base units (USDC uses 6).
+    #[serde(default = "default_usdc_decimals")]
+    pub usdc_decimals: u32,
+
     // === Operation Modes ===
     /// Simulation mode (no real orders).
     #[serde(default = "default_true")]
     pub dry_run: bool,
 
+    /// What a dry run exercises: full simulation, order signing, or balance checks.
+    #[serde(default)]
+    pub dry_run_mode: DryRunMode,
+
     /// Starting balance for simulation.
     #[serde(default = "default_sim_balance")]
     pub sim_balance: Decimal,
 
+    /// Seed for simulation randomness (random if unset).
+    #[serde(default)]
+    pub sim_seed: Option<u64>,
+
+    /// Probability (0-1) that each simulated leg is not rejected.
+    #[serde(default = "default_sim_fill_probability")]
+    pub sim_fill_probability: Decimal,
+
+    /// Probability (0-1) that a simulated execution loses the race on exactly one leg.
+    #[serde(default)]
+    pub sim_leg_failure_rate: Decimal,
+
+    /// Slippage in basis points added to simulated fill prices.
+    #[serde(default)]
+    pub sim_slippage_bps: Decimal,
+
+    /// Closed-market results kept for the history endpoint.
+    #[serde(default = "default_history_limit")]
+    pub history_limit: usize,
+
     /// Minimum seconds between trade executions.
     #[serde(default = "default_cooldown")]
     pub cooldown_seconds: u64,
 
+    /// Maximum USDC invested across markets that have not yet settled (0 disables).
+    #[serde(default)]
+    pub max_total_exposure: Decimal,
+
+    /// Largest ORDER_SIZE `POST /api/v1/params` may set (0: MAX_TOTAL_EXPOSURE when
+    /// set, otherwise the startup ORDER_SIZE).
+    #[serde(default)]
+    pub max_order_size: Decimal,
+
+    /// Probability (0-1) assumed for UP resolving when valuing an imbalanced position.
+    #[serde(default = "default_assumed_up_probability")]
+    pub assumed_up_probability: Decimal,
+
+    /// Skip execution when a balanced position of at least ORDER_SIZE is already held.
+    #[serde(default)]
+    pub skip_if_already_positioned: bool,
+
+    /// Redeem winning shares for USDC once each traded market resolves (live only).
+    #[serde(default)]
+    pub auto_redeem: bool,
+
+    /// POL price in USDC used to net redemption gas from profit (0 leaves gas out).
+    #[serde(default)]
+    pub pol_price_usdc: Decimal,
+
+    /// File recording executed trade intents across restarts (empty disables persistence).
+    #[serde(default = "default_state_file")]
+    pub state_file: String,
+
+    /// Resting (GTD) order lifetime in seconds, capped at one buffer before market
+    /// close (0: until then). FOK/FAK orders never expire.
+    #[serde(default)]
+    pub default_order_ttl_seconds: u64,
+
+    /// Reject an order identical (token, side, price, size) to one submitted within
+    /// this many milliseconds (0 disables).
+    #[serde(default)]
+    pub order_dedup_window_ms: u64,
+
+    /// Consecutive execution failures before the circuit breaker trips.
+    #[serde(default = "default_max_consecutive_failures")]
+    pub max_consecutive_failures: u32,
+
+    /// Seconds the circuit breaker stays open before retrying.
+    #[serde(default = "default_circuit_breaker_cooldown")]
+    pub circuit_breaker_cooldown_seconds: u64,
+
+    /// Minimum ask depth (shares) at or better than the fill price on each side (0 disables).
+    #[serde(default)]
+    pub min_top_of_book_size: Decimal,
+
+    /// Minimum ask levels on each side before a book is trusted (0 disables).
+    #[serde(default)]
+    pub min_book_levels: usize,
+
+    /// Seconds before market close inside which new entries are rejected (0 disables).
+    #[serde(default)]
+    pub min_seconds_to_close: u64,
+
+    /// Enforce MIN_SECONDS_TO_CLOSE (false only logs entries inside the window).
+    #[serde(default = "default_true")]
+    pub stop_new_entries_before_close: bool,
+
+    /// Consecutive scans with an inverted book treated as no opportunity before erroring.
+    #[serde(default = "default_inverted_book_tolerance_scans")]
+    pub inverted_book_tolerance_scans: u32,
+
+    /// Skip new entries while either book's mid has been unchanged this many seconds (0 disables).
+    #[serde(default)]
+    pub frozen_book_seconds: u64,
+
+    /// Reject opportunities on REST books whose exchange timestamp is older than this (0 disables).
+    #[serde(default)]
+    pub max_book_age_ms: u64,
+
+    /// Skip `/book` fetches when the `/price` quotes exceed target by more than this (0 disables).
+    #[serde(default)]
+    pub prescreen_margin: Decimal,
+
+    /// Skip evaluation when a book's token ID does not match the current market.
+    #[serde(default = "default_true")]
+    pub verify_book_tokens: bool,
+
+    /// Treat configuration warnings as validation errors.
+    #[serde(default)]
+    pub strict_validation: bool,
+
     // === Market Discovery ===
     /// Force specific market slug (bypasses auto-discovery).
     #[serde(default)]
     pub polymarket_market_slug: Option<String>,
 
+    /// Seconds added to the local clock when computing window slugs (clock or boundary offset).
+    #[serde(default)]
+    pub window_offset_seconds: i64,
+
+    /// Regex patterns a discovered slug must match one of to be traded (empty allows all).
+    #[serde(default, deserialize_with = "deserialize_patterns")]
+    pub slug_allowlist: Vec<String>,
+
+    /// Regex patterns of discovered slugs to skip.
+    #[serde(default, deserialize_with = "deserialize_patterns")]
+    pub slug_denylist: Vec<String>,
+
+    /// Markets traded concurrently, one task each (empty trades a single market).
+    #[serde(default, deserialize_with = "deserialize_market_specs")]
+    pub markets: Vec<MarketSpec>,
+
     // === WebSocket Configuration ===
     /// Enable WebSocket market feed instead of polling.
     #[serde(default)]
     pub use_wss: bool,
 
+    /// Track order fills via the authenticated user WebSocket instead of polling.
+    #[serde(default)]
+    pub use_user_ws: bool,
+
     /// WebSocket base URL.
     #[serde(default = "default_ws_url")]
     pub polymarket_ws_url: String,
@@ -79,6 +441,14 @@ pub struct Config {
     #[serde(default = "default_clob_url")]
     pub polymarket_clob_url: String,
 
+    /// Polygon JSON-RPC URL for on-chain redemption.
+    #[serde(default = "default_polygon_rpc_url")]
+    pub polygon_rpc_url: String,
+
+    /// Path prefix for every CLOB endpoint, e.g. `/v1` behind a proxy (empty for none).
+    #[serde(default)]
+    pub api_base_path: String,
+
     // === Server Configuration ===
     /// HTTP server port for health/metrics endpoints.
     #[serde(default = "default_port")]
@@ -92,6 +462,10 @@ pub struct Config {
     #[serde(default)]
     pub verbose: bool,
 
+    /// Log output format (text, json).
+    #[serde(default)]
+    pub log_format: LogFormat,
+
     // === Low-Latency Tuning ===
     /// HTTP request timeout in milliseconds.
     #[serde(default = "default_http_timeout_ms")]
@@ -101,6 +475,30 @@ pub struct Config {
     #[serde(default = "default_http_pool_size")]
     pub http_pool_size: usize,
 
+    /// Maximum authenticated CLOB requests in flight at once.
+    #[serde(default = "default_max_concurrent_requests")]
+    pub max_concurrent_requests: usize,
+
+    /// Immediate retries of a book fetch after a connection reset or timeout.
+    #[serde(default = "default_book_fetch_retries")]
+    pub book_fetch_retries: u32,
+
+    /// User-Agent sent with CLOB and discovery requests.
+    #[serde(default = "default_http_user_agent")]
+    pub http_user_agent: String,
+
+    /// Extra headers sent with every request, as `NAME:VALUE` pairs (e.g. `X-Bot-Id:arb-1`).
+    #[serde(default, deserialize_with = "deserialize_http_headers")]
+    pub http_extra_headers: Vec<(String, String)>,
+
+    /// Warn when local and CLOB server clocks differ by more than this many seconds.
+    #[serde(default = "default_max_clock_skew_seconds")]
+    pub max_clock_skew_seconds: u64,
+
+    /// Pause between polling-mode scans in milliseconds (0 for continuous).
+    #[serde(default = "default_scan_interval_ms")]
+    pub scan_interval_ms: u64,
+
     /// Order status polling timeout in milliseconds.
     #[serde(default = "default_order_timeout_ms")]
     pub order_timeout_ms: u64,
@@ -109,6 +507,15 @@ pub struct Config {
     #[serde(default = "default_order_poll_interval_ms")]
     pub order_poll_interval_ms: u64,
 
+    /// How long a submitted order may be "not found" on status polls before it is
+    /// treated as rejected, in milliseconds.
+    #[serde(default = "default_order_not_found_grace_ms")]
+    pub order_not_found_grace_ms: u64,
+
+    /// Reuse a fetched USDC balance for this many milliseconds (0 fetches every time).
+    #[serde(default = "default_balance_cache_ttl_ms")]
+    pub balance_cache_ttl_ms: u64,
+
     /// WebSocket reconnection max delay in seconds.
     #[serde(default = "default_ws_reconnect_max_delay_s")]
     pub ws_reconnect_max_delay_s: u64,
@@ -117,6 +524,15 @@ pub struct Config {
     #[serde(default = "default_ws_heartbeat_interval_s")]
     pub ws_heartbeat_interval_s: u64,
 
+    /// Book updates buffered between the WebSocket and the scanner; price changes
+    /// are coalesced per asset when it is nearly full.
+    #[serde(default = "default_ws_channel_capacity")]
+    pub ws_channel_capacity: usize,
+
+    /// Reconnect the WebSocket at each market rotation (false swaps subscriptions on one connection).
+    #[serde(default = "default_reconnect_on_market_rotation")]
+    pub reconnect_on_market_rotation: bool,
+
     // === Metrics ===
     /// Enable Prometheus metrics endpoint.
     #[serde(default = "default_true")]
@@ -125,6 +541,35 @@ pub struct Config {
     /// Prometheus metrics port.
     #[serde(default = "default_metrics_port")]
     pub metrics_port: u16,
+
+    /// Webhook POSTed on live fills, partial fills, and failures (alerts off when unset).
+    #[serde(default)]
+    pub alert_webhook_url: Option<String>,
+
+    /// Bearer token required by `POST /api/v1/params` (endpoint disabled when unset).
+    #[serde(default)]
+    pub params_api_token: Option<String>,
+
+    /// Directory for daily JSONL snapshots of the books behind each execution (empty disables).
+    #[serde(default)]
+    pub snapshot_dir: String,
+
+    /// CSV file that gets a row per live fill and market settlement (empty disables).
+    #[serde(default)]
+    pub journal_csv: String,
+
+    /// Directory for daily CSV files of closed-market results (empty disables).
+    #[serde(default)]
+    pub summary_dir: String,
+
+    /// Decimal places computed VWAPs, costs and percentages are rounded to; at
+    /// least the tick size's decimals. Order prices are never rounded with it.
+    #[serde(default = "default_calc_decimals")]
+    pub calc_decimals: u32,
+
+    /// Rounding rule applied at `calc_decimals`.
+    #[serde(default)]
+    pub calc_rounding: CalcRounding,
 }
 
 fn default_target_cost() -> Decimal {
@@ -139,6 +584,108 @@ fn default_order_type() -> String {
     "FOK".to_string()
 }
 
+/// Parse a size ladder like `0.995:5,0.98:50` into (max pair cost, size) tiers.
+pub fn parse_size_ladder(s: &str) -> Result<Vec<(Decimal, Decimal)>, String> {
+    s.split(',')
+        .map(str::trim)
+        .filter(|tier| !tier.is_empty())
+        .map(|tier| {
+            let (cost, size) = tier
+                .split_once(':')
+                .ok_or_else(|| format!("size ladder tier '{}' must be COST:SIZE", tier))?;
+            let cost = cost
+                .trim()
+                .parse::<Decimal>()
+                .map_err(|e| format!("invalid cost in size ladder tier '{}': {}", tier, e))?;
+            let size = size
+                .trim()
+                .parse::<Decimal>()
+                .map_err(|e| format!("invalid size in size ladder tier '{}': {}", tier, e))?;
+            Ok((cost, size))
+        })
+        .collect()
+}
+
+/// Parse extra headers like `X-Bot-Id:arb-1,X-Team:ops` into (name, value) pairs.
+pub fn parse_http_headers(s: &str) -> Result<Vec<(String, String)>, String> {
+    s.split(',')
+        .map(str::trim)
+        .filter(|header| !header.is_empty())
+        .map(|header| {
+            let (name, value) = header
+                .split_once(':')
+                .ok_or_else(|| format!("header '{}' must be NAME:VALUE", header))?;
+            Ok((name.trim().to_string(), value.trim().to_string()))
+        })
+        .collect()
+}
+
+fn deserialize_http_headers<'de, D>(deserializer: D) -> Result<Vec<(String, String)>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let s = String::deserialize(deserializer)?;
+    parse_http_headers(&s).map_err(serde::de::Error::custom)
+}
+
+/// Parse market specs like `current:0,next:900` into (name, window offset) tasks.
+pub fn parse_market_specs(s: &str) -> Result<Vec<MarketSpec>, String> {
+    s.split(',')
+        .map(str::trim)
+        .filter(|spec| !spec.is_empty())
+        .map(|spec| {
+            let (name, offset) = spec
+                .split_once(':')
+                .ok_or_else(|| format!("market spec '{}' must be NAME:WINDOW_OFFSET", spec))?;
+            let window_offset_seconds = offset
+                .trim()
+                .parse::<i64>()
+                .map_err(|e| format!("invalid window offset in market spec '{}': {}", spec, e))?;
+            Ok(MarketSpec {
+                name: name.trim().to_string(),
+                window_offset_seconds,
+            })
+        })
+        .collect()
+}
+
+fn deserialize_market_specs<'de, D>(deserializer: D) -> Result<Vec<MarketSpec>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let s = String::deserialize(deserializer)?;
+    parse_market_specs(&s).map_err(serde::de::Error::custom)
+}
+
+/// Parse a comma-separated list of patterns, dropping empty entries.
+pub fn parse_patterns(s: &str) -> Vec<String> {
+    s.split(',')
+        .map(str::trim)
+        .filter(|pattern| !pattern.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+fn deserialize_patterns<'de, D>(deserializer: D) -> Result<Vec<String>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let s = String::deserialize(deserializer)?;
+    Ok(parse_patterns(&s))
+}
+
+fn deserialize_size_ladder<'de, D>(deserializer: D) -> Result<Vec<(Decimal, Decimal)>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let s = String::deserialize(deserializer)?;
+    parse_size_ladder(&s).map_err(serde::de::Error::custom)
+}
+
+fn default_tick_size() -> Decimal {
+    Decimal::new(1, 2) // 0.01
+}
+
 fn default_balance_margin() -> Decimal {
     Decimal::new(12, 1) // 1.2
 }
@@ -147,14 +694,46 @@ fn default_true() -> bool {
     true
 }
 
+fn default_usdc_decimals() -> u32 {
+    6
+}
+
 fn default_sim_balance() -> Decimal {
     Decimal::new(100, 0) // $100
 }
 
+fn default_history_limit() -> usize {
+    96 // One day of 15-minute markets
+}
+
+fn default_sim_fill_probability() -> Decimal {
+    Decimal::ONE // Always fill
+}
+
+fn default_assumed_up_probability() -> Decimal {
+    Decimal::new(5, 1) // No directional view
+}
+
 fn default_cooldown() -> u64 {
     10
 }
 
+fn default_state_file() -> String {
+    "bot_state.json".to_string()
+}
+
+fn default_max_consecutive_failures() -> u32 {
+    5
+}
+
+fn default_inverted_book_tolerance_scans() -> u32 {
+    3
+}
+
+fn default_circuit_breaker_cooldown() -> u64 {
+    300
+}
+
 fn default_ws_url() -> String {
     "wss://ws-subscriptions-clob.polymarket.com".to_string()
 }
@@ -163,6 +742,10 @@ fn default_clob_url() -> String {
     "https://clob.polymarket.com".to_string()
 }
 
+fn default_polygon_rpc_url() -> String {
+    "https://polygon-rpc.com".to_string()
+}
+
 fn default_port() -> u16 {
     8080
 }
@@ -179,14 +762,46 @@ fn default_http_pool_size() -> usize {
     10
 }
 
+fn default_max_concurrent_requests() -> usize {
+    8
+}
+
+fn default_book_fetch_retries() -> u32 {
+    1
+}
+
+fn default_http_user_agent() -> String {
+    DEFAULT_HTTP_USER_AGENT.to_string()
+}
+
+fn default_balance_cache_ttl_ms() -> u64 {
+    5000
+}
+
 fn default_order_timeout_ms() -> u64 {
     500 // 500ms (down from 3000ms)
 }
 
+fn default_strict_hedge_deadline_ms() -> u64 {
+    2000
+}
+
+fn default_max_clock_skew_seconds() -> u64 {
+    2
+}
+
+fn default_scan_interval_ms() -> u64 {
+    100
+}
+
 fn default_order_poll_interval_ms() -> u64 {
     50 // 50ms (down from 250ms)
 }
 
+fn default_order_not_found_grace_ms() -> u64 {
+    1000
+}
+
 fn default_ws_reconnect_max_delay_s() -> u64 {
     30
 }
@@ -195,65 +810,409 @@ fn default_ws_heartbeat_interval_s() -> u64 {
     30
 }
 
+fn default_ws_channel_capacity() -> usize {
+    1000
+}
+
+fn default_calc_decimals() -> u32 {
+    6
+}
+
+fn default_reconnect_on_market_rotation() -> bool {
+    true
+}
+
 fn default_metrics_port() -> u16 {
     9090
 }
 
-impl Config {
-    /// Load configuration from environment, reading .env file first.
-    pub fn load() -> Result<Self, envy::Error> {
-        dotenvy::dotenv().ok();
-        envy::from_env()
+/// Trading thresholds that can be changed while the bot runs (`POST /api/v1/params`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RuntimeParams {
+    /// Maximum combined cost to trigger arbitrage.
+    pub target_pair_cost: Decimal,
+    /// Number of shares per trade.
+    pub order_size: Decimal,
+    /// Fee rate the cost threshold is checked against; fixed at startup.
+    pub fee_rate_bps: Decimal,
+    /// Largest order size allowed; fixed at startup.
+    pub max_order_size: Decimal,
+}
+
+impl Default for RuntimeParams {
+    fn default() -> Self {
+        Self {
+            target_pair_cost: default_target_cost(),
+            order_size: default_order_size(),
+            fee_rate_bps: Decimal::ZERO,
+            max_order_size: default_order_size(),
+        }
     }
+}
 
-    /// Check if the configuration is valid.
+impl RuntimeParams {
+    /// Check the thresholds; `Config::validate` applies the same rules at startup.
     pub fn validate(&self) -> Result<(), String> {
-        if self.polymarket_private_key.is_empty() {
-            return Err("POLYMARKET_PRIVATE_KEY is required".to_string());
+        if self.order_size < Decimal::new(5, 0) {
+            return Err("ORDER_SIZE must be at least 5".to_string());
         }
 
-        if !self.polymarket_private_key.starts_with("0x") {
-            return Err("POLYMARKET_PRIVATE_KEY must start with 0x".to_string());
+        if self.order_size > self.max_order_size {
+            return Err(format!(
+                "ORDER_SIZE {} must not exceed the maximum {}",
+                self.order_size, self.max_order_size
+            ));
         }
 
-        if self.order_size < Decimal::new(5, 0) {
-            return Err("ORDER_SIZE must be at least 5".to_string());
+        if self.target_pair_cost <= Decimal::ZERO {
+            return Err("TARGET_PAIR_COST must be greater than 0".to_string());
         }
 
         if self.target_pair_cost >= Decimal::ONE {
             return Err("TARGET_PAIR_COST must be less than 1.0".to_string());
         }
 
+        let break_even = self.fee_break_even();
+        if self.target_pair_cost >= break_even {
+            return Err(format!(
+                "TARGET_PAIR_COST {} must be below the fee break-even {}: \
+                 a {} bps fee needs at least {} of edge per pair",
+                self.target_pair_cost,
+                break_even,
+                self.fee_rate_bps,
+                Decimal::ONE - break_even
+            ));
+        }
+
         Ok(())
     }
 
-    /// Get the effective order type (uppercase).
-    pub fn order_type_upper(&self) -> String {
-        self.order_type.to_uppercase()
+    /// Pair cost at which fees eat the whole $1 payout (`1 - FEE_RATE_BPS / 10000`).
+    pub fn fee_break_even(&self) -> Decimal {
+        Decimal::ONE - self.fee_rate_bps / BPS
     }
 
-    /// Check if using Magic.link (signature_type == 1).
-    pub fn is_magic_link(&self) -> bool {
-        self.polymarket_signature_type == 1
+    /// Overwrite `config`'s thresholds with these.
+    pub fn apply(&self, config: &mut Config) {
+        config.target_pair_cost = self.target_pair_cost;
+        config.order_size = self.order_size;
     }
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn default_values_are_sensible() {
-        assert_eq!(default_target_cost(), Decimal::new(991, 3));
-        assert_eq!(default_order_size(), Decimal::new(5, 0));
-        assert_eq!(default_order_type(), "FOK");
-        assert!(default_true());
+impl Config {
+    /// Load configuration from environment, reading .env file first.
+    pub fn load() -> Result<Self, envy::Error> {
+        dotenvy::dotenv().ok();
+        envy::from_env()
     }
 
-    #[test]
-    fn validate_rejects_empty_private_key() {
-        let config = Config {
-            polymarket_private_key: "".to_string(),
+    /// Check if the configuration is valid.
+    pub fn validate(&self) -> Result<(), String> {
+        if self.polymarket_private_key.is_empty() {
+            return Err("POLYMARKET_PRIVATE_KEY is required".to_string());
+        }
+
+        if !self.polymarket_private_key.starts_with("0x") {
+            return Err("POLYMARKET_PRIVATE_KEY must start with 0x".to_string());
+        }
+
+        if self.fee_rate_bps < Decimal::ZERO || self.fee_rate_bps >= BPS {
+            return Err("FEE_RATE_BPS must be between 0 and 10000".to_string());
+        }
+
+        if self.max_order_size < Decimal::ZERO {
+            return Err("MAX_ORDER_SIZE must not be negative".to_string());
+        }
+
+        self.runtime_params().validate()?;
+
+        if self.ws_channel_capacity == 0 {
+            return Err("WS_CHANNEL_CAPACITY must be at least 1".to_string());
+        }
+
+        if self.min_edge_bps < Decimal::ZERO || self.min_edge_bps >= BPS {
+            return Err("MIN_EDGE_BPS must be between 0 and 10000".to_string());
+        }
+
+        for &(max_cost, size) in &self.size_ladder {
+            if max_cost <= Decimal::ZERO || max_cost >= Decimal::ONE {
+                return Err(format!("SIZE_LADDER cost {} must be between 0 and 1", max_cost));
+            }
+            if size < Decimal::new(5, 0) {
+                return Err(format!("SIZE_LADDER size {} must be at least 5", size));
+            }
+        }
+
+        if self.entry_price_offset_bps.abs() >= BPS {
+            return Err("ENTRY_PRICE_OFFSET_BPS must be between -10000 and 10000".to_string());
+        }
+
+        // A missing order must get at least one more poll before it counts as rejected
+        if self.order_not_found_grace_ms < self.order_poll_interval_ms {
+            return Err(
+                "ORDER_NOT_FOUND_GRACE_MS must be at least ORDER_POLL_INTERVAL_MS".to_string()
+            );
+        }
+
+        if self.tick_size <= Decimal::ZERO || self.tick_size >= Decimal::ONE {
+            return Err("TICK_SIZE must be between 0 and 1".to_string());
+        }
+
+        // Rounding below the tick's precision would move prices off the tick
+        let tick_decimals = self.tick_size.normalize().scale();
+        if self.calc_decimals < tick_decimals || self.calc_decimals > Decimal::MAX_SCALE {
+            return Err(format!(
+                "CALC_DECIMALS must be between {} (the TICK_SIZE decimals) and {}",
+                tick_decimals,
+                Decimal::MAX_SCALE
+            ));
+        }
+
+        if self.usdc_decimals > Decimal::MAX_SCALE {
+            return Err(format!("USDC_DECIMALS must be at most {}", Decimal::MAX_SCALE));
+        }
+
+        if self.max_concurrent_requests == 0 {
+            return Err("MAX_CONCURRENT_REQUESTS must be at least 1".to_string());
+        }
+
+        if self.history_limit == 0 {
+            return Err("HISTORY_LIMIT must be at least 1".to_string());
+        }
+
+        if reqwest::header::HeaderValue::from_str(&self.http_user_agent).is_err() {
+            return Err("HTTP_USER_AGENT must be a valid header value".to_string());
+        }
+
+        for (name, value) in &self.http_extra_headers {
+            if reqwest::header::HeaderName::from_bytes(name.as_bytes()).is_err() {
+                return Err(format!("HTTP_EXTRA_HEADERS name '{}' is not a valid header", name));
+            }
+            if reqwest::header::HeaderValue::from_str(value).is_err() {
+                return Err(format!("HTTP_EXTRA_HEADERS value for '{}' is not valid", name));
+            }
+        }
+
+        for (name, patterns) in [
+            ("SLUG_ALLOWLIST", &self.slug_allowlist),
+            ("SLUG_DENYLIST", &self.slug_denylist),
+        ] {
+            for pattern in patterns {
+                if let Err(e) = regex::Regex::new(pattern) {
+                    return Err(format!("{} pattern '{}' is invalid: {}", name, pattern, e));
+                }
+            }
+        }
+
+        if self.sim_fill_probability < Decimal::ZERO || self.sim_fill_probability > Decimal::ONE {
+            return Err("SIM_FILL_PROBABILITY must be between 0 and 1".to_string());
+        }
+
+        if self.sim_leg_failure_rate < Decimal::ZERO || self.sim_leg_failure_rate > Decimal::ONE {
+            return Err("SIM_LEG_FAILURE_RATE must be between 0 and 1".to_string());
+        }
+
+        if self.min_fill_rate < Decimal::ZERO || self.min_fill_rate > Decimal::ONE {
+            return Err("MIN_FILL_RATE must be between 0 and 1".to_string());
+        }
+
+        validate_window_offset(self.window_offset_seconds)?;
+
+        let mut names = std::collections::HashSet::new();
+        for spec in &self.markets {
+            if spec.name.is_empty() || !names.insert(spec.name.as_str()) {
+                return Err(format!("MARKETS names must be unique and non-empty: '{}'", spec.name));
+            }
+            // One window ahead is allowed, so a task can trade the next window
+            if spec.window_offset_seconds.abs() > 900 {
+                return Err(format!(
+                    "MARKETS offset for '{}' must be within one 15-minute window",
+                    spec.name
+                ));
+            }
+        }
+
+        if let Some(url) = self.alert_webhook_url.as_deref().filter(|url| !url.is_empty()) {
+            if reqwest::Url::parse(url).is_err() {
+                return Err(format!("ALERT_WEBHOOK_URL is not a valid URL: {}", url));
+            }
+        }
+
+        if self.prescreen_margin < Decimal::ZERO {
+            return Err("PRESCREEN_MARGIN must not be negative".to_string());
+        }
+
+        if self.sim_slippage_bps < Decimal::ZERO {
+            return Err("SIM_SLIPPAGE_BPS must not be negative".to_string());
+        }
+
+        if self.max_total_exposure < Decimal::ZERO {
+            return Err("MAX_TOTAL_EXPOSURE must not be negative".to_string());
+        }
+
+        if self.assumed_up_probability < Decimal::ZERO || self.assumed_up_probability > Decimal::ONE
+        {
+            return Err("ASSUMED_UP_PROBABILITY must be between 0 and 1".to_string());
+        }
+
+        if (self.is_magic_link() || self.is_gnosis_safe()) && self.polymarket_funder.is_none() {
+            return Err(format!(
+                "POLYMARKET_FUNDER is required for POLYMARKET_SIGNATURE_TYPE={}",
+                self.polymarket_signature_type
+            ));
+        }
+
+        if self.max_consecutive_failures == 0 {
+            return Err("MAX_CONSECUTIVE_FAILURES must be at least 1".to_string());
+        }
+
+        if self.pol_price_usdc < Decimal::ZERO {
+            return Err("POL_PRICE_USDC cannot be negative".to_string());
+        }
+
+        // Proxy and Safe wallets hold the shares, so only an EOA can redeem them directly
+        if self.auto_redeem && !self.dry_run && self.polymarket_signature_type != 0 {
+            return Err("AUTO_REDEEM requires POLYMARKET_SIGNATURE_TYPE=0 (EOA)".to_string());
+        }
+
+        let warnings = self.warnings();
+        for warning in &warnings {
+            warn!("{}", warning);
+        }
+        if self.strict_validation {
+            if let Some(warning) = warnings.into_iter().next() {
+                return Err(warning);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Collect non-fatal configuration warnings.
+    ///
+    /// These become errors when `strict_validation` is enabled.
+    pub fn warnings(&self) -> Vec<String> {
+        let mut warnings = Vec::new();
+
+        // GTC entries rest on the book and can fill one leg long after detection
+        if self.order_type_upper() == "GTC" && !self.passive_posting {
+            warnings.push(
+                "ORDER_TYPE=GTC can leave one leg filled; use FOK/FAK or set PASSIVE_POSTING=true"
+                    .to_string(),
+            );
+        }
+
+        warnings
+    }
+
+    /// Get the effective order type (uppercase).
+    pub fn order_type_upper(&self) -> String {
+        self.order_type.to_uppercase()
+    }
+
+    /// Check if using Magic.link (signature_type == 1).
+    pub fn is_magic_link(&self) -> bool {
+        self.polymarket_signature_type == 1
+    }
+
+    /// Check if using a Gnosis Safe (signature_type == 2).
+    pub fn is_gnosis_safe(&self) -> bool {
+        self.polymarket_signature_type == 2
+    }
+
+    /// Pair cost at which fees eat the whole $1 payout (`1 - FEE_RATE_BPS / 10000`).
+    pub fn fee_break_even(&self) -> Decimal {
+        self.runtime_params().fee_break_even()
+    }
+
+    /// Thresholds the API may change at runtime, starting from this config.
+    ///
+    /// Order size is capped by MAX_ORDER_SIZE, else MAX_TOTAL_EXPOSURE, else the
+    /// startup ORDER_SIZE, so the API can never size up without a configured bound.
+    pub fn runtime_params(&self) -> RuntimeParams {
+        let max_order_size = if !self.max_order_size.is_zero() {
+            self.max_order_size
+        } else if !self.max_total_exposure.is_zero() {
+            self.max_total_exposure
+        } else {
+            self.order_size
+        };
+        RuntimeParams {
+            target_pair_cost: self.target_pair_cost,
+            order_size: self.order_size,
+            fee_rate_bps: self.fee_rate_bps,
+            max_order_size,
+        }
+    }
+
+    /// Edge in basis points left from `profit_per_share` after fees.
+    pub fn net_edge_bps(&self, profit_per_share: Decimal) -> Decimal {
+        profit_per_share * BPS - self.fee_rate_bps
+    }
+
+    /// Net edge in basis points an opportunity must clear: the stricter of
+    /// `min_edge_bps` and the edge implied by `target_pair_cost`.
+    pub fn required_edge_bps(&self) -> Decimal {
+        let ceiling_edge = self.net_edge_bps(Decimal::ONE - self.target_pair_cost);
+        ceiling_edge.max(self.min_edge_bps)
+    }
+
+    /// Market tasks to run; a single task on `WINDOW_OFFSET_SECONDS` when `MARKETS` is unset.
+    pub fn market_specs(&self) -> Vec<MarketSpec> {
+        if !self.markets.is_empty() {
+            return self.markets.clone();
+        }
+        vec![MarketSpec {
+            name: "default".to_string(),
+            window_offset_seconds: self.window_offset_seconds,
+        }]
+    }
+
+    /// Config for one market task.
+    ///
+    /// With several tasks, each gets its own state file (`bot_state-<name>.json`)
+    /// so their trade intents don't overwrite each other.
+    pub fn for_market(&self, spec: &MarketSpec) -> Config {
+        let mut config = self.clone();
+        config.window_offset_seconds = spec.window_offset_seconds;
+        if !self.markets.is_empty() && !self.state_file.is_empty() {
+            let path = std::path::Path::new(&self.state_file);
+            let stem = path.file_stem().unwrap_or_default().to_string_lossy();
+            let file_name = match path.extension() {
+                Some(ext) => format!("{}-{}.{}", stem, spec.name, ext.to_string_lossy()),
+                None => format!("{}-{}", stem, spec.name),
+            };
+            config.state_file = path.with_file_name(file_name).to_string_lossy().into_owned();
+        }
+        config
+    }
+
+    /// Pause between polling-mode scans.
+    pub fn scan_interval(&self) -> std::time::Duration {
+        std::time::Duration::from_millis(self.scan_interval_ms)
+    }
+
+    /// Time allowed to liquidate an unhedged leg in strict-hedge mode.
+    pub fn strict_hedge_deadline(&self) -> std::time::Duration {
+        std::time::Duration::from_millis(self.strict_hedge_deadline_ms)
+    }
+
+    /// Seconds before close at which new entries stop (0 when not enforced).
+    pub fn entry_cutoff_seconds(&self) -> u64 {
+        if self.stop_new_entries_before_close {
+            self.min_seconds_to_close
+        } else {
+            0
+        }
+    }
+}
+
+#[cfg(test)]
+impl Config {
+    /// Valid dry-run config with every other setting at its default, shared by unit tests.
+    pub(crate) fn test_default() -> Self {
+        Config {
+            polymarket_private_key: "0x0123456789abcdef0123456789abcdef0123456789abcdef0123456789abcdef".to_string(),
             polymarket_api_key: None,
             polymarket_api_secret: None,
             polymarket_api_passphrase: None,
@@ -270,6 +1229,7 @@ mod tests {
             use_wss: false,
             polymarket_ws_url: default_ws_url(),
             polymarket_clob_url: default_clob_url(),
+            polygon_rpc_url: default_polygon_rpc_url(),
             port: default_port(),
             rust_log: default_log_level(),
             verbose: false,
@@ -277,10 +1237,99 @@ mod tests {
             http_pool_size: default_http_pool_size(),
             order_timeout_ms: default_order_timeout_ms(),
             order_poll_interval_ms: default_order_poll_interval_ms(),
+            order_not_found_grace_ms: default_order_not_found_grace_ms(),
             ws_reconnect_max_delay_s: default_ws_reconnect_max_delay_s(),
             ws_heartbeat_interval_s: default_ws_heartbeat_interval_s(),
             metrics_enabled: true,
             metrics_port: default_metrics_port(),
+            passive_posting: false,
+            strict_validation: false,
+            log_format: LogFormat::Text,
+            reconnect_on_market_rotation: true,
+            max_consecutive_failures: default_max_consecutive_failures(),
+            circuit_breaker_cooldown_seconds: default_circuit_breaker_cooldown(),
+            sim_seed: None,
+            sim_fill_probability: default_sim_fill_probability(),
+            verify_book_tokens: true,
+            skip_if_already_positioned: false,
+            auto_redeem: false,
+            pol_price_usdc: Decimal::ZERO,
+            min_top_of_book_size: Decimal::ZERO,
+            use_user_ws: false,
+            dry_run_mode: Default::default(),
+            min_seconds_to_close: 0,
+            stop_new_entries_before_close: true,
+            history_limit: 96,
+            order_dedup_window_ms: 0,
+            scan_interval_ms: default_scan_interval_ms(),
+            sim_slippage_bps: Decimal::ZERO,
+            max_clock_skew_seconds: default_max_clock_skew_seconds(),
+            tick_size: default_tick_size(),
+            entry_price_offset_bps: Decimal::ZERO,
+            size_ladder: Vec::new(),
+            state_file: String::new(),
+            balance_cache_ttl_ms: 0,
+            neg_risk_default: true,
+            max_concurrent_requests: default_max_concurrent_requests(),
+            leg_mode: LegMode::Parallel,
+            inverted_book_tolerance_scans: default_inverted_book_tolerance_scans(),
+            usdc_decimals: default_usdc_decimals(),
+            max_total_exposure: Decimal::ZERO,
+            max_order_size: Decimal::ZERO,
+            params_api_token: None,
+            default_order_ttl_seconds: 0,
+            http_user_agent: DEFAULT_HTTP_USER_AGENT.to_string(),
+            http_extra_headers: Vec::new(),
+            book_fetch_retries: default_book_fetch_retries(),
+            frozen_book_seconds: 0,
+            min_fill_rate: Decimal::ZERO,
+            verify_token_pair: true,
+            prescreen_margin: Decimal::ZERO,
+            window_offset_seconds: 0,
+            first_leg_policy: FirstLegPolicy::default(),
+            alert_webhook_url: None,
+            fee_rate_bps: Decimal::ZERO,
+            min_edge_bps: Decimal::ZERO,
+            snapshot_dir: String::new(),
+            ws_channel_capacity: default_ws_channel_capacity(),
+            sim_leg_failure_rate: Decimal::ZERO,
+            api_base_path: String::new(),
+            assumed_up_probability: default_assumed_up_probability(),
+            slug_allowlist: Vec::new(),
+            slug_denylist: Vec::new(),
+            markets: Vec::new(),
+            maker_leg: MakerLeg::default(),
+            max_book_age_ms: 0,
+            journal_csv: String::new(),
+            calc_rounding: CalcRounding::default(),
+            calc_decimals: default_calc_decimals(),
+            min_book_levels: 0,
+            strict_hedge: false,
+            strict_hedge_deadline_ms: default_strict_hedge_deadline_ms(),
+            summary_dir: String::new(),
+            order_size_unit: Default::default(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    #[test]
+    fn default_values_are_sensible() {
+        assert_eq!(default_target_cost(), Decimal::new(991, 3));
+        assert_eq!(default_order_size(), Decimal::new(5, 0));
+        assert_eq!(default_order_type(), "FOK");
+        assert!(default_true());
+    }
+
+    #[test]
+    fn validate_rejects_empty_private_key() {
+        let config = Config {
+            polymarket_private_key: "".to_string(),
+            ..Config::test_default()
         };
 
         assert!(config.validate().is_err());
@@ -290,35 +1339,354 @@ mod tests {
     fn validate_rejects_invalid_private_key_prefix() {
         let config = Config {
             polymarket_private_key: "abc123".to_string(),
-            polymarket_api_key: None,
-            polymarket_api_secret: None,
-            polymarket_api_passphrase: None,
+            ..Config::test_default()
+        };
+
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn validate_warns_on_gtc_without_passive_posting() {
+        let config = Config {
+            order_type: "GTC".to_string(),
+            ..Config::test_default()
+        };
+
+        assert_eq!(config.warnings().len(), 1);
+        assert!(config.validate().is_ok());
+    }
+
+    #[test]
+    fn validate_rejects_gtc_in_strict_mode() {
+        let config = Config {
+            order_type: "gtc".to_string(),
+            strict_validation: true,
+            ..Config::test_default()
+        };
+
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn parse_size_ladder_tiers() {
+        let ladder = parse_size_ladder("0.995:5, 0.98:50").unwrap();
+        assert_eq!(
+            ladder,
+            vec![
+                (Decimal::new(995, 3), Decimal::new(5, 0)),
+                (Decimal::new(98, 2), Decimal::new(50, 0)),
+            ]
+        );
+
+        assert!(parse_size_ladder("").unwrap().is_empty());
+        assert!(parse_size_ladder("0.98").is_err());
+        assert!(parse_size_ladder("0.98:lots").is_err());
+
+        let config = Config {
+            size_ladder: parse_size_ladder("0.98:2").unwrap(),
+            ..Config::test_default()
+        };
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn parse_http_headers_pairs() {
+        assert_eq!(
+            parse_http_headers("X-Bot-Id: arb-1, X-Team:ops").unwrap(),
+            vec![
+                ("X-Bot-Id".to_string(), "arb-1".to_string()),
+                ("X-Team".to_string(), "ops".to_string()),
+            ]
+        );
+        assert!(parse_http_headers("").unwrap().is_empty());
+        assert!(parse_http_headers("X-Bot-Id").is_err());
+
+        let config = Config {
+            http_extra_headers: parse_http_headers("Bad Name:x").unwrap(),
+            ..Config::test_default()
+        };
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn slug_patterns_are_parsed_and_validated() {
+        assert_eq!(
+            parse_patterns(" ^btc-updown-15m-, -1765301400$ ,"),
+            vec!["^btc-updown-15m-".to_string(), "-1765301400$".to_string()]
+        );
+
+        let config = Config {
+            slug_denylist: parse_patterns("btc-(updown"),
+            ..Config::test_default()
+        };
+        let err = config.validate().unwrap_err();
+        assert!(err.contains("SLUG_DENYLIST"), "{}", err);
+    }
+
+    #[test]
+    fn market_specs_give_each_task_its_own_config() {
+        let specs = parse_market_specs("current:0, next:900").unwrap();
+        assert_eq!(specs[1].name, "next");
+        assert_eq!(specs[1].window_offset_seconds, 900);
+        assert!(parse_market_specs("next").is_err());
+
+        // Unset runs one task on the global offset and state file
+        let config = Config::test_default();
+        let single = config.market_specs();
+        assert_eq!(single.len(), 1);
+        assert_eq!(config.for_market(&single[0]).state_file, config.state_file);
+
+        let config = Config {
+            markets: specs.clone(),
+            state_file: "state/bot_state.json".to_string(),
+            ..Config::test_default()
+        };
+        assert!(config.validate().is_ok());
+        let next = config.for_market(&specs[1]);
+        assert_eq!(next.window_offset_seconds, 900);
+        assert_eq!(next.state_file, "state/bot_state-next.json");
+
+        let duplicate = Config {
+            markets: parse_market_specs("a:0,a:900").unwrap(),
+            ..Config::test_default()
+        };
+        assert!(duplicate.validate().is_err());
+    }
+
+    #[test]
+    fn scan_interval_uses_configured_value() {
+        assert_eq!(Config::test_default().scan_interval(), std::time::Duration::from_millis(100));
+
+        let config = Config {
+            scan_interval_ms: 250,
+            ..Config::test_default()
+        };
+        assert_eq!(config.scan_interval(), std::time::Duration::from_millis(250));
+    }
+
+    #[test]
+    fn validate_accepts_continuous_scanning() {
+        let config = Config {
+            scan_interval_ms: 0,
+            ..Config::test_default()
+        };
+
+        assert!(config.validate().is_ok());
+        assert!(config.scan_interval().is_zero());
+    }
+
+    #[test]
+    fn validate_accepts_gtc_with_passive_posting() {
+        let config = Config {
+            order_type: "GTC".to_string(),
+            passive_posting: true,
+            strict_validation: true,
+            ..Config::test_default()
+        };
+
+        assert!(config.warnings().is_empty());
+        assert!(config.validate().is_ok());
+    }
+
+    #[test]
+    fn validate_requires_funder_for_magic_link() {
+        let mut config = Config::test_default();
+        config.polymarket_signature_type = 1;
+        assert!(config.validate().is_err());
+
+        config.polymarket_funder = Some("0x00000000000000000000000000000000000000bb".to_string());
+        assert!(config.validate().is_ok());
+    }
+
+    #[test]
+    fn validate_rejects_target_cost_above_fee_break_even() {
+        let config = Config {
+            target_pair_cost: dec!(0.999),
+            ..Config::test_default()
+        };
+        assert!(config.validate().is_ok());
+
+        // 0.999 is below 1.0 but above the 0.998 break-even of a 20 bps fee
+        let config = Config {
+            fee_rate_bps: dec!(20),
+            ..config
+        };
+        assert_eq!(config.fee_break_even(), dec!(0.998));
+        let err = config.validate().unwrap_err();
+        assert!(err.contains("break-even"), "{}", err);
+
+        let config = Config {
+            target_pair_cost: dec!(0.991),
+            ..config
+        };
+        assert!(config.validate().is_ok());
+    }
+
+    #[test]
+    fn runtime_order_size_is_bounded_by_configured_max() {
+        let config = Config {
+            order_size: dec!(10),
+            ..Config::test_default()
+        };
+        // Nothing configured: the API may not size above the startup order
+        assert_eq!(config.runtime_params().max_order_size, dec!(10));
+
+        let config = Config {
+            max_total_exposure: dec!(200),
+            ..config
+        };
+        assert_eq!(config.runtime_params().max_order_size, dec!(200));
+
+        let config = Config {
+            max_order_size: dec!(50),
+            ..config
+        };
+        assert_eq!(config.runtime_params().max_order_size, dec!(50));
+
+        let mut params = config.runtime_params();
+        params.order_size = dec!(51);
+        let err = params.validate().unwrap_err();
+        assert!(err.contains("maximum 50"), "{}", err);
+    }
+
+    #[test]
+    fn required_edge_is_stricter_of_ceiling_and_floor() {
+        let config = Config {
+            target_pair_cost: dec!(0.991),
+            fee_rate_bps: dec!(20),
+            ..Config::test_default()
+        };
+        // 90 bps below $1, less 20 bps of fees
+        assert_eq!(config.required_edge_bps(), dec!(70));
+
+        let config = Config {
+            min_edge_bps: dec!(150),
+            ..config
+        };
+        assert_eq!(config.required_edge_bps(), dec!(150));
+        assert!(config.validate().is_ok());
+    }
+
+    #[test]
+    fn validate_rejects_out_of_range_fee_rate() {
+        let config = Config {
+            fee_rate_bps: dec!(-1),
+            ..Config::test_default()
+        };
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn validate_rejects_zero_history_limit() {
+        let config = Config {
+            history_limit: 0,
+            ..Config::test_default()
+        };
+        assert_eq!(config.validate().unwrap_err(), "HISTORY_LIMIT must be at least 1");
+    }
+
+    #[test]
+    fn discovery_config_parses_and_validates_window_offset() {
+        let parse = |offset: &str| -> DiscoveryConfig {
+            envy::from_iter([("WINDOW_OFFSET_SECONDS".to_string(), offset.to_string())]).unwrap()
+        };
+
+        let discovery = parse("-30");
+        assert_eq!(discovery.window_offset_seconds, -30);
+        assert!(discovery.validate().is_ok());
+        assert_eq!(
+            parse("900").validate().unwrap_err(),
+            "WINDOW_OFFSET_SECONDS must be within one 15-minute window"
+        );
+
+        let invalid: Result<DiscoveryConfig, _> =
+            envy::from_iter([("WINDOW_OFFSET_SECONDS".to_string(), "soon".to_string())]);
+        assert!(invalid.is_err());
+    }
+
+    #[test]
+    fn validate_rejects_out_of_range_sim_fill_probability() {
+        for sim_fill_probability in [dec!(-0.1), dec!(1.1)] {
+            let config = Config {
+                sim_fill_probability,
+                ..Config::test_default()
+            };
+            assert_eq!(
+                config.validate().unwrap_err(),
+                "SIM_FILL_PROBABILITY must be between 0 and 1"
+            );
+        }
+    }
+
+    #[test]
+    fn validate_rejects_not_found_grace_below_poll_interval() {
+        let config = Config {
+            order_not_found_grace_ms: 10,
+            ..Config::test_default()
+        };
+        assert_eq!(
+            config.validate().unwrap_err(),
+            "ORDER_NOT_FOUND_GRACE_MS must be at least ORDER_POLL_INTERVAL_MS"
+        );
+    }
+
+    #[test]
+    fn validate_rejects_live_auto_redeem_from_proxy_wallet() {
+        let config = Config {
+            auto_redeem: true,
+            dry_run: false,
+            polymarket_signature_type: 1,
+            polymarket_funder: Some("0xfunder".to_string()),
+            ..Config::test_default()
+        };
+        assert_eq!(
+            config.validate().unwrap_err(),
+            "AUTO_REDEEM requires POLYMARKET_SIGNATURE_TYPE=0 (EOA)"
+        );
+
+        let eoa = Config {
             polymarket_signature_type: 0,
             polymarket_funder: None,
-            target_pair_cost: default_target_cost(),
-            order_size: default_order_size(),
-            order_type: default_order_type(),
-            balance_margin: default_balance_margin(),
-            dry_run: true,
-            sim_balance: default_sim_balance(),
-            cooldown_seconds: default_cooldown(),
-            polymarket_market_slug: None,
-            use_wss: false,
-            polymarket_ws_url: default_ws_url(),
-            polymarket_clob_url: default_clob_url(),
-            port: default_port(),
-            rust_log: default_log_level(),
-            verbose: false,
-            http_timeout_ms: default_http_timeout_ms(),
-            http_pool_size: default_http_pool_size(),
-            order_timeout_ms: default_order_timeout_ms(),
-            order_poll_interval_ms: default_order_poll_interval_ms(),
-            ws_reconnect_max_delay_s: default_ws_reconnect_max_delay_s(),
-            ws_heartbeat_interval_s: default_ws_heartbeat_interval_s(),
-            metrics_enabled: true,
-            metrics_port: default_metrics_port(),
+            ..config
         };
+        assert!(eoa.validate().is_ok());
+    }
 
-        assert!(config.validate().is_err());
+    #[test]
+    fn validate_rejects_calc_decimals_below_tick_precision() {
+        for calc_decimals in [0, 1] {
+            let config = Config {
+                calc_decimals,
+                ..Config::test_default()
+            };
+            assert_eq!(
+                config.validate().unwrap_err(),
+                "CALC_DECIMALS must be between 2 (the TICK_SIZE decimals) and 28"
+            );
+        }
+
+        let config = Config {
+            calc_decimals: 2,
+            ..Config::test_default()
+        };
+        assert!(config.validate().is_ok());
+    }
+
+    #[test]
+    fn usdc_order_size_converts_at_pair_cost() {
+        // $10 at a $0.99 pair is 10.1010... shares, rounded down to the size tick
+        assert_eq!(OrderSizeUnit::Usdc.shares(dec!(10), dec!(0.99), 2), Some(dec!(10.10)));
+        assert_eq!(OrderSizeUnit::Shares.shares(dec!(10), dec!(0.99), 2), Some(dec!(10)));
+        // Below the exchange minimum the trade is skipped, not raised over budget
+        assert_eq!(OrderSizeUnit::Usdc.shares(dec!(4.5), dec!(0.99), 2), None);
+
+        let config: Config = envy::from_iter([
+            ("POLYMARKET_PRIVATE_KEY".to_string(), "0xabc".to_string()),
+            ("ORDER_SIZE_UNIT".to_string(), "usdc".to_string()),
+        ])
+        .unwrap();
+        assert_eq!(config.order_size_unit, OrderSizeUnit::Usdc);
+        assert_eq!(config.order_size_unit.describe(dec!(100)), "$100 USDC per pair");
+        assert_eq!(OrderSizeUnit::Shares.describe(dec!(10)), "10 shares");
     }
 }
@@ -165,10 +165,31 @@ pub enum TradingError {
         reason: String,
     },
 
+    /// Order not (yet) known to the exchange.
+    #[error("order {0} not found")]
+    OrderNotFound(String),
+
     /// Invalid order parameters.
     #[error("invalid order parameters: {0}")]
     InvalidParams(String),
 
+    /// Live-only operation requested while in dry-run.
+    #[error("refusing to {0} in dry-run mode; set DRY_RUN=false to act on the live account")]
+    DryRun(String),
+
+    /// Redeeming resolved positions failed.
+    #[error("redemption failed: {0}")]
+    RedemptionFailed(String),
+
+    /// The market or wallet cannot be redeemed through the CTF contract, so
+    /// retrying will not help.
+    #[error("redemption not possible: {0}")]
+    RedemptionUnsupported(String),
+
+    /// Identical order already submitted within the dedup window.
+    #[error("duplicate order rejected: {0}")]
+    DuplicateOrder(String),
+
     /// Signing error.
     #[error("signing error: {0}")]
     SigningError(String),
@@ -191,13 +212,27 @@ pub enum TradingError {
         reason: String,
     },
 
-    /// Insufficient funds for the order.
-    #[error("insufficient funds: need {required}, have {available}")]
+    /// Post-only order rejected because it would have crossed the book.
+    ///
+    /// The order never rested, so it is a non-fill rather than a failure.
+    #[error("post-only order would cross: {reason}")]
+    PostOnlyCrossed {
+        /// Rejection reason from the exchange.
+        reason: String,
+    },
+
+    /// Exchange rejected the order because the book moved past its limit price.
+    #[error("price moved: {reason}")]
+    PriceMoved {
+        /// Rejection reason from the exchange.
+        reason: String,
+    },
+
+    /// Exchange rejected the order for lack of balance or allowance.
+    #[error("insufficient balance or allowance: {reason}")]
     InsufficientFunds {
-        /// Required amount.
-        required: rust_decimal::Decimal,
-        /// Available amount.
-        available: rust_decimal::Decimal,
+        /// Rejection reason from the exchange.
+        reason: String,
     },
 }
 
@@ -225,6 +260,10 @@ pub enum WsError {
     #[error("failed to send websocket message: {0}")]
     SendFailed(String),
 
+    /// Authentication on a private channel failed.
+    #[error("websocket authentication failed: {0}")]
+    AuthFailed(String),
+
     /// Tungstenite error.
     #[error("tungstenite error: {0}")]
     Tungstenite(#[from] tokio_tungstenite::tungstenite::Error),
@@ -1,24 +1,45 @@
 //! BTC 15-minute Polymarket arbitrage bot entry point.
 
 use std::net::SocketAddr;
+use std::sync::atomic::AtomicU64;
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 
 use clap::{Parser, Subcommand};
 use futures::StreamExt;
 use tokio::net::TcpListener;
-use tracing::{error, info, warn};
-use tracing_subscriber::{fmt, prelude::*, EnvFilter};
+use tokio::sync::{mpsc, oneshot};
+use tokio::task::JoinHandle;
+use tracing::{debug, error, info, warn};
+use tracing_subscriber::EnvFilter;
 
 use polymarket_arb::api::{create_router, AppState};
-use polymarket_arb::arbitrage::{check_arbitrage, ArbitrageExecutor};
-use polymarket_arb::config::Config;
-use polymarket_arb::market::{discover_active_market, PolymarketClient};
+use polymarket_arb::arbitrage::benchmark::{detect_to_submit_latency, paired_auth_header_latency};
+use polymarket_arb::arbitrage::{
+    check_arbitrage, format_top_of_book, load_summaries, prescreen, run_sim_loop,
+    summarize_history, ArbitrageExecutor, ExposureGuard, InversionTracker, PendingSettlement,
+    SimLoopOptions,
+};
+use polymarket_arb::config::{Config, DiscoveryConfig, DryRunMode, DEFAULT_HTTP_USER_AGENT};
+use polymarket_arb::error::{MarketError, TradingError};
+use polymarket_arb::market::{
+    clock_skew_seconds, default_headers, discover_active_market, Market, MarketStatus,
+    PolymarketClient, SlugFilter,
+};
 use polymarket_arb::metrics;
-use polymarket_arb::orderbook::types::OutcomeBook;
-use polymarket_arb::orderbook::websocket::{MarketWebSocket, ReconnectConfig};
+use polymarket_arb::orderbook::types::{BookUpdate, OutcomeBook};
+use polymarket_arb::orderbook::MidPriceMonitor;
+use polymarket_arb::orderbook::websocket::{MarketWebSocket, ReconnectConfig, WsCredentials};
 use polymarket_arb::signing::address_from_private_key;
-use polymarket_arb::utils::shutdown_signal;
+use polymarket_arb::trading::execution::{
+    cancel_all_command, cancel_all_orders, redeem_after_close,
+};
+use polymarket_arb::trading::{redeem_positions, Redemption};
+use polymarket_arb::trading::UserWebSocket;
+use polymarket_arb::utils::{
+    cancel_all_on_shutdown, init_logging, spawn_market_tasks, LogFormat,
+    MarketLoop, Shutdown, SHUTDOWN_CANCEL_TIMEOUT, SHUTDOWN_DRAIN_TIMEOUT,
+};
 
 /// BTC 15-minute Polymarket arbitrage bot.
 #[derive(Parser, Debug)]
@@ -30,6 +51,10 @@ struct Args {
     #[arg(short, long, global = true)]
     verbose: bool,
 
+    /// Log output format (overrides LOG_FORMAT).
+    #[arg(long, global = true, value_enum)]
+    log_format: Option<LogFormat>,
+
     #[command(subcommand)]
     command: Option<Command>,
 
@@ -37,6 +62,10 @@ struct Args {
     #[arg(long)]
     dry_run: Option<bool>,
 
+    /// What a dry run exercises (full, sign, balance-only).
+    #[arg(long, value_enum)]
+    dry_run_mode: Option<DryRunMode>,
+
     /// HTTP server port for health/metrics.
     #[arg(short, long, default_value = "8080")]
     port: u16,
@@ -50,6 +79,10 @@ enum Command {
         #[arg(long)]
         dry_run: Option<bool>,
 
+        /// What a dry run exercises (full, sign, balance-only).
+        #[arg(long, value_enum)]
+        dry_run_mode: Option<DryRunMode>,
+
         /// HTTP server port for health/metrics.
         #[arg(short, long, default_value = "8080")]
         port: u16,
@@ -57,6 +90,19 @@ enum Command {
         /// Use WebSocket for market data (lower latency).
         #[arg(long)]
         websocket: bool,
+
+        /// Trade a single market window, then exit.
+        #[arg(long)]
+        once: bool,
+
+        /// Log the top 3 book levels for UP and DOWN every SCANS scans (default 100).
+        #[arg(long, value_name = "SCANS", num_args = 0..=1, default_missing_value = "100")]
+        dump_books: Option<u64>,
+
+        /// On the first Ctrl+C/SIGTERM, stop new entries and exit once the current
+        /// market closes; a second signal shuts down immediately.
+        #[arg(long)]
+        soft_stop: bool,
     },
 
     /// Check configuration validity.
@@ -68,11 +114,32 @@ enum Command {
     /// Discover the current active BTC 15min market.
     DiscoverMarket,
 
+    /// Cancel all open orders for the account (live mode only).
+    CancelAll,
+
     /// Test WebSocket connection (diagnostic).
     WsTest,
 
     /// Run latency benchmark.
     Benchmark,
+
+    /// Run the scan loop against a mock exchange with injected latency (always dry-run).
+    SimLoop {
+        /// Delay added to every mock book fetch and order submission.
+        #[arg(long, default_value = "0")]
+        simulate_latency_ms: u64,
+
+        /// Scans to run, each on a fresh synthetic market window.
+        #[arg(long, default_value = "20")]
+        scans: usize,
+    },
+
+    /// Print all-time totals from the daily summary files.
+    Stats {
+        /// Summary directory to read (defaults to SUMMARY_DIR).
+        #[arg(long)]
+        dir: Option<String>,
+    },
 }
 
 #[tokio::main]
@@ -88,10 +155,11 @@ async fn main() -> anyhow::Result<()> {
             .unwrap_or_else(|_| EnvFilter::new("info"))
     };
 
-    tracing_subscriber::registry()
-        .with(fmt::layer())
-        .with(filter)
-        .init();
+    // The flag wins; otherwise LOG_FORMAT from the environment or .env
+    let log_format = args
+        .log_format
+        .unwrap_or_else(|| Config::load().map(|config| config.log_format).unwrap_or_default());
+    init_logging(log_format, filter);
 
     // Initialize metrics
     metrics::init_metrics();
@@ -101,16 +169,27 @@ async fn main() -> anyhow::Result<()> {
         Some(Command::CheckConfig) => cmd_check_config().await,
         Some(Command::CheckBalance) => cmd_check_balance().await,
         Some(Command::DiscoverMarket) => cmd_discover_market().await,
-        Some(Command::Run { dry_run, port, websocket }) => {
-            if websocket {
-                cmd_run_websocket(dry_run, port).await
-            } else {
-                cmd_run(dry_run, port).await
-            }
+        Some(Command::CancelAll) => cmd_cancel_all().await,
+        Some(Command::Run {
+            dry_run,
+            dry_run_mode,
+            port,
+            websocket,
+            once,
+            dump_books,
+            soft_stop,
+        }) => {
+            cmd_run(dry_run, dry_run_mode, port, websocket, once, dump_books, soft_stop).await
         }
         Some(Command::WsTest) => cmd_ws_test().await,
         Some(Command::Benchmark) => cmd_benchmark().await,
-        None => cmd_run(args.dry_run, args.port).await,
+        Some(Command::SimLoop { simulate_latency_ms, scans }) => {
+            cmd_sim_loop(simulate_latency_ms, scans).await
+        }
+        Some(Command::Stats { dir }) => cmd_stats(dir),
+        None => {
+            cmd_run(args.dry_run, args.dry_run_mode, args.port, false, false, None, false).await
+        }
     }
 }
 
@@ -159,6 +238,24 @@ async fn cmd_check_config() -> anyhow::Result<()> {
         }
     }
 
+    // Check clock skew against the CLOB server
+    print!("Checking clock skew... ");
+    let client = PolymarketClient::new(&config);
+    match measure_clock_skew(&client).await {
+        Ok(skew) if skew.unsigned_abs() > config.max_clock_skew_seconds => {
+            println!("WARNING");
+            println!(
+                "  Local clock is {}s off server time (max {}s); orders may be rejected",
+                skew, config.max_clock_skew_seconds
+            );
+        }
+        Ok(skew) => println!("OK ({}s)", skew),
+        Err(e) => {
+            println!("SKIPPED");
+            println!("  Error: {}", e);
+        }
+    }
+
     // Show configuration summary
     println!("----------------------------------------------------------------------");
     println!("Configuration Summary:");
@@ -178,10 +275,21 @@ async fn cmd_check_config() -> anyhow::Result<()> {
     }
 
     println!("  Target Pair Cost: ${}", config.target_pair_cost);
-    println!("  Order Size: {} shares", config.order_size);
+    println!("  Fee Rate: {} bps", config.fee_rate_bps);
+    println!("  Required Edge: {} bps (net of fees)", config.required_edge_bps());
+    println!("  Order Size: {}", config.order_size_unit.describe(config.order_size));
     println!("  Order Type: {}", config.order_type);
+    for warning in config.warnings() {
+        println!("  WARNING: {}", warning);
+    }
     println!("  Dry Run: {}", config.dry_run);
     println!("  Cooldown: {}s", config.cooldown_seconds);
+    println!("  Assumed UP Probability: {}", config.assumed_up_probability);
+    if config.scan_interval_ms == 0 {
+        println!("  Scan Interval: continuous");
+    } else {
+        println!("  Scan Interval: {}ms", config.scan_interval_ms);
+    }
     println!("  WebSocket: {}", if config.use_wss { "Enabled" } else { "Disabled" });
     println!("======================================================================");
     println!("CONFIGURATION CHECK PASSED");
@@ -262,19 +370,52 @@ async fn cmd_check_balance() -> anyhow::Result<()> {
     Ok(())
 }
 
+/// Cancel all open orders, e.g. to clean up after a crash.
+async fn cmd_cancel_all() -> anyhow::Result<()> {
+    println!("======================================================================");
+    println!("BTC 15M ARB BOT - CANCEL ALL ORDERS");
+    println!("======================================================================");
+
+    let config = Config::load()?;
+    config.validate().map_err(|e| anyhow::anyhow!(e))?;
+    let client = PolymarketClient::new(&config);
+
+    print!("\nCancelling all open orders... ");
+    match cancel_all_command(config.dry_run, || cancel_all_orders(&client)).await {
+        Ok(count) => {
+            println!("OK");
+            println!("   Orders cancelled: {}", count);
+        }
+        Err(e) => {
+            println!("FAILED");
+            println!("   Error: {}", e);
+            return Err(anyhow::anyhow!("Cancel all failed"));
+        }
+    }
+
+    println!("\n======================================================================");
+    Ok(())
+}
+
 /// Discover the current active BTC 15min market.
 async fn cmd_discover_market() -> anyhow::Result<()> {
     println!("======================================================================");
     println!("BTC 15M ARB BOT - MARKET DISCOVERY");
     println!("======================================================================");
 
+    // Runs without a config, so the default User-Agent applies
     let http_client = reqwest::Client::builder()
         .timeout(Duration::from_secs(30))
+        .user_agent(DEFAULT_HTTP_USER_AGENT)
         .build()?;
 
+    // Honour WINDOW_OFFSET_SECONDS when set, since this is where a misaligned clock shows up
+    let discovery = DiscoveryConfig::load()?;
+    discovery.validate().map_err(|e| anyhow::anyhow!(e))?;
+
     println!("\nSearching for active BTC 15min market...\n");
 
-    match discover_active_market(&http_client).await {
+    match discover_active_market(&http_client, discovery.window_offset_seconds).await {
         Ok(market) => {
             println!("MARKET FOUND");
             println!("----------------------------------------------------------------------");
@@ -300,7 +441,15 @@ async fn cmd_discover_market() -> anyhow::Result<()> {
 }
 
 /// Run the main arbitrage bot loop.
-async fn cmd_run(dry_run_override: Option<bool>, port: u16) -> anyhow::Result<()> {
+async fn cmd_run(
+    dry_run_override: Option<bool>,
+    dry_run_mode_override: Option<DryRunMode>,
+    port: u16,
+    websocket: bool,
+    once: bool,
+    dump_books: Option<u64>,
+    soft_stop: bool,
+) -> anyhow::Result<()> {
     // Load configuration
     info!("Loading configuration...");
     let mut config = Config::load().map_err(|e| {
@@ -312,6 +461,9 @@ async fn cmd_run(dry_run_override: Option<bool>, port: u16) -> anyhow::Result<()
     if let Some(dry_run) = dry_run_override {
         config.dry_run = dry_run;
     }
+    if let Some(mode) = dry_run_mode_override {
+        config.dry_run_mode = mode;
+    }
 
     // Validate configuration
     if let Err(e) = config.validate() {
@@ -320,13 +472,24 @@ async fn cmd_run(dry_run_override: Option<bool>, port: u16) -> anyhow::Result<()
     }
 
     info!("Configuration loaded successfully");
-    info!("Mode: {}", if config.dry_run { "SIMULATION" } else { "LIVE TRADING" });
+    info!(
+        "Mode: {}{}",
+        if config.dry_run { "SIMULATION" } else { "LIVE TRADING" },
+        if websocket { " (WebSocket-driven)" } else { "" }
+    );
     info!("Target pair cost: ${}", config.target_pair_cost);
-    info!("Order size: {} shares", config.order_size);
+    info!("Order size: {}", config.order_size_unit.describe(config.order_size));
+    if config.dry_run {
+        info!("Dry-run mode: {:?}", config.dry_run_mode);
+    }
+    if let (true, Some(seed)) = (config.dry_run, config.sim_seed) {
+        info!("Simulation seed: {}", seed);
+    }
     info!("Order type: {}", config.order_type);
 
-    // Create app state
-    let app_state = AppState::new();
+    // Create app state; the API may change the trading thresholds from here on
+    let app_state = AppState::new().with_params_token(config.params_api_token.clone());
+    *app_state.params.write().await = config.runtime_params();
 
     // Start HTTP server
     let addr = SocketAddr::from(([0, 0, 0, 0], port));
@@ -335,48 +498,237 @@ async fn cmd_run(dry_run_override: Option<bool>, port: u16) -> anyhow::Result<()
 
     let router = create_router(app_state.clone());
 
-    // Spawn HTTP server
-    let _server_handle = tokio::spawn(async move {
+    // Signals stop the loop, but only once any in-flight execution has finished
+    let shutdown = Shutdown::new();
+    shutdown.listen_for_signals(soft_stop);
+
+    // Spawn HTTP server; it stops on shutdown or when the bot loop ends
+    let (server_stop, server_stopped) = oneshot::channel::<()>();
+    let server_shutdown = shutdown.clone();
+    let server_handle = tokio::spawn(async move {
         axum::serve(listener, router)
-            .with_graceful_shutdown(shutdown_signal())
+            .with_graceful_shutdown(async move {
+                tokio::select! {
+                    _ = server_shutdown.requested() => {}
+                    _ = server_stopped => {}
+                }
+            })
             .await
     });
 
-    // Create Polymarket client
+    // Create Polymarket client; clones share its balance cache and rate limiter
     let client = PolymarketClient::new(&config);
-    let http_client = client.http().clone();
+    let slug_filter = SlugFilter::new(&config.slug_allowlist, &config.slug_denylist)?;
 
-    // Create executor
-    let mut executor = ArbitrageExecutor::new(&config);
+    // Order timestamps are rejected by the CLOB if the local clock drifts
+    check_clock_skew(&client, &config, &app_state).await;
 
-    // Main bot loop
-    info!("Starting arbitrage bot...");
+    // Each market task gets its own executor, capped by one shared exposure total
+    // and numbering executions from one counter so logs and journals never repeat an ID
+    let user_ws = start_user_ws(&config);
+    let exposure = ExposureGuard::new();
+    let exec_ids = Arc::new(AtomicU64::new(0));
 
-    loop {
-        // Discover active market
-        info!("Searching for active BTC 15min market...");
+    // Main bot loop, one task per configured market
+    if websocket {
+        info!("Starting WebSocket-driven arbitrage bot...");
+    } else {
+        info!("Starting arbitrage bot...");
+    }
 
-        let market = match discover_active_market(&http_client).await {
-            Ok(m) => {
-                info!("Found market: {}", m.slug);
-                info!("Time remaining: {}", m.time_remaining_str());
+    let mut tasks = spawn_market_tasks(config.market_specs(), |spec| {
+        let market_config = config.for_market(&spec);
+        let mut executor = ArbitrageExecutor::new(&market_config)
+            .with_exposure_guard(exposure.clone())
+            .with_exec_ids(exec_ids.clone());
+        if let Some(user_ws) = &user_ws {
+            executor = executor.with_user_ws(user_ws.clone());
+        }
+        MarketTask {
+            config: market_config,
+            client: client.clone(),
+            executor,
+            app_state: app_state.clone(),
+            shutdown: shutdown.clone(),
+            slug_filter: slug_filter.clone(),
+            feed: BookFeed::new(websocket),
+            once,
+            dump_books,
+            redeeming: None,
+        }
+        .run()
+    });
+    let bot_loop = async move {
+        while let Some(result) = tasks.join_next().await {
+            if let Err(e) = result {
+                error!("Market task failed: {}", e);
+            }
+        }
+    };
+
+    // Run until a shutdown signal, letting an in-flight execution (and its unwind) finish
+    tokio::select! {
+        _ = bot_loop => {}
+        _ = shutdown.drained(SHUTDOWN_DRAIN_TIMEOUT) => {}
+    }
+
+    // Best-effort cancel of resting orders so nothing is left unmanaged
+    cancel_all_on_shutdown(config.dry_run, SHUTDOWN_CANCEL_TIMEOUT, || {
+        cancel_all_orders(&client)
+    })
+    .await;
+
+    // Let the HTTP server finish in-flight requests before exiting
+    let _ = server_stop.send(());
+    let _ = server_handle.await;
+
+    Ok(())
+}
 
-                // Update app state
-                *app_state.market_slug.write().await = Some(m.slug.clone());
-                app_state.set_ready(true);
+/// One configured market stream: discovers each window and trades it with its own executor.
+struct MarketTask {
+    /// Config with this task's window offset and state file.
+    config: Config,
+    /// CLOB client, shared with the other tasks.
+    client: PolymarketClient,
+    /// Executor for this task's markets.
+    executor: ArbitrageExecutor,
+    /// State reported by the HTTP API.
+    app_state: AppState,
+    /// Shutdown handle shared with the other tasks.
+    shutdown: Shutdown,
+    /// Slug allow/deny rules.
+    slug_filter: SlugFilter,
+    /// Source of this task's order books.
+    feed: BookFeed,
+    /// Stop after one market.
+    once: bool,
+    /// Scans between book dumps, if enabled.
+    dump_books: Option<u64>,
+    /// Redemptions running off the scan loop, if any.
+    redeeming: Option<RedemptionBatch>,
+}
+
+/// Redemptions running in their own task, so a slow receipt never stalls a scan.
+struct RedemptionBatch {
+    /// Markets being redeemed, in the order of the task's results.
+    markets: Vec<PendingSettlement>,
+    /// Task redeeming them one after another.
+    task: JoinHandle<Vec<Result<Option<Redemption>, TradingError>>>,
+}
 
-                m
+impl RedemptionBatch {
+    /// Start redeeming `markets` in the background.
+    fn spawn(config: &Config, markets: Vec<PendingSettlement>) -> Self {
+        let config = config.clone();
+        let to_redeem: Vec<Market> = markets.iter().map(|p| p.market.clone()).collect();
+        let task = tokio::spawn(async move {
+            let mut results = Vec::with_capacity(to_redeem.len());
+            for market in &to_redeem {
+                let redeem = || redeem_positions(&config, market);
+                results.push(redeem_after_close(config.dry_run, config.auto_redeem, redeem).await);
             }
+            results
+        });
+        Self { markets, task }
+    }
+
+    /// Wait for the task and record each outcome in the executor.
+    ///
+    /// A task that panicked counts as a failed attempt for every market, so
+    /// they are retried. Returns whether any payout was received.
+    async fn finish(self, executor: &mut ArbitrageExecutor) -> bool {
+        let results = match self.task.await {
+            Ok(results) => results,
             Err(e) => {
-                warn!("No active market found: {}. Retrying in 30s...", e);
-                app_state.set_ready(false);
-                tokio::time::sleep(Duration::from_secs(30)).await;
-                continue;
+                let failed = || Err(TradingError::RedemptionFailed(e.to_string()));
+                self.markets.iter().map(|_| failed()).collect()
             }
         };
+        let mut paid = false;
+        for (pending, result) in self.markets.into_iter().zip(results) {
+            paid |= executor.finish_redemption(pending, result);
+        }
+        paid
+    }
+}
+
+impl MarketTask {
+    /// Discover and trade markets until the loop stops.
+    async fn run(mut self) {
+        let http_client = self.client.http().clone();
+        let mut market_loop = MarketLoop::new(self.once);
+
+        while !stopped_before_next_market(&self.shutdown) {
+            // Discover active market
+            info!("Searching for active BTC 15min market...");
+
+            let discovered =
+                discover_active_market(&http_client, self.config.window_offset_seconds).await;
+            let market = match discovered {
+                Ok(m) => {
+                    info!("Found market: {}", m.slug);
+                    info!("Time remaining: {}", m.time_remaining_str());
+
+                    if let Some(reason) = self.slug_filter.skip_reason(&m.slug) {
+                        skip_market(&m, &reason).await;
+                        continue;
+                    }
+                    // Overlapping specs can discover the same window
+                    if !self.app_state.claim_market(&m.slug).await {
+                        skip_market(&m, "already traded by another market task").await;
+                        continue;
+                    }
+
+                    // Open a pooled connection before the first book fetch
+                    match self.client.warmup().await {
+                        Ok(latency) => info!(
+                            latency_ms = latency.as_millis() as u64,
+                            "Connection pool warmed up"
+                        ),
+                        Err(e) => warn!(error = %e, "Connection warmup failed"),
+                    }
+
+                    m
+                }
+                Err(e) => {
+                    warn!("No active market found: {}. Retrying in 30s...", e);
+                    tokio::time::sleep(Duration::from_secs(30)).await;
+                    continue;
+                }
+            };
+
+            self.run_market(&market).await;
+
+            if !market_loop.finish_market() || stopped_before_next_market(&self.shutdown) {
+                break;
+            }
+
+            // Brief pause before searching for next market
+            info!("Searching for next market in {}s...", NEXT_MARKET_PAUSE.as_secs());
+            tokio::time::sleep(NEXT_MARKET_PAUSE).await;
+        }
+        // Let an in-flight redemption land so its outcome is recorded
+        if let Some(batch) = self.redeeming.take() {
+            batch.finish(&mut self.executor).await;
+        }
+        warn_unsettled(&self.executor);
+    }
+
+    /// Scan and trade one market until it closes, then record and log its result.
+    async fn run_market(&mut self, market: &Market) {
+        let (config, client) = (&self.config, &self.client);
+        let (app_state, shutdown) = (&self.app_state, &self.shutdown);
+        let (feed, executor) = (&mut self.feed, &mut self.executor);
+        let redeeming = &mut self.redeeming;
+        let dump_books = self.dump_books;
 
         info!("========================================");
-        info!("BTC 15MIN ARBITRAGE BOT STARTED");
+        if matches!(feed, BookFeed::WebSocket(_)) {
+            info!("WEBSOCKET-DRIVEN ARBITRAGE BOT STARTED");
+        } else {
+            info!("BTC 15MIN ARBITRAGE BOT STARTED");
+        }
         info!("========================================");
         info!("Market: {}", market.slug);
         info!("UP Token: {}", market.up_token_id);
@@ -384,78 +736,119 @@ async fn cmd_run(dry_run_override: Option<bool>, port: u16) -> anyhow::Result<()
         info!("Mode: {}", if config.dry_run { "SIMULATION" } else { "LIVE TRADING" });
         info!("========================================");
 
+        feed.subscribe(market, config).await;
+
         // Market monitoring loop
         let mut scan_count = 0u64;
+        let stats_before = executor.stats();
+        let mut status_check = MarketStatusCheck::default();
+        let mut inversions = InversionTracker::new(config.inverted_book_tolerance_scans);
+        let mut frozen_books =
+            MidPriceMonitor::new(Duration::from_secs(config.frozen_book_seconds));
+        let mut scan_config = config.clone();
+
+        while !status_check.is_stopped(client, market).await {
+            feed.publish_status(app_state).await;
+
+            // Earlier markets resolve while this one trades
+            let slug = &market.slug;
+            settle_closed_markets(client, executor, config, app_state, slug, redeeming).await;
+
+            // Thresholds set through the API take effect from the next scan
+            app_state.params.read().await.apply(&mut scan_config);
+            let config = &scan_config;
+
+            // Cheap quotes clearly above target: skip the full book fetch
+            let polling = matches!(feed, BookFeed::Polling);
+            if polling && prescreen_rules_out(client, market, config).await {
+                // Mids go unobserved while skipping, so don't judge them stale later
+                frozen_books.clear();
+                app_state.set_book_frozen(&market.slug, false).await;
+                feed.pause(config).await;
+                continue;
+            }
 
-        while !market.is_closed() {
-            scan_count += 1;
-
-            // Fetch order books concurrently
-            let (up_result, down_result) = tokio::join!(
-                client.get_order_book(&market.up_token_id),
-                client.get_order_book(&market.down_token_id),
-            );
-
-            let up_book = match up_result {
-                Ok(book) => book,
-                Err(e) => {
-                    warn!("Failed to fetch UP order book: {}", e);
-                    tokio::time::sleep(Duration::from_secs(1)).await;
-                    continue;
-                }
+            let books = feed.next_books(client, market, &mut status_check).await;
+            let Some((up_book, down_book)) = books else {
+                continue;
             };
+            scan_count += 1;
+            let detection_start = Instant::now();
 
-            let down_book = match down_result {
-                Ok(book) => book,
-                Err(e) => {
-                    warn!("Failed to fetch DOWN order book: {}", e);
-                    tokio::time::sleep(Duration::from_secs(1)).await;
-                    continue;
-                }
-            };
+            // Don't enter against a book that has stopped moving
+            let frozen = frozen_books.observe_pair(&up_book, &down_book, detection_start);
+            app_state.set_book_frozen(&market.slug, frozen).await;
+            if frozen {
+                feed.pause(config).await;
+                continue;
+            }
 
             // Check for arbitrage opportunity
-            match check_arbitrage(&market, &up_book, &down_book, &config) {
+            match inversions.filter(check_arbitrage(market, &up_book, &down_book, config)) {
                 Ok(Some(opportunity)) => {
-                    // Execute arbitrage
-                    match executor.execute(&client, &opportunity, &config).await {
-                        Ok(result) => {
-                            info!("Execution result: {:?}", result);
-
-                            // Update stats in app state
-                            let stats = executor.stats();
-                            *app_state.stats.write().await = stats;
+                    metrics::record_opportunity_detection_latency(detection_start);
+                    metrics::inc_opportunities_detected();
+
+                    // Execute arbitrage; no new execution once a soft stop or shutdown is
+                    // requested, but keep scanning at the usual interval until the market closes
+                    if let Some(_execution) = shutdown.begin_execution().await {
+                        if app_state.take_resume_request() {
+                            executor.resume();
                         }
-                        Err(e) => {
-                            error!("Execution failed: {}", e);
+                        match executor.execute(client, &opportunity, config).await {
+                            Ok(result) => {
+                                info!("Execution result: {}", result);
+                                metrics::inc_opportunities_executed();
+
+                                // Update stats in app state
+                                app_state.update_market_stats(&market.slug, executor.stats()).await;
+                            }
+                            Err(e) => {
+                                error!("Execution failed: {}", e);
+                            }
                         }
+                        executor.update_gauges(client);
+                    } else {
+                        debug!("Stop requested, not entering opportunity");
                     }
                 }
                 Ok(None) => {
-                    // No opportunity
-                    let diagnosis = polymarket_arb::arbitrage::diagnose_no_opportunity(
-                        &up_book,
-                        &down_book,
-                        config.order_size,
-                        config.target_pair_cost,
-                    );
-                    info!(
-                        "[Scan #{}] No arbitrage: {} [Time: {}]",
-                        scan_count,
-                        diagnosis,
-                        market.time_remaining_str()
-                    );
+                    // No opportunity; WebSocket updates are too frequent to log each one
+                    if polling {
+                        let diagnosis = polymarket_arb::arbitrage::diagnose_no_opportunity(
+                            market, &up_book, &down_book, config,
+                        );
+                        info!(
+                            "[Scan #{}] No arbitrage: {} [Time: {}]",
+                            scan_count,
+                            diagnosis,
+                            market.time_remaining_str()
+                        );
+                    }
+                    log_book_dump(dump_books, scan_count, &up_book, &down_book);
                 }
                 Err(e) => {
                     warn!("Arbitrage check error: {}", e);
                 }
             }
 
-            // Brief pause between scans (configurable, 0 for continuous)
-            tokio::time::sleep(Duration::from_millis(100)).await;
+            feed.pause(config).await;
         }
 
-        // Market closed
+        // Market closed with its expected profit; the realized one replaces it once it resolves
+        if !config.dry_run {
+            let market_invested = executor.stats().total_invested - stats_before.total_invested;
+            executor.queue_settlement(market, market_invested);
+        }
+        let market_result = executor.record_market_result(&market.slug, &stats_before);
+        if !config.dry_run {
+            let slug = &market.slug;
+            settle_closed_markets(client, executor, config, app_state, slug, redeeming).await;
+            app_state.update_market_stats(&market.slug, executor.stats()).await;
+        }
+        app_state.merge_history(&executor.history(), config.history_limit).await;
+        executor.update_gauges(client);
+
         info!("========================================");
         info!("MARKET CLOSED - FINAL SUMMARY");
         info!("========================================");
@@ -470,7 +863,16 @@ async fn cmd_run(dry_run_override: Option<bool>, port: u16) -> anyhow::Result<()
         info!("----------------------------------------");
         info!("Total invested: ${}", stats.total_invested);
         info!("Expected payout: ${}", stats.total_shares_bought / rust_decimal::Decimal::TWO);
-        info!("Expected profit: ${}", stats.expected_profit());
+        info!("Fees paid: ${}", stats.total_fees);
+        info!("Redemption gas: {} POL (${})", stats.total_gas, stats.gas_usdc);
+        info!("Expected profit (net): ${}", stats.expected_profit());
+        if let Some(realized) = stats.realized_profit {
+            info!("Realized profit: ${}", realized);
+        }
+        info!(
+            "This market: {} trades, ${} invested, ${} profit",
+            market_result.trades, market_result.invested, market_result.profit
+        );
 
         if config.dry_run {
             info!("----------------------------------------");
@@ -480,207 +882,389 @@ async fn cmd_run(dry_run_override: Option<bool>, port: u16) -> anyhow::Result<()
         }
 
         info!("========================================");
+        app_state.release_market(&market.slug).await;
+    }
+}
+
+/// Record realized profit for closed markets that resolved, then redeem their
+/// shares in the background, retrying failed redemptions with backoff.
+///
+/// A finished redemption batch is recorded here; the next one starts only once
+/// it has, so at most one is in flight.
+async fn settle_closed_markets(
+    client: &PolymarketClient,
+    executor: &mut ArbitrageExecutor,
+    config: &Config,
+    app_state: &AppState,
+    current_slug: &str,
+    redeeming: &mut Option<RedemptionBatch>,
+) {
+    let settled = executor.settle_pending(client).await;
+    for market in &settled {
+        executor.queue_redemption(market);
+    }
 
-        // Brief pause before searching for next market
-        info!("Searching for next market in 10s...");
-        tokio::time::sleep(Duration::from_secs(10)).await;
+    let finished = redeeming.as_ref().is_some_and(|batch| batch.task.is_finished());
+    if finished {
+        let batch = redeeming.take().expect("checked above");
+        if batch.finish(executor).await {
+            // Payout lands in the wallet, so the cached balance is now low
+            client.invalidate_balance_cache();
+        }
+    }
+
+    let mut started = false;
+    if redeeming.is_none() {
+        let due = executor.take_due_redemptions(Instant::now());
+        if !due.is_empty() {
+            *redeeming = Some(RedemptionBatch::spawn(config, due));
+            started = true;
+        }
+    }
+    if settled.is_empty() && !finished && !started {
+        return;
     }
+    app_state.update_market_stats(current_slug, executor.stats()).await;
+    app_state.merge_history(&executor.history(), config.history_limit).await;
 }
 
-/// Run the bot with WebSocket-driven execution (lower latency).
-async fn cmd_run_websocket(dry_run_override: Option<bool>, port: u16) -> anyhow::Result<()> {
-    // Load configuration
-    info!("Loading configuration...");
-    let mut config = Config::load().map_err(|e| {
-        error!("Failed to load configuration: {}", e);
-        e
-    })?;
+/// Warn about closed markets whose realized profit will not be recorded or
+/// whose shares were not redeemed.
+fn warn_unsettled(executor: &ArbitrageExecutor) {
+    let pending = executor.pending_settlements();
+    if pending > 0 {
+        warn!(pending, "Exiting before closed markets resolved, realized profit not recorded");
+    }
+    let unredeemed = executor.pending_redemptions();
+    if unredeemed > 0 {
+        warn!(unredeemed, "Exiting with settled markets not yet redeemed, redeem manually");
+    }
+}
 
-    // Override with CLI args if provided
-    if let Some(dry_run) = dry_run_override {
-        config.dry_run = dry_run;
+/// Whether a soft stop means no further market should be discovered.
+fn stopped_before_next_market(shutdown: &Shutdown) -> bool {
+    let stopped = shutdown.is_soft_stop_requested();
+    if stopped {
+        info!("Soft stop requested, exiting");
     }
+    stopped
+}
 
-    // Validate configuration
-    if let Err(e) = config.validate() {
-        error!("Invalid configuration: {}", e);
-        return Err(anyhow::anyhow!("Configuration validation failed: {}", e));
+/// Where a market task gets its order books.
+enum BookFeed {
+    /// REST snapshots, fetched once per scan.
+    Polling,
+    /// Market WebSocket updates, with REST snapshots while the feed is stale.
+    ///
+    /// The connection carries over to the next market unless
+    /// RECONNECT_ON_MARKET_ROTATION is set.
+    WebSocket(Option<(Arc<MarketWebSocket>, mpsc::Receiver<BookUpdate>)>),
+}
+
+impl BookFeed {
+    /// Create a feed for the `--websocket` flag, connecting on the first market.
+    fn new(websocket: bool) -> Self {
+        if websocket {
+            Self::WebSocket(None)
+        } else {
+            Self::Polling
+        }
     }
 
-    info!("Configuration loaded successfully");
-    info!("Mode: {} (WebSocket-driven)", if config.dry_run { "SIMULATION" } else { "LIVE TRADING" });
-    info!("Target pair cost: ${}", config.target_pair_cost);
-    info!("Order size: {} shares", config.order_size);
+    /// Point the feed at a new market's tokens.
+    async fn subscribe(&mut self, market: &Market, config: &Config) {
+        let Self::WebSocket(connection) = self else {
+            return;
+        };
+        let asset_ids = vec![market.up_token_id.clone(), market.down_token_id.clone()];
 
-    // Create app state
-    let app_state = AppState::new();
+        // Reuse the previous connection when configured, otherwise reconnect
+        *connection = Some(match connection.take() {
+            Some((ws, receiver)) if !config.reconnect_on_market_rotation => {
+                if !ws.rotate_subscription(asset_ids) {
+                    warn!("Subscription rotation not sent, will apply on reconnect");
+                }
+                (ws, receiver)
+            }
+            _ => connect_market_ws(config, asset_ids).await,
+        });
+        info!("WebSocket connected, waiting for book updates...");
+    }
 
-    // Start HTTP server
-    let addr = SocketAddr::from(([0, 0, 0, 0], port));
-    let listener = TcpListener::bind(addr).await?;
-    info!("HTTP server listening on {}", addr);
+    /// Publish the WebSocket connection state to the status endpoint.
+    async fn publish_status(&self, app_state: &AppState) {
+        if let Self::WebSocket(Some((ws, _))) = self {
+            *app_state.websocket.write().await = Some(ws.status());
+        }
+    }
 
-    let router = create_router(app_state.clone());
+    /// Wait for the next pair of books, or `None` if there is nothing to scan yet.
+    async fn next_books(
+        &mut self,
+        client: &PolymarketClient,
+        market: &Market,
+        status_check: &mut MarketStatusCheck,
+    ) -> Option<(OutcomeBook, OutcomeBook)> {
+        let Self::WebSocket(Some((ws, receiver))) = self else {
+            return fetch_books(client, market).await;
+        };
 
-    // Spawn HTTP server
-    let _server_handle = tokio::spawn(async move {
-        axum::serve(listener, router)
-            .with_graceful_shutdown(shutdown_signal())
-            .await
-    });
+        tokio::select! {
+            Some(_update) = receiver.recv() => ws_outcome_books(ws, market),
+            _ = tokio::time::sleep(Duration::from_secs(1)) => {
+                // Periodic check if market is still open
+                if status_check.is_stopped(client, market).await {
+                    return None;
+                }
 
-    // Create Polymarket client
-    let client = PolymarketClient::new(&config);
-    let http_client = client.http().clone();
+                // Keep trading on REST snapshots while the feed is stalled
+                ws.stale_fallback(|| async {
+                    let (up, down) = tokio::join!(
+                        client.get_order_book(&market.up_token_id),
+                        client.get_order_book(&market.down_token_id),
+                    );
+                    Ok((up?, down?))
+                })
+                .await
+            }
+        }
+    }
 
-    // Create executor
-    let mut executor = ArbitrageExecutor::new(&config);
+    /// Pause between polling scans; WebSocket updates are handled as they arrive.
+    async fn pause(&self, config: &Config) {
+        if let Self::Polling = self {
+            // Brief pause between scans (configurable, 0 for continuous)
+            tokio::time::sleep(config.scan_interval()).await;
+        }
+    }
+}
 
-    // Main bot loop
-    info!("Starting WebSocket-driven arbitrage bot...");
+/// Fetch both order books concurrently, pausing briefly if either fetch fails.
+async fn fetch_books(
+    client: &PolymarketClient,
+    market: &Market,
+) -> Option<(OutcomeBook, OutcomeBook)> {
+    let (up_result, down_result) = tokio::join!(
+        client.get_order_book(&market.up_token_id),
+        client.get_order_book(&market.down_token_id),
+    );
+
+    let up_book = match up_result {
+        Ok(book) => book,
+        Err(e) => {
+            warn!("Failed to fetch UP order book: {}", e);
+            tokio::time::sleep(Duration::from_secs(1)).await;
+            return None;
+        }
+    };
 
-    loop {
-        // Discover active market
-        info!("Searching for active BTC 15min market...");
+    let down_book = match down_result {
+        Ok(book) => book,
+        Err(e) => {
+            warn!("Failed to fetch DOWN order book: {}", e);
+            tokio::time::sleep(Duration::from_secs(1)).await;
+            return None;
+        }
+    };
 
-        let market = match discover_active_market(&http_client).await {
-            Ok(m) => {
-                info!("Found market: {}", m.slug);
-                info!("Time remaining: {}", m.time_remaining_str());
+    Some((up_book, down_book))
+}
 
-                // Update app state
-                *app_state.market_slug.write().await = Some(m.slug.clone());
-                app_state.set_ready(true);
+/// Convert the WebSocket book state for a market into UP/DOWN outcome books.
+fn ws_outcome_books(ws: &MarketWebSocket, market: &Market) -> Option<(OutcomeBook, OutcomeBook)> {
+    let up_state = ws.get_book(&market.up_token_id)?;
+    let down_state = ws.get_book(&market.down_token_id)?;
+
+    let (up_bids, up_asks) = up_state.to_levels();
+    let (down_bids, down_asks) = down_state.to_levels();
+
+    let up_outcome_book = OutcomeBook {
+        token_id: market.up_token_id.clone(),
+        outcome: polymarket_arb::market::Outcome::Up,
+        bids: up_bids,
+        asks: up_asks,
+        updated_at: time::OffsetDateTime::now_utc(),
+        as_of: None,
+    };
 
-                m
-            }
-            Err(e) => {
-                warn!("No active market found: {}. Retrying in 30s...", e);
-                app_state.set_ready(false);
-                tokio::time::sleep(Duration::from_secs(30)).await;
-                continue;
-            }
-        };
+    let down_outcome_book = OutcomeBook {
+        token_id: market.down_token_id.clone(),
+        outcome: polymarket_arb::market::Outcome::Down,
+        bids: down_bids,
+        asks: down_asks,
+        updated_at: time::OffsetDateTime::now_utc(),
+        as_of: None,
+    };
 
-        info!("========================================");
-        info!("WEBSOCKET-DRIVEN ARBITRAGE BOT STARTED");
-        info!("========================================");
-        info!("Market: {}", market.slug);
-        info!("UP Token: {}", market.up_token_id);
-        info!("DOWN Token: {}", market.down_token_id);
-        info!("Mode: {}", if config.dry_run { "SIMULATION" } else { "LIVE TRADING" });
-        info!("========================================");
+    Some((up_outcome_book, down_outcome_book))
+}
 
-        // Create WebSocket client with reconnection config
-        let reconnect_config = ReconnectConfig::from_config(
-            config.ws_reconnect_max_delay_s,
-            config.ws_heartbeat_interval_s,
+/// Log the top book levels every `dump_books` scans (disabled when unset).
+fn log_book_dump(dump_books: Option<u64>, scan_count: u64, up: &OutcomeBook, down: &OutcomeBook) {
+    if dump_books.is_some_and(|every| scan_count.is_multiple_of(every.max(1))) {
+        info!(
+            "[Scan #{}] Top of book:\n{}",
+            scan_count,
+            format_top_of_book(up, down, DUMP_BOOK_DEPTH)
         );
-        let ws = Arc::new(MarketWebSocket::with_reconnect_config(
-            config.polymarket_ws_url.clone(),
-            reconnect_config,
-        ));
+    }
+}
 
-        // Start WebSocket with auto-reconnect
-        let asset_ids = vec![
-            market.up_token_id.clone(),
-            market.down_token_id.clone(),
-        ];
+/// Whether the `/price` buy quotes rule out an opportunity, so the books can be skipped.
+///
+/// Disabled when `prescreen_margin` is zero; a failed quote falls through to the books.
+async fn prescreen_rules_out(client: &PolymarketClient, market: &Market, config: &Config) -> bool {
+    if config.prescreen_margin.is_zero() {
+        return false;
+    }
 
-        let mut ws_receiver = ws.clone().run_with_reconnect(asset_ids).await;
+    let (up, down) = tokio::join!(
+        client.get_buy_price(&market.up_token_id),
+        client.get_buy_price(&market.down_token_id),
+    );
+    match (up, down) {
+        (Ok(up), Ok(down)) => {
+            let worth_fetching =
+                prescreen(up, down, config.target_pair_cost, config.prescreen_margin);
+            if !worth_fetching {
+                debug!("Prescreen: UP {} + DOWN {} = {}, skipping books", up, down, up + down);
+            }
+            !worth_fetching
+        }
+        (Err(e), _) | (_, Err(e)) => {
+            debug!("Prescreen price fetch failed, fetching books: {}", e);
+            false
+        }
+    }
+}
 
-        info!("WebSocket connected, waiting for book updates...");
+/// Measure local minus CLOB server clock in seconds.
+async fn measure_clock_skew(client: &PolymarketClient) -> Result<i64, MarketError> {
+    let server_time = client.server_time().await?;
+    Ok(clock_skew_seconds(server_time, chrono::Utc::now().timestamp()))
+}
 
-        // Process WebSocket updates until market closes
-        while !market.is_closed() {
-            tokio::select! {
-                Some(_update) = ws_receiver.recv() => {
-                    let detection_start = Instant::now();
-
-                    // Get both books from WebSocket state
-                    let up_book = ws.get_book(&market.up_token_id);
-                    let down_book = ws.get_book(&market.down_token_id);
-
-                    if let (Some(up_state), Some(down_state)) = (up_book, down_book) {
-                        // Convert WebSocket state to OutcomeBook
-                        let (up_bids, up_asks) = up_state.to_levels();
-                        let (down_bids, down_asks) = down_state.to_levels();
-
-                        let up_outcome_book = OutcomeBook {
-                            token_id: market.up_token_id.clone(),
-                            outcome: polymarket_arb::market::Outcome::Up,
-                            bids: up_bids,
-                            asks: up_asks,
-                            updated_at: time::OffsetDateTime::now_utc(),
-                        };
-
-                        let down_outcome_book = OutcomeBook {
-                            token_id: market.down_token_id.clone(),
-                            outcome: polymarket_arb::market::Outcome::Down,
-                            bids: down_bids,
-                            asks: down_asks,
-                            updated_at: time::OffsetDateTime::now_utc(),
-                        };
-
-                        // Check for arbitrage opportunity
-                        match check_arbitrage(&market, &up_outcome_book, &down_outcome_book, &config) {
-                            Ok(Some(opportunity)) => {
-                                metrics::record_opportunity_detection_latency(detection_start);
-                                metrics::inc_opportunities_detected();
-
-                                // Execute arbitrage immediately
-                                match executor.execute(&client, &opportunity, &config).await {
-                                    Ok(result) => {
-                                        info!("Execution result: {:?}", result);
-                                        metrics::inc_opportunities_executed();
-
-                                        // Update stats in app state
-                                        let stats = executor.stats();
-                                        *app_state.stats.write().await = stats;
-                                    }
-                                    Err(e) => {
-                                        error!("Execution failed: {}", e);
-                                    }
-                                }
-                            }
-                            Ok(None) => {
-                                // No opportunity - just continue listening
-                            }
-                            Err(e) => {
-                                warn!("Arbitrage check error: {}", e);
-                            }
-                        }
-                    }
-                }
-                _ = tokio::time::sleep(Duration::from_secs(1)) => {
-                    // Periodic check if market is still open
-                    if market.is_closed() {
-                        break;
-                    }
-                }
+/// Measure clock skew at startup, warn if it exceeds the limit, and publish it.
+async fn check_clock_skew(client: &PolymarketClient, config: &Config, app_state: &AppState) {
+    match measure_clock_skew(client).await {
+        Ok(skew) => {
+            if skew.unsigned_abs() > config.max_clock_skew_seconds {
+                warn!(
+                    "Local clock is {}s off server time (max {}s); orders may be rejected",
+                    skew, config.max_clock_skew_seconds
+                );
+            } else {
+                info!("Clock skew: {}s", skew);
             }
+            *app_state.clock_skew_seconds.write().await = Some(skew);
         }
+        Err(e) => warn!("Failed to check clock skew: {}", e),
+    }
+}
 
-        // Market closed - print summary
-        info!("========================================");
-        info!("MARKET CLOSED - FINAL SUMMARY");
-        info!("========================================");
-        info!("Market: {}", market.slug);
+/// Start the user-channel WebSocket for fill notifications, if enabled.
+fn start_user_ws(config: &Config) -> Option<Arc<UserWebSocket>> {
+    if !config.use_user_ws {
+        return None;
+    }
 
-        let stats = executor.stats();
-        info!("Total opportunities detected: {}", stats.opportunities_found);
-        info!("Total trades executed: {}", stats.trades_executed);
-        info!("Total shares bought: {}", stats.total_shares_bought);
-        info!("Total invested: ${}", stats.total_invested);
-        info!("Expected profit: ${}", stats.expected_profit());
+    let Some(credentials) = WsCredentials::from_config(config) else {
+        warn!("USE_USER_WS requires POLYMARKET_API_KEY/SECRET/PASSPHRASE, falling back to polling");
+        return None;
+    };
 
-        if config.dry_run {
-            info!("Sim ending balance: ${}", stats.sim_ending_balance());
+    let reconnect_config = ReconnectConfig::from_config(
+        config.ws_reconnect_max_delay_s,
+        config.ws_heartbeat_interval_s,
+    );
+    let user_ws = Arc::new(UserWebSocket::new(
+        config.polymarket_ws_url.clone(),
+        credentials,
+        config.polymarket_private_key.clone(),
+        reconnect_config,
+    ));
+    user_ws.clone().spawn();
+    info!("User WebSocket enabled for fill notifications");
+
+    Some(user_ws)
+}
+
+/// Create a market WebSocket and start it with auto-reconnect.
+async fn connect_market_ws(
+    config: &Config,
+    asset_ids: Vec<String>,
+) -> (Arc<MarketWebSocket>, mpsc::Receiver<BookUpdate>) {
+    let reconnect_config = ReconnectConfig::from_config(
+        config.ws_reconnect_max_delay_s,
+        config.ws_heartbeat_interval_s,
+    );
+    let ws = Arc::new(
+        MarketWebSocket::with_reconnect_config(config.polymarket_ws_url.clone(), reconnect_config)
+            .with_channel_capacity(config.ws_channel_capacity),
+    );
+    let receiver = ws.clone().run_with_reconnect(asset_ids).await;
+
+    (ws, receiver)
+}
+
+/// Leave a discovered market alone and wait for the next window.
+///
+/// Sleeps until the market closes, but at least the usual pause between
+/// markets so a just-closed window is not rediscovered in a tight loop.
+async fn skip_market(market: &Market, reason: &str) {
+    let wait = market.time_remaining().unwrap_or_default().max(NEXT_MARKET_PAUSE);
+    info!(
+        slug = %market.slug,
+        reason = %reason,
+        wait_secs = wait.as_secs(),
+        "Skipping market, waiting for the next window"
+    );
+    tokio::time::sleep(wait).await;
+}
+
+/// Book levels per side shown by `--dump-books`.
+const DUMP_BOOK_DEPTH: usize = 3;
+
+/// Pause before searching for the next market after one closes or is skipped.
+const NEXT_MARKET_PAUSE: Duration = Duration::from_secs(10);
+
+/// Iterations of the detect-to-submit benchmark.
+const DETECT_TO_SUBMIT_ITERATIONS: usize = 1000;
+
+/// Interval between market status checks against the Gamma API.
+const MARKET_STATUS_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Throttled market status check that prefers the API over the end timestamp.
+#[derive(Default)]
+struct MarketStatusCheck {
+    /// When the API was last queried.
+    last_check: Option<Instant>,
+    /// Last status returned by the API.
+    last_status: Option<MarketStatus>,
+}
+
+impl MarketStatusCheck {
+    /// Whether the market has stopped trading.
+    async fn is_stopped(&mut self, client: &PolymarketClient, market: &Market) -> bool {
+        if !matches!(self.last_check, Some(t) if t.elapsed() < MARKET_STATUS_INTERVAL) {
+            self.last_check = Some(Instant::now());
+            self.last_status = match client.get_market_status(&market.slug).await {
+                Ok(status) => Some(status),
+                Err(e) => {
+                    warn!("Market status unavailable, using end timestamp: {}", e);
+                    None
+                }
+            };
         }
 
-        info!("========================================");
-        info!("Searching for next market in 10s...");
-        tokio::time::sleep(Duration::from_secs(10)).await;
+        match self.last_status {
+            Some(MarketStatus::Open) => false,
+            Some(status) => {
+                info!("Market {} reported {}", market.slug, status);
+                true
+            }
+            None => market.is_closed(),
+        }
     }
 }
 
@@ -696,10 +1280,11 @@ async fn cmd_ws_test() -> anyhow::Result<()> {
     // First discover a market to get token IDs
     let http_client = reqwest::Client::builder()
         .timeout(Duration::from_secs(30))
+        .default_headers(default_headers(&config))
         .build()?;
 
     println!("\n1. Discovering active market...");
-    let market = discover_active_market(&http_client).await?;
+    let market = discover_active_market(&http_client, config.window_offset_seconds).await?;
     println!("   Found: {}", market.slug);
     println!("   UP Token: {}", market.up_token_id);
     println!("   DOWN Token: {}", market.down_token_id);
@@ -767,7 +1352,7 @@ async fn cmd_benchmark() -> anyhow::Result<()> {
     // Discover market
     println!("\n1. Discovering market...");
     let http_client = client.http().clone();
-    let market = discover_active_market(&http_client).await?;
+    let market = discover_active_market(&http_client, config.window_offset_seconds).await?;
     println!("   Found: {}", market.slug);
 
     // Benchmark order book fetches
@@ -812,9 +1397,256 @@ async fn cmd_benchmark() -> anyhow::Result<()> {
     let avg_sign = sign_latencies.iter().sum::<f64>() / sign_latencies.len() as f64;
     println!("\n   Average signing latency: {:.1}ms", avg_sign);
 
+    // Compare per-leg and shared auth headers for one execution
+    println!("\n4. Benchmarking auth headers for both legs (10 iterations)...");
+    if let Some(latency) = paired_auth_header_latency(&config, 10).await? {
+        println!("   - Per-leg P50: {:.3}ms", latency.per_leg.p50.as_secs_f64() * 1000.0);
+        println!("   - Shared P50:  {:.3}ms", latency.shared.p50.as_secs_f64() * 1000.0);
+    }
+
+    // Benchmark the full hot path against a mock exchange
+    println!(
+        "\n5. Benchmarking detect-to-submit latency ({} iterations, mock submit)...",
+        DETECT_TO_SUBMIT_ITERATIONS
+    );
+    let summary = detect_to_submit_latency(&config, DETECT_TO_SUBMIT_ITERATIONS).await?;
+    if let Some(summary) = summary {
+        println!("\n   Results:");
+        println!("   - P50: {:.3}ms", summary.p50.as_secs_f64() * 1000.0);
+        println!("   - P95: {:.3}ms", summary.p95.as_secs_f64() * 1000.0);
+        println!("   - Min: {:.3}ms", summary.min.as_secs_f64() * 1000.0);
+        println!("   - Max: {:.3}ms", summary.max.as_secs_f64() * 1000.0);
+    }
+
     println!("\n======================================================================");
     println!("BENCHMARK COMPLETE");
     println!("======================================================================");
 
     Ok(())
 }
+
+/// Run the scan loop against the mock exchange and print what it observed.
+async fn cmd_sim_loop(latency_ms: u64, scans: usize) -> anyhow::Result<()> {
+    println!("======================================================================");
+    println!("BTC 15M ARB BOT - SIMULATED LOOP");
+    println!("======================================================================");
+
+    let config = Config::load()?;
+    config.validate().map_err(|e| anyhow::anyhow!(e))?;
+
+    println!("\nRunning {} scans with {}ms injected latency...", scans, latency_ms);
+    let report = run_sim_loop(&config, SimLoopOptions { scans, latency_ms }).await?;
+
+    println!("\n   Results:");
+    println!("   - Opportunities: {}", report.opportunities);
+    println!("   - Executed: {}", report.executed);
+    println!("   - Skipped: {}", report.skipped);
+    println!("   - Mock orders: {}", report.mock_orders);
+    if let Some(summary) = report.detect_to_execute {
+        println!("\n   Detect-to-execute:");
+        println!("   - P50: {:.3}ms", summary.p50.as_secs_f64() * 1000.0);
+        println!("   - P95: {:.3}ms", summary.p95.as_secs_f64() * 1000.0);
+        println!("   - Min: {:.3}ms", summary.min.as_secs_f64() * 1000.0);
+        println!("   - Max: {:.3}ms", summary.max.as_secs_f64() * 1000.0);
+    }
+
+    println!("\n======================================================================");
+    println!("SIMULATION COMPLETE");
+    println!("======================================================================");
+
+    Ok(())
+}
+
+/// Print all-time totals from the daily summary files.
+fn cmd_stats(dir: Option<String>) -> anyhow::Result<()> {
+    println!("======================================================================");
+    println!("BTC 15M ARB BOT - ALL-TIME STATS");
+    println!("======================================================================");
+
+    let dir = match dir {
+        Some(dir) => dir,
+        None => Config::load()?.summary_dir,
+    };
+    if dir.is_empty() {
+        return Err(anyhow::anyhow!("No summary directory: set SUMMARY_DIR or pass --dir"));
+    }
+
+    let results = load_summaries(std::path::Path::new(&dir))
+        .map_err(|e| anyhow::anyhow!("Failed to read summaries in {}: {}", dir, e))?;
+    let summary = summarize_history(&results);
+    let percent = |ratio: Option<rust_decimal::Decimal>| match ratio {
+        Some(ratio) => format!("{:.1}%", ratio * rust_decimal::Decimal::ONE_HUNDRED),
+        None => "n/a".to_string(),
+    };
+
+    println!("\n   Summaries: {}", dir);
+    println!("   - Markets closed: {}", summary.markets);
+    println!("   - Markets traded: {}", summary.traded_markets);
+    println!("   - Total invested: ${:.2}", summary.total_invested);
+    println!("   - Net profit: ${:.2}", summary.total_profit);
+    println!("   - ROI: {}", percent(summary.roi));
+    println!("   - Net-positive markets: {}", percent(summary.win_rate));
+
+    println!("\n======================================================================");
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Test-only private key (not a funded wallet).
+    const TEST_PRIVATE_KEY: &str =
+        "0x0123456789abcdef0123456789abcdef0123456789abcdef0123456789abcdef";
+
+    /// Dry-run config from defaults, with nothing persisted to disk.
+    fn dry_run_config() -> Config {
+        envy::from_iter([
+            ("POLYMARKET_PRIVATE_KEY".to_string(), TEST_PRIVATE_KEY.to_string()),
+            ("STATE_FILE".to_string(), String::new()),
+            ("HTTP_TIMEOUT_MS".to_string(), "500".to_string()),
+        ])
+        .unwrap()
+    }
+
+    /// Local Gamma markets URL reporting every requested slug as closed.
+    async fn closed_gamma_url() -> String {
+        use axum::extract::Query;
+        use std::collections::HashMap;
+
+        let markets = |Query(query): Query<HashMap<String, String>>| async move {
+            axum::Json(serde_json::json!([
+                {"slug": query.get("slug"), "closed": true, "active": false}
+            ]))
+        };
+        let router = axum::Router::new().route("/markets", axum::routing::get(markets));
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/markets", listener.local_addr().unwrap());
+        tokio::spawn(async move { axum::serve(listener, router).await });
+
+        url
+    }
+
+    /// Local CLOB whose `/book` always offers a 0.48 + 0.48 pair, and whose Gamma
+    /// `/markets` fails so market status falls back to the end timestamp.
+    ///
+    /// Returns the base URL and a count of book requests.
+    async fn standing_opportunity_url() -> (String, Arc<std::sync::atomic::AtomicU32>) {
+        use axum::extract::Query;
+        use std::collections::HashMap;
+        use std::sync::atomic::{AtomicU32, Ordering};
+
+        let book_requests = Arc::new(AtomicU32::new(0));
+        let requests = book_requests.clone();
+        let book = move |Query(query): Query<HashMap<String, String>>| {
+            requests.fetch_add(1, Ordering::SeqCst);
+            async move {
+                axum::Json(serde_json::json!({
+                    "market": "market-id",
+                    "asset_id": query.get("token_id"),
+                    "bids": [{"price": "0.47", "size": "100"}],
+                    "asks": [{"price": "0.48", "size": "100"}],
+                }))
+            }
+        };
+        let markets = || async { axum::http::StatusCode::SERVICE_UNAVAILABLE };
+        let router = axum::Router::new()
+            .route("/book", axum::routing::get(book))
+            .route("/markets", axum::routing::get(markets));
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move { axum::serve(listener, router).await });
+
+        (url, book_requests)
+    }
+
+    /// Market with the given end timestamp and the test token IDs.
+    fn market_ending_at(end: i64) -> Market {
+        Market {
+            slug: format!("btc-updown-15m-{}", end - Market::WINDOW_SECONDS),
+            id: "market-id".to_string(),
+            up_token_id: "up-token".to_string(),
+            down_token_id: "down-token".to_string(),
+            start_timestamp: end - Market::WINDOW_SECONDS,
+            end_timestamp: end,
+            question: None,
+            neg_risk: None,
+            condition_id: None,
+            accepting_orders_until: None,
+            tick_size: None,
+        }
+    }
+
+    /// Polling task for one market with a fresh executor and app state.
+    fn polling_task(config: Config, client: PolymarketClient) -> MarketTask {
+        MarketTask {
+            executor: ArbitrageExecutor::new(&config),
+            config,
+            client,
+            app_state: AppState::new(),
+            shutdown: Shutdown::new(),
+            slug_filter: SlugFilter::new(&[], &[]).unwrap(),
+            feed: BookFeed::Polling,
+            once: true,
+            dump_books: None,
+            redeeming: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn soft_stopped_market_scans_at_interval_without_entering() {
+        use std::sync::atomic::Ordering;
+
+        let mut config = dry_run_config();
+        config.scan_interval_ms = 200;
+        let (url, book_requests) = standing_opportunity_url().await;
+        let client = PolymarketClient::with_base_url(&url, TEST_PRIVATE_KEY)
+            .unwrap()
+            .with_gamma_url(format!("{}/markets", url));
+        let market = market_ending_at(time::OffsetDateTime::now_utc().unix_timestamp() + 2);
+
+        // The books stand as an opportunity on every scan
+        let up = client.get_order_book(&market.up_token_id).await.unwrap();
+        let down = client.get_order_book(&market.down_token_id).await.unwrap();
+        assert!(check_arbitrage(&market, &up, &down, &config).unwrap().is_some());
+        book_requests.store(0, Ordering::SeqCst);
+
+        let mut task = polling_task(config, client);
+        task.shutdown.request_soft_stop();
+        let started = Instant::now();
+        task.run_market(&market).await;
+
+        // Two book fetches per scan, one scan per interval until the market closes
+        let intervals = started.elapsed().as_millis() as u32 / 200;
+        let requests = book_requests.load(Ordering::SeqCst);
+        assert!(requests >= 2);
+        assert!(
+            requests <= 2 * (intervals + 1),
+            "{} book requests in {} intervals",
+            requests,
+            intervals
+        );
+        assert_eq!(task.executor.stats().trades_executed, 0);
+        assert_eq!(task.executor.history()[0].trades, 0);
+    }
+
+    #[tokio::test]
+    async fn run_market_records_a_closed_market() {
+        let config = dry_run_config();
+        let client = PolymarketClient::new(&config).with_gamma_url(closed_gamma_url().await);
+        let mut task = polling_task(config, client);
+        let market = market_ending_at(time::OffsetDateTime::now_utc().unix_timestamp() + 60);
+        assert!(task.app_state.claim_market(&market.slug).await);
+
+        // Gamma reporting the market closed ends it at once
+        task.run_market(&market).await;
+
+        let history = task.executor.history();
+        assert_eq!(history.len(), 1);
+        assert_eq!(history[0].slug, market.slug);
+        assert_eq!(history[0].trades, 0);
+        assert_eq!(*task.app_state.history.read().await, history);
+        assert!(task.app_state.markets.read().await.is_empty());
+    }
+}
@@ -1,17 +1,32 @@
 //! Polymarket API client wrapper.
 
-use std::time::Duration;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::{Duration, Instant};
 
+use dashmap::mapref::entry::Entry;
+use dashmap::DashMap;
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue, USER_AGENT};
 use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
+use tokio::sync::{Mutex as AsyncMutex, Semaphore, SemaphorePermit};
 use tracing::{debug, instrument, warn};
 
 use crate::config::Config;
 use crate::error::{MarketError, TradingError};
+use crate::metrics;
 use crate::orderbook::types::OutcomeBook;
 use crate::signing;
+use crate::trading::order::{OrderParams, OrderPrecision, Side};
 
-use super::types::Outcome;
+use super::discovery::GAMMA_API_URL;
+use super::types::{GammaMarket, MarketStatus, Outcome};
+
+/// How long signed auth headers are reused, so both legs of an execution share one signature.
+const AUTH_HEADER_TTL: Duration = Duration::from_secs(2);
+
+/// Auth headers and when they were signed.
+type CachedAuthHeaders = Option<(Instant, Vec<(String, String)>)>;
 
 /// Polymarket CLOB API client.
 #[derive(Debug, Clone)]
@@ -20,6 +35,10 @@ pub struct PolymarketClient {
     http: reqwest::Client,
     /// Base URL for CLOB API.
     clob_url: String,
+    /// Path prefix joined before every CLOB endpoint.
+    api_base_path: String,
+    /// Gamma API markets URL, queried for market status.
+    gamma_url: String,
     /// Wallet private key.
     private_key: String,
     /// Signature type (0=EOA, 1=Magic.link, 2=Gnosis).
@@ -28,10 +47,41 @@ pub struct PolymarketClient {
     funder: Option<String>,
     /// Chain ID (137 for Polygon).
     chain_id: u64,
+    /// Last order nonce issued (seeded from the clock).
+    nonce: Arc<AtomicU64>,
+    /// Recently submitted orders keyed by (token, side, price, size).
+    recent_orders: Arc<DashMap<OrderKey, Instant>>,
+    /// Window in which identical orders are rejected (zero disables).
+    dedup_window: Duration,
+    /// Price and size precision for order bodies.
+    order_precision: OrderPrecision,
+    /// Last fetched balance (shared across clones).
+    balance_cache: Arc<Mutex<Option<CachedBalance>>>,
+    /// How long a fetched balance is reused (zero disables caching).
+    balance_cache_ttl: Duration,
+    /// Bounds in-flight authenticated requests (shared across clones).
+    request_limiter: Arc<Semaphore>,
+    /// Decimals of raw USDC amounts returned by the API.
+    usdc_decimals: u32,
+    /// Immediate retries of a book fetch after a connection error.
+    book_fetch_retries: u32,
+    /// Wallet address, derived from the private key on first use.
+    address: Arc<OnceLock<String>>,
+    /// Recently signed auth headers (shared across clones).
+    auth_headers: Arc<AsyncMutex<CachedAuthHeaders>>,
+}
+
+/// USDC balance as last fetched, adjusted locally for fills since.
+#[derive(Debug, Clone, Copy)]
+pub struct CachedBalance {
+    /// Balance in USDC.
+    pub value: Decimal,
+    /// When the balance was fetched.
+    pub fetched_at: Instant,
 }
 
 /// Order book response from API.
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Default, Deserialize)]
 pub struct OrderBookResponse {
     /// Bid levels.
     pub bids: Option<Vec<OrderLevel>>,
@@ -41,6 +91,74 @@ pub struct OrderBookResponse {
     pub market: Option<String>,
     /// Asset ID.
     pub asset_id: Option<String>,
+    /// Server timestamp of the snapshot, in Unix milliseconds.
+    #[serde(default, deserialize_with = "millis_timestamp")]
+    pub timestamp: Option<i64>,
+}
+
+/// Shapes the CLOB uses for an order book, depending on the endpoint.
+///
+/// Variants are tried in order; a body matching none of the first three parses
+/// as a book with no levels rather than failing.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(untagged)]
+pub enum OrderBookPayload {
+    /// Book nested under a `book` key.
+    Wrapped {
+        /// The book.
+        book: OrderBookResponse,
+    },
+    /// Bare list of price levels, with no side to assign them to.
+    ///
+    /// Tried before `List`, whose all-optional books would otherwise accept
+    /// each level as an empty book.
+    Levels(Vec<OrderLevel>),
+    /// List of books, one per token.
+    List(Vec<OrderBookResponse>),
+    /// Book object with `bids`/`asks` at the top level.
+    Book(OrderBookResponse),
+}
+
+impl OrderBookPayload {
+    /// The book for `token_id`.
+    ///
+    /// A list without a matching `asset_id` falls back to a book that carries no
+    /// `asset_id`; another token's book is never used, so that yields no levels.
+    /// A bare list of levels is rejected, since bids and asks cannot be told apart.
+    pub fn into_book(self, token_id: &str) -> Result<OrderBookResponse, MarketError> {
+        match self {
+            OrderBookPayload::Wrapped { book } | OrderBookPayload::Book(book) => Ok(book),
+            // An empty array is read as an empty list of books
+            OrderBookPayload::Levels(levels) if levels.is_empty() => {
+                Ok(OrderBookResponse::default())
+            }
+            OrderBookPayload::Levels(levels) => Err(MarketError::ParseError(format!(
+                "order book for {} is a bare list of {} price levels without bids/asks",
+                token_id,
+                levels.len()
+            ))),
+            OrderBookPayload::List(books) => {
+                let matching = books
+                    .iter()
+                    .position(|book| book.asset_id.as_deref() == Some(token_id))
+                    .or_else(|| books.iter().position(|book| book.asset_id.is_none()));
+                Ok(matching.and_then(|i| books.into_iter().nth(i)).unwrap_or_default())
+            }
+        }
+    }
+}
+
+/// Deserialize a millisecond timestamp sent as a number or a numeric string.
+fn millis_timestamp<'de, D>(deserializer: D) -> Result<Option<i64>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let value = Option::<serde_json::Value>::deserialize(deserializer)?;
+    Ok(match value {
+        Some(serde_json::Value::Number(n)) => n.as_i64(),
+        Some(serde_json::Value::String(s)) => s.trim().parse().ok(),
+        _ => None,
+    })
 }
 
 /// Single price level in order book.
@@ -61,6 +179,18 @@ pub struct BalanceAllowanceResponse {
     pub allowance: Option<String>,
 }
 
+impl BalanceAllowanceResponse {
+    /// Balance in whole token units.
+    pub fn balance_units(&self, decimals: u32) -> Decimal {
+        wei_to_units(self.balance.as_deref().unwrap_or_default(), decimals)
+    }
+
+    /// Allowance in whole token units.
+    pub fn allowance_units(&self, decimals: u32) -> Decimal {
+        wei_to_units(self.allowance.as_deref().unwrap_or_default(), decimals)
+    }
+}
+
 /// Position response from API.
 #[derive(Debug, Clone, Deserialize)]
 pub struct PositionResponse {
@@ -72,6 +202,9 @@ pub struct PositionResponse {
     pub size: Option<String>,
     /// Average entry price.
     pub avg_price: Option<String>,
+    /// Current price (1 or 0 once the market has resolved).
+    #[serde(default, alias = "curPrice")]
+    pub cur_price: Option<String>,
 }
 
 /// Asset info in position.
@@ -81,10 +214,79 @@ pub struct AssetInfo {
     pub token_id: Option<String>,
 }
 
+/// Whether a request failed at the connection level (refused, reset, or timed out)
+/// rather than with a response.
+fn is_connection_error(e: &reqwest::Error) -> bool {
+    e.is_connect() || e.is_timeout() || e.is_request()
+}
+
+/// Identity of an order for duplicate detection: (token, side, price, size).
+type OrderKey = (String, Side, Decimal, Decimal);
+
+/// Identity of an order for duplicate detection.
+fn order_key(params: &OrderParams) -> OrderKey {
+    (params.token_id.clone(), params.side, params.price, params.size)
+}
+
+/// Dedup entry held for an order being submitted; released on drop unless kept.
+#[derive(Debug)]
+pub struct OrderReservation<'a> {
+    /// Recent orders the entry was inserted into (none when dedup is disabled).
+    recent_orders: Option<&'a DashMap<OrderKey, Instant>>,
+    /// Identity of the reserved order.
+    key: OrderKey,
+    /// When the entry was inserted.
+    reserved_at: Instant,
+    /// Whether the entry outlives the reservation.
+    kept: bool,
+}
+
+impl OrderReservation<'_> {
+    /// Keep the entry for the dedup window: the order was accepted, or may have
+    /// reached the exchange.
+    pub fn keep(&mut self) {
+        self.kept = true;
+    }
+}
+
+impl Drop for OrderReservation<'_> {
+    fn drop(&mut self) {
+        if let (false, Some(recent_orders)) = (self.kept, self.recent_orders) {
+            // The entry may have expired and been taken by a later twin
+            recent_orders.remove_if(&self.key, |_, reserved_at| *reserved_at == self.reserved_at);
+        }
+    }
+}
+
+/// Default headers for every request: the configured User-Agent plus any extra headers.
+///
+/// Entries that are not valid headers are skipped with a warning; `Config::validate`
+/// rejects them up front.
+pub fn default_headers(config: &Config) -> HeaderMap {
+    let mut headers = HeaderMap::new();
+    match HeaderValue::from_str(&config.http_user_agent) {
+        Ok(value) => {
+            headers.insert(USER_AGENT, value);
+        }
+        Err(_) => warn!(user_agent = %config.http_user_agent, "Ignoring invalid User-Agent"),
+    }
+
+    for (name, value) in &config.http_extra_headers {
+        match (HeaderName::from_bytes(name.as_bytes()), HeaderValue::from_str(value)) {
+            (Ok(name), Ok(value)) => {
+                headers.insert(name, value);
+            }
+            _ => warn!(header = %name, "Ignoring invalid extra header"),
+        }
+    }
+    headers
+}
+
 impl PolymarketClient {
     /// Create a new Polymarket client from config with optimized HTTP settings.
     pub fn new(config: &Config) -> Self {
         let http = reqwest::Client::builder()
+            .default_headers(default_headers(config))
             // Configurable timeout (default 2s, down from 30s)
             .timeout(Duration::from_millis(config.http_timeout_ms))
             // Fast connection establishment
@@ -103,13 +305,143 @@ impl PolymarketClient {
         Self {
             http,
             clob_url: config.polymarket_clob_url.clone(),
+            api_base_path: config.api_base_path.clone(),
+            gamma_url: GAMMA_API_URL.to_string(),
             private_key: config.polymarket_private_key.clone(),
             signature_type: config.polymarket_signature_type,
             funder: config.polymarket_funder.clone(),
             chain_id: 137, // Polygon mainnet
+            nonce: Arc::new(AtomicU64::new(chrono::Utc::now().timestamp_millis().max(0) as u64)),
+            recent_orders: Arc::new(DashMap::new()),
+            dedup_window: Duration::from_millis(config.order_dedup_window_ms),
+            order_precision: OrderPrecision::from_tick_size(config.tick_size),
+            balance_cache: Arc::new(Mutex::new(None)),
+            balance_cache_ttl: Duration::from_millis(config.balance_cache_ttl_ms),
+            request_limiter: Arc::new(Semaphore::new(config.max_concurrent_requests.max(1))),
+            usdc_decimals: config.usdc_decimals,
+            book_fetch_retries: config.book_fetch_retries,
+            address: Arc::new(OnceLock::new()),
+            auth_headers: Arc::new(AsyncMutex::new(None)),
+        }
+    }
+
+    /// Client for the CLOB at `clob_url` signing with `private_key`, with every
+    /// other setting at its config default.
+    ///
+    /// Lets tests point a client at a local server without building a `Config`.
+    /// Fails when the URL does not parse or the resulting config is invalid.
+    pub fn with_base_url(
+        clob_url: impl Into<String>,
+        private_key: impl Into<String>,
+    ) -> Result<Self, String> {
+        let clob_url = clob_url.into();
+        reqwest::Url::parse(&clob_url)
+            .map_err(|e| format!("invalid CLOB URL {}: {}", clob_url, e))?;
+
+        let config: Config = envy::from_iter([
+            ("POLYMARKET_PRIVATE_KEY".to_string(), private_key.into()),
+            ("POLYMARKET_CLOB_URL".to_string(), clob_url),
+        ])
+        .map_err(|e| e.to_string())?;
+        config.validate()?;
+
+        Ok(Self::new(&config))
+    }
+
+    /// Query market status from the Gamma markets URL `gamma_url` instead of the
+    /// public Gamma API, so tests can serve it locally.
+    pub fn with_gamma_url(mut self, gamma_url: impl Into<String>) -> Self {
+        self.gamma_url = gamma_url.into();
+        self
+    }
+
+    /// Price and size precision for order bodies.
+    pub fn order_precision(&self) -> OrderPrecision {
+        self.order_precision
+    }
+
+    /// Get a unique, strictly increasing order nonce.
+    pub fn next_nonce(&self) -> u64 {
+        self.nonce.fetch_add(1, Ordering::SeqCst) + 1
+    }
+
+    /// Reserve an order's dedup entry, rejecting it if an identical order was
+    /// submitted within the dedup window or is being submitted now.
+    ///
+    /// The check and insert are one atomic step, so two concurrent submissions
+    /// of the same order cannot both pass. Drop the reservation without keeping
+    /// it when the exchange rejected the order, so it can be retried.
+    pub fn reserve_order(
+        &self,
+        params: &OrderParams,
+    ) -> Result<OrderReservation<'_>, TradingError> {
+        let now = Instant::now();
+        if self.dedup_window.is_zero() {
+            return Ok(OrderReservation {
+                recent_orders: None,
+                key: order_key(params),
+                reserved_at: now,
+                kept: false,
+            });
+        }
+
+        self.recent_orders
+            .retain(|_, submitted| now.duration_since(*submitted) < self.dedup_window);
+
+        match self.recent_orders.entry(order_key(params)) {
+            Entry::Occupied(_) => Err(TradingError::DuplicateOrder(format!(
+                "{} {} {} @ {} within {}ms",
+                params.side,
+                params.size,
+                params.token_id,
+                params.price,
+                self.dedup_window.as_millis()
+            ))),
+            Entry::Vacant(entry) => {
+                let key = entry.key().clone();
+                entry.insert(now);
+                Ok(OrderReservation {
+                    recent_orders: Some(&self.recent_orders),
+                    key,
+                    reserved_at: now,
+                    kept: false,
+                })
+            }
         }
     }
 
+    /// Wait for a slot under the in-flight request limit.
+    ///
+    /// Hold the permit for the duration of the request.
+    pub async fn acquire_request_permit(&self) -> SemaphorePermit<'_> {
+        self.request_limiter
+            .acquire()
+            .await
+            .expect("request limiter is never closed")
+    }
+
+    /// CLOB auth headers, signed at most once per [`AUTH_HEADER_TTL`].
+    ///
+    /// Concurrent callers wait for one signature rather than each signing.
+    pub async fn auth_headers(&self) -> Result<Vec<(String, String)>, TradingError> {
+        let mut cached = self.auth_headers.lock().await;
+        if let Some((signed_at, headers)) = cached.as_ref() {
+            if signed_at.elapsed() < AUTH_HEADER_TTL {
+                return Ok(headers.clone());
+            }
+        }
+
+        let headers = signing::generate_auth_headers(&self.private_key, self.signature_type).await?;
+        metrics::inc_auth_headers_signed();
+        *cached = Some((Instant::now(), headers.clone()));
+        Ok(headers)
+    }
+
+    /// Drop cached auth headers so the next request signs fresh ones.
+    pub async fn invalidate_auth_headers(&self) {
+        *self.auth_headers.lock().await = None;
+    }
+
     /// Get the HTTP client reference.
     pub fn http(&self) -> &reqwest::Client {
         &self.http
@@ -130,18 +462,102 @@ impl PolymarketClient {
         self.funder.as_deref()
     }
 
-    /// Get order book for a token.
-    #[instrument(skip(self), fields(token_id = %token_id))]
-    pub async fn get_order_book(&self, token_id: &str) -> Result<OutcomeBook, MarketError> {
-        let url = format!("{}/book", self.clob_url);
-
+    /// Get the trading status of a market from the Gamma API.
+    #[instrument(skip(self))]
+    pub async fn get_market_status(&self, slug: &str) -> Result<MarketStatus, MarketError> {
         let response = self
             .http
-            .get(&url)
-            .query(&[("token_id", token_id)])
+            .get(&self.gamma_url)
+            .query(&[("slug", slug)])
             .send()
             .await?;
 
+        if !response.status().is_success() {
+            return Err(MarketError::FetchFailed {
+                slug: slug.to_string(),
+                reason: format!("HTTP {}", response.status()),
+            });
+        }
+
+        let markets: Vec<GammaMarket> = response.json().await.map_err(|e| {
+            MarketError::ParseError(format!("Failed to parse Gamma API response: {}", e))
+        })?;
+
+        markets
+            .iter()
+            .find(|m| m.slug.as_deref() == Some(slug))
+            .map(GammaMarket::status)
+            .ok_or_else(|| MarketError::FetchFailed {
+                slug: slug.to_string(),
+                reason: "Market not found in Gamma API".to_string(),
+            })
+    }
+
+    /// Get the CLOB server time (unix seconds).
+    #[instrument(skip(self))]
+    pub async fn server_time(&self) -> Result<i64, MarketError> {
+        let url = self.endpoint("time");
+
+        let response = self.http.get(&url).send().await?;
+
+        if !response.status().is_success() {
+            return Err(MarketError::FetchFailed {
+                slug: "time".to_string(),
+                reason: format!("HTTP {}", response.status()),
+            });
+        }
+
+        let body = response.text().await?;
+        parse_server_time(&body)
+            .ok_or_else(|| MarketError::ParseError(format!("Unexpected server time: {}", body)))
+    }
+
+    /// Open a pooled connection to the CLOB with a cheap `/time` request.
+    ///
+    /// Run right after discovering a market so its first book fetch skips the
+    /// TCP and TLS handshakes. Returns the warmup latency.
+    #[instrument(skip(self))]
+    pub async fn warmup(&self) -> Result<Duration, MarketError> {
+        let start = Instant::now();
+        let url = self.endpoint("time");
+
+        let response = self.http.get(&url).send().await?;
+        if !response.status().is_success() {
+            return Err(MarketError::FetchFailed {
+                slug: "time".to_string(),
+                reason: format!("HTTP {}", response.status()),
+            });
+        }
+
+        // Read the body so the connection goes back to the pool
+        response.bytes().await?;
+        Ok(start.elapsed())
+    }
+
+    /// Get order book for a token.
+    #[instrument(skip(self), fields(token_id = %token_id))]
+    pub async fn get_order_book(&self, token_id: &str) -> Result<OutcomeBook, MarketError> {
+        let url = self.endpoint("book");
+
+        // A blip retries straight away rather than stalling the scan loop
+        let mut attempt = 0;
+        let response = loop {
+            match self.http.get(&url).query(&[("token_id", token_id)]).send().await {
+                Ok(response) => break response,
+                Err(e) if is_connection_error(&e) && attempt < self.book_fetch_retries => {
+                    attempt += 1;
+                    debug!(attempt, error = %e, "Retrying book fetch after connection error");
+                }
+                Err(e) if is_connection_error(&e) => {
+                    return Err(MarketError::FetchFailed {
+                        slug: token_id.to_string(),
+                        reason: e.to_string(),
+                    });
+                }
+                Err(e) => return Err(e.into()),
+            }
+        };
+
         if !response.status().is_success() {
             return Err(MarketError::FetchFailed {
                 slug: token_id.to_string(),
@@ -149,13 +565,54 @@ impl PolymarketClient {
             });
         }
 
-        let book: OrderBookResponse = response.json().await.map_err(|e| {
+        let payload: OrderBookPayload = response.json().await.map_err(|e| {
             MarketError::ParseError(format!("Failed to parse order book: {}", e))
         })?;
 
+        let book = payload.into_book(token_id).inspect_err(|e| {
+            warn!(error = %e, "Rejecting order book payload");
+        })?;
+
         Ok(self.convert_order_book(token_id, book))
     }
 
+    /// Get the best price to buy a token from `/price`, without the full book.
+    #[instrument(skip(self), fields(token_id = %token_id))]
+    pub async fn get_buy_price(&self, token_id: &str) -> Result<Decimal, MarketError> {
+        self.get_single_price("price", &[("token_id", token_id), ("side", "buy")], "price")
+            .await
+    }
+
+    /// Get a token's midpoint price from `/midpoint`, without the full book.
+    #[instrument(skip(self), fields(token_id = %token_id))]
+    pub async fn get_midpoint(&self, token_id: &str) -> Result<Decimal, MarketError> {
+        self.get_single_price("midpoint", &[("token_id", token_id)], "mid").await
+    }
+
+    /// Fetch a CLOB endpoint that answers with a single price under `field`.
+    async fn get_single_price(
+        &self,
+        path: &str,
+        query: &[(&str, &str)],
+        field: &str,
+    ) -> Result<Decimal, MarketError> {
+        let url = self.endpoint(path);
+
+        let response = self.http.get(&url).query(query).send().await?;
+
+        if !response.status().is_success() {
+            return Err(MarketError::FetchFailed {
+                slug: path.to_string(),
+                reason: format!("HTTP {}", response.status()),
+            });
+        }
+
+        let body = response.text().await?;
+        parse_single_price(&body, field).ok_or_else(|| {
+            MarketError::ParseError(format!("Unexpected {} response: {}", path, body))
+        })
+    }
+
     /// Convert API response to OutcomeBook.
     fn convert_order_book(&self, token_id: &str, response: OrderBookResponse) -> OutcomeBook {
         use crate::orderbook::types::PriceLevel;
@@ -185,12 +642,17 @@ impl PolymarketClient {
         // Sort asks ascending by price
         asks.sort_by(|a, b| a.price.cmp(&b.price));
 
+        let as_of = response.timestamp.and_then(|ms| {
+            OffsetDateTime::from_unix_timestamp_nanos(i128::from(ms) * 1_000_000).ok()
+        });
+
         OutcomeBook {
             token_id: token_id.to_string(),
             outcome: Outcome::Up, // Will be set by caller
             bids,
             asks,
             updated_at: OffsetDateTime::now_utc(),
+            as_of,
         }
     }
 
@@ -199,8 +661,9 @@ impl PolymarketClient {
     pub async fn get_balance(&self) -> Result<Decimal, TradingError> {
         debug!("Getting balance from Polymarket API");
 
-        let url = format!("{}/balance-allowance", self.clob_url);
-        let auth_headers = signing::generate_auth_headers(&self.private_key, self.signature_type).await?;
+        let url = self.endpoint("balance-allowance");
+        let _permit = self.acquire_request_permit().await;
+        let auth_headers = self.auth_headers().await?;
 
         let mut request = self.http.get(&url);
         for (key, value) in auth_headers {
@@ -224,22 +687,55 @@ impl PolymarketClient {
             TradingError::SubmissionFailed(format!("Failed to parse balance: {}", e))
         })?;
 
-        // Parse balance from response (in wei, 6 decimals for USDC)
-        let balance_wei: Decimal = balance_response
-            .balance
-            .as_deref()
-            .unwrap_or("0")
-            .parse()
-            .unwrap_or(Decimal::ZERO);
+        let balance = balance_response.balance_units(self.usdc_decimals);
+        let allowance = balance_response.allowance_units(self.usdc_decimals);
 
-        // Convert from wei (6 decimals) to USDC
-        let balance = balance_wei / Decimal::new(1_000_000, 0);
-
-        debug!(balance = %balance, "Retrieved USDC balance");
+        debug!(balance = %balance, allowance = %allowance, "Retrieved USDC balance");
 
         Ok(balance)
     }
 
+    /// Get USDC balance, reusing the last fetch while it is younger than the cache TTL.
+    pub async fn get_balance_cached(&self) -> Result<Decimal, TradingError> {
+        if let Some(cached) = self.cached_balance() {
+            if cached.fetched_at.elapsed() < self.balance_cache_ttl {
+                return Ok(cached.value);
+            }
+        }
+
+        let value = self.get_balance().await?;
+        if !self.balance_cache_ttl.is_zero() {
+            if let Ok(mut cache) = self.balance_cache.lock() {
+                *cache = Some(CachedBalance {
+                    value,
+                    fetched_at: Instant::now(),
+                });
+            }
+        }
+        Ok(value)
+    }
+
+    /// Get the cached balance, fresh or not.
+    pub fn cached_balance(&self) -> Option<CachedBalance> {
+        self.balance_cache.lock().ok().and_then(|cache| *cache)
+    }
+
+    /// Deduct USDC spent on a fill from the cached balance.
+    pub fn debit_cached_balance(&self, amount: Decimal) {
+        if let Ok(mut cache) = self.balance_cache.lock() {
+            if let Some(cached) = cache.as_mut() {
+                cached.value -= amount;
+            }
+        }
+    }
+
+    /// Drop the cached balance so the next read fetches it.
+    pub fn invalidate_balance_cache(&self) {
+        if let Ok(mut cache) = self.balance_cache.lock() {
+            *cache = None;
+        }
+    }
+
     /// Get positions for specified token IDs.
     #[instrument(skip(self))]
     pub async fn get_positions(
@@ -249,10 +745,11 @@ impl PolymarketClient {
         debug!("Getting positions from Polymarket API");
 
         // Use the REST API for positions
-        let url = format!("{}/positions", self.clob_url);
+        let url = self.endpoint("positions");
 
         // Get the wallet address for the query
         let address = self.get_address()?;
+        let _permit = self.acquire_request_permit().await;
 
         let response = self
             .http
@@ -298,8 +795,29 @@ impl PolymarketClient {
     }
 
     /// Get the wallet address derived from the private key.
+    ///
+    /// Derived once and cached, since every order body carries it.
     pub fn get_address(&self) -> Result<String, TradingError> {
-        signing::address_from_private_key(&self.private_key)
+        if let Some(address) = self.address.get() {
+            return Ok(address.clone());
+        }
+        let address = signing::address_from_private_key(&self.private_key)?;
+        Ok(self.address.get_or_init(|| address).clone())
+    }
+
+    /// Get the order maker address.
+    ///
+    /// For Magic.link (type 1) and Gnosis Safe (type 2) wallets the funder
+    /// (proxy or Safe) is the maker while the EOA signs.
+    pub fn maker_address(&self) -> Result<String, TradingError> {
+        match (self.signature_type, &self.funder) {
+            (1 | 2, Some(funder)) => Ok(funder.clone()),
+            (1 | 2, None) => Err(TradingError::InvalidParams(format!(
+                "signature type {} requires a funder address",
+                self.signature_type
+            ))),
+            _ => self.get_address(),
+        }
     }
 
     /// Get the CLOB base URL.
@@ -307,46 +825,76 @@ impl PolymarketClient {
         &self.clob_url
     }
 
+    /// Full URL of a CLOB endpoint such as `order` or `book`, under the base path.
+    pub fn endpoint(&self, path: &str) -> String {
+        join_endpoint(&self.clob_url, &self.api_base_path, path)
+    }
+
     /// Get the chain ID.
     pub fn chain_id(&self) -> u64 {
         self.chain_id
     }
 }
 
+/// Convert a raw base-unit amount (e.g. `"25000000"`) to whole units with `decimals` places.
+///
+/// Missing or empty amounts are zero; malformed or out-of-range ones log a
+/// warning and are treated as zero.
+pub fn wei_to_units(raw: &str, decimals: u32) -> Decimal {
+    let raw = raw.trim();
+    if raw.is_empty() {
+        return Decimal::ZERO;
+    }
+
+    let scaled = raw.parse::<Decimal>().map_err(|e| e.to_string()).and_then(|wei| {
+        let unit = Decimal::try_from_i128_with_scale(1, decimals).map_err(|e| e.to_string())?;
+        wei.checked_mul(unit).ok_or_else(|| "overflow".to_string())
+    });
+    scaled.unwrap_or_else(|e| {
+        warn!(raw = %raw, decimals = decimals, error = %e, "Invalid base-unit amount, using 0");
+        Decimal::ZERO
+    })
+}
+
+/// Parse a `/time` response: a bare unix timestamp, optionally JSON-quoted.
+pub fn parse_server_time(body: &str) -> Option<i64> {
+    body.trim().trim_matches('"').parse().ok()
+}
+
+/// Join a base URL, path prefix, and endpoint path, collapsing duplicate slashes.
+pub fn join_endpoint(base_url: &str, prefix: &str, path: &str) -> String {
+    let segments: Vec<&str> = prefix
+        .split('/')
+        .chain(path.split('/'))
+        .filter(|segment| !segment.is_empty())
+        .collect();
+    format!("{}/{}", base_url.trim_end_matches('/'), segments.join("/"))
+}
+
+/// Parse a single-price response such as `{"price": "0.48"}` or `{"mid": 0.475}`.
+pub fn parse_single_price(body: &str, field: &str) -> Option<Decimal> {
+    let value: serde_json::Value = serde_json::from_str(body).ok()?;
+    match value.get(field)? {
+        serde_json::Value::String(price) => price.parse().ok(),
+        serde_json::Value::Number(price) => price.to_string().parse().ok(),
+        _ => None,
+    }
+}
+
+/// Local clock minus server clock, in seconds (positive when local is ahead).
+pub fn clock_skew_seconds(server_time: i64, local_time: i64) -> i64 {
+    local_time - server_time
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::config::DEFAULT_HTTP_USER_AGENT;
 
     fn test_config() -> Config {
         Config {
-            polymarket_private_key: "0x0123456789abcdef0123456789abcdef0123456789abcdef0123456789abcdef".to_string(),
-            polymarket_api_key: None,
-            polymarket_api_secret: None,
-            polymarket_api_passphrase: None,
-            polymarket_signature_type: 0,
-            polymarket_funder: None,
-            target_pair_cost: Decimal::new(991, 3),
-            order_size: Decimal::new(5, 0),
-            order_type: "FOK".to_string(),
-            balance_margin: Decimal::new(12, 1),
-            dry_run: true,
-            sim_balance: Decimal::new(100, 0),
-            cooldown_seconds: 10,
-            polymarket_market_slug: None,
-            use_wss: false,
             polymarket_ws_url: "wss://test".to_string(),
-            polymarket_clob_url: "https://clob.polymarket.com".to_string(),
-            port: 8080,
-            rust_log: "info".to_string(),
-            verbose: false,
-            http_timeout_ms: 2000,
-            http_pool_size: 10,
-            order_timeout_ms: 500,
-            order_poll_interval_ms: 50,
-            ws_reconnect_max_delay_s: 30,
-            ws_heartbeat_interval_s: 30,
-            metrics_enabled: true,
-            metrics_port: 9090,
+            ..Config::test_default()
         }
     }
 
@@ -358,6 +906,205 @@ mod tests {
         assert_eq!(client.clob_url(), "https://clob.polymarket.com");
     }
 
+    #[test]
+    fn with_base_url_points_client_at_injected_server() {
+        let key = test_config().polymarket_private_key;
+        let client = PolymarketClient::with_base_url("http://127.0.0.1:4010", &key).unwrap();
+
+        assert_eq!(client.clob_url(), "http://127.0.0.1:4010");
+        assert_eq!(client.endpoint("book"), "http://127.0.0.1:4010/book");
+        assert_eq!(client.chain_id(), 137);
+
+        assert!(PolymarketClient::with_base_url("not a url", &key).is_err());
+        assert!(PolymarketClient::with_base_url("http://127.0.0.1:4010", "").is_err());
+    }
+
+    #[test]
+    fn default_headers_carry_configured_user_agent() {
+        let config = Config {
+            http_user_agent: "arb-bot/test (ops@example.com)".to_string(),
+            http_extra_headers: vec![("X-Bot-Id".to_string(), "arb-1".to_string())],
+            ..test_config()
+        };
+
+        let headers = default_headers(&config);
+        assert_eq!(headers[USER_AGENT], "arb-bot/test (ops@example.com)");
+        assert_eq!(headers["x-bot-id"], "arb-1");
+        assert_eq!(default_headers(&test_config())[USER_AGENT], DEFAULT_HTTP_USER_AGENT);
+    }
+
+    #[test]
+    fn parse_server_time_and_skew() {
+        assert_eq!(parse_server_time("1765301400\n"), Some(1765301400));
+        assert_eq!(parse_server_time("\"1765301400\""), Some(1765301400));
+        assert_eq!(parse_server_time("not a time"), None);
+
+        let server = parse_server_time("1765301400").unwrap();
+        assert_eq!(clock_skew_seconds(server, 1765301403), 3);
+        assert_eq!(clock_skew_seconds(server, 1765301398), -2);
+    }
+
+    #[test]
+    fn endpoint_joins_optional_base_path() {
+        let base = "https://clob.polymarket.com";
+        assert_eq!(join_endpoint(base, "", "order"), "https://clob.polymarket.com/order");
+        assert_eq!(join_endpoint(base, "/v1", "order/0xabc"), format!("{}/v1/order/0xabc", base));
+        assert_eq!(join_endpoint("https://proxy/", "/v1/", "/book"), "https://proxy/v1/book");
+        let staging = join_endpoint(base, "//staging//v2", "book");
+        assert_eq!(staging, format!("{}/staging/v2/book", base));
+
+        let client = PolymarketClient::new(&Config {
+            api_base_path: "v1".to_string(),
+            ..test_config()
+        });
+        assert_eq!(client.endpoint("time"), format!("{}/v1/time", base));
+    }
+
+    #[test]
+    fn parse_single_price_responses() {
+        assert_eq!(parse_single_price(r#"{"price":"0.48"}"#, "price"), Some(Decimal::new(48, 2)));
+        assert_eq!(parse_single_price(r#"{"mid":0.475}"#, "mid"), Some(Decimal::new(475, 3)));
+        assert_eq!(parse_single_price(r#"{"price":"0.48"}"#, "mid"), None);
+        assert_eq!(parse_single_price(r#"{"price":null}"#, "price"), None);
+        assert_eq!(parse_single_price("not json", "price"), None);
+    }
+
+    #[test]
+    fn book_timestamp_becomes_as_of() {
+        let client = PolymarketClient::new(&test_config());
+        let book = |json: serde_json::Value| {
+            let response: OrderBookResponse = serde_json::from_value(json).unwrap();
+            client.convert_order_book("up-token", response)
+        };
+
+        let stamped = book(serde_json::json!({"asks": [], "timestamp": "1765301400123"}));
+        let as_of = stamped.as_of.unwrap();
+        assert_eq!(as_of.unix_timestamp_nanos(), 1_765_301_400_123_000_000);
+        assert_eq!(stamped.server_age_ms(as_of + time::Duration::seconds(2)), Some(2000));
+
+        assert!(book(serde_json::json!({"timestamp": 1765301400123i64})).as_of.is_some());
+        assert!(book(serde_json::json!({"asks": []})).as_of.is_none());
+    }
+
+    #[test]
+    fn order_book_payload_shapes() {
+        let client = PolymarketClient::new(&test_config());
+        let book = |json: serde_json::Value| {
+            let payload: OrderBookPayload = serde_json::from_value(json).unwrap();
+            client.convert_order_book("up-token", payload.into_book("up-token").unwrap())
+        };
+        let levels = serde_json::json!({
+            "asset_id": "up-token",
+            "bids": [{"price": "0.47", "size": "100"}],
+            "asks": [{"price": "0.49", "size": "100"}, {"price": "0.48", "size": "50"}],
+        });
+
+        let plain = book(levels.clone());
+        assert_eq!(plain.best_bid(), Some(Decimal::new(47, 2)));
+        assert_eq!(plain.best_ask(), Some(Decimal::new(48, 2)));
+
+        let wrapped = book(serde_json::json!({"book": levels.clone()}));
+        assert_eq!(wrapped.best_ask(), Some(Decimal::new(48, 2)));
+        assert_eq!(wrapped.total_ask_liquidity(), Decimal::from(150));
+
+        let other = serde_json::json!({
+            "asset_id": "down-token",
+            "asks": [{"price": "0.9", "size": "10"}],
+        });
+        let listed = book(serde_json::json!([other, levels]));
+        assert_eq!(listed.best_ask(), Some(Decimal::new(48, 2)));
+        // Another token's book is never taken for this one
+        let unmatched = book(serde_json::json!([other, {"asset_id": "down-2", "asks": []}]));
+        assert!(unmatched.bids.is_empty() && unmatched.asks.is_empty());
+        let untagged = book(serde_json::json!([other, {"asks": [{"price": "0.5", "size": "1"}]}]));
+        assert_eq!(untagged.best_ask(), Some(Decimal::new(5, 1)));
+
+        let empty = book(serde_json::json!({}));
+        assert!(empty.bids.is_empty() && empty.asks.is_empty());
+        assert!(book(serde_json::json!([])).asks.is_empty());
+
+        // A bare list of levels is not a list of books
+        let payload: OrderBookPayload =
+            serde_json::from_value(serde_json::json!([{"price": "0.48", "size": "50"}])).unwrap();
+        assert!(matches!(payload.into_book("up-token"), Err(MarketError::ParseError(_))));
+    }
+
+    #[test]
+    fn rapid_nonces_are_distinct() {
+        let client = PolymarketClient::new(&test_config());
+        let clone = client.clone();
+
+        let first = client.next_nonce();
+        let second = clone.next_nonce();
+        let third = client.next_nonce();
+
+        assert!(first < second && second < third);
+    }
+
+    #[test]
+    fn duplicate_order_within_window_is_rejected() {
+        let mut config = test_config();
+        config.order_dedup_window_ms = 60_000;
+        let client = PolymarketClient::new(&config);
+        let params = OrderParams::buy("up-token", Decimal::new(48, 2), Decimal::new(10, 0));
+
+        // A twin is refused while the first submission is in flight
+        let reservation = client.reserve_order(&params).unwrap();
+        assert!(matches!(
+            client.reserve_order(&params),
+            Err(TradingError::DuplicateOrder(_))
+        ));
+
+        // A rejected order releases its entry
+        drop(reservation);
+        let mut reservation = client.reserve_order(&params).unwrap();
+
+        // Only a submitted order blocks its twin after the submission ends
+        reservation.keep();
+        drop(reservation);
+        assert!(matches!(
+            client.reserve_order(&params),
+            Err(TradingError::DuplicateOrder(_))
+        ));
+
+        // A different price or side is a different order
+        let repriced = OrderParams::buy("up-token", Decimal::new(49, 2), Decimal::new(10, 0));
+        assert!(client.reserve_order(&repriced).is_ok());
+        let sell = OrderParams::sell("up-token", Decimal::new(48, 2), Decimal::new(10, 0));
+        assert!(client.reserve_order(&sell).is_ok());
+    }
+
+    #[test]
+    fn dedup_guard_disabled_by_default() {
+        let client = PolymarketClient::new(&test_config());
+        let params = OrderParams::buy("up-token", Decimal::new(48, 2), Decimal::new(10, 0));
+
+        client.reserve_order(&params).unwrap().keep();
+        assert!(client.reserve_order(&params).is_ok());
+    }
+
+    #[test]
+    fn wei_to_units_converts_six_decimals() {
+        assert_eq!(wei_to_units("25000000", 6), Decimal::new(25, 0));
+        assert_eq!(wei_to_units(" 1234567 ", 6), Decimal::new(1_234_567, 6));
+        assert_eq!(wei_to_units("25", 0), Decimal::new(25, 0));
+
+        let response: BalanceAllowanceResponse =
+            serde_json::from_str(r#"{"balance":"10500000"}"#).unwrap();
+        assert_eq!(response.balance_units(6), Decimal::new(105, 1));
+        assert_eq!(response.allowance_units(6), Decimal::ZERO);
+    }
+
+    #[test]
+    fn wei_to_units_treats_malformed_input_as_zero() {
+        assert_eq!(wei_to_units("", 6), Decimal::ZERO);
+        assert_eq!(wei_to_units("12abc", 6), Decimal::ZERO);
+        // More digits than a Decimal can hold
+        assert_eq!(wei_to_units(&"9".repeat(40), 6), Decimal::ZERO);
+        // Scale beyond Decimal's 28 places
+        assert_eq!(wei_to_units("1", 29), Decimal::ZERO);
+    }
+
     #[test]
     fn get_address_works() {
         let config = test_config();
@@ -1,44 +1,183 @@
 //! Market discovery strategies for finding active BTC 15-minute markets.
 
+use std::time::{Duration, Instant};
+
+use rand::Rng;
 use regex::Regex;
+use rust_decimal::Decimal;
 use serde_json::Value;
+use time::format_description::well_known::Rfc3339;
 use time::OffsetDateTime;
-use tracing::{debug, info, instrument};
+use tracing::{debug, info, instrument, warn};
 
-use super::types::{GammaMarket, Market, MarketData};
+use super::types::{ClobMarket, GammaMarket, Market, MarketData, TokenData};
 use crate::error::MarketError;
 
 /// 15-minute window duration in seconds.
 const BTC_15M_WINDOW: i64 = 900;
 
+/// How far into a window the previous window's market is still accepted.
+///
+/// Covers a local clock running slightly ahead of Polymarket's right after a boundary.
+const PREVIOUS_WINDOW_GRACE_SECONDS: i64 = 60;
+
 /// Polymarket event base URL.
 const POLYMARKET_EVENT_URL: &str = "https://polymarket.com/event";
 
 /// Gamma API base URL.
-const GAMMA_API_URL: &str = "https://gamma-api.polymarket.com/markets";
+pub(crate) const GAMMA_API_URL: &str = "https://gamma-api.polymarket.com/markets";
 
 /// Crypto 15M page URL.
 const CRYPTO_15M_URL: &str = "https://polymarket.com/crypto/15M";
 
+/// CLOB API base URL.
+const CLOB_API_URL: &str = "https://clob.polymarket.com";
+
+/// URLs queried by market discovery.
+#[derive(Debug, Clone)]
+pub struct DiscoveryEndpoints {
+    /// Event page base URL (slug is appended).
+    pub event_url: String,
+    /// Gamma API markets URL.
+    pub gamma_url: String,
+    /// Crypto 15M listing page URL.
+    pub crypto_page_url: String,
+    /// CLOB API base URL, queried for a market's tokens when the payload lacks them.
+    pub clob_url: String,
+}
+
+impl Default for DiscoveryEndpoints {
+    fn default() -> Self {
+        Self {
+            event_url: POLYMARKET_EVENT_URL.to_string(),
+            gamma_url: GAMMA_API_URL.to_string(),
+            crypto_page_url: CRYPTO_15M_URL.to_string(),
+            clob_url: CLOB_API_URL.to_string(),
+        }
+    }
+}
+
+/// Retry schedule for market discovery.
+///
+/// Around window boundaries the next market can appear a few seconds late, so
+/// discovery retries with short jittered backoff before giving up.
+#[derive(Debug, Clone, Copy)]
+pub struct DiscoveryRetry {
+    /// Delay before the first retry.
+    pub initial_delay: Duration,
+    /// Maximum delay between retries.
+    pub max_delay: Duration,
+    /// Total time to keep retrying before returning an error.
+    pub max_duration: Duration,
+}
+
+impl Default for DiscoveryRetry {
+    fn default() -> Self {
+        Self {
+            initial_delay: Duration::from_millis(500),
+            max_delay: Duration::from_secs(4),
+            max_duration: Duration::from_secs(20),
+        }
+    }
+}
+
+impl DiscoveryRetry {
+    /// Backoff before retry `attempt` (0-based): doubling, capped, with up to 50% jitter.
+    pub fn delay(&self, attempt: u32) -> Duration {
+        let base = self
+            .initial_delay
+            .saturating_mul(2u32.saturating_pow(attempt))
+            .min(self.max_delay);
+        let half_ms = base.as_millis() as u64 / 2;
+        let jitter_ms = rand::thread_rng().gen_range(0..=half_ms);
+        Duration::from_millis(half_ms + jitter_ms)
+    }
+}
+
 /// Find active BTC 15-minute market using multiple strategies.
-#[instrument(skip(client))]
-pub async fn discover_active_market(client: &reqwest::Client) -> Result<Market, MarketError> {
-    // Strategy 1: Computed slugs for current/next windows
+///
+/// Retries with [`DiscoveryRetry::default`] backoff before giving up. Requests
+/// carry `client`'s default headers, so build it with
+/// [`default_headers`](super::client::default_headers) to send the configured User-Agent.
+/// `window_offset_seconds` is added to the local clock before computing window slugs.
+pub async fn discover_active_market(
+    client: &reqwest::Client,
+    window_offset_seconds: i64,
+) -> Result<Market, MarketError> {
+    discover_with_retry(
+        client,
+        &DiscoveryEndpoints::default(),
+        DiscoveryRetry::default(),
+        window_offset_seconds,
+    )
+    .await
+}
+
+/// Run discovery against `endpoints`, retrying until `retry.max_duration` elapses.
+pub async fn discover_with_retry(
+    client: &reqwest::Client,
+    endpoints: &DiscoveryEndpoints,
+    retry: DiscoveryRetry,
+    window_offset_seconds: i64,
+) -> Result<Market, MarketError> {
+    let start = Instant::now();
+    let mut attempt = 0u32;
+
+    loop {
+        attempt += 1;
+        let err = match discover_once(client, endpoints, window_offset_seconds).await {
+            Ok(market) => {
+                if attempt > 1 {
+                    info!(slug = %market.slug, attempts = attempt, "Market found after retrying");
+                }
+                return Ok(market);
+            }
+            Err(e) => e,
+        };
+
+        let delay = retry.delay(attempt - 1);
+        if start.elapsed() + delay > retry.max_duration {
+            warn!(attempts = attempt, error = %err, "Market discovery gave up");
+            return Err(err);
+        }
+
+        info!(
+            attempt = attempt,
+            delay_ms = delay.as_millis() as u64,
+            "No active market yet, retrying discovery"
+        );
+        tokio::time::sleep(delay).await;
+    }
+}
+
+/// Try each discovery strategy once.
+#[instrument(skip(client, endpoints))]
+async fn discover_once(
+    client: &reqwest::Client,
+    endpoints: &DiscoveryEndpoints,
+    window_offset_seconds: i64,
+) -> Result<Market, MarketError> {
+    // Strategy 1: Computed slugs for current/previous/next windows
     info!("Searching for current BTC 15min market...");
 
-    if let Ok(market) = try_computed_slugs(client).await {
-        info!(slug = %market.slug, "Found market via computed slug");
+    let now = OffsetDateTime::now_utc().unix_timestamp();
+    if let Ok(market) = try_computed_slugs(client, endpoints, now, window_offset_seconds).await {
+        info!(
+            slug = %market.slug,
+            window_offset_seconds,
+            "Found market via computed slug"
+        );
         return Ok(market);
     }
 
     // Strategy 2: Gamma API
-    if let Ok(market) = try_gamma_api(client).await {
+    if let Ok(market) = try_gamma_api(client, endpoints).await {
         info!(slug = %market.slug, "Found market via Gamma API");
         return Ok(market);
     }
 
     // Strategy 3: Page scrape
-    if let Ok(market) = try_page_scrape(client).await {
+    if let Ok(market) = try_page_scrape(client, endpoints).await {
         info!(slug = %market.slug, "Found market via page scrape");
         return Ok(market);
     }
@@ -46,22 +185,40 @@ pub async fn discover_active_market(client: &reqwest::Client) -> Result<Market,
     Err(MarketError::NoActiveMarketFound)
 }
 
-/// Try computed slugs for current and upcoming 15-minute windows.
-#[instrument(skip(client))]
-async fn try_computed_slugs(client: &reqwest::Client) -> Result<Market, MarketError> {
-    let now = OffsetDateTime::now_utc().unix_timestamp();
+/// Start of the 15-minute window containing `now` shifted by `offset_seconds`.
+fn window_start(now: i64, offset_seconds: i64) -> i64 {
+    (now + offset_seconds).div_euclid(BTC_15M_WINDOW) * BTC_15M_WINDOW
+}
 
-    for i in 0..7 {
-        let ts = now + (i * BTC_15M_WINDOW);
-        let ts_rounded = (ts / BTC_15M_WINDOW) * BTC_15M_WINDOW;
+/// Try computed slugs for the current window, the previous one, then upcoming windows.
+///
+/// `now` is shifted by `offset_seconds` to line up with Polymarket's window boundaries.
+/// The previous window is only accepted within [`PREVIOUS_WINDOW_GRACE_SECONDS`] of
+/// its close, in case the local clock runs ahead.
+#[instrument(skip(client, endpoints))]
+async fn try_computed_slugs(
+    client: &reqwest::Client,
+    endpoints: &DiscoveryEndpoints,
+    now: i64,
+    offset_seconds: i64,
+) -> Result<Market, MarketError> {
+    let current = window_start(now, offset_seconds);
+    let now = now + offset_seconds;
+
+    for i in [0, -1, 1, 2, 3, 4, 5, 6] {
+        let ts_rounded = current + i * BTC_15M_WINDOW;
         let slug = format!("btc-updown-15m-{}", ts_rounded);
+        let grace = if i < 0 { PREVIOUS_WINDOW_GRACE_SECONDS } else { 0 };
 
         debug!(slug = %slug, "Checking computed slug");
 
-        match fetch_market_from_slug(client, &slug).await {
+        match fetch_event_market(client, endpoints, &slug).await {
             Ok(market) => {
                 // Check if market is still open
-                if now < ts_rounded + BTC_15M_WINDOW {
+                if now < ts_rounded + BTC_15M_WINDOW + grace {
+                    if i < 0 {
+                        warn!(slug = %slug, "Using previous window; local clock may be ahead");
+                    }
                     return Ok(market);
                 }
                 debug!(slug = %slug, "Market exists but is closed");
@@ -76,12 +233,14 @@ async fn try_computed_slugs(client: &reqwest::Client) -> Result<Market, MarketEr
 }
 
 /// Try to find market via Gamma API.
-#[instrument(skip(client))]
-async fn try_gamma_api(client: &reqwest::Client) -> Result<Market, MarketError> {
+#[instrument(skip(client, endpoints))]
+async fn try_gamma_api(
+    client: &reqwest::Client,
+    endpoints: &DiscoveryEndpoints,
+) -> Result<Market, MarketError> {
     let response = client
-        .get(GAMMA_API_URL)
+        .get(&endpoints.gamma_url)
         .query(&[("closed", "false"), ("limit", "500")])
-        .header("User-Agent", "Mozilla/5.0")
         .send()
         .await?;
 
@@ -116,20 +275,16 @@ async fn try_gamma_api(client: &reqwest::Client) -> Result<Market, MarketError>
     candidates.sort_by_key(|(ts, _)| *ts);
 
     let (_, slug) = candidates.into_iter().next().expect("non-empty candidates");
-    fetch_market_from_slug(client, &slug).await
+    fetch_event_market(client, endpoints, &slug).await
 }
 
 /// Try to find market via page scraping.
-#[instrument(skip(client))]
-async fn try_page_scrape(client: &reqwest::Client) -> Result<Market, MarketError> {
-    let response = client
-        .get(CRYPTO_15M_URL)
-        .header(
-            "User-Agent",
-            "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36",
-        )
-        .send()
-        .await?;
+#[instrument(skip(client, endpoints))]
+async fn try_page_scrape(
+    client: &reqwest::Client,
+    endpoints: &DiscoveryEndpoints,
+) -> Result<Market, MarketError> {
+    let response = client.get(&endpoints.crypto_page_url).send().await?;
 
     let text = response.text().await?;
     let now = OffsetDateTime::now_utc().unix_timestamp();
@@ -150,14 +305,14 @@ async fn try_page_scrape(client: &reqwest::Client) -> Result<Market, MarketError
     for ts in &timestamps {
         if now < ts + BTC_15M_WINDOW {
             let slug = format!("btc-updown-15m-{}", ts);
-            if let Ok(market) = fetch_market_from_slug(client, &slug).await {
+            if let Ok(market) = fetch_event_market(client, endpoints, &slug).await {
                 return Ok(market);
             }
         }
     }
 
     // If no open markets, try __NEXT_DATA__ extraction
-    if let Some(market) = try_next_data_extraction(&text, client).await {
+    if let Some(market) = try_next_data_extraction(&text, client, endpoints).await {
         return Ok(market);
     }
 
@@ -165,7 +320,11 @@ async fn try_page_scrape(client: &reqwest::Client) -> Result<Market, MarketError
 }
 
 /// Try to extract market from __NEXT_DATA__ script tag.
-async fn try_next_data_extraction(html: &str, client: &reqwest::Client) -> Option<Market> {
+async fn try_next_data_extraction(
+    html: &str,
+    client: &reqwest::Client,
+    endpoints: &DiscoveryEndpoints,
+) -> Option<Market> {
     let pattern =
         Regex::new(r#"<script id="__NEXT_DATA__"[^>]*>(.*?)</script>"#).expect("valid regex");
 
@@ -175,7 +334,7 @@ async fn try_next_data_extraction(html: &str, client: &reqwest::Client) -> Optio
                 // Navigate through the nested structure to find slugs
                 if let Some(slugs) = find_btc_slugs_in_json(&data) {
                     for slug in slugs {
-                        if let Ok(market) = fetch_market_from_slug(client, &slug).await {
+                        if let Ok(market) = fetch_event_market(client, endpoints, &slug).await {
                             return Some(market);
                         }
                     }
@@ -222,20 +381,29 @@ fn find_btc_slugs_in_json(value: &Value) -> Option<Vec<String>> {
 }
 
 /// Fetch market information from a slug.
-#[instrument(skip(client))]
 pub async fn fetch_market_from_slug(
     client: &reqwest::Client,
     slug: &str,
+) -> Result<Market, MarketError> {
+    fetch_event_market(client, &DiscoveryEndpoints::default(), slug).await
+}
+
+/// Fetch market information from the event page, falling back to the Gamma API.
+///
+/// The page embeds the market in a `__NEXT_DATA__` payload whose layout can
+/// change with a front-end redesign; when it cannot be located, the market is
+/// queried from Gamma by slug instead.
+#[instrument(skip(client, endpoints))]
+async fn fetch_event_market(
+    client: &reqwest::Client,
+    endpoints: &DiscoveryEndpoints,
+    slug: &str,
 ) -> Result<Market, MarketError> {
     // Strip query params if present
     let slug = slug.split('?').next().expect("non-empty slug");
-    let url = format!("{}/{}", POLYMARKET_EVENT_URL, slug);
+    let url = format!("{}/{}", endpoints.event_url, slug);
 
-    let response = client
-        .get(&url)
-        .header("User-Agent", "Mozilla/5.0")
-        .send()
-        .await?;
+    let response = client.get(&url).send().await?;
 
     if !response.status().is_success() {
         return Err(MarketError::FetchFailed {
@@ -246,10 +414,109 @@ pub async fn fetch_market_from_slug(
 
     let text = response.text().await?;
 
-    // Extract __NEXT_DATA__ JSON payload
+    match parse_event_page(&text, slug) {
+        Ok(data) => verified_market(client, endpoints, slug, data).await,
+        Err(e) => {
+            warn!(slug = %slug, error = %e, "Event page payload not found, querying Gamma API");
+            fetch_gamma_market(client, endpoints, slug).await
+        }
+    }
+}
+
+/// Fetch a market by slug from the Gamma API.
+#[instrument(skip(client, endpoints))]
+async fn fetch_gamma_market(
+    client: &reqwest::Client,
+    endpoints: &DiscoveryEndpoints,
+    slug: &str,
+) -> Result<Market, MarketError> {
+    let response = client.get(&endpoints.gamma_url).query(&[("slug", slug)]).send().await?;
+
+    if !response.status().is_success() {
+        return Err(MarketError::FetchFailed {
+            slug: slug.to_string(),
+            reason: format!("Gamma API HTTP {}", response.status()),
+        });
+    }
+
+    let markets: Vec<MarketData> = response.json().await.map_err(|e| {
+        MarketError::ParseError(format!("Failed to parse Gamma API response: {}", e))
+    })?;
+    let data = markets
+        .into_iter()
+        .find(|m| m.slug.as_deref() == Some(slug))
+        .ok_or_else(|| MarketError::FetchFailed {
+            slug: slug.to_string(),
+            reason: "Market not found in Gamma API".to_string(),
+        })?;
+
+    verified_market(client, endpoints, slug, data).await
+}
+
+/// Build a market from its payload, taking the token metadata from the CLOB
+/// when the payload has none, so the token pair can always be checked.
+async fn verified_market(
+    client: &reqwest::Client,
+    endpoints: &DiscoveryEndpoints,
+    slug: &str,
+    mut data: MarketData,
+) -> Result<Market, MarketError> {
+    if data.tokens.is_none() {
+        let condition_id = data.condition_id.clone().ok_or_else(|| {
+            MarketError::ParseError(format!(
+                "{} has neither token metadata nor a conditionId to verify its tokens",
+                slug
+            ))
+        })?;
+        data.tokens = Some(fetch_clob_tokens(client, &endpoints.clob_url, &condition_id).await?);
+    }
+
+    market_from_data(slug, data)
+}
+
+/// Fetch a condition's outcome tokens from the CLOB `/markets/{condition_id}` endpoint.
+#[instrument(skip(client, clob_url))]
+async fn fetch_clob_tokens(
+    client: &reqwest::Client,
+    clob_url: &str,
+    condition_id: &str,
+) -> Result<Vec<TokenData>, MarketError> {
+    let url = format!("{}/markets/{}", clob_url.trim_end_matches('/'), condition_id);
+    let response = client.get(&url).send().await?;
+
+    if !response.status().is_success() {
+        return Err(MarketError::FetchFailed {
+            slug: condition_id.to_string(),
+            reason: format!("CLOB markets HTTP {}", response.status()),
+        });
+    }
+
+    let market: ClobMarket = response.json().await.map_err(|e| {
+        MarketError::ParseError(format!("Failed to parse CLOB market response: {}", e))
+    })?;
+    if market.condition_id.as_deref() != Some(condition_id) {
+        return Err(MarketError::ParseError(format!(
+            "CLOB returned condition {:?} for {}",
+            market.condition_id, condition_id
+        )));
+    }
+
+    // The listing is the condition's own, so each token settles on it
+    Ok(market
+        .tokens
+        .into_iter()
+        .map(|token| TokenData {
+            condition_id: Some(condition_id.to_string()),
+            ..token
+        })
+        .collect())
+}
+
+/// Extract the market for `slug` from an event page's `__NEXT_DATA__` payload.
+fn parse_event_page(text: &str, slug: &str) -> Result<MarketData, MarketError> {
     let pattern =
         Regex::new(r#"<script id="__NEXT_DATA__"[^>]*>(.*?)</script>"#).expect("valid regex");
-    let captures = pattern.captures(&text).ok_or_else(|| {
+    let captures = pattern.captures(text).ok_or_else(|| {
         MarketError::ParseError("__NEXT_DATA__ payload not found on page".to_string())
     })?;
 
@@ -283,19 +550,26 @@ pub async fn fetch_market_from_slug(
         }
     }
 
-    let data = market_data.ok_or_else(|| MarketError::FetchFailed {
+    market_data.ok_or_else(|| MarketError::FetchFailed {
         slug: slug.to_string(),
         reason: "Market slug not found in dehydrated state".to_string(),
-    })?;
+    })
+}
 
+/// Build a market from its payload, checking the two tokens are one condition's pair.
+fn market_from_data(slug: &str, data: MarketData) -> Result<Market, MarketError> {
     // Validate token IDs
-    let clob_tokens = data.clob_token_ids.ok_or_else(|| MarketError::ParseError("No clobTokenIds".to_string()))?;
+    let clob_tokens = data
+        .clob_token_ids
+        .clone()
+        .ok_or_else(|| MarketError::ParseError("No clobTokenIds".to_string()))?;
     if clob_tokens.len() != 2 {
         return Err(MarketError::ParseError(format!(
             "Expected 2 token IDs, got {}",
             clob_tokens.len()
         )));
     }
+    check_token_pair(&data, &clob_tokens[0], &clob_tokens[1])?;
 
     // Extract timestamp from slug
     let ts_pattern = Regex::new(r"btc-updown-15m-(\d+)").expect("valid regex");
@@ -305,17 +579,80 @@ pub async fn fetch_market_from_slug(
         .and_then(|m| m.as_str().parse::<i64>().ok())
         .ok_or_else(|| MarketError::ParseError("Could not parse timestamp from slug".to_string()))?;
 
+    let end_timestamp = start_timestamp + BTC_15M_WINDOW;
+    let accepting_orders_until = accepting_orders_until(&data, start_timestamp, end_timestamp);
+
     Ok(Market {
         slug: slug.to_string(),
         id: data.id.unwrap_or_default(),
         up_token_id: clob_tokens[0].clone(),
         down_token_id: clob_tokens[1].clone(),
         start_timestamp,
-        end_timestamp: start_timestamp + BTC_15M_WINDOW,
+        end_timestamp,
         question: data.question,
+        neg_risk: data.neg_risk,
+        condition_id: data.condition_id,
+        accepting_orders_until,
+        tick_size: data.order_price_min_tick_size.filter(|tick| *tick > Decimal::ZERO),
     })
 }
 
+/// When the market stops accepting orders, if that is before its close.
+///
+/// A market flagged as not accepting orders has no entry window at all; a
+/// cutoff that doesn't parse or falls outside the window is ignored, leaving
+/// the close as the deadline.
+fn accepting_orders_until(data: &MarketData, start: i64, end: i64) -> Option<i64> {
+    if data.accepting_orders == Some(false) {
+        return Some(start);
+    }
+    let cutoff = data.accepting_orders_timestamp.as_deref()?;
+    let cutoff = OffsetDateTime::parse(cutoff, &Rfc3339).ok()?.unix_timestamp();
+    (start < cutoff && cutoff < end).then_some(cutoff)
+}
+
+/// Check the UP and DOWN tokens are the complementary pair of this market.
+///
+/// Buying tokens of two different conditions is not a hedge, so a payload whose
+/// token metadata disagrees on the condition or market, or that has none to
+/// check against, is rejected.
+fn check_token_pair(data: &MarketData, up: &str, down: &str) -> Result<(), MarketError> {
+    if up == down {
+        return Err(MarketError::ParseError(format!("UP and DOWN token IDs are both {}", up)));
+    }
+    let tokens = data.tokens.as_ref().ok_or_else(|| {
+        MarketError::ParseError("no token metadata to verify the UP/DOWN pair".to_string())
+    })?;
+
+    let find = |token_id: &str| -> Result<&TokenData, MarketError> {
+        tokens.iter().find(|t| t.token_id == token_id).ok_or_else(|| {
+            MarketError::ParseError(format!("token {} not listed in market tokens", token_id))
+        })
+    };
+    let (up_token, down_token) = (find(up)?, find(down)?);
+
+    // Only fields present on both sides can disagree
+    let differ =
+        |a: &Option<String>, b: &Option<String>| matches!((a, b), (Some(a), Some(b)) if a != b);
+    for token in [up_token, down_token] {
+        if differ(&token.condition_id, &data.condition_id) || differ(&token.market, &data.id) {
+            return Err(MarketError::ParseError(format!(
+                "token {} belongs to condition {:?}, not this market's {:?}",
+                token.token_id, token.condition_id, data.condition_id
+            )));
+        }
+    }
+    if differ(&up_token.condition_id, &down_token.condition_id)
+        || differ(&up_token.market, &down_token.market)
+    {
+        return Err(MarketError::ParseError(format!(
+            "UP token {} and DOWN token {} belong to different markets",
+            up, down
+        )));
+    }
+    Ok(())
+}
+
 /// Get the next market slug based on current slug.
 pub fn next_slug(slug: &str) -> Result<String, MarketError> {
     let pattern = Regex::new(r"^(.+-)?(\d+)$").expect("valid regex");
@@ -357,4 +694,326 @@ mod tests {
         let slugs = find_btc_slugs_in_json(&json).unwrap();
         assert_eq!(slugs, vec!["btc-updown-15m-123"]);
     }
+
+    #[test]
+    fn retry_delay_doubles_with_jitter_and_caps() {
+        let retry = DiscoveryRetry::default();
+
+        for _ in 0..20 {
+            let first = retry.delay(0);
+            assert!(first >= Duration::from_millis(250) && first <= Duration::from_millis(500));
+
+            let capped = retry.delay(10);
+            assert!(capped >= Duration::from_secs(2) && capped <= Duration::from_secs(4));
+        }
+    }
+
+    fn market_data(tokens: serde_json::Value) -> MarketData {
+        serde_json::from_value(serde_json::json!({
+            "slug": "btc-updown-15m-1765301400",
+            "id": "market-id",
+            "conditionId": "0xcondition",
+            "clobTokenIds": ["up-token", "down-token"],
+            "tokens": tokens,
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn market_with_matching_tokens_is_accepted() {
+        let data = market_data(serde_json::json!([
+            {"token_id": "up-token", "outcome": "Up", "conditionId": "0xcondition"},
+            {"token_id": "down-token", "outcome": "Down", "conditionId": "0xcondition"},
+        ]));
+
+        let market = market_from_data("btc-updown-15m-1765301400", data).unwrap();
+
+        assert_eq!(market.up_token_id, "up-token");
+        assert_eq!(market.condition_id.as_deref(), Some("0xcondition"));
+    }
+
+    #[test]
+    fn accepting_orders_cutoff_is_parsed_within_the_window() {
+        let slug = "btc-updown-15m-1765301400";
+        let with = |mut fields: serde_json::Value| {
+            fields["clobTokenIds"] = serde_json::json!(["up-token", "down-token"]);
+            fields["tokens"] =
+                serde_json::json!([{"token_id": "up-token"}, {"token_id": "down-token"}]);
+            market_from_data(slug, serde_json::from_value(fields).unwrap()).unwrap()
+        };
+
+        // Orders stop 30s before the 1765302300 close
+        let market = with(serde_json::json!({
+            "acceptingOrders": true,
+            "acceptingOrdersTimestamp": "2025-12-09T17:44:30Z",
+        }));
+        assert_eq!(market.accepting_orders_until, Some(1765302270));
+        assert_eq!(market.entry_deadline(), 1765302270);
+
+        // Absent or outside the window, the close is the deadline
+        let market = with(serde_json::json!({}));
+        assert_eq!(market.entry_deadline(), market.end_timestamp);
+        let market = with(serde_json::json!({"acceptingOrdersTimestamp": "2025-12-09T16:00:00Z"}));
+        assert_eq!(market.accepting_orders_until, None);
+
+        let market = with(serde_json::json!({"acceptingOrders": false}));
+        assert_eq!(market.entry_deadline(), market.start_timestamp);
+    }
+
+    #[test]
+    fn market_with_mismatched_tokens_is_rejected() {
+        let slug = "btc-updown-15m-1765301400";
+        let other_condition = market_data(serde_json::json!([
+            {"token_id": "up-token", "conditionId": "0xcondition"},
+            {"token_id": "down-token", "conditionId": "0xother"},
+        ]));
+        let unlisted = market_data(serde_json::json!([{"token_id": "up-token"}]));
+        let mut same_token = market_data(serde_json::Value::Null);
+        same_token.clob_token_ids = Some(vec!["up-token".to_string(), "up-token".to_string()]);
+        let unverified = market_data(serde_json::Value::Null);
+
+        for data in [other_condition, unlisted, same_token, unverified] {
+            assert!(matches!(market_from_data(slug, data), Err(MarketError::ParseError(_))));
+        }
+    }
+
+    /// Event page embedding `slug` in the `__NEXT_DATA__` payload.
+    fn event_page(slug: &str) -> String {
+        let payload = serde_json::json!({
+            "props": {"pageProps": {"dehydratedState": {"queries": [{"state": {"data": {
+                "markets": [{
+                    "slug": slug,
+                    "id": "market-id",
+                    "conditionId": "0xcondition",
+                    "clobTokenIds": ["up-token", "down-token"],
+                    "question": "Bitcoin Up or Down?",
+                    "negRisk": false,
+                    "orderPriceMinTickSize": 0.001
+                }]
+            }}}]}}}
+        });
+        format!(r#"<script id="__NEXT_DATA__" type="application/json">{}</script>"#, payload)
+    }
+
+    /// CLOB `/markets/{condition_id}` listing the UP and DOWN tokens for any condition.
+    async fn clob_market(
+        axum::extract::Path(condition_id): axum::extract::Path<String>,
+    ) -> axum::Json<serde_json::Value> {
+        axum::Json(serde_json::json!({
+            "condition_id": condition_id,
+            "tokens": [
+                {"token_id": "up-token", "outcome": "Up"},
+                {"token_id": "down-token", "outcome": "Down"},
+            ],
+        }))
+    }
+
+    /// Serve a mock Polymarket that 404s the first `failed_attempts` discovery passes.
+    async fn mock_polymarket(failed_attempts: u32) -> String {
+        use std::sync::atomic::{AtomicU32, Ordering};
+        use std::sync::Arc;
+
+        use axum::extract::{Path, State};
+        use axum::http::StatusCode;
+        use axum::routing::get;
+
+        // Gamma is hit once per discovery pass, so its hit count is the attempt count
+        let attempts = Arc::new(AtomicU32::new(0));
+        let event = move |attempts: State<Arc<AtomicU32>>, Path(slug): Path<String>| async move {
+            if attempts.load(Ordering::SeqCst) < failed_attempts {
+                return Err(StatusCode::NOT_FOUND);
+            }
+            Ok(event_page(&slug))
+        };
+        let router = axum::Router::new()
+            .route("/event/:slug", get(event))
+            .route("/markets/:condition_id", get(clob_market))
+            .route(
+                "/markets",
+                get(|State(attempts): State<Arc<AtomicU32>>| async move {
+                    attempts.fetch_add(1, Ordering::SeqCst);
+                    StatusCode::NOT_FOUND
+                }),
+            )
+            .route("/crypto/15M", get(|| async { StatusCode::NOT_FOUND }))
+            .with_state(attempts);
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, router).await });
+
+        format!("http://{}", addr)
+    }
+
+    /// Serve event pages for `slug` only.
+    async fn mock_event_page(slug: String) -> String {
+        use axum::extract::Path;
+        use axum::http::StatusCode;
+        use axum::routing::get;
+
+        let event = move |Path(requested): Path<String>| {
+            let page = (requested == slug).then(|| event_page(&slug));
+            async move { page.ok_or(StatusCode::NOT_FOUND) }
+        };
+        let router = axum::Router::new()
+            .route("/event/:slug", get(event))
+            .route("/markets/:condition_id", get(clob_market));
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, router).await });
+
+        format!("http://{}", addr)
+    }
+
+    fn mock_endpoints(base: &str) -> DiscoveryEndpoints {
+        DiscoveryEndpoints {
+            event_url: format!("{}/event", base),
+            gamma_url: format!("{}/markets", base),
+            crypto_page_url: format!("{}/crypto/15M", base),
+            clob_url: base.to_string(),
+        }
+    }
+
+    fn fast_retry(max_duration: Duration) -> DiscoveryRetry {
+        DiscoveryRetry {
+            initial_delay: Duration::from_millis(10),
+            max_delay: Duration::from_millis(40),
+            max_duration,
+        }
+    }
+
+    #[tokio::test]
+    async fn discovery_retries_until_market_appears() {
+        let base = mock_polymarket(2).await;
+        let client = reqwest::Client::new();
+
+        let market = discover_with_retry(
+            &client,
+            &mock_endpoints(&base),
+            fast_retry(Duration::from_secs(5)),
+            0,
+        )
+        .await
+        .unwrap();
+
+        let now = OffsetDateTime::now_utc().unix_timestamp();
+        assert!(market.slug.starts_with("btc-updown-15m-"));
+        assert!(market.start_timestamp <= now && now < market.end_timestamp);
+        assert_eq!(market.up_token_id, "up-token");
+        assert_eq!(market.neg_risk, Some(false));
+        assert_eq!(market.tick_size, Some(Decimal::new(1, 3)));
+    }
+
+    #[tokio::test]
+    async fn discovery_gives_up_after_max_duration() {
+        let base = mock_polymarket(u32::MAX).await;
+        let client = reqwest::Client::new();
+
+        let result = discover_with_retry(
+            &client,
+            &mock_endpoints(&base),
+            fast_retry(Duration::from_millis(50)),
+            0,
+        )
+        .await;
+
+        assert!(matches!(result, Err(MarketError::NoActiveMarketFound)));
+    }
+
+    #[test]
+    fn window_offset_shifts_computed_window() {
+        let window = 1765301400;
+
+        assert_eq!(window_start(window + 30, 0), window);
+        assert_eq!(window_start(window + 30, -60), window - BTC_15M_WINDOW);
+        assert_eq!(window_start(window - 30, 60), window);
+    }
+
+    #[tokio::test]
+    async fn computed_slugs_fall_back_to_previous_window() {
+        let window = 1765301400;
+        let previous = window - BTC_15M_WINDOW;
+        let base = mock_event_page(format!("btc-updown-15m-{}", previous)).await;
+        let endpoints = mock_endpoints(&base);
+        let client = reqwest::Client::new();
+
+        // Just past the boundary, the previous window's market is still taken
+        let market = try_computed_slugs(&client, &endpoints, window + 30, 0).await.unwrap();
+        assert_eq!(market.start_timestamp, previous);
+
+        // A negative offset makes it the current window outright, past the grace period
+        let market = try_computed_slugs(&client, &endpoints, window + 120, -180).await.unwrap();
+        assert_eq!(market.start_timestamp, previous);
+
+        let result = try_computed_slugs(&client, &endpoints, window + 120, 0).await;
+        assert!(matches!(result, Err(MarketError::NoActiveMarketFound)));
+    }
+
+    #[tokio::test]
+    async fn missing_page_payload_falls_back_to_gamma_by_slug() {
+        use axum::extract::Query;
+        use axum::routing::get;
+        use std::collections::HashMap;
+
+        let slug = "btc-updown-15m-1765301400";
+        // Gamma encodes the list fields as JSON strings
+        let gamma = move |Query(query): Query<HashMap<String, String>>| async move {
+            let markets = (query.get("slug").map(String::as_str) == Some(slug)).then(|| {
+                serde_json::json!({
+                    "slug": slug,
+                    "id": "market-id",
+                    "conditionId": "0xcondition",
+                    "clobTokenIds": "[\"up-token\", \"down-token\"]",
+                    "outcomes": "[\"Up\", \"Down\"]"
+                })
+            });
+            axum::Json(markets.into_iter().collect::<Vec<_>>())
+        };
+        let router = axum::Router::new()
+            .route("/event/:slug", get(|| async { "<html>redesigned page</html>" }))
+            .route("/markets", get(gamma))
+            .route("/markets/:condition_id", get(clob_market));
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let base = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move { axum::serve(listener, router).await });
+
+        let client = reqwest::Client::new();
+        let market = fetch_event_market(&client, &mock_endpoints(&base), slug).await.unwrap();
+
+        assert_eq!(market.slug, slug);
+        assert_eq!(market.up_token_id, "up-token");
+        assert_eq!(market.down_token_id, "down-token");
+    }
+
+    #[tokio::test]
+    async fn tokens_missing_from_payload_are_verified_against_the_clob() {
+        use axum::routing::get;
+
+        let slug = "btc-updown-15m-1765301400";
+        let data = |token_ids: serde_json::Value| -> MarketData {
+            serde_json::from_value(serde_json::json!({
+                "slug": slug,
+                "id": "market-id",
+                "conditionId": "0xcondition",
+                "clobTokenIds": token_ids,
+            }))
+            .unwrap()
+        };
+        let router = axum::Router::new().route("/markets/:condition_id", get(clob_market));
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let base = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move { axum::serve(listener, router).await });
+        let client = reqwest::Client::new();
+        let endpoints = mock_endpoints(&base);
+
+        let pair = data(serde_json::json!(["up-token", "down-token"]));
+        let market = verified_market(&client, &endpoints, slug, pair).await.unwrap();
+        assert_eq!(market.down_token_id, "down-token");
+
+        // A token the CLOB does not list for the condition is not part of the pair
+        let foreign = data(serde_json::json!(["up-token", "other-token"]));
+        let result = verified_market(&client, &endpoints, slug, foreign).await;
+        assert!(matches!(result, Err(MarketError::ParseError(_))), "got {:?}", result);
+    }
 }
@@ -0,0 +1,81 @@
+//! Operator allow/deny rules for discovered market slugs.
+//!
+//! Lets a deployment sit out specific windows (e.g. around scheduled news) or
+//! trade only windows matching a pattern. A skipped market is left alone and
+//! the bot waits for the next window.
+
+use regex::Regex;
+
+/// Regex rules deciding which discovered markets may be traded.
+#[derive(Debug, Clone, Default)]
+pub struct SlugFilter {
+    /// Slugs must match one of these when any are set.
+    allow: Vec<Regex>,
+    /// Slugs matching any of these are skipped.
+    deny: Vec<Regex>,
+}
+
+impl SlugFilter {
+    /// Compile allowlist and denylist patterns.
+    pub fn new(allowlist: &[String], denylist: &[String]) -> Result<Self, regex::Error> {
+        let compile = |patterns: &[String]| -> Result<Vec<Regex>, regex::Error> {
+            patterns.iter().map(|pattern| Regex::new(pattern)).collect()
+        };
+        Ok(Self {
+            allow: compile(allowlist)?,
+            deny: compile(denylist)?,
+        })
+    }
+
+    /// Why `slug` should be skipped; `None` when it may be traded.
+    ///
+    /// The denylist wins over the allowlist.
+    pub fn skip_reason(&self, slug: &str) -> Option<String> {
+        if let Some(pattern) = self.deny.iter().find(|pattern| pattern.is_match(slug)) {
+            return Some(format!("matches denylist pattern '{}'", pattern));
+        }
+        if !self.allow.is_empty() && !self.allow.iter().any(|pattern| pattern.is_match(slug)) {
+            return Some("matches no allowlist pattern".to_string());
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn patterns(patterns: &[&str]) -> Vec<String> {
+        patterns.iter().map(|pattern| pattern.to_string()).collect()
+    }
+
+    #[test]
+    fn denylisted_slug_is_skipped() {
+        let filter = SlugFilter::new(&[], &patterns(&["-1765301400$"])).unwrap();
+
+        let reason = filter.skip_reason("btc-updown-15m-1765301400").unwrap();
+        assert!(reason.contains("denylist"), "{}", reason);
+        assert_eq!(filter.skip_reason("btc-updown-15m-1765302300"), None);
+    }
+
+    #[test]
+    fn allowlisted_slug_is_accepted() {
+        let filter = SlugFilter::new(&patterns(&["^btc-updown-15m-"]), &[]).unwrap();
+
+        assert_eq!(filter.skip_reason("btc-updown-15m-1765301400"), None);
+        assert!(filter.skip_reason("eth-updown-15m-1765301400").is_some());
+
+        // Denylist overrides a matching allowlist entry
+        let filter =
+            SlugFilter::new(&patterns(&["^btc-updown-15m-"]), &patterns(&["1765301400"])).unwrap();
+        assert!(filter.skip_reason("btc-updown-15m-1765301400").is_some());
+    }
+
+    #[test]
+    fn empty_filter_accepts_everything() {
+        let filter = SlugFilter::default();
+
+        assert_eq!(filter.skip_reason("anything"), None);
+        assert!(SlugFilter::new(&patterns(&["("]), &[]).is_err());
+    }
+}
@@ -5,11 +5,13 @@
 
 use rust_decimal::Decimal;
 use std::collections::HashMap;
+use std::sync::atomic::{AtomicU32, Ordering};
 use std::sync::{Arc, Mutex};
 
 use crate::error::{MarketError, TradingError};
 use crate::market::client::PositionResponse;
 use crate::orderbook::types::{OutcomeBook, PriceLevel};
+use crate::trading::execution::OrderRequest;
 
 use super::types::Outcome;
 
@@ -48,6 +50,8 @@ pub struct MockConfig {
     pub fail_positions: bool,
     /// Simulated latency in milliseconds.
     pub latency_ms: u64,
+    /// Open orders reported as cancelled by cancel-all.
+    pub open_orders: u32,
 }
 
 /// Mock Polymarket client for testing.
@@ -61,6 +65,10 @@ pub struct MockPolymarketClient {
     positions: Arc<Mutex<Vec<MockPosition>>>,
     /// Wallet address.
     address: String,
+    /// Number of cancel-all calls made.
+    cancel_all_calls: Arc<AtomicU32>,
+    /// Number of orders submitted.
+    orders_submitted: Arc<AtomicU32>,
 }
 
 impl MockPolymarketClient {
@@ -71,6 +79,8 @@ impl MockPolymarketClient {
             order_books: Arc::new(Mutex::new(HashMap::new())),
             positions: Arc::new(Mutex::new(Vec::new())),
             address: "0xMOCK000000000000000000000000000000000001".to_string(),
+            cancel_all_calls: Arc::new(AtomicU32::new(0)),
+            orders_submitted: Arc::new(AtomicU32::new(0)),
         }
     }
 
@@ -81,6 +91,8 @@ impl MockPolymarketClient {
             order_books: Arc::new(Mutex::new(HashMap::new())),
             positions: Arc::new(Mutex::new(Vec::new())),
             address: "0xMOCK000000000000000000000000000000000001".to_string(),
+            cancel_all_calls: Arc::new(AtomicU32::new(0)),
+            orders_submitted: Arc::new(AtomicU32::new(0)),
         }
     }
 
@@ -148,6 +160,7 @@ impl MockPolymarketClient {
                 bids: mock_book.bids.clone(),
                 asks: mock_book.asks.clone(),
                 updated_at: time::OffsetDateTime::now_utc(),
+                as_of: None,
             })
         } else {
             // Return an empty book if not configured
@@ -157,10 +170,43 @@ impl MockPolymarketClient {
                 bids: Vec::new(),
                 asks: Vec::new(),
                 updated_at: time::OffsetDateTime::now_utc(),
+                as_of: None,
             })
         }
     }
 
+    /// Cancel all mock open orders, returning how many were cancelled.
+    pub async fn cancel_all_orders(&self) -> Result<u32, TradingError> {
+        self.cancel_all_calls.fetch_add(1, Ordering::SeqCst);
+
+        if self.config.latency_ms > 0 {
+            tokio::time::sleep(std::time::Duration::from_millis(self.config.latency_ms)).await;
+        }
+
+        Ok(self.config.open_orders)
+    }
+
+    /// Get the number of cancel-all calls made.
+    pub fn cancel_all_calls(&self) -> u32 {
+        self.cancel_all_calls.load(Ordering::SeqCst)
+    }
+
+    /// Accept a signed order, returning a mock order ID.
+    pub async fn submit_order(&self, _order: &OrderRequest) -> Result<String, TradingError> {
+        let n = self.orders_submitted.fetch_add(1, Ordering::SeqCst) + 1;
+
+        if self.config.latency_ms > 0 {
+            tokio::time::sleep(std::time::Duration::from_millis(self.config.latency_ms)).await;
+        }
+
+        Ok(format!("0xmockorder{}", n))
+    }
+
+    /// Get the number of orders submitted.
+    pub fn orders_submitted(&self) -> u32 {
+        self.orders_submitted.load(Ordering::SeqCst)
+    }
+
     /// Get mock positions.
     pub async fn get_positions(
         &self,
@@ -185,6 +231,7 @@ impl MockPolymarketClient {
                 asset: None,
                 size: Some(p.size.to_string()),
                 avg_price: Some(p.avg_price.to_string()),
+                cur_price: None,
             })
             .collect();
 
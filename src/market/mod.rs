@@ -3,15 +3,24 @@
 //! This module handles:
 //! - Market types and data structures
 //! - Market discovery (finding active markets)
+//! - Slug allow/deny rules for discovered markets
 //! - Polymarket API client
 //! - Mock client for testing
 
 pub mod client;
 pub mod discovery;
+pub mod filter;
 pub mod mock;
 pub mod types;
 
-pub use client::PolymarketClient;
-pub use discovery::{discover_active_market, fetch_market_from_slug};
+pub use client::{
+    clock_skew_seconds, default_headers, join_endpoint, parse_server_time, parse_single_price,
+    PolymarketClient,
+};
+pub use discovery::{
+    discover_active_market, discover_with_retry, fetch_market_from_slug, DiscoveryEndpoints,
+    DiscoveryRetry,
+};
+pub use filter::SlugFilter;
 pub use mock::{MockOrderBook, MockOrderBookBuilder, MockPolymarketClient, MockPosition};
-pub use types::{Market, Outcome};
+pub use types::{Market, MarketStatus, Outcome};
@@ -1,6 +1,7 @@
 //! Market-related types for BTC 15-minute prediction markets.
 
-use serde::{Deserialize, Serialize};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Deserializer, Serialize};
 use strum::{Display, EnumString};
 use time::OffsetDateTime;
 
@@ -46,12 +47,42 @@ pub struct Market {
     pub end_timestamp: i64,
     /// Market question text.
     pub question: Option<String>,
+    /// Whether the market trades on the neg-risk exchange, if the metadata says.
+    pub neg_risk: Option<bool>,
+    /// CTF condition ID, used to redeem winning shares after resolution.
+    pub condition_id: Option<String>,
+    /// Unix timestamp when the exchange stops accepting orders, if before the close.
+    pub accepting_orders_until: Option<i64>,
+    /// Minimum price increment, if the metadata says.
+    pub tick_size: Option<Decimal>,
 }
 
 impl Market {
     /// Duration of a BTC 15-minute market in seconds.
     pub const WINDOW_SECONDS: i64 = 900;
 
+    /// Start of the 15-minute window containing the market's start time.
+    pub fn window_start(&self) -> i64 {
+        self.start_timestamp.div_euclid(Self::WINDOW_SECONDS) * Self::WINDOW_SECONDS
+    }
+
+    /// Neg-risk flag for orders, falling back to `default` when the metadata omits it.
+    pub fn neg_risk_or(&self, default: bool) -> bool {
+        self.neg_risk.unwrap_or(default)
+    }
+
+    /// Price tick size for orders, falling back to `default` when the metadata omits it.
+    pub fn tick_size_or(&self, default: Decimal) -> Decimal {
+        self.tick_size.unwrap_or(default)
+    }
+
+    /// Whether the UP and DOWN tokens can form a hedged pair: both set and distinct.
+    pub fn has_token_pair(&self) -> bool {
+        !self.up_token_id.is_empty()
+            && !self.down_token_id.is_empty()
+            && self.up_token_id != self.down_token_id
+    }
+
     /// Get the token ID for a given outcome.
     pub fn token_id(&self, outcome: Outcome) -> &str {
         match outcome {
@@ -68,13 +99,23 @@ impl Market {
 
     /// Get remaining time until market closes.
     pub fn time_remaining(&self) -> Option<std::time::Duration> {
-        let now = OffsetDateTime::now_utc().unix_timestamp();
-        let remaining = self.end_timestamp - now;
-        if remaining <= 0 {
-            None
-        } else {
-            Some(std::time::Duration::from_secs(remaining as u64))
-        }
+        remaining_until(self.end_timestamp)
+    }
+
+    /// Unix timestamp after which orders are refused: the accepting-orders
+    /// cutoff when the metadata has one, otherwise the close.
+    pub fn entry_deadline(&self) -> i64 {
+        self.accepting_orders_until.unwrap_or(self.end_timestamp)
+    }
+
+    /// Whether the metadata's accepting-orders cutoff has passed.
+    pub fn stopped_accepting_orders(&self) -> bool {
+        self.accepting_orders_until.is_some() && self.entry_time_remaining().is_none()
+    }
+
+    /// Get remaining time until the market stops accepting orders.
+    pub fn entry_time_remaining(&self) -> Option<std::time::Duration> {
+        remaining_until(self.entry_deadline())
     }
 
     /// Format remaining time as "Xm Ys" string.
@@ -91,6 +132,17 @@ impl Market {
     }
 }
 
+/// Time from now until `timestamp`, or `None` once it has passed.
+fn remaining_until(timestamp: i64) -> Option<std::time::Duration> {
+    let now = OffsetDateTime::now_utc().unix_timestamp();
+    let remaining = timestamp - now;
+    if remaining <= 0 {
+        None
+    } else {
+        Some(std::time::Duration::from_secs(remaining as u64))
+    }
+}
+
 /// Parsed market data from Polymarket API.
 #[derive(Debug, Clone, Deserialize)]
 pub struct MarketData {
@@ -99,9 +151,10 @@ pub struct MarketData {
     /// Market ID.
     pub id: Option<String>,
     /// CLOB token IDs.
-    #[serde(rename = "clobTokenIds")]
+    #[serde(rename = "clobTokenIds", default, deserialize_with = "string_list")]
     pub clob_token_ids: Option<Vec<String>>,
     /// Market outcomes.
+    #[serde(default, deserialize_with = "string_list")]
     pub outcomes: Option<Vec<String>>,
     /// Market question.
     pub question: Option<String>,
@@ -111,6 +164,73 @@ pub struct MarketData {
     /// End date (ISO format).
     #[serde(rename = "endDate")]
     pub end_date: Option<String>,
+    /// Whether the market uses the neg-risk exchange.
+    #[serde(rename = "negRisk")]
+    pub neg_risk: Option<bool>,
+    /// CTF condition ID.
+    #[serde(rename = "conditionId")]
+    pub condition_id: Option<String>,
+    /// Whether the order book is accepting orders.
+    #[serde(default, rename = "acceptingOrders")]
+    pub accepting_orders: Option<bool>,
+    /// When the order book stops accepting orders (ISO format).
+    #[serde(default, rename = "acceptingOrdersTimestamp")]
+    pub accepting_orders_timestamp: Option<String>,
+    /// Minimum price increment of the order book.
+    #[serde(default, rename = "orderPriceMinTickSize")]
+    pub order_price_min_tick_size: Option<Decimal>,
+    /// Per-token metadata, when the payload includes it.
+    #[serde(default)]
+    pub tokens: Option<Vec<TokenData>>,
+}
+
+/// Deserialize a string list given as a JSON array or JSON-encoded in a string,
+/// as the Gamma API returns `clobTokenIds` and `outcomes`.
+fn string_list<'de, D>(deserializer: D) -> Result<Option<Vec<String>>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum StringList {
+        List(Vec<String>),
+        Encoded(String),
+    }
+
+    match Option::<StringList>::deserialize(deserializer)? {
+        None => Ok(None),
+        Some(StringList::List(list)) => Ok(Some(list)),
+        Some(StringList::Encoded(text)) => {
+            serde_json::from_str(&text).map(Some).map_err(serde::de::Error::custom)
+        }
+    }
+}
+
+/// Per-token metadata in a market payload.
+#[derive(Debug, Clone, Deserialize)]
+pub struct TokenData {
+    /// CLOB token ID.
+    #[serde(alias = "tokenId")]
+    pub token_id: String,
+    /// Outcome label.
+    #[serde(default)]
+    pub outcome: Option<String>,
+    /// Condition the token settles on.
+    #[serde(default, alias = "conditionId")]
+    pub condition_id: Option<String>,
+    /// Market the token belongs to.
+    #[serde(default, alias = "marketId")]
+    pub market: Option<String>,
+}
+
+/// Market from the CLOB `/markets/{condition_id}` endpoint.
+#[derive(Debug, Clone, Deserialize)]
+pub struct ClobMarket {
+    /// CTF condition ID.
+    pub condition_id: Option<String>,
+    /// The condition's outcome tokens.
+    #[serde(default)]
+    pub tokens: Vec<TokenData>,
 }
 
 /// Market info from Gamma API.
@@ -120,6 +240,36 @@ pub struct GammaMarket {
     pub slug: Option<String>,
     /// Whether market is closed.
     pub closed: Option<bool>,
+    /// Whether market is active.
+    #[serde(default)]
+    pub active: Option<bool>,
+    /// Whether the order book is accepting orders.
+    #[serde(default, rename = "acceptingOrders")]
+    pub accepting_orders: Option<bool>,
+}
+
+impl GammaMarket {
+    /// Derive the trading status from the Gamma flags.
+    pub fn status(&self) -> MarketStatus {
+        if self.closed == Some(true) {
+            MarketStatus::Resolved
+        } else if self.active == Some(false) || self.accepting_orders == Some(false) {
+            MarketStatus::NotAcceptingOrders
+        } else {
+            MarketStatus::Open
+        }
+    }
+}
+
+/// Trading status of a market as reported by the API.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Display)]
+pub enum MarketStatus {
+    /// Open and accepting orders.
+    Open,
+    /// Not closed yet, but the book is frozen.
+    NotAcceptingOrders,
+    /// Closed (resolved or awaiting resolution).
+    Resolved,
 }
 
 #[cfg(test)]
@@ -151,9 +301,34 @@ mod tests {
             start_timestamp: 0,
             end_timestamp: 900,
             question: None,
+            neg_risk: None,
+            condition_id: None,
+            accepting_orders_until: None,
+            tick_size: None,
         };
 
         assert_eq!(market.token_id(Outcome::Up), "up-token");
         assert_eq!(market.token_id(Outcome::Down), "down-token");
     }
+
+    #[test]
+    fn gamma_market_status_parses_each_state() {
+        let fixture = r#"[
+            {"slug": "btc-updown-15m-1", "closed": false, "active": true, "acceptingOrders": true},
+            {"slug": "btc-updown-15m-2", "closed": false, "active": true, "acceptingOrders": false},
+            {"slug": "btc-updown-15m-3", "closed": true, "active": false, "acceptingOrders": false}
+        ]"#;
+
+        let markets: Vec<GammaMarket> = serde_json::from_str(fixture).unwrap();
+        let statuses: Vec<MarketStatus> = markets.iter().map(GammaMarket::status).collect();
+
+        assert_eq!(
+            statuses,
+            vec![
+                MarketStatus::Open,
+                MarketStatus::NotAcceptingOrders,
+                MarketStatus::Resolved
+            ]
+        );
+    }
 }
@@ -6,10 +6,15 @@
 //! - Opportunity detection latency
 //! - HTTP request latency
 //! - Signing operation latency
+//! - Balance and open exposure gauges
 
 use std::time::Instant;
 
-use metrics::{counter, describe_counter, describe_histogram, histogram};
+use metrics::{
+    counter, describe_counter, describe_gauge, describe_histogram, gauge, histogram,
+};
+use rust_decimal::prelude::ToPrimitive;
+use rust_decimal::Decimal;
 use tracing::debug;
 
 // === Metric Name Constants ===
@@ -40,6 +45,22 @@ pub const METRIC_OPPORTUNITIES_EXECUTED: &str = "opportunities_executed_total";
 pub const METRIC_WS_MESSAGES_RECEIVED: &str = "ws_messages_received_total";
 /// WebSocket reconnects counter metric name.
 pub const METRIC_WS_RECONNECTS: &str = "ws_reconnects_total";
+/// WebSocket binary frames counter metric name.
+pub const METRIC_WS_BINARY_FRAMES: &str = "ws_binary_frames_total";
+/// Stale WebSocket REST fallbacks counter metric name.
+pub const METRIC_WS_STALE_FALLBACKS: &str = "ws_stale_fallbacks_total";
+/// WebSocket task restarts counter metric name.
+pub const METRIC_WS_TASK_RESTARTS: &str = "ws_task_restarts_total";
+/// Coalesced WebSocket book updates counter metric name.
+pub const METRIC_WS_UPDATES_DROPPED: &str = "ws_updates_dropped_total";
+/// CLOB auth header signatures counter metric name.
+pub const METRIC_AUTH_HEADERS_SIGNED: &str = "auth_headers_signed_total";
+/// Current USDC balance gauge metric name.
+pub const METRIC_CURRENT_BALANCE: &str = "current_balance_usdc";
+/// Simulated USDC balance gauge metric name.
+pub const METRIC_SIM_BALANCE: &str = "sim_balance_usdc";
+/// Open (unhedged) exposure gauge metric name.
+pub const METRIC_OPEN_EXPOSURE: &str = "open_exposure_shares";
 
 /// Initialize all metric descriptions.
 /// Call this once at startup to register metrics with descriptions.
@@ -99,6 +120,40 @@ pub fn init_metrics() {
         METRIC_WS_RECONNECTS,
         "Total number of WebSocket reconnections"
     );
+    describe_counter!(
+        METRIC_WS_BINARY_FRAMES,
+        "Total number of WebSocket binary frames received"
+    );
+    describe_counter!(
+        METRIC_WS_STALE_FALLBACKS,
+        "Total number of REST order book fetches while the WebSocket feed was stale"
+    );
+    describe_counter!(
+        METRIC_WS_TASK_RESTARTS,
+        "Total number of WebSocket connection tasks restarted after a panic"
+    );
+    describe_counter!(
+        METRIC_WS_UPDATES_DROPPED,
+        "Total number of WebSocket price change updates coalesced under backpressure"
+    );
+    describe_counter!(
+        METRIC_AUTH_HEADERS_SIGNED,
+        "Total number of CLOB auth header sets signed (cached headers are reused)"
+    );
+
+    // Gauges
+    describe_gauge!(
+        METRIC_CURRENT_BALANCE,
+        "Current USDC balance"
+    );
+    describe_gauge!(
+        METRIC_SIM_BALANCE,
+        "Simulated USDC balance"
+    );
+    describe_gauge!(
+        METRIC_OPEN_EXPOSURE,
+        "Unhedged shares left after a failed unwind"
+    );
 
     debug!("Metrics initialized");
 }
@@ -174,6 +229,46 @@ pub fn inc_ws_reconnects() {
     counter!(METRIC_WS_RECONNECTS).increment(1);
 }
 
+/// Increment WebSocket binary frames counter.
+pub fn inc_ws_binary_frames() {
+    counter!(METRIC_WS_BINARY_FRAMES).increment(1);
+}
+
+/// Increment stale WebSocket REST fallbacks counter.
+pub fn inc_ws_stale_fallbacks() {
+    counter!(METRIC_WS_STALE_FALLBACKS).increment(1);
+}
+
+/// Increment WebSocket task restarts counter.
+pub fn inc_ws_task_restarts() {
+    counter!(METRIC_WS_TASK_RESTARTS).increment(1);
+}
+
+/// Increment coalesced WebSocket book updates counter.
+pub fn inc_ws_updates_dropped() {
+    counter!(METRIC_WS_UPDATES_DROPPED).increment(1);
+}
+
+/// Increment auth header signatures counter.
+pub fn inc_auth_headers_signed() {
+    counter!(METRIC_AUTH_HEADERS_SIGNED).increment(1);
+}
+
+/// Set the balance gauges.
+///
+/// The current balance gauge is left unchanged until a balance has been fetched.
+pub fn set_balance_gauge(current: Option<Decimal>, sim: Decimal) {
+    if let Some(current) = current {
+        gauge!(METRIC_CURRENT_BALANCE).set(current.to_f64().unwrap_or_default());
+    }
+    gauge!(METRIC_SIM_BALANCE).set(sim.to_f64().unwrap_or_default());
+}
+
+/// Set the open exposure gauge.
+pub fn set_exposure_gauge(shares: Decimal) {
+    gauge!(METRIC_OPEN_EXPOSURE).set(shares.to_f64().unwrap_or_default());
+}
+
 /// RAII guard for timing operations.
 /// Automatically records latency when dropped.
 pub struct LatencyTimer {
@@ -237,4 +332,11 @@ mod tests {
         assert!(elapsed >= 9.0); // Allow some tolerance
         // Timer will record on drop
     }
+
+    #[test]
+    fn gauges_set_without_recorder() {
+        set_balance_gauge(None, Decimal::new(100, 0));
+        set_balance_gauge(Some(Decimal::new(2550, 2)), Decimal::new(100, 0));
+        set_exposure_gauge(Decimal::ZERO);
+    }
 }
@@ -1,14 +1,17 @@
 //! Order book aggregation and fill price calculations.
 
 use rust_decimal::Decimal;
-use tracing::instrument;
+use tracing::{instrument, warn};
 
 use super::types::{FillInfo, OutcomeBook, PriceLevel};
 use crate::error::ArbitrageError;
 
 /// Calculate worst-case fill price by walking the ask book.
 ///
-/// Returns VWAP and worst price needed to fill target_size.
+/// Returns VWAP and worst price needed to fill target_size. Zero-size levels
+/// are skipped; a fill that ends at a non-positive worst price, or with a VWAP
+/// above it, comes from a malformed book and is rejected as insufficient liquidity
+/// rather than reported as a near-free fill.
 #[instrument(skip(asks), fields(target_size = %target_size))]
 pub fn calculate_fill_price(
     asks: &[PriceLevel],
@@ -28,12 +31,15 @@ pub fn calculate_fill_price(
     let mut remaining = target_size;
     let mut total_cost = Decimal::ZERO;
     let mut worst_price = Decimal::ZERO;
-    let best_price = asks.first().map(|l| l.price);
+    let best_price = asks.iter().find(|l| l.size > Decimal::ZERO).map(|l| l.price);
 
     for level in asks {
         if remaining.is_zero() {
             break;
         }
+        if level.size <= Decimal::ZERO {
+            continue;
+        }
 
         let fill_size = remaining.min(level.size);
         total_cost += fill_size * level.price;
@@ -50,6 +56,19 @@ pub fn calculate_fill_price(
 
     let vwap = total_cost / target_size;
 
+    // Asks are ascending, so a sane fill has 0 < vwap <= worst price
+    if worst_price <= Decimal::ZERO || vwap > worst_price {
+        warn!(
+            worst_price = %worst_price,
+            vwap = %vwap,
+            "Rejecting fill from malformed ask book"
+        );
+        return Err(ArbitrageError::InsufficientLiquidity {
+            required: target_size,
+            available: Decimal::ZERO,
+        });
+    }
+
     Ok(FillInfo {
         filled_size: target_size,
         total_cost,
@@ -181,6 +200,46 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn calculate_fill_price_skips_zero_size_levels() {
+        let degenerate = vec![
+            PriceLevel::new(dec!(0.10), dec!(0)),
+            PriceLevel::new(dec!(0.20), dec!(0)),
+        ];
+        assert!(matches!(
+            calculate_fill_price(&degenerate, dec!(10)),
+            Err(ArbitrageError::InsufficientLiquidity { available, .. }) if available.is_zero()
+        ));
+
+        // A phantom zero-size level doesn't set the best or worst price
+        let asks = vec![
+            PriceLevel::new(dec!(0.10), dec!(0)),
+            PriceLevel::new(dec!(0.50), dec!(100)),
+        ];
+        let result = calculate_fill_price(&asks, dec!(10)).unwrap();
+        assert_eq!(result.best_price, Some(dec!(0.50)));
+        assert_eq!(result.worst_price, dec!(0.50));
+    }
+
+    #[test]
+    fn calculate_fill_price_rejects_free_or_unsorted_fills() {
+        let free = vec![PriceLevel::new(dec!(0), dec!(100))];
+        assert!(matches!(
+            calculate_fill_price(&free, dec!(10)),
+            Err(ArbitrageError::InsufficientLiquidity { .. })
+        ));
+
+        // Descending asks would put the VWAP above the last level walked
+        let unsorted = vec![
+            PriceLevel::new(dec!(0.60), dec!(5)),
+            PriceLevel::new(dec!(0.40), dec!(5)),
+        ];
+        assert!(matches!(
+            calculate_fill_price(&unsorted, dec!(10)),
+            Err(ArbitrageError::InsufficientLiquidity { .. })
+        ));
+    }
+
     #[test]
     fn calculate_fill_price_invalid_size() {
         let asks = vec![PriceLevel::new(dec!(0.50), dec!(100))];
@@ -233,6 +292,7 @@ mod tests {
             bids: vec![PriceLevel::new(dec!(0.48), dec!(50))],
             asks: vec![PriceLevel::new(dec!(0.52), dec!(50))],
             updated_at: OffsetDateTime::now_utc(),
+            as_of: None,
         };
 
         assert_eq!(mid_price(&book), Some(dec!(0.50)));
@@ -0,0 +1,161 @@
+//! Frozen-book detection from mid-price drift.
+//!
+//! Around settlement a book can stop updating or be held in place by spoofed
+//! quotes. Real books tick constantly, so a mid that has not moved for a while
+//! is treated as stale and new entries against it are skipped.
+
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+use rust_decimal::Decimal;
+use tracing::{info, warn};
+
+use super::aggregator::mid_price;
+use super::types::OutcomeBook;
+
+/// Last mid price seen for a token and when it last changed.
+#[derive(Debug, Clone, Copy)]
+struct MidState {
+    /// Most recent mid price.
+    mid: Decimal,
+    /// When the mid last moved.
+    changed_at: Instant,
+    /// Whether the token is currently flagged as frozen.
+    frozen: bool,
+}
+
+/// Tracks per-token mid prices and flags books whose mid has stopped moving.
+#[derive(Debug, Default)]
+pub struct MidPriceMonitor {
+    /// How long a mid may stay unchanged before the book is flagged (zero disables).
+    threshold: Duration,
+    /// Mid state per token ID.
+    tokens: HashMap<String, MidState>,
+}
+
+impl MidPriceMonitor {
+    /// Create a monitor flagging books whose mid is unchanged for `threshold`.
+    pub fn new(threshold: Duration) -> Self {
+        Self {
+            threshold,
+            tokens: HashMap::new(),
+        }
+    }
+
+    /// Record a book's mid at `now` and return whether it appears frozen.
+    ///
+    /// Books without both sides carry no mid and are never flagged.
+    pub fn observe(&mut self, book: &OutcomeBook, now: Instant) -> bool {
+        if self.threshold.is_zero() {
+            return false;
+        }
+        let Some(mid) = mid_price(book) else {
+            self.tokens.remove(&book.token_id);
+            return false;
+        };
+
+        let state = self.tokens.entry(book.token_id.clone()).or_insert(MidState {
+            mid,
+            changed_at: now,
+            frozen: false,
+        });
+        if state.mid != mid {
+            state.mid = mid;
+            state.changed_at = now;
+        }
+
+        let frozen = now.duration_since(state.changed_at) >= self.threshold;
+        // Log transitions only
+        if frozen && !state.frozen {
+            warn!(
+                token_id = %book.token_id,
+                mid = %mid,
+                unchanged_secs = self.threshold.as_secs(),
+                "Book appears frozen, skipping new entries"
+            );
+        } else if !frozen && state.frozen {
+            info!(token_id = %book.token_id, mid = %mid, "Book is moving again");
+        }
+        state.frozen = frozen;
+        frozen
+    }
+
+    /// Record both books of a market; true if either appears frozen.
+    pub fn observe_pair(&mut self, up: &OutcomeBook, down: &OutcomeBook, now: Instant) -> bool {
+        let up_frozen = self.observe(up, now);
+        let down_frozen = self.observe(down, now);
+        up_frozen || down_frozen
+    }
+
+    /// Forget all tracked mids, e.g. after a stretch without book updates.
+    pub fn clear(&mut self) {
+        self.tokens.clear();
+    }
+
+    /// Whether any tracked book is currently flagged.
+    pub fn any_frozen(&self) -> bool {
+        self.tokens.values().any(|state| state.frozen)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::market::Outcome;
+    use crate::orderbook::PriceLevel;
+    use rust_decimal_macros::dec;
+    use time::OffsetDateTime;
+
+    fn book(bid: Decimal, ask: Decimal) -> OutcomeBook {
+        OutcomeBook {
+            token_id: "up".to_string(),
+            outcome: Outcome::Up,
+            bids: vec![PriceLevel::new(bid, dec!(100))],
+            asks: vec![PriceLevel::new(ask, dec!(100))],
+            updated_at: OffsetDateTime::now_utc(),
+            as_of: None,
+        }
+    }
+
+    #[test]
+    fn constant_mid_is_flagged_after_threshold() {
+        let mut monitor = MidPriceMonitor::new(Duration::from_secs(30));
+        let start = Instant::now();
+
+        for secs in [0, 10, 20] {
+            let now = start + Duration::from_secs(secs);
+            assert!(!monitor.observe(&book(dec!(0.47), dec!(0.49)), now));
+        }
+        assert!(monitor.observe(&book(dec!(0.47), dec!(0.49)), start + Duration::from_secs(30)));
+        assert!(monitor.any_frozen());
+
+        // Any move in the mid clears the flag
+        let moved = book(dec!(0.48), dec!(0.50));
+        assert!(!monitor.observe(&moved, start + Duration::from_secs(31)));
+        assert!(!monitor.any_frozen());
+    }
+
+    #[test]
+    fn moving_mid_is_not_flagged() {
+        let mut monitor = MidPriceMonitor::new(Duration::from_secs(30));
+        let start = Instant::now();
+
+        for (i, secs) in (0..120).step_by(10).enumerate() {
+            let bid = dec!(0.40) + Decimal::new(i as i64 % 2, 2);
+            let now = start + Duration::from_secs(secs);
+            assert!(!monitor.observe(&book(bid, bid + dec!(0.02)), now));
+        }
+        assert!(!monitor.any_frozen());
+    }
+
+    #[test]
+    fn zero_threshold_disables_monitor() {
+        let mut monitor = MidPriceMonitor::new(Duration::ZERO);
+        let start = Instant::now();
+
+        monitor.observe(&book(dec!(0.47), dec!(0.49)), start);
+        let later = start + Duration::from_secs(3600);
+
+        assert!(!monitor.observe(&book(dec!(0.47), dec!(0.49)), later));
+    }
+}
@@ -3,12 +3,17 @@
 //! This module handles:
 //! - Order book types and data structures
 //! - Fill price calculations and book aggregation
+//! - Frozen-book detection from mid-price drift
 //! - WebSocket connection for real-time updates
 
 pub mod aggregator;
+pub mod drift;
 pub mod types;
 pub mod websocket;
 
 pub use aggregator::{calculate_fill_price, mid_price};
+pub use drift::MidPriceMonitor;
 pub use types::{BookUpdate, FillInfo, OutcomeBook, PriceLevel, WsEventType};
-pub use websocket::{L2BookState, MarketWebSocket};
+pub use websocket::{
+    rotation_frames, L2BookState, MarketWebSocket, WsAuthFrame, WsAuthResponse, WsCredentials,
+};
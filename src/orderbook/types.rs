@@ -35,6 +35,8 @@ pub struct OutcomeBook {
     pub asks: Vec<PriceLevel>,
     /// When this book was last updated.
     pub updated_at: OffsetDateTime,
+    /// Exchange timestamp of the snapshot, when the source reports one.
+    pub as_of: Option<OffsetDateTime>,
 }
 
 impl Default for OutcomeBook {
@@ -45,6 +47,7 @@ impl Default for OutcomeBook {
             bids: Vec::new(),
             asks: Vec::new(),
             updated_at: OffsetDateTime::now_utc(),
+            as_of: None,
         }
     }
 }
@@ -95,6 +98,11 @@ impl OutcomeBook {
     pub fn total_ask_liquidity(&self) -> Decimal {
         self.asks.iter().map(|l| l.size).sum()
     }
+
+    /// Milliseconds between the exchange timestamp and `now`; `None` without one.
+    pub fn server_age_ms(&self, now: OffsetDateTime) -> Option<i64> {
+        self.as_of.map(|as_of| (now - as_of).whole_milliseconds() as i64)
+    }
 }
 
 /// Result of calculating fill price by walking the book.
@@ -156,6 +164,7 @@ mod tests {
                 PriceLevel::new(dec!(0.51), dec!(100)),
             ],
             updated_at: OffsetDateTime::now_utc(),
+            as_of: None,
         };
 
         assert_eq!(book.best_bid(), Some(dec!(0.48)));
@@ -171,6 +180,7 @@ mod tests {
             bids: vec![PriceLevel::new(dec!(0.52), dec!(50))],
             asks: vec![PriceLevel::new(dec!(0.50), dec!(50))],
             updated_at: OffsetDateTime::now_utc(),
+            as_of: None,
         };
 
         assert!(inverted_book.is_inverted());
@@ -181,6 +191,7 @@ mod tests {
             bids: vec![PriceLevel::new(dec!(0.48), dec!(50))],
             asks: vec![PriceLevel::new(dec!(0.50), dec!(50))],
             updated_at: OffsetDateTime::now_utc(),
+            as_of: None,
         };
 
         assert!(!normal_book.is_inverted());
@@ -200,6 +211,7 @@ mod tests {
                 PriceLevel::new(dec!(0.51), dec!(100)),
             ],
             updated_at: OffsetDateTime::now_utc(),
+            as_of: None,
         };
 
         assert_eq!(book.total_bid_liquidity(), dec!(150));
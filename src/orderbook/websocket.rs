@@ -4,10 +4,12 @@
 //! - Automatic reconnection with exponential backoff
 //! - Heartbeat/ping-pong handling
 //! - SmallVec optimization for price levels
+//! - Price change coalescing when the update channel backs up
 
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
+use std::future::Future;
 use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
-use std::sync::Arc;
+use std::sync::{Arc, Mutex, RwLock};
 use std::time::{Duration, Instant};
 
 use dashmap::DashMap;
@@ -15,12 +17,16 @@ use futures::{SinkExt, StreamExt};
 use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
 use tokio::sync::mpsc;
-use tokio_tungstenite::{connect_async, tungstenite::Message};
+use tokio_tungstenite::connect_async;
+use tokio_tungstenite::tungstenite::protocol::CloseFrame;
+use tokio_tungstenite::tungstenite::Message;
 use tracing::{debug, error, info, warn};
 
 use super::types::{BookUpdate, PriceLevel, WsEventType};
-use crate::error::WsError;
+use crate::config::Config;
+use crate::error::{MarketError, WsError};
 use crate::metrics;
+use crate::signing;
 
 /// L2 book state maintained from WebSocket updates.
 #[derive(Debug, Clone, Default)]
@@ -31,7 +37,10 @@ pub struct L2BookState {
     pub asks: HashMap<Decimal, Decimal>,
     /// Last update timestamp (milliseconds).
     pub last_timestamp_ms: Option<i64>,
-    /// Last hash for debugging.
+    /// Server hash of the book after the last applied message.
+    ///
+    /// The hash digests the book itself rather than chaining messages, so it
+    /// identifies a book state but does not predict the next message's hash.
     pub last_hash: Option<String>,
 }
 
@@ -58,21 +67,21 @@ impl L2BookState {
         }
     }
 
-    /// Apply a price change delta.
-    pub fn apply_delta(&mut self, change: &WsPriceChange) {
+    /// Apply a price change delta; returns false if the change could not be parsed.
+    pub fn apply_delta(&mut self, change: &WsPriceChange) -> bool {
         let price = match change.price.parse::<Decimal>() {
             Ok(p) => p,
-            Err(_) => return,
+            Err(_) => return false,
         };
         let size = match change.size.parse::<Decimal>() {
             Ok(s) => s,
-            Err(_) => return,
+            Err(_) => return false,
         };
 
         let book = match change.side.to_uppercase().as_str() {
             "BUY" => &mut self.bids,
             "SELL" => &mut self.asks,
-            _ => return,
+            _ => return false,
         };
 
         if size <= Decimal::ZERO {
@@ -80,10 +89,16 @@ impl L2BookState {
         } else {
             book.insert(price, size);
         }
+        true
+    }
 
-        if let Some(hash) = &change.hash {
-            self.last_hash = Some(hash.clone());
-        }
+    /// Record the timestamp and hash of an applied message.
+    ///
+    /// A message without a hash clears `last_hash`, so a hash never outlives
+    /// the book state it described.
+    pub fn record_message(&mut self, timestamp_ms: Option<i64>, hash: Option<&str>) {
+        self.last_timestamp_ms = timestamp_ms;
+        self.last_hash = hash.map(str::to_string);
     }
 
     /// Convert to sorted price level vectors.
@@ -173,6 +188,136 @@ struct SubscribeMessage {
     assets_ids: Vec<String>,
 }
 
+/// Subscription change on an already-open market connection.
+#[derive(Debug, Serialize)]
+struct SubscriptionUpdate {
+    /// Asset IDs (token IDs) to add or remove.
+    assets_ids: Vec<String>,
+    /// Operation ("subscribe" or "unsubscribe").
+    operation: String,
+}
+
+/// Build the unsubscribe and subscribe frames for rotating from `old` to `new` assets.
+pub fn rotation_frames(old: &[String], new: &[String]) -> Vec<String> {
+    let removed: Vec<String> = old.iter().filter(|id| !new.contains(id)).cloned().collect();
+    let added: Vec<String> = new.iter().filter(|id| !old.contains(id)).cloned().collect();
+
+    let mut frames = Vec::new();
+    for (assets_ids, operation) in [(removed, "unsubscribe"), (added, "subscribe")] {
+        if assets_ids.is_empty() {
+            continue;
+        }
+        let update = SubscriptionUpdate {
+            assets_ids,
+            operation: operation.to_string(),
+        };
+        frames.push(serde_json::json!(update).to_string());
+    }
+
+    frames
+}
+
+/// API credentials for authenticating private WebSocket channels.
+#[derive(Clone, Serialize)]
+pub struct WsCredentials {
+    /// API key.
+    #[serde(rename = "apiKey")]
+    pub api_key: String,
+    /// API secret.
+    pub secret: String,
+    /// API passphrase.
+    pub passphrase: String,
+}
+
+impl WsCredentials {
+    /// Build credentials from config, if all three values are configured.
+    pub fn from_config(config: &Config) -> Option<Self> {
+        Some(Self {
+            api_key: config.polymarket_api_key.clone()?,
+            secret: config.polymarket_api_secret.clone()?,
+            passphrase: config.polymarket_api_passphrase.clone()?,
+        })
+    }
+}
+
+impl std::fmt::Debug for WsCredentials {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("WsCredentials")
+            .field("api_key", &self.api_key)
+            .field("secret", &"<redacted>")
+            .field("passphrase", &"<redacted>")
+            .finish()
+    }
+}
+
+/// Authentication frame sent as the first message on private channels.
+///
+/// Format:
+/// ```json
+/// {"type":"auth","auth":{"apiKey":"..","secret":"..","passphrase":".."},
+///  "headers":{"POLY_ADDRESS":"0x..","POLY_SIGNATURE":"0x..","POLY_TIMESTAMP":"..","POLY_NONCE":"0"}}
+/// ```
+#[derive(Debug, Serialize)]
+pub struct WsAuthFrame {
+    /// Message type (always "auth").
+    #[serde(rename = "type")]
+    msg_type: String,
+    /// API credentials.
+    auth: WsCredentials,
+    /// L2 auth headers (address, signature, timestamp, nonce).
+    headers: BTreeMap<String, String>,
+}
+
+impl WsAuthFrame {
+    /// Create an auth frame from credentials and signed auth headers.
+    pub fn new(credentials: &WsCredentials, headers: Vec<(String, String)>) -> Self {
+        Self {
+            msg_type: "auth".to_string(),
+            auth: credentials.clone(),
+            headers: headers.into_iter().collect(),
+        }
+    }
+}
+
+/// Server reply to an auth frame.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WsAuthResponse {
+    /// Authentication accepted.
+    Ack,
+    /// Authentication rejected with a reason.
+    Error(String),
+}
+
+/// Raw auth reply from the server.
+#[derive(Debug, Deserialize)]
+struct WsAuthReply {
+    #[serde(rename = "type", alias = "event_type")]
+    msg_type: Option<String>,
+    status: Option<String>,
+    message: Option<String>,
+    error: Option<String>,
+}
+
+impl WsAuthResponse {
+    /// Parse an auth reply, returning None for non-auth messages.
+    pub fn parse(text: &str) -> Option<Self> {
+        let reply: WsAuthReply = serde_json::from_str(text).ok()?;
+        if reply.msg_type.as_deref() != Some("auth") {
+            return None;
+        }
+
+        match reply.status.as_deref() {
+            Some("ok") | Some("success") if reply.error.is_none() => Some(WsAuthResponse::Ack),
+            _ => Some(WsAuthResponse::Error(
+                reply
+                    .error
+                    .or(reply.message)
+                    .unwrap_or_else(|| "unknown auth error".to_string()),
+            )),
+        }
+    }
+}
+
 /// Reconnection configuration for WebSocket.
 #[derive(Debug, Clone)]
 pub struct ReconnectConfig {
@@ -214,6 +359,42 @@ impl ReconnectConfig {
         let clamped_ms = delay_ms.min(max_delay_ms as f64) as u64;
         Duration::from_millis(clamped_ms)
     }
+
+    /// Delay before reconnecting after a connection ended with `failure`.
+    ///
+    /// An auth rejection won't clear up on a quick retry, so it waits the
+    /// maximum delay; anything else backs off exponentially from `attempt`.
+    pub fn delay_after(&self, attempt: u32, failure: Option<&WsError>) -> Duration {
+        if failure.is_some_and(is_auth_rejection) {
+            return Duration::from_secs(self.max_delay_s);
+        }
+        self.next_delay(attempt)
+    }
+}
+
+/// Error for a server close frame, keeping its code and reason.
+pub fn close_error(frame: Option<&CloseFrame<'_>>) -> WsError {
+    WsError::ConnectionClosed {
+        code: frame.map(|frame| u16::from(frame.code)),
+        reason: frame.map(|frame| frame.reason.to_string()).unwrap_or_default(),
+    }
+}
+
+/// Whether the server refused our credentials rather than dropping the connection.
+///
+/// Covers auth error replies, policy-violation closes (1008), the
+/// unauthorized/forbidden application codes (4001, 4003, 4401, 4403), and
+/// close reasons that mention auth.
+pub fn is_auth_rejection(error: &WsError) -> bool {
+    match error {
+        WsError::AuthFailed(_) => true,
+        WsError::ConnectionClosed { code, reason } => {
+            let reason = reason.to_lowercase();
+            matches!(code, Some(1008 | 4001 | 4003 | 4401 | 4403))
+                || ["auth", "forbidden"].iter().any(|word| reason.contains(word))
+        }
+        _ => false,
+    }
 }
 
 /// WebSocket connection state.
@@ -229,6 +410,33 @@ pub enum ConnectionState {
     Reconnecting,
 }
 
+impl ConnectionState {
+    /// Lowercase name for status output.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Disconnected => "disconnected",
+            Self::Connecting => "connecting",
+            Self::Connected => "connected",
+            Self::Reconnecting => "reconnecting",
+        }
+    }
+}
+
+/// Point-in-time view of the market feed for status reporting.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WsStatus {
+    /// Connection state.
+    pub state: ConnectionState,
+    /// Whether currently connected.
+    pub connected: bool,
+    /// Reconnection attempts so far.
+    pub reconnect_attempts: u64,
+    /// Whether the feed appears stale.
+    pub stale: bool,
+    /// Time since the last message, if any has arrived.
+    pub last_message_age: Option<Duration>,
+}
+
 /// Manages WebSocket connection and L2 book state.
 pub struct MarketWebSocket {
     /// Book state per asset ID.
@@ -243,6 +451,107 @@ pub struct MarketWebSocket {
     reconnect_attempts: Arc<AtomicU64>,
     /// Last successful message timestamp.
     last_message_time: Arc<std::sync::RwLock<Option<Instant>>>,
+    /// Credentials and private key for authenticated channels.
+    auth: Option<(WsCredentials, String)>,
+    /// Currently subscribed asset IDs (used on reconnect).
+    assets: RwLock<Vec<String>>,
+    /// Outbound frames for the live connection.
+    outbound: Mutex<Option<mpsc::UnboundedSender<String>>>,
+    /// Capacity of the book update channel.
+    channel_capacity: usize,
+    /// Why the server ended the last connection, if it said (close frame or auth error).
+    last_close: Arc<Mutex<Option<WsError>>>,
+}
+
+/// Default capacity of the book update channel.
+pub const DEFAULT_WS_CHANNEL_CAPACITY: usize = 1000;
+
+/// Sends book updates to the scanner, coalescing price changes under backpressure.
+///
+/// Updates only say which asset changed; the book itself lives in the shared
+/// state. When the channel is nearly full, intermediate price changes are held
+/// back as one pending update per asset rather than blocking the feed, so the
+/// scanner still learns of every asset that moved and reads its latest book.
+#[derive(Debug)]
+struct UpdateForwarder {
+    /// Channel to the scanner.
+    tx: mpsc::Sender<BookUpdate>,
+    /// Price changes held back while the channel is nearly full, one per asset.
+    pending: Vec<BookUpdate>,
+}
+
+impl UpdateForwarder {
+    /// Wrap the sending half of the update channel.
+    fn new(tx: mpsc::Sender<BookUpdate>) -> Self {
+        Self {
+            tx,
+            pending: Vec::new(),
+        }
+    }
+
+    /// Whether free slots are below a tenth of the capacity.
+    fn is_near_full(&self) -> bool {
+        self.tx.capacity() < (self.tx.max_capacity() / 10).max(1)
+    }
+
+    /// Whether any price change is waiting for room in the channel.
+    fn has_pending(&self) -> bool {
+        !self.pending.is_empty()
+    }
+
+    /// Forward an update; errors once the receiver is dropped.
+    async fn forward(&mut self, update: BookUpdate) -> Result<(), ()> {
+        if update.event_type == WsEventType::PriceChange && self.is_near_full() {
+            if self.pending.iter().any(|p| p.asset_id == update.asset_id) {
+                metrics::inc_ws_updates_dropped();
+            } else {
+                self.pending.push(update);
+            }
+            return Ok(());
+        }
+
+        // Keep older price changes ahead of this update
+        for pending in std::mem::take(&mut self.pending) {
+            self.tx.send(pending).await.map_err(|_| ())?;
+        }
+        self.tx.send(update).await.map_err(|_| ())
+    }
+
+    /// Deliver the oldest pending update once the channel has room.
+    async fn deliver_pending(&mut self) -> Result<(), ()> {
+        let permit = self.tx.reserve().await.map_err(|_| ())?;
+        if !self.pending.is_empty() {
+            permit.send(self.pending.remove(0));
+        }
+        Ok(())
+    }
+}
+
+/// Run `task` until it returns, respawning it whenever it panics or is cancelled.
+///
+/// Every run gets a clone of `tx`, so the consumer's receiver stays open across
+/// restarts. Returns the number of restarts once a run returns normally.
+async fn supervise<F, Fut>(
+    tx: mpsc::Sender<BookUpdate>,
+    mut task: F,
+    restart_delay: Duration,
+) -> u64
+where
+    F: FnMut(mpsc::Sender<BookUpdate>) -> Fut,
+    Fut: Future<Output = ()> + Send + 'static,
+{
+    let mut restarts = 0;
+    loop {
+        match tokio::spawn(task(tx.clone())).await {
+            Ok(()) => return restarts,
+            Err(e) => {
+                restarts += 1;
+                metrics::inc_ws_task_restarts();
+                error!(error = %e, restarts, "WebSocket task died, restarting");
+                tokio::time::sleep(restart_delay).await;
+            }
+        }
+    }
 }
 
 impl MarketWebSocket {
@@ -255,6 +564,11 @@ impl MarketWebSocket {
             connected: Arc::new(AtomicBool::new(false)),
             reconnect_attempts: Arc::new(AtomicU64::new(0)),
             last_message_time: Arc::new(std::sync::RwLock::new(None)),
+            auth: None,
+            assets: RwLock::new(Vec::new()),
+            outbound: Mutex::new(None),
+            channel_capacity: DEFAULT_WS_CHANNEL_CAPACITY,
+            last_close: Arc::new(Mutex::new(None)),
         }
     }
 
@@ -267,9 +581,26 @@ impl MarketWebSocket {
             connected: Arc::new(AtomicBool::new(false)),
             reconnect_attempts: Arc::new(AtomicU64::new(0)),
             last_message_time: Arc::new(std::sync::RwLock::new(None)),
+            auth: None,
+            assets: RwLock::new(Vec::new()),
+            outbound: Mutex::new(None),
+            channel_capacity: DEFAULT_WS_CHANNEL_CAPACITY,
+            last_close: Arc::new(Mutex::new(None)),
         }
     }
 
+    /// Buffer up to `capacity` book updates before coalescing price changes.
+    pub fn with_channel_capacity(mut self, capacity: usize) -> Self {
+        self.channel_capacity = capacity.max(1);
+        self
+    }
+
+    /// Authenticate with API credentials after connecting (for private channels).
+    pub fn with_auth(mut self, credentials: WsCredentials, private_key: String) -> Self {
+        self.auth = Some((credentials, private_key));
+        self
+    }
+
     /// Check if currently connected.
     pub fn is_connected(&self) -> bool {
         self.connected.load(Ordering::SeqCst)
@@ -280,6 +611,46 @@ impl MarketWebSocket {
         self.reconnect_attempts.load(Ordering::SeqCst)
     }
 
+    /// Time since the last message, if any has arrived.
+    pub fn last_message_age(&self) -> Option<Duration> {
+        self.last_message_time
+            .read()
+            .ok()
+            .and_then(|time| time.map(|last| last.elapsed()))
+    }
+
+    /// Current connection state.
+    ///
+    /// A feed that has dropped at least once reports `Reconnecting` until it
+    /// is back up; before the first connection it reports `Connecting`.
+    pub fn connection_state(&self) -> ConnectionState {
+        if self.is_connected() {
+            ConnectionState::Connected
+        } else if self.reconnect_attempts() > 0 {
+            ConnectionState::Reconnecting
+        } else if self.subscribed_assets().is_empty() {
+            ConnectionState::Disconnected
+        } else {
+            ConnectionState::Connecting
+        }
+    }
+
+    /// Snapshot connection health for the status endpoint.
+    pub fn status(&self) -> WsStatus {
+        WsStatus {
+            state: self.connection_state(),
+            connected: self.is_connected(),
+            reconnect_attempts: self.reconnect_attempts(),
+            stale: self.is_stale(),
+            last_message_age: self.last_message_age(),
+        }
+    }
+
+    /// Take the reason the server gave for ending the last connection.
+    fn take_close(&self) -> Option<WsError> {
+        self.last_close.lock().ok().and_then(|mut close| close.take())
+    }
+
     /// Get book state for an asset.
     pub fn get_book(&self, asset_id: &str) -> Option<L2BookState> {
         self.books.get(asset_id).map(|b| b.clone())
@@ -292,6 +663,39 @@ impl MarketWebSocket {
         }
     }
 
+    /// Get the currently subscribed asset IDs.
+    pub fn subscribed_assets(&self) -> Vec<String> {
+        self.assets.read().map(|a| a.clone()).unwrap_or_default()
+    }
+
+    /// Swap subscriptions to new asset IDs on the live connection.
+    ///
+    /// The new set is kept even if sending fails, so the next reconnect subscribes to it;
+    /// returns whether the frames were sent on a live connection.
+    pub fn rotate_subscription(&self, asset_ids: Vec<String>) -> bool {
+        let old = match self.assets.write() {
+            Ok(mut assets) => std::mem::replace(&mut *assets, asset_ids.clone()),
+            Err(_) => Vec::new(),
+        };
+
+        self.init_books(&asset_ids);
+        for id in old.iter().filter(|id| !asset_ids.contains(id)) {
+            self.books.remove(id);
+        }
+
+        let Some(outbound) = self.outbound.lock().ok().and_then(|o| o.clone()) else {
+            return false;
+        };
+        for frame in rotation_frames(&old, &asset_ids) {
+            if outbound.send(frame).is_err() {
+                return false;
+            }
+        }
+
+        info!(assets = ?asset_ids, "Rotated WebSocket subscription");
+        true
+    }
+
     /// Check if connection appears stale (no messages in heartbeat interval).
     pub fn is_stale(&self) -> bool {
         if let Ok(time) = self.last_message_time.read() {
@@ -303,6 +707,30 @@ impl MarketWebSocket {
         false
     }
 
+    /// Fetch a one-shot REST snapshot when the feed is stale.
+    ///
+    /// Returns `None` without calling `fetch_rest` while the feed is fresh, so
+    /// trading continues on REST data only until reconnect restores the stream.
+    pub async fn stale_fallback<T, F, Fut>(&self, fetch_rest: F) -> Option<T>
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = Result<T, MarketError>>,
+    {
+        if !self.is_stale() {
+            return None;
+        }
+
+        metrics::inc_ws_stale_fallbacks();
+        warn!("WebSocket feed stale, falling back to REST order books");
+        match fetch_rest().await {
+            Ok(books) => Some(books),
+            Err(e) => {
+                warn!(error = %e, "REST fallback fetch failed");
+                None
+            }
+        }
+    }
+
     /// Run the WebSocket connection, yielding book updates.
     pub async fn run(
         &self,
@@ -312,6 +740,9 @@ impl MarketWebSocket {
 
         // Initialize books
         self.init_books(&asset_ids);
+        if let Ok(mut assets) = self.assets.write() {
+            *assets = asset_ids.clone();
+        }
 
         info!(url = %url, assets = ?asset_ids, "Connecting to WebSocket");
 
@@ -324,6 +755,22 @@ impl MarketWebSocket {
 
         let (mut write, read) = ws_stream.split();
 
+        // Authenticate first when credentials are configured
+        if let Some((credentials, private_key)) = &self.auth {
+            let headers = signing::generate_auth_headers(private_key, 0)
+                .await
+                .map_err(|e| WsError::AuthFailed(e.to_string()))?;
+            let auth_json = serde_json::to_string(&WsAuthFrame::new(credentials, headers))
+                .map_err(|e| WsError::SendFailed(e.to_string()))?;
+
+            write
+                .send(Message::Text(auth_json))
+                .await
+                .map_err(|e| WsError::SendFailed(e.to_string()))?;
+
+            debug!("Sent auth frame");
+        }
+
         // Subscribe to assets
         let subscribe_msg = SubscribeMessage {
             msg_type: "MARKET".to_string(),
@@ -340,15 +787,37 @@ impl MarketWebSocket {
 
         info!("Subscribed to {} assets", asset_ids.len());
 
+        // Forward later subscription changes to the socket
+        let (out_tx, mut out_rx) = mpsc::unbounded_channel::<String>();
+        tokio::spawn(async move {
+            while let Some(frame) = out_rx.recv().await {
+                if let Err(e) = write.send(Message::Text(frame)).await {
+                    warn!(error = %e, "Failed to send WebSocket frame");
+                    break;
+                }
+            }
+        });
+        if let Ok(mut outbound) = self.outbound.lock() {
+            *outbound = Some(out_tx);
+        }
+
         // Process messages with metrics tracking
         let books = &self.books;
         let connected = self.connected.clone();
         let last_msg_time = self.last_message_time.clone();
+        let last_close = self.last_close.clone();
+        let expects_auth = self.auth.is_some();
 
         let stream = read.filter_map(move |msg| {
             let books = books;
             let connected = connected.clone();
             let last_msg_time = last_msg_time.clone();
+            let last_close = last_close.clone();
+            let record_close = move |error: WsError| {
+                if let Ok(mut close) = last_close.lock() {
+                    *close = Some(error);
+                }
+            };
 
             async move {
                 // Update last message time on any message
@@ -358,12 +827,36 @@ impl MarketWebSocket {
 
                 match msg {
                     Ok(Message::Text(text)) => {
+                        if expects_auth {
+                            match WsAuthResponse::parse(&text) {
+                                Some(WsAuthResponse::Ack) => {
+                                    info!("WebSocket authenticated");
+                                    return None;
+                                }
+                                Some(WsAuthResponse::Error(reason)) => {
+                                    let rejected = WsError::AuthFailed(reason);
+                                    error!(error = %rejected, "WebSocket auth rejected");
+                                    connected.store(false, Ordering::SeqCst);
+                                    record_close(rejected);
+                                    return None;
+                                }
+                                None => {}
+                            }
+                        }
+
                         let start = Instant::now();
                         metrics::inc_ws_messages_received();
                         let result = Self::process_message(books, &text);
                         metrics::record_ws_message_latency(start);
                         result
                     }
+                    Ok(Message::Binary(data)) => {
+                        let start = Instant::now();
+                        metrics::inc_ws_messages_received();
+                        let result = Self::process_binary(books, &data);
+                        metrics::record_ws_message_latency(start);
+                        result
+                    }
                     Ok(Message::Ping(_)) => {
                         debug!("Received ping");
                         // Note: tungstenite auto-responds to pings
@@ -374,8 +867,10 @@ impl MarketWebSocket {
                         None
                     }
                     Ok(Message::Close(frame)) => {
-                        warn!(frame = ?frame, "WebSocket closed");
+                        let closed = close_error(frame.as_ref());
+                        warn!(error = %closed, "WebSocket closed by server");
                         connected.store(false, Ordering::SeqCst);
+                        record_close(closed);
                         None
                     }
                     Ok(_) => None,
@@ -393,56 +888,99 @@ impl MarketWebSocket {
 
     /// Run with automatic reconnection on disconnect.
     /// Returns a channel receiver that yields book updates.
+    ///
+    /// The connection loop runs under a watchdog that restarts it if it panics
+    /// or is cancelled; the receiver stays open across restarts.
     pub async fn run_with_reconnect(
         self: Arc<Self>,
         asset_ids: Vec<String>,
     ) -> mpsc::Receiver<BookUpdate> {
-        let (tx, rx) = mpsc::channel(1000);
-
-        let ws = self;
-        let assets = asset_ids;
+        let (tx, rx) = mpsc::channel(self.channel_capacity);
 
-        tokio::spawn(async move {
-            let mut attempt = 0u32;
-
-            loop {
-                info!(attempt = attempt, "Attempting WebSocket connection");
+        if let Ok(mut assets) = self.assets.write() {
+            *assets = asset_ids;
+        }
 
-                match ws.run(assets.clone()).await {
-                    Ok(stream) => {
-                        attempt = 0; // Reset on successful connection
+        let restart_delay = self.reconnect_config.next_delay(0);
+        tokio::spawn(supervise(
+            tx,
+            move |tx| self.clone().reconnect_loop(tx),
+            restart_delay,
+        ));
 
-                        // Pin the stream to use with .next()
-                        let mut stream = Box::pin(stream);
+        rx
+    }
 
-                        while let Some(update) = stream.next().await {
-                            if tx.send(update).await.is_err() {
-                                info!("Channel closed, stopping WebSocket");
-                                return;
+    /// Connect, forward updates and reconnect with backoff until `tx` is closed.
+    async fn reconnect_loop(self: Arc<Self>, tx: mpsc::Sender<BookUpdate>) {
+        let mut forwarder = UpdateForwarder::new(tx);
+        let mut attempt = 0u32;
+
+        loop {
+            info!(attempt = attempt, "Attempting WebSocket connection");
+
+            let failure = match self.run(self.subscribed_assets()).await {
+                Ok(stream) => {
+                    attempt = 0; // Reset on successful connection
+
+                    // Pin the stream to use with .next()
+                    let mut stream = Box::pin(stream);
+
+                    loop {
+                        let update = tokio::select! {
+                            update = stream.next() => update,
+                            delivered = forwarder.deliver_pending(),
+                                if forwarder.has_pending() =>
+                            {
+                                if delivered.is_err() {
+                                    info!("Channel closed, stopping WebSocket");
+                                    return;
+                                }
+                                continue;
                             }
+                        };
+                        let Some(update) = update else {
+                            break;
+                        };
+                        if forwarder.forward(update).await.is_err() {
+                            info!("Channel closed, stopping WebSocket");
+                            return;
                         }
-
-                        // Stream ended - connection closed
-                        warn!("WebSocket stream ended, will reconnect");
-                    }
-                    Err(e) => {
-                        error!(error = %e, attempt = attempt, "WebSocket connection failed");
                     }
+
+                    // Stream ended - connection closed
+                    warn!("WebSocket stream ended, will reconnect");
+                    self.take_close()
+                }
+                Err(e) => {
+                    error!(error = %e, attempt = attempt, "WebSocket connection failed");
+                    Some(e)
                 }
+            };
 
-                // Calculate backoff delay
-                let delay = ws.reconnect_config.next_delay(attempt);
-                ws.reconnect_attempts.fetch_add(1, Ordering::SeqCst);
-                metrics::inc_ws_reconnects();
+            // Calculate backoff delay, longer when the server rejected our credentials
+            let delay = self.reconnect_config.delay_after(attempt, failure.as_ref());
+            self.reconnect_attempts.fetch_add(1, Ordering::SeqCst);
+            metrics::inc_ws_reconnects();
 
-                info!(delay_ms = delay.as_millis(), "Reconnecting after delay");
-                tokio::time::sleep(delay).await;
+            info!(delay_ms = delay.as_millis(), "Reconnecting after delay");
+            tokio::time::sleep(delay).await;
 
-                attempt = attempt.saturating_add(1);
-            }
-        });
+            attempt = attempt.saturating_add(1);
+        }
+    }
 
-        rx
+    /// Process a binary frame, treating it as UTF-8 JSON.
+    fn process_binary(books: &DashMap<String, L2BookState>, data: &[u8]) -> Option<BookUpdate> {
+        metrics::inc_ws_binary_frames();
+
+        match std::str::from_utf8(data) {
+            Ok(text) => Self::process_message(books, text),
+            Err(e) => {
+                warn!(len = data.len(), error = %e, "Dropping non-UTF-8 binary WebSocket frame");
+                None
+            }
+        }
     }
 
     /// Process a WebSocket message.
@@ -470,8 +1008,7 @@ impl MarketWebSocket {
                             event.bids.unwrap_or_default(),
                             event.asks.unwrap_or_default(),
                         );
-                        book.last_timestamp_ms = event.timestamp;
-                        book.last_hash = event.hash.clone();
+                        book.record_message(event.timestamp, event.hash.as_deref());
                     }
                     last_update = Some(BookUpdate {
                         asset_id: asset_id.clone(),
@@ -483,8 +1020,11 @@ impl MarketWebSocket {
                         for change in changes {
                             if let Some(asset_id) = &change.asset_id {
                                 if let Some(mut book) = books.get_mut(asset_id) {
-                                    book.apply_delta(change);
-                                    book.last_timestamp_ms = event.timestamp;
+                                    if book.apply_delta(change) {
+                                        // Hashes come per change, or per event on older feeds
+                                        let hash = change.hash.as_deref().or(event.hash.as_deref());
+                                        book.record_message(event.timestamp, hash);
+                                    }
                                 }
                                 last_update = Some(BookUpdate {
                                     asset_id: asset_id.clone(),
@@ -551,6 +1091,40 @@ mod tests {
         assert!(!state.bids.contains_key(&dec!(0.48)));
     }
 
+    #[test]
+    fn hash_tracks_snapshot_then_deltas() {
+        let books = DashMap::new();
+        books.insert("123".to_string(), L2BookState::default());
+        let hash =
+            |books: &DashMap<String, L2BookState>| books.get("123").unwrap().last_hash.clone();
+
+        let snapshot = r#"{"event_type":"book","asset_id":"123","timestamp":1,"hash":"h1","bids":[{"price":"0.48","size":"100"}],"asks":[{"price":"0.52","size":"50"}]}"#;
+        MarketWebSocket::process_message(&books, snapshot).unwrap();
+        assert_eq!(hash(&books).as_deref(), Some("h1"));
+
+        let delta = r#"{"event_type":"price_change","timestamp":2,"price_changes":[{"asset_id":"123","price":"0.52","size":"40","side":"SELL","hash":"h2"}]}"#;
+        MarketWebSocket::process_message(&books, delta).unwrap();
+        assert_eq!(hash(&books).as_deref(), Some("h2"));
+
+        // Event-level hash when the change carries none
+        let delta = r#"{"event_type":"price_change","timestamp":3,"hash":"h3","price_changes":[{"asset_id":"123","price":"0.52","size":"30","side":"SELL"}]}"#;
+        MarketWebSocket::process_message(&books, delta).unwrap();
+        assert_eq!(hash(&books).as_deref(), Some("h3"));
+
+        // An unparseable change is not applied and keeps the last hash
+        let delta = r#"{"event_type":"price_change","timestamp":4,"price_changes":[{"asset_id":"123","price":"x","size":"30","side":"SELL","hash":"h4"}]}"#;
+        MarketWebSocket::process_message(&books, delta).unwrap();
+        assert_eq!(hash(&books).as_deref(), Some("h3"));
+
+        // A hashless delta changes the book, so the old hash no longer applies
+        let delta = r#"{"event_type":"price_change","timestamp":5,"price_changes":[{"asset_id":"123","price":"0.52","size":"20","side":"SELL"}]}"#;
+        MarketWebSocket::process_message(&books, delta).unwrap();
+        let book = books.get("123").unwrap();
+        assert_eq!(book.last_hash, None);
+        assert_eq!(book.last_timestamp_ms, Some(5));
+        assert_eq!(book.asks.get(&dec!(0.52)), Some(&dec!(20)));
+    }
+
     #[test]
     fn l2_book_state_to_levels_sorted() {
         let mut state = L2BookState::default();
@@ -566,4 +1140,269 @@ mod tests {
         assert_eq!(asks[0].price, dec!(0.50)); // Lowest first
         assert_eq!(asks[1].price, dec!(0.51));
     }
+
+    #[test]
+    fn auth_frame_serializes_credentials_and_headers() {
+        let credentials = WsCredentials {
+            api_key: "key-123".to_string(),
+            secret: "secret-456".to_string(),
+            passphrase: "pass-789".to_string(),
+        };
+        let headers = vec![
+            ("POLY_ADDRESS".to_string(), "0xabc".to_string()),
+            ("POLY_SIGNATURE".to_string(), "0xsig".to_string()),
+            ("POLY_TIMESTAMP".to_string(), "1700000000000".to_string()),
+        ];
+
+        let json = serde_json::to_value(WsAuthFrame::new(&credentials, headers)).unwrap();
+
+        assert_eq!(json["type"], "auth");
+        assert_eq!(json["auth"]["apiKey"], "key-123");
+        assert_eq!(json["auth"]["secret"], "secret-456");
+        assert_eq!(json["auth"]["passphrase"], "pass-789");
+        assert_eq!(json["headers"]["POLY_ADDRESS"], "0xabc");
+        assert_eq!(json["headers"]["POLY_TIMESTAMP"], "1700000000000");
+    }
+
+    #[test]
+    fn auth_response_parses_ack_and_error() {
+        assert_eq!(
+            WsAuthResponse::parse(r#"{"type":"auth","status":"ok"}"#),
+            Some(WsAuthResponse::Ack)
+        );
+        assert_eq!(
+            WsAuthResponse::parse(r#"{"type":"auth","status":"error","message":"invalid api key"}"#),
+            Some(WsAuthResponse::Error("invalid api key".to_string()))
+        );
+        assert_eq!(
+            WsAuthResponse::parse(r#"{"event_type":"book","asset_id":"123"}"#),
+            None
+        );
+    }
+
+    #[test]
+    fn credentials_debug_redacts_secrets() {
+        let credentials = WsCredentials {
+            api_key: "key-123".to_string(),
+            secret: "secret-456".to_string(),
+            passphrase: "pass-789".to_string(),
+        };
+
+        let debug = format!("{:?}", credentials);
+        assert!(debug.contains("key-123"));
+        assert!(!debug.contains("secret-456"));
+        assert!(!debug.contains("pass-789"));
+    }
+
+    #[test]
+    fn rotation_frames_unsubscribe_then_subscribe() {
+        let old = vec!["up-1".to_string(), "down-1".to_string()];
+        let new = vec!["up-2".to_string(), "down-2".to_string()];
+
+        let frames = rotation_frames(&old, &new);
+        assert_eq!(frames.len(), 2);
+
+        let unsubscribe: serde_json::Value = serde_json::from_str(&frames[0]).unwrap();
+        assert_eq!(unsubscribe["operation"], "unsubscribe");
+        assert_eq!(unsubscribe["assets_ids"], serde_json::json!(["up-1", "down-1"]));
+
+        let subscribe: serde_json::Value = serde_json::from_str(&frames[1]).unwrap();
+        assert_eq!(subscribe["operation"], "subscribe");
+        assert_eq!(subscribe["assets_ids"], serde_json::json!(["up-2", "down-2"]));
+    }
+
+    #[test]
+    fn rotate_subscription_keeps_new_assets_when_disconnected() {
+        let ws = MarketWebSocket::new("wss://test".to_string());
+        ws.init_books(&["up-1".to_string()]);
+        *ws.assets.write().unwrap() = vec!["up-1".to_string()];
+
+        assert!(!ws.rotate_subscription(vec!["up-2".to_string()]));
+        assert_eq!(ws.subscribed_assets(), vec!["up-2".to_string()]);
+        assert!(ws.get_book("up-1").is_none());
+        assert!(ws.get_book("up-2").is_some());
+    }
+
+    #[test]
+    fn status_reports_reconnecting_feed() {
+        let ws = MarketWebSocket::new("wss://test".to_string());
+        assert_eq!(ws.connection_state(), ConnectionState::Disconnected);
+
+        *ws.assets.write().unwrap() = vec!["up-1".to_string()];
+        assert_eq!(ws.connection_state(), ConnectionState::Connecting);
+
+        ws.reconnect_attempts.store(3, Ordering::SeqCst);
+        let stale = Instant::now().checked_sub(Duration::from_secs(120)).unwrap();
+        *ws.last_message_time.write().unwrap() = Some(stale);
+
+        let status = ws.status();
+        assert_eq!(status.state, ConnectionState::Reconnecting);
+        assert!(!status.connected);
+        assert_eq!(status.reconnect_attempts, 3);
+        assert!(status.stale);
+        assert!(status.last_message_age.unwrap() >= Duration::from_secs(120));
+    }
+
+    #[tokio::test]
+    async fn stale_feed_falls_back_to_rest() {
+        let ws = MarketWebSocket::new("wss://test".to_string());
+        let stale = Instant::now().checked_sub(Duration::from_secs(120)).unwrap();
+        *ws.last_message_time.write().unwrap() = Some(stale);
+        assert!(ws.is_stale());
+
+        let fetched = ws.stale_fallback(|| async { Ok("rest") }).await;
+
+        assert_eq!(fetched, Some("rest"));
+    }
+
+    #[tokio::test]
+    async fn fresh_feed_skips_rest_fallback() {
+        let ws = MarketWebSocket::new("wss://test".to_string());
+        *ws.last_message_time.write().unwrap() = Some(Instant::now());
+
+        let mut fetched_rest = false;
+        let fetched = ws
+            .stale_fallback(|| {
+                fetched_rest = true;
+                async { Ok::<_, MarketError>("rest") }
+            })
+            .await;
+
+        assert!(fetched.is_none());
+        assert!(!fetched_rest);
+    }
+
+    #[tokio::test]
+    async fn coalescing_keeps_latest_book_under_backpressure() {
+        let books = DashMap::new();
+        books.insert("123".to_string(), L2BookState::default());
+        let (tx, mut rx) = mpsc::channel(4);
+        let mut forwarder = UpdateForwarder::new(tx);
+
+        // The scanner is stalled while 50 ask changes arrive
+        for i in 1..=50 {
+            let frame = format!(
+                r#"{{"event_type":"price_change","price_changes":[{{"asset_id":"123","price":"0.52","size":"{}","side":"SELL"}}]}}"#,
+                i
+            );
+            let update = MarketWebSocket::process_message(&books, &frame).unwrap();
+            forwarder.forward(update).await.unwrap();
+        }
+        assert_eq!(forwarder.pending.len(), 1);
+
+        // Catching up delivers the channel's backlog, then the coalesced update
+        for _ in 0..4 {
+            rx.recv().await.unwrap();
+        }
+        forwarder.deliver_pending().await.unwrap();
+        let last = rx.recv().await.unwrap();
+        assert!(!forwarder.has_pending());
+        assert!(rx.try_recv().is_err());
+
+        assert_eq!(last.asset_id, "123");
+        let (_, asks) = books.get("123").unwrap().to_levels();
+        assert_eq!(asks[0].size, dec!(50));
+    }
+
+    #[tokio::test]
+    async fn watchdog_restarts_panicking_task() {
+        let (tx, mut rx) = mpsc::channel(4);
+        let runs = Arc::new(AtomicU64::new(0));
+
+        let task_runs = runs.clone();
+        let restarts = supervise(
+            tx,
+            move |tx| {
+                let run = task_runs.fetch_add(1, Ordering::SeqCst);
+                async move {
+                    if run == 0 {
+                        panic!("connection loop bug");
+                    }
+                    let update = BookUpdate {
+                        asset_id: "123".to_string(),
+                        event_type: WsEventType::Book,
+                    };
+                    tx.send(update).await.unwrap();
+                }
+            },
+            Duration::ZERO,
+        )
+        .await;
+
+        assert_eq!(restarts, 1);
+        assert_eq!(runs.load(Ordering::SeqCst), 2);
+        // The receiver handed out before the panic sees the restarted task's updates
+        assert_eq!(rx.recv().await.unwrap().asset_id, "123");
+    }
+
+    #[tokio::test]
+    async fn close_frame_code_and_reason_are_captured() {
+        use tokio_tungstenite::tungstenite::protocol::frame::coding::CloseCode;
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            let (stream, _) = listener.accept().await.unwrap();
+            let mut socket = tokio_tungstenite::accept_async(stream).await.unwrap();
+            // Wait for the subscription, then refuse it
+            socket.next().await;
+            let frame = CloseFrame {
+                code: CloseCode::Policy,
+                reason: "invalid api key".into(),
+            };
+            socket.send(Message::Close(Some(frame))).await.unwrap();
+            while socket.next().await.is_some() {}
+        });
+
+        let ws = MarketWebSocket::new(format!("ws://{}", addr));
+        {
+            let stream = ws.run(vec!["123".to_string()]).await.unwrap();
+            let updates: Vec<BookUpdate> = stream.collect().await;
+            assert!(updates.is_empty());
+        }
+
+        assert!(!ws.is_connected());
+        let closed = ws.take_close().unwrap();
+        match &closed {
+            WsError::ConnectionClosed { code, reason } => {
+                assert_eq!(*code, Some(1008));
+                assert_eq!(reason, "invalid api key");
+            }
+            other => panic!("expected a close error, got {:?}", other),
+        }
+        assert!(is_auth_rejection(&closed));
+        assert!(ws.take_close().is_none());
+    }
+
+    #[test]
+    fn auth_close_backs_off_longer_than_network_close() {
+        let config = ReconnectConfig::default();
+        let auth = close_error(Some(&CloseFrame {
+            code: 4401.into(),
+            reason: "unauthorized".into(),
+        }));
+        let going_away = close_error(Some(&CloseFrame {
+            code: 1001.into(),
+            reason: "".into(),
+        }));
+
+        assert_eq!(config.delay_after(0, Some(&auth)), Duration::from_secs(30));
+        assert_eq!(config.delay_after(0, Some(&going_away)), Duration::from_secs(1));
+        assert_eq!(config.delay_after(1, None), Duration::from_secs(2));
+        assert!(!is_auth_rejection(&close_error(None)));
+        assert!(is_auth_rejection(&WsError::AuthFailed("bad key".to_string())));
+    }
+
+    #[test]
+    fn binary_frame_with_json_is_processed() {
+        let books = DashMap::new();
+        books.insert("123".to_string(), L2BookState::default());
+
+        let frame = br#"{"event_type":"book","asset_id":"123","bids":[{"price":"0.48","size":"100"}],"asks":[{"price":"0.52","size":"50"}]}"#;
+        let update = MarketWebSocket::process_binary(&books, frame).unwrap();
+
+        assert_eq!(update.asset_id, "123");
+        assert_eq!(books.get("123").unwrap().asks.len(), 1);
+        assert!(MarketWebSocket::process_binary(&books, &[0xff, 0xfe]).is_none());
+    }
 }
@@ -1,5 +1,6 @@
 //! Order execution and verification.
 
+use std::future::Future;
 use std::time::{Duration, Instant};
 
 use rust_decimal::Decimal;
@@ -11,7 +12,7 @@ use crate::error::TradingError;
 use crate::market::PolymarketClient;
 use crate::signing;
 
-use super::order::{OrderParams, OrderState, OrderStatus, Side, TimeInForce};
+use super::order::{OrderParams, OrderPrecision, OrderState, OrderStatus, Side, TimeInForce};
 
 /// Order submission request body.
 #[derive(Debug, Clone, Serialize)]
@@ -32,8 +33,10 @@ pub struct OrderRequest {
     pub expiration: String,
     /// Taker address.
     pub taker: String,
-    /// Maker address.
+    /// Maker address (funds the order).
     pub maker: String,
+    /// Signer address (EOA that produced the signature).
+    pub signer: String,
     /// Signature type.
     pub signature_type: u8,
     /// Order signature.
@@ -42,6 +45,38 @@ pub struct OrderRequest {
     pub order_type: String,
     /// Neg risk flag.
     pub neg_risk: bool,
+    /// Reject instead of matching if the order would cross the book.
+    pub post_only: bool,
+}
+
+impl OrderRequest {
+    /// Copy of the order with its signature redacted, safe to log or report.
+    pub fn redacted(&self) -> Self {
+        Self {
+            signature: "<redacted>".to_string(),
+            ..self.clone()
+        }
+    }
+
+    /// Log what was sent for an order the CLOB refused.
+    ///
+    /// Only the fields that explain tick or precision rejections are logged,
+    /// never the signature.
+    fn log_rejection(&self, status: reqwest::StatusCode, body: &str) {
+        error!(
+            status = %status,
+            token_id = %self.token_id,
+            side = %self.side,
+            price = %self.price,
+            size = %self.size,
+            nonce = %self.nonce,
+            order_type = %self.order_type,
+            neg_risk = self.neg_risk,
+            post_only = self.post_only,
+            body = %body,
+            "Order rejected by CLOB"
+        );
+    }
 }
 
 /// Order submission result.
@@ -58,9 +93,40 @@ pub struct SubmitResult {
     pub success: Option<bool>,
 }
 
+impl SubmitResult {
+    /// Typed error for a rejection reported in the body; `None` when it gives no reason.
+    ///
+    /// Balance and allowance rejections become `InsufficientFunds`, so callers can
+    /// refresh the balance instead of retrying, a post-only order that would
+    /// have crossed becomes `PostOnlyCrossed`, and a FOK/FAK order left with
+    /// nothing to match at its price becomes `PriceMoved`; anything else is
+    /// `OrderRejected`.
+    pub fn rejection(&self) -> Option<TradingError> {
+        let error = self.error.as_deref().filter(|e| !e.is_empty());
+        let code = self.error_code.as_deref().filter(|c| !c.is_empty());
+        let reason = match (error, code) {
+            (Some(error), Some(code)) => format!("{} ({})", error, code),
+            (Some(reason), None) | (None, Some(reason)) => reason.to_string(),
+            (None, None) => return None,
+        };
+
+        let lower = reason.to_lowercase();
+        if lower.contains("balance") || lower.contains("allowance") {
+            Some(TradingError::InsufficientFunds { reason })
+        } else if lower.contains("post-only") || lower.contains("post only") {
+            Some(TradingError::PostOnlyCrossed { reason })
+        } else if lower.contains("fully filled")
+            || lower.contains("not_filled")
+            || lower.contains("no orders found to match")
+        {
+            Some(TradingError::PriceMoved { reason })
+        } else {
+            Some(TradingError::OrderRejected { reason })
+        }
+    }
+}
+
 /// Submit a single order using the Polymarket CLOB API.
-///
-/// CRITICAL: Always uses neg_risk=true for BTC 15min markets.
 #[instrument(skip(client, params), fields(token = %params.token_id, side = ?params.side))]
 pub async fn submit_order(
     client: &PolymarketClient,
@@ -69,6 +135,9 @@ pub async fn submit_order(
     // Validate parameters
     params.validate().map_err(TradingError::InvalidParams)?;
 
+    // Guard against double-submitting an identical order, including one in flight
+    let mut reservation = client.reserve_order(params)?;
+
     debug!(
         price = %params.price,
         size = %params.size,
@@ -76,77 +145,44 @@ pub async fn submit_order(
         "Submitting order"
     );
 
-    // Get wallet address
-    let address = client.get_address()?;
-
-    // Generate auth headers
-    let auth_headers = signing::generate_auth_headers(client.private_key(), client.signature_type()).await?;
-
-    // Generate nonce and expiration
-    let nonce = chrono::Utc::now().timestamp_millis().to_string();
-    let expiration = (chrono::Utc::now().timestamp() + 3600).to_string(); // 1 hour from now
-
-    // Convert side to string
-    let side_str = match params.side {
-        Side::Buy => "BUY",
-        Side::Sell => "SELL",
-    };
+    // Auth headers are shared with the other leg when signed moments ago
+    let auth_headers = client.auth_headers().await?;
 
-    // Convert time in force to order type
-    let order_type = match params.tif {
-        TimeInForce::FOK => "FOK",
-        TimeInForce::FAK => "GTC", // FAK maps to GTC
-        TimeInForce::GTC => "GTC",
-    };
-
-    // Create order message to sign
-    // Format: token_id + side + price + size + nonce + expiration
-    let order_message = format!(
-        "{}:{}:{}:{}:{}:{}",
-        params.token_id,
-        side_str,
-        params.price,
-        params.size,
-        nonce,
-        expiration
-    );
-
-    // Sign the order
-    let signature_bytes = signing::sign_message(client.private_key(), order_message.as_bytes()).await?;
-    let signature = format!("0x{}", hex::encode(&signature_bytes));
-
-    // Build order request
-    let order_request = OrderRequest {
-        token_id: params.token_id.clone(),
-        side: side_str.to_string(),
-        price: params.price.to_string(),
-        size: params.size.to_string(),
-        fee_rate_bps: "0".to_string(),
-        nonce,
-        expiration,
-        taker: "0x0000000000000000000000000000000000000000".to_string(),
-        maker: address,
-        signature_type: client.signature_type(),
-        signature,
-        order_type: order_type.to_string(),
-        neg_risk: true, // CRITICAL: Always true for BTC 15min markets
-    };
+    // Build and sign the order
+    let order_request = build_order_request(client, params).await?;
 
     // Submit order via API
-    let url = format!("{}/order", client.clob_url());
+    let url = client.endpoint("order");
+    let _permit = client.acquire_request_permit().await;
 
     let mut request = client.http().post(&url).json(&order_request);
     for (key, value) in auth_headers {
         request = request.header(&key, &value);
     }
 
+    // Past this point the order may have reached the exchange, so unless it was
+    // explicitly rejected a retry within the dedup window counts as a duplicate
     let response = request.send().await.map_err(|e| {
+        reservation.keep();
         TradingError::SubmissionFailed(format!("HTTP request failed: {}", e))
     })?;
 
     if !response.status().is_success() {
         let status = response.status();
         let body = response.text().await.unwrap_or_else(|_| "<body unavailable>".to_string());
+        order_request.log_rejection(status, &body);
+        // A 4xx with a reason is a rejection of this order, not a transport failure
+        if status.is_client_error() {
+            let rejection = serde_json::from_str::<SubmitResult>(&body)
+                .ok()
+                .and_then(|result| result.rejection());
+            if let Some(rejection) = rejection {
+                return Err(rejection);
+            }
+        }
+        if status.is_server_error() {
+            reservation.keep();
+        }
         return Err(TradingError::SubmissionFailed(format!(
             "Order submission failed: HTTP {} - {}",
             status, body
@@ -154,13 +190,15 @@ pub async fn submit_order(
     }
 
     let result: SubmitResult = response.json().await.map_err(|e| {
+        reservation.keep();
         TradingError::SubmissionFailed(format!("Failed to parse response: {}", e))
     })?;
 
     // Check for errors in response
-    if let Some(error) = result.error {
-        return Err(TradingError::SubmissionFailed(error));
+    if let Some(rejection) = result.rejection() {
+        return Err(rejection);
     }
+    reservation.keep();
 
     // Extract order ID
     let order_id = result.order_id.ok_or_else(|| {
@@ -179,6 +217,70 @@ pub async fn submit_order(
     Ok(order_id)
 }
 
+/// Build a signed order request.
+///
+/// The EOA always signs; the maker is the funding wallet for the configured
+/// signature type (the proxy for Magic.link, the Safe for Gnosis Safe users).
+pub async fn build_order_request(
+    client: &PolymarketClient,
+    params: &OrderParams,
+) -> Result<OrderRequest, TradingError> {
+    // Signer is the EOA; maker depends on the wallet type
+    let signer = client.get_address()?;
+    let maker = client.maker_address()?;
+
+    let nonce = client.next_nonce().to_string();
+    let expiration = params.expiration.to_string();
+
+    // Convert side to string
+    let side_str = match params.side {
+        Side::Buy => "BUY",
+        Side::Sell => "SELL",
+    };
+
+    // Round to the market's precision so the exchange accepts the values
+    let precision = params
+        .tick_size
+        .map(OrderPrecision::from_tick_size)
+        .unwrap_or_else(|| client.order_precision());
+    let price = precision.format_price(params.price, params.side);
+    let size = precision.format_size(params.size);
+
+    // Create order message to sign
+    // Format: token_id + side + price + size + nonce + expiration
+    let order_message = format!(
+        "{}:{}:{}:{}:{}:{}",
+        params.token_id,
+        side_str,
+        price,
+        size,
+        nonce,
+        expiration
+    );
+
+    // Sign the order
+    let signature_bytes = signing::sign_message(client.private_key(), order_message.as_bytes()).await?;
+    let signature = format!("0x{}", hex::encode(&signature_bytes));
+
+    Ok(OrderRequest {
+        token_id: params.token_id.clone(),
+        side: side_str.to_string(),
+        price,
+        size,
+        fee_rate_bps: "0".to_string(),
+        nonce,
+        expiration,
+        taker: "0x0000000000000000000000000000000000000000".to_string(),
+        maker,
+        signer,
+        signature_type: client.signature_type(),
+        signature,
+        order_type: params.tif.as_order_type().to_string(),
+        neg_risk: params.neg_risk,
+        post_only: params.post_only,
+    })
+}
+
 /// Submit multiple orders as fast as possible.
 #[instrument(skip(client, orders))]
 pub async fn submit_orders_fast(
@@ -215,6 +317,10 @@ pub async fn submit_orders_fast(
 }
 
 /// Poll for order status until terminal or timeout.
+///
+/// A freshly submitted order can 404 until it propagates, so "not found" is
+/// polled through for `not_found_grace`; an order that never appears is
+/// reported as rejected.
 #[instrument(skip(client), fields(order_id = %order_id))]
 pub async fn wait_for_terminal_order(
     client: &PolymarketClient,
@@ -222,8 +328,10 @@ pub async fn wait_for_terminal_order(
     requested_size: Decimal,
     timeout: Duration,
     poll_interval: Duration,
+    not_found_grace: Duration,
 ) -> OrderState {
     let start = Instant::now();
+    let mut seen = false;
 
     loop {
         if start.elapsed() >= timeout {
@@ -238,6 +346,8 @@ pub async fn wait_for_terminal_order(
 
         match get_order_status(client, order_id).await {
             Ok(state) => {
+                seen = true;
+
                 // Check if fully filled by size
                 if let Some(filled) = state.filled_size {
                     if filled >= requested_size {
@@ -260,6 +370,19 @@ pub async fn wait_for_terminal_order(
                     }
                 }
             }
+            Err(TradingError::OrderNotFound(_)) if !seen => {
+                if start.elapsed() >= not_found_grace {
+                    warn!("Order never appeared, treating as rejected");
+                    return OrderState {
+                        order_id: order_id.to_string(),
+                        status: Some(OrderStatus::Rejected),
+                        is_terminal: true,
+                        is_filled: false,
+                        ..Default::default()
+                    };
+                }
+                debug!("Order not visible yet, polling again");
+            }
             Err(e) => {
                 warn!(error = %e, "Error getting order status");
             }
@@ -277,7 +400,8 @@ pub async fn get_order_status(
     debug!(order_id = %order_id, "Getting order status");
 
     // Use the REST API to get order status
-    let url = format!("{}/order/{}", client.clob_url(), order_id);
+    let url = client.endpoint(&format!("order/{}", order_id));
+    let _permit = client.acquire_request_permit().await;
 
     let response = client
         .http()
@@ -289,6 +413,9 @@ pub async fn get_order_status(
             reason: format!("HTTP request failed: {}", e),
         })?;
 
+    if response.status() == reqwest::StatusCode::NOT_FOUND {
+        return Err(TradingError::OrderNotFound(order_id.to_string()));
+    }
     if !response.status().is_success() {
         return Err(TradingError::StatusFailed {
             order_id: order_id.to_string(),
@@ -296,57 +423,135 @@ pub async fn get_order_status(
         });
     }
 
-    let json: serde_json::Value =
-        response.json().await.map_err(|e| TradingError::StatusFailed {
-            order_id: order_id.to_string(),
-            reason: format!("Failed to parse response: {}", e),
-        })?;
+    let body = response.text().await.map_err(|e| TradingError::StatusFailed {
+        order_id: order_id.to_string(),
+        reason: format!("Failed to read response: {}", e),
+    })?;
+    let parsed = OrderStatusResponse::parse(&body).map_err(|e| TradingError::StatusFailed {
+        order_id: order_id.to_string(),
+        reason: format!("Failed to parse response: {}", e),
+    })?;
+
+    Ok(parsed.into_state(order_id))
+}
 
-    // Parse the response - handle various field name conventions
-    let status = json
-        .get("status")
-        .or_else(|| json.get("orderStatus"))
-        .or_else(|| json.get("order_status"))
-        .and_then(|v| v.as_str())
-        .and_then(|s| s.parse::<OrderStatus>().ok());
+/// Order status response, accepting the field names seen across CLOB versions.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default)]
+pub struct OrderStatusResponse {
+    /// Order status as reported.
+    #[serde(alias = "orderStatus", alias = "order_status")]
+    pub status: Option<String>,
+    /// Filled size.
+    #[serde(
+        alias = "filledSize",
+        alias = "filled_size",
+        alias = "sizeFilled",
+        alias = "size_matched",
+        deserialize_with = "lenient_decimal"
+    )]
+    pub filled: Option<Decimal>,
+    /// Remaining size.
+    #[serde(
+        alias = "remainingSize",
+        alias = "remaining_size",
+        alias = "sizeRemaining",
+        deserialize_with = "lenient_decimal"
+    )]
+    pub remaining: Option<Decimal>,
+    /// Original size.
+    #[serde(
+        alias = "originalSize",
+        alias = "original_size",
+        deserialize_with = "lenient_decimal"
+    )]
+    pub size: Option<Decimal>,
+    /// Fees charged on the order's fills.
+    #[serde(
+        alias = "fees",
+        alias = "feePaid",
+        alias = "fee_paid",
+        deserialize_with = "lenient_decimal"
+    )]
+    pub fee: Option<Decimal>,
+}
 
-    let filled_size = parse_decimal_field(&json, &["filled", "filledSize", "filled_size", "sizeFilled"]);
-    let remaining_size = parse_decimal_field(&json, &["remaining", "remainingSize", "remaining_size", "sizeRemaining"]);
-    let original_size = parse_decimal_field(&json, &["size", "originalSize", "original_size"]);
+impl OrderStatusResponse {
+    /// Parse a response body.
+    ///
+    /// Bodies the typed shape rejects, such as one carrying two aliases of the
+    /// same field, are probed field by field instead.
+    pub fn parse(body: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(body).or_else(|e| {
+            let json: serde_json::Value = serde_json::from_str(body)?;
+            debug!(error = %e, "Order status shape not recognized, probing fields");
+            Ok(Self::from_value(&json))
+        })
+    }
 
-    let is_terminal = status.map(|s| s.is_terminal()).unwrap_or(false);
-    let is_filled = status.map(|s| s.is_filled()).unwrap_or(false);
+    /// Probe a raw JSON response for each field under its known names.
+    fn from_value(json: &serde_json::Value) -> Self {
+        let status = ["status", "orderStatus", "order_status"]
+            .iter()
+            .find_map(|key| json.get(*key)?.as_str())
+            .map(str::to_string);
+
+        Self {
+            status,
+            filled: parse_decimal_field(
+                json,
+                &["filled", "filledSize", "filled_size", "sizeFilled", "size_matched"],
+            ),
+            remaining: parse_decimal_field(
+                json,
+                &["remaining", "remainingSize", "remaining_size", "sizeRemaining"],
+            ),
+            size: parse_decimal_field(json, &["size", "originalSize", "original_size"]),
+            fee: parse_decimal_field(json, &["fee", "fees", "feePaid", "fee_paid"]),
+        }
+    }
 
-    Ok(OrderState {
-        order_id: order_id.to_string(),
-        status,
-        filled_size,
-        remaining_size,
-        original_size,
-        is_terminal,
-        is_filled,
-    })
+    /// Order state for `order_id`; unknown statuses leave it non-terminal.
+    pub fn into_state(self, order_id: &str) -> OrderState {
+        let status = self.status.and_then(|s| s.parse::<OrderStatus>().ok());
+
+        OrderState {
+            order_id: order_id.to_string(),
+            status,
+            filled_size: self.filled,
+            remaining_size: self.remaining,
+            original_size: self.size,
+            is_terminal: status.map(|s| s.is_terminal()).unwrap_or(false),
+            is_filled: status.map(|s| s.is_filled()).unwrap_or(false),
+            fee: self.fee,
+        }
+    }
+}
+
+/// Deserialize a decimal given as a string or number; anything unparseable is `None`.
+fn lenient_decimal<'de, D>(deserializer: D) -> Result<Option<Decimal>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let value = Option::<serde_json::Value>::deserialize(deserializer)?;
+    Ok(value.as_ref().and_then(decimal_value))
 }
 
 /// Parse a decimal field from JSON, trying multiple field names.
 fn parse_decimal_field(json: &serde_json::Value, keys: &[&str]) -> Option<Decimal> {
-    for key in keys {
-        if let Some(value) = json.get(*key) {
-            // Try as string first
-            if let Some(s) = value.as_str() {
-                if let Ok(d) = s.parse::<Decimal>() {
-                    return Some(d);
-                }
-            }
-            // Try as number
-            if let Some(n) = value.as_f64() {
-                if let Ok(d) = Decimal::try_from(n) {
-                    return Some(d);
-                }
-            }
+    keys.iter().find_map(|key| decimal_value(json.get(*key)?))
+}
+
+/// Decimal from a JSON string or number.
+fn decimal_value(value: &serde_json::Value) -> Option<Decimal> {
+    // Try as string first
+    if let Some(s) = value.as_str() {
+        if let Ok(d) = s.parse::<Decimal>() {
+            return Some(d);
         }
     }
-    None
+    // Try as number
+    value.as_f64().and_then(|n| Decimal::try_from(n).ok())
 }
 
 /// Cancel one or more orders.
@@ -361,10 +566,10 @@ pub async fn cancel_orders(
 
     debug!(count = order_ids.len(), "Cancelling orders");
 
-    let auth_headers = signing::generate_auth_headers(client.private_key(), client.signature_type()).await?;
+    let auth_headers = client.auth_headers().await?;
 
     for order_id in order_ids {
-        let url = format!("{}/order/{}", client.clob_url(), order_id);
+        let url = client.endpoint(&format!("order/{}", order_id));
 
         let mut request = client.http().delete(&url);
         for (key, value) in &auth_headers {
@@ -401,9 +606,9 @@ pub async fn cancel_orders(
 pub async fn cancel_all_orders(client: &PolymarketClient) -> Result<u32, TradingError> {
     debug!("Cancelling all open orders");
 
-    let auth_headers = signing::generate_auth_headers(client.private_key(), client.signature_type()).await?;
+    let auth_headers = client.auth_headers().await?;
 
-    let url = format!("{}/cancel-all", client.clob_url());
+    let url = client.endpoint("cancel-all");
 
     let mut request = client.http().delete(&url);
     for (key, value) in auth_headers {
@@ -440,6 +645,42 @@ pub async fn cancel_all_orders(client: &PolymarketClient) -> Result<u32, Trading
     Ok(count)
 }
 
+/// Redeem a resolved market's winning shares.
+///
+/// Skipped in dry-run or unless `auto_redeem` is set, returning `Ok(None)`;
+/// otherwise returns the redemption result, or the error for the caller to retry.
+pub async fn redeem_after_close<F, Fut, T>(
+    dry_run: bool,
+    auto_redeem: bool,
+    redeem: F,
+) -> Result<Option<T>, TradingError>
+where
+    F: FnOnce() -> Fut,
+    Fut: Future<Output = Result<T, TradingError>>,
+{
+    if dry_run || !auto_redeem {
+        return Ok(None);
+    }
+
+    redeem().await.map(Some)
+}
+
+/// Cancel every open order on operator request (the `cancel-all` command).
+///
+/// Refuses in dry-run, which never touches the live account; returns the
+/// number of orders cancelled.
+pub async fn cancel_all_command<F, Fut>(dry_run: bool, cancel_all: F) -> Result<u32, TradingError>
+where
+    F: FnOnce() -> Fut,
+    Fut: Future<Output = Result<u32, TradingError>>,
+{
+    if dry_run {
+        return Err(TradingError::DryRun("cancel open orders".to_string()));
+    }
+
+    cancel_all().await
+}
+
 /// Extract order ID from API response.
 pub fn extract_order_id(result: &serde_json::Value) -> Option<String> {
     // Try various field names
@@ -477,9 +718,15 @@ pub fn poll_interval_from_config(config: &crate::config::Config) -> Duration {
     Duration::from_millis(config.order_poll_interval_ms)
 }
 
+/// Create order-not-found grace period from config.
+pub fn not_found_grace_from_config(config: &crate::config::Config) -> Duration {
+    Duration::from_millis(config.order_not_found_grace_ms)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use rust_decimal_macros::dec;
 
     #[test]
     fn extract_order_id_various_formats() {
@@ -496,6 +743,41 @@ mod tests {
         assert_eq!(extract_order_id(&json4), None);
     }
 
+    #[test]
+    fn rejection_bodies_map_to_typed_errors() {
+        let rejection = |body: &str| {
+            serde_json::from_str::<SubmitResult>(body).unwrap().rejection()
+        };
+
+        assert!(matches!(
+            rejection(r#"{"error":"not enough balance / allowance"}"#),
+            Some(TradingError::InsufficientFunds { .. })
+        ));
+        assert!(matches!(
+            rejection(r#"{"success":false,"error_code":"INSUFFICIENT_BALANCE"}"#),
+            Some(TradingError::InsufficientFunds { .. })
+        ));
+        match rejection(
+            r#"{"success":false,"error":"order couldn't be fully filled. FOK orders are fully filled or killed.","error_code":"FOK_ORDER_NOT_FILLED_ERROR"}"#,
+        ) {
+            Some(TradingError::PriceMoved { reason }) => {
+                assert!(reason.contains("FOK_ORDER_NOT_FILLED_ERROR"), "{}", reason);
+            }
+            other => panic!("expected PriceMoved, got {:?}", other),
+        }
+        assert!(matches!(
+            rejection(r#"{"error":"no orders found to match with FAK order"}"#),
+            Some(TradingError::PriceMoved { .. })
+        ));
+        assert!(matches!(
+            rejection(r#"{"error":"invalid post-only order: order crosses book"}"#),
+            Some(TradingError::PostOnlyCrossed { .. })
+        ));
+        let tick_size = r#"{"error":"invalid tick size","error_code":"INVALID_ORDER_MIN_TICK_SIZE"}"#;
+        assert!(matches!(rejection(tick_size), Some(TradingError::OrderRejected { .. })));
+        assert!(rejection(r#"{"orderID":"0x1","success":true,"error":""}"#).is_none());
+    }
+
     #[test]
     fn parse_decimal_field_works() {
         let json = serde_json::json!({
@@ -514,4 +796,240 @@ mod tests {
         );
         assert_eq!(parse_decimal_field(&json, &["missing"]), None);
     }
+
+    #[test]
+    fn order_status_response_shapes() {
+        let parse = |body: &str| OrderStatusResponse::parse(body).unwrap();
+
+        // Snake-case strings, as the mock CLOB and older API versions return
+        let legacy = parse(r#"{"status":"filled","size":"10","filled":"10"}"#);
+        assert_eq!(legacy.status.as_deref(), Some("filled"));
+        assert_eq!((legacy.size, legacy.filled), (Some(dec!(10)), Some(dec!(10))));
+
+        // Camel-case with numeric values
+        let camel = parse(
+            &serde_json::json!({
+                "orderStatus": "LIVE",
+                "originalSize": 25,
+                "filledSize": 7.5,
+                "remainingSize": 17.5,
+                "feePaid": "0.02"
+            })
+            .to_string(),
+        );
+        let state = camel.into_state("0xabc");
+        assert_eq!(state.status, Some(OrderStatus::Live));
+        assert_eq!(state.original_size, Some(dec!(25)));
+        assert_eq!(state.filled_size, Some(dec!(7.5)));
+        assert_eq!(state.remaining_size, Some(dec!(17.5)));
+        assert_eq!(state.fee, Some(dec!(0.02)));
+        assert!(!state.is_terminal);
+
+        // CLOB order payload reporting matched size
+        let clob = parse(
+            r#"{"id":"0xabc","status":"CANCELED","original_size":"10","size_matched":"4"}"#,
+        );
+        let state = clob.into_state("0xabc");
+        assert_eq!(state.filled_size, Some(dec!(4)));
+        assert!(state.is_terminal && !state.is_filled);
+
+        // Two names for one field fail the typed shape and fall back to probing
+        let duplicated =
+            parse(r#"{"status":"filled","size":"10","original_size":"10","fees":"x"}"#);
+        assert_eq!(duplicated.size, Some(dec!(10)));
+        assert_eq!(duplicated.fee, None);
+        assert!(duplicated.into_state("0xabc").is_filled);
+
+        // Unknown status and junk values leave the state open
+        let unknown = parse(r#"{"status":"unmatched","filled":null,"remaining":"n/a"}"#);
+        assert_eq!((unknown.filled, unknown.remaining), (None, None));
+        assert!(!unknown.into_state("0xabc").is_terminal);
+        assert!(OrderStatusResponse::parse("not json").is_err());
+    }
+
+    fn test_config(signature_type: u8, funder: Option<&str>) -> crate::config::Config {
+        crate::config::Config {
+            polymarket_signature_type: signature_type,
+            polymarket_funder: funder.map(str::to_string),
+            order_size: dec!(10),
+            polymarket_ws_url: "wss://test".to_string(),
+            polymarket_clob_url: "https://test".to_string(),
+            ..crate::config::Config::test_default()
+        }
+    }
+
+    fn test_params() -> OrderParams {
+        OrderParams {
+            token_id: "up-token".to_string(),
+            side: Side::Buy,
+            price: dec!(0.48),
+            size: dec!(10),
+            tif: TimeInForce::FOK,
+            neg_risk: true,
+            expiration: 0,
+            post_only: false,
+            tick_size: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn gnosis_safe_order_uses_safe_as_maker() {
+        let safe = "0x00000000000000000000000000000000000000aa";
+        let client = PolymarketClient::new(&test_config(2, Some(safe)));
+
+        let order = build_order_request(&client, &test_params()).await.unwrap();
+
+        assert_eq!(order.maker, safe);
+        assert_eq!(order.signer, client.get_address().unwrap());
+        assert_ne!(order.maker, order.signer);
+        assert_eq!(order.signature_type, 2);
+    }
+
+    #[tokio::test]
+    async fn magic_link_order_uses_funder_as_maker() {
+        let funder = "0x00000000000000000000000000000000000000bb";
+        let client = PolymarketClient::new(&test_config(1, Some(funder)));
+
+        let order = build_order_request(&client, &test_params()).await.unwrap();
+
+        assert_eq!(order.maker, funder);
+        assert_eq!(order.signature_type, 1);
+    }
+
+    #[tokio::test]
+    async fn order_body_carries_param_expiration() {
+        let client = PolymarketClient::new(&test_config(0, None));
+        let gtd = test_params().with_tif(TimeInForce::GTD).with_expiration(1_765_302_300);
+
+        let fok_order = build_order_request(&client, &test_params()).await.unwrap();
+        let gtd_order = build_order_request(&client, &gtd).await.unwrap();
+
+        assert_eq!(fok_order.expiration, "0");
+        assert_eq!(gtd_order.expiration, "1765302300");
+        assert_eq!(gtd_order.order_type, "GTD");
+    }
+
+    #[tokio::test]
+    async fn paired_orders_get_distinct_nonces() {
+        let client = PolymarketClient::new(&test_config(0, None));
+        let params = test_params();
+
+        let (up, down) = tokio::join!(
+            build_order_request(&client, &params),
+            build_order_request(&client, &params),
+        );
+
+        assert_ne!(up.unwrap().nonce, down.unwrap().nonce);
+    }
+
+    #[tokio::test]
+    async fn fak_order_type_is_not_gtc() {
+        let client = PolymarketClient::new(&test_config(0, None));
+        let fak = test_params().with_tif(TimeInForce::FAK);
+        let gtc = OrderParams {
+            price: dec!(0.47),
+            ..test_params().with_tif(TimeInForce::GTC)
+        };
+
+        let fak_order = build_order_request(&client, &fak).await.unwrap();
+        let gtc_order = build_order_request(&client, &gtc).await.unwrap();
+
+        assert_eq!(fak_order.order_type, "FAK");
+        assert_eq!(gtc_order.order_type, "GTC");
+        let json = serde_json::to_value(&fak_order).unwrap();
+        assert_eq!(json["order_type"], "FAK");
+    }
+
+    #[tokio::test]
+    async fn post_only_flag_is_serialized() {
+        let client = PolymarketClient::new(&test_config(0, None));
+        let maker = test_params().with_tif(TimeInForce::GTC).with_post_only(true);
+
+        let maker_order = build_order_request(&client, &maker).await.unwrap();
+        let taker_order = build_order_request(&client, &test_params()).await.unwrap();
+
+        assert_eq!(serde_json::to_value(&maker_order).unwrap()["post_only"], true);
+        assert_eq!(serde_json::to_value(&taker_order).unwrap()["post_only"], false);
+        // A post-only order must be able to rest
+        assert!(test_params().with_post_only(true).validate().is_err());
+    }
+
+    #[tokio::test]
+    async fn order_body_uses_tick_precision() {
+        let client = PolymarketClient::new(&test_config(0, None));
+        let params = OrderParams {
+            price: dec!(0.4800),
+            size: dec!(10.000),
+            ..test_params()
+        };
+        let over_precise = OrderParams {
+            price: dec!(0.48437),
+            ..test_params()
+        };
+
+        let market_tick = OrderParams {
+            price: dec!(0.4837),
+            tick_size: Some(dec!(0.001)),
+            ..test_params()
+        };
+
+        let order = build_order_request(&client, &params).await.unwrap();
+        let rounded = build_order_request(&client, &over_precise).await.unwrap();
+        let fine = build_order_request(&client, &market_tick).await.unwrap();
+
+        assert_eq!(order.price, "0.48");
+        assert_eq!(order.size, "10");
+        assert_eq!(rounded.price, "0.48");
+        // The market's own tick wins over the configured TICK_SIZE
+        assert_eq!(fine.price, "0.483");
+    }
+
+    #[tokio::test]
+    async fn gnosis_safe_order_requires_funder() {
+        let client = PolymarketClient::new(&test_config(2, None));
+
+        assert!(build_order_request(&client, &test_params()).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn redemption_skipped_in_dry_run_or_when_disabled() {
+        for (dry_run, auto_redeem) in [(true, true), (false, false)] {
+            let mut called = false;
+            let received = redeem_after_close(dry_run, auto_redeem, || {
+                called = true;
+                async { Ok(dec!(10)) }
+            })
+            .await;
+
+            assert_eq!(received.unwrap(), None);
+            assert!(!called);
+        }
+
+        let received = redeem_after_close(false, true, || async { Ok(dec!(10)) }).await;
+        assert_eq!(received.unwrap(), Some(dec!(10)));
+
+        let failed = redeem_after_close(false, true, || async {
+            Err::<Decimal, _>(TradingError::RedemptionFailed("reverted".to_string()))
+        })
+        .await;
+        assert!(failed.is_err());
+    }
+
+    #[tokio::test]
+    async fn cancel_all_command_reports_count_and_refuses_dry_run() {
+        use crate::market::mock::{MockConfig, MockPolymarketClient};
+
+        let client = MockPolymarketClient::with_config(MockConfig {
+            open_orders: 3,
+            ..Default::default()
+        });
+
+        let dry_run = cancel_all_command(true, || client.cancel_all_orders()).await;
+        assert!(matches!(dry_run, Err(TradingError::DryRun(_))), "got {:?}", dry_run);
+        assert_eq!(client.cancel_all_calls(), 0);
+
+        let cancelled = cancel_all_command(false, || client.cancel_all_orders()).await.unwrap();
+        assert_eq!(cancelled, 3);
+        assert_eq!(client.cancel_all_calls(), 1);
+    }
 }
@@ -4,11 +4,17 @@
 //! - Order types and creation
 //! - Order submission and execution
 //! - Position tracking
+//! - On-chain redemption of resolved positions
+//! - Real-time order updates via the user WebSocket channel
 
 pub mod execution;
 pub mod order;
 pub mod position;
+pub mod redeem;
+pub mod user_ws;
 
 pub use execution::{cancel_orders, submit_order, submit_orders_fast, wait_for_terminal_order};
-pub use order::{OrderParams, OrderState, OrderStatus, Side, TimeInForce};
+pub use order::{OrderParams, OrderPrecision, OrderState, OrderStatus, Side, TimeInForce};
 pub use position::{MarketPositions, Position};
+pub use redeem::{redeem_positions, Redemption};
+pub use user_ws::{parse_user_message, UserWebSocket};
@@ -1,6 +1,6 @@
 //! Order types and creation.
 
-use rust_decimal::Decimal;
+use rust_decimal::{Decimal, RoundingStrategy};
 use serde::{Deserialize, Serialize};
 use strum::{Display, EnumString};
 
@@ -32,6 +32,114 @@ pub enum TimeInForce {
     /// Good-till-cancelled: stays on book until filled or cancelled.
     #[strum(serialize = "GTC", serialize = "gtc")]
     GTC,
+    /// Good-till-date: stays on book until filled, cancelled or expired.
+    #[strum(serialize = "GTD", serialize = "gtd")]
+    GTD,
+}
+
+/// Seconds beyond now the CLOB requires of a GTD expiration; resting orders
+/// also expire this long before the market closes.
+pub const GTD_SECURITY_BUFFER_SECS: i64 = 60;
+
+impl TimeInForce {
+    /// Order type string expected by the CLOB order endpoint.
+    pub fn as_order_type(&self) -> &'static str {
+        match self {
+            TimeInForce::FOK => "FOK",
+            TimeInForce::FAK => "FAK",
+            TimeInForce::GTC => "GTC",
+            TimeInForce::GTD => "GTD",
+        }
+    }
+
+    /// Whether orders of this kind can rest on the book.
+    pub fn rests(&self) -> bool {
+        matches!(self, TimeInForce::GTC | TimeInForce::GTD)
+    }
+
+    /// Time-in-force and expiration (unix seconds, 0 for none) for a resting order.
+    ///
+    /// Only GTD orders may carry an expiration, so a resting order is sent GTD
+    /// expiring [`GTD_SECURITY_BUFFER_SECS`] before `market_end`, or after
+    /// `ttl_seconds` when that is sooner. Once the close is too near for the CLOB
+    /// to accept a GTD, the order is sent GTC without one.
+    pub fn resting(market_end: i64, ttl_seconds: u64, now: i64) -> (Self, i64) {
+        let earliest = now.saturating_add(GTD_SECURITY_BUFFER_SECS);
+        let mut expiration = market_end - GTD_SECURITY_BUFFER_SECS;
+        if ttl_seconds > 0 {
+            let ttl = i64::try_from(ttl_seconds).unwrap_or(i64::MAX);
+            expiration = expiration.min(earliest.saturating_add(ttl));
+        }
+        if expiration > earliest {
+            (TimeInForce::GTD, expiration)
+        } else {
+            (TimeInForce::GTC, 0)
+        }
+    }
+}
+
+/// Price and size precision the exchange accepts for a market.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OrderPrecision {
+    /// Price tick size; prices are whole multiples of it.
+    pub tick_size: Decimal,
+    /// Decimal places for sizes.
+    pub size_dp: u32,
+}
+
+impl Default for OrderPrecision {
+    fn default() -> Self {
+        Self::from_tick_size(Decimal::new(1, 2))
+    }
+}
+
+impl OrderPrecision {
+    /// Size decimals accepted by the CLOB.
+    pub const SIZE_DECIMALS: u32 = 2;
+
+    /// Precision for a market with the given tick size.
+    pub fn from_tick_size(tick_size: Decimal) -> Self {
+        Self {
+            tick_size,
+            size_dp: Self::SIZE_DECIMALS,
+        }
+    }
+
+    /// Decimal places of the tick size (e.g. 0.01 gives 2).
+    pub fn price_dp(&self) -> u32 {
+        self.tick_size.normalize().scale()
+    }
+
+    /// Round a price onto the tick grid, never against the order's limit.
+    ///
+    /// BUY prices round down and SELL prices round up, so the submitted limit
+    /// never pays more or accepts less than the price that was computed.
+    pub fn round_price(&self, price: Decimal, side: Side) -> Decimal {
+        if self.tick_size <= Decimal::ZERO {
+            return price;
+        }
+        let ticks = price / self.tick_size;
+        let ticks = match side {
+            Side::Buy => ticks.floor(),
+            Side::Sell => ticks.ceil(),
+        };
+        ticks * self.tick_size
+    }
+
+    /// Round a size down to the size precision, never above the tracked size.
+    pub fn round_size(&self, size: Decimal) -> Decimal {
+        size.round_dp_with_strategy(self.size_dp, RoundingStrategy::ToZero)
+    }
+
+    /// Format a price for the order body with [`Self::round_price`].
+    pub fn format_price(&self, price: Decimal, side: Side) -> String {
+        self.round_price(price, side).normalize().to_string()
+    }
+
+    /// Format a size for the order body with [`Self::round_size`].
+    pub fn format_size(&self, size: Decimal) -> String {
+        self.round_size(size).normalize().to_string()
+    }
 }
 
 /// Order parameters for submission.
@@ -47,6 +155,14 @@ pub struct OrderParams {
     pub size: Decimal,
     /// Time-in-force.
     pub tif: TimeInForce,
+    /// Whether the market trades on the neg-risk exchange.
+    pub neg_risk: bool,
+    /// Expiration in unix seconds (0 for none).
+    pub expiration: i64,
+    /// Rest on the book as maker; the CLOB rejects the order if it would cross.
+    pub post_only: bool,
+    /// The market's price tick size, if known; `None` uses the configured `TICK_SIZE`.
+    pub tick_size: Option<Decimal>,
 }
 
 impl OrderParams {
@@ -58,6 +174,10 @@ impl OrderParams {
             price,
             size,
             tif: TimeInForce::FOK,
+            neg_risk: true,
+            expiration: 0,
+            post_only: false,
+            tick_size: None,
         }
     }
 
@@ -69,6 +189,10 @@ impl OrderParams {
             price,
             size,
             tif: TimeInForce::FOK,
+            neg_risk: true,
+            expiration: 0,
+            post_only: false,
+            tick_size: None,
         }
     }
 
@@ -78,6 +202,18 @@ impl OrderParams {
         self
     }
 
+    /// Set the expiration (unix seconds, 0 for none).
+    pub fn with_expiration(mut self, expiration: i64) -> Self {
+        self.expiration = expiration;
+        self
+    }
+
+    /// Set the post-only flag.
+    pub fn with_post_only(mut self, post_only: bool) -> Self {
+        self.post_only = post_only;
+        self
+    }
+
     /// Validate order parameters.
     pub fn validate(&self) -> Result<(), String> {
         if self.token_id.is_empty() {
@@ -89,6 +225,14 @@ impl OrderParams {
         if self.size <= Decimal::ZERO {
             return Err("size must be positive".to_string());
         }
+        // FOK and FAK orders never rest, so they cannot be makers
+        if self.post_only && !self.tif.rests() {
+            return Err("post-only orders must be GTC or GTD".to_string());
+        }
+        // The CLOB accepts an expiration only on GTD orders
+        if (self.expiration != 0) != (self.tif == TimeInForce::GTD) {
+            return Err("only GTD orders carry an expiration".to_string());
+        }
         Ok(())
     }
 }
@@ -149,6 +293,8 @@ pub struct OrderState {
     pub is_terminal: bool,
     /// Whether order was fully filled.
     pub is_filled: bool,
+    /// Fees charged on the order's fills, in USDC.
+    pub fee: Option<Decimal>,
 }
 
 impl Default for OrderState {
@@ -161,6 +307,7 @@ impl Default for OrderState {
             original_size: None,
             is_terminal: false,
             is_filled: false,
+            fee: None,
         }
     }
 }
@@ -170,6 +317,31 @@ mod tests {
     use super::*;
     use rust_decimal_macros::dec;
 
+    #[test]
+    fn resting_orders_are_gtd_before_market_close() {
+        let now = 1_765_301_400;
+        let close = now + 600;
+
+        // Resting orders expire one buffer before close
+        assert_eq!(TimeInForce::resting(close, 0, now), (TimeInForce::GTD, close - 60));
+
+        // A TTL shortens the life, on top of the CLOB's security buffer
+        assert_eq!(TimeInForce::resting(close, 30, now), (TimeInForce::GTD, now + 90));
+        assert_eq!(TimeInForce::resting(close, 3600, now), (TimeInForce::GTD, close - 60));
+
+        // Too near the close for a GTD, the order rests without an expiration
+        assert_eq!(TimeInForce::resting(now + 120, 0, now), (TimeInForce::GTC, 0));
+    }
+
+    #[test]
+    fn only_gtd_orders_carry_an_expiration() {
+        let fok = OrderParams::buy("token", dec!(0.50), dec!(10));
+        assert!(fok.clone().with_expiration(1_765_302_000).validate().is_err());
+        assert!(fok.clone().with_tif(TimeInForce::GTD).validate().is_err());
+        let gtd = fok.with_tif(TimeInForce::GTD).with_expiration(1_765_302_000);
+        assert!(gtd.validate().is_ok());
+    }
+
     #[test]
     fn order_params_creation() {
         let buy = OrderParams::buy("token-123", dec!(0.50), dec!(10));
@@ -183,6 +355,32 @@ mod tests {
         assert_eq!(sell.tif, TimeInForce::GTC);
     }
 
+    #[test]
+    fn order_precision_formats_to_tick_scale() {
+        let precision = OrderPrecision::from_tick_size(dec!(0.01));
+
+        assert_eq!(precision.format_price(dec!(0.48), Side::Buy), "0.48");
+        assert_eq!(precision.format_price(dec!(0.480), Side::Buy), "0.48");
+        assert_eq!(precision.format_price(dec!(0.4800), Side::Sell), "0.48");
+        // Over-precise BUY prices round down, so the limit never rises
+        assert_eq!(precision.format_price(dec!(0.48437), Side::Buy), "0.48");
+        assert_eq!(precision.format_price(dec!(0.495), Side::Buy), "0.49");
+        // SELL prices round up, so the limit never falls
+        assert_eq!(precision.format_price(dec!(0.48437), Side::Sell), "0.49");
+        assert_eq!(precision.format_size(dec!(10.000)), "10");
+        assert_eq!(precision.format_size(dec!(10.125)), "10.12");
+        assert_eq!(precision.format_size(dec!(10.129)), "10.12");
+
+        let fine = OrderPrecision::from_tick_size(dec!(0.001));
+        assert_eq!(fine.price_dp(), 3);
+        assert_eq!(fine.format_price(dec!(0.4815), Side::Buy), "0.481");
+
+        // Ticks that are not a power of ten still snap to the grid
+        let half_cent = OrderPrecision::from_tick_size(dec!(0.005));
+        assert_eq!(half_cent.format_price(dec!(0.4849), Side::Buy), "0.48");
+        assert_eq!(half_cent.format_price(dec!(0.4801), Side::Sell), "0.485");
+    }
+
     #[test]
     fn order_params_validation() {
         let valid = OrderParams::buy("token", dec!(0.50), dec!(10));
@@ -214,5 +412,7 @@ mod tests {
         assert_eq!(TimeInForce::from_str("FOK").unwrap(), TimeInForce::FOK);
         assert_eq!(TimeInForce::from_str("fok").unwrap(), TimeInForce::FOK);
         assert_eq!(TimeInForce::from_str("GTC").unwrap(), TimeInForce::GTC);
+        assert_eq!(TimeInForce::from_str("gtd").unwrap(), TimeInForce::GTD);
+        assert_eq!(TimeInForce::GTD.as_order_type(), "GTD");
     }
 }
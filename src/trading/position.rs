@@ -75,6 +75,26 @@ impl MarketPositions {
         let cost = self.up.avg_price * pairs + self.down.avg_price * pairs;
         pairs - cost
     }
+
+    /// Expected settlement value of the unpaired shares, given the probability UP resolves.
+    pub fn directional_value(&self, up_probability: Decimal) -> Decimal {
+        let imbalance = self.imbalance();
+        if imbalance > Decimal::ZERO {
+            imbalance * up_probability
+        } else {
+            -imbalance * (Decimal::ONE - up_probability)
+        }
+    }
+
+    /// Expected settlement value of all shares: guaranteed pairs plus the unpaired remainder.
+    pub fn expected_value(&self, up_probability: Decimal) -> Decimal {
+        self.expected_payout() + self.directional_value(up_probability)
+    }
+
+    /// Expected profit of all shares against their full cost basis.
+    pub fn expected_total_profit(&self, up_probability: Decimal) -> Decimal {
+        self.expected_value(up_probability) - self.total_cost_basis()
+    }
 }
 
 /// Position response from API.
@@ -186,4 +206,41 @@ mod tests {
         // Expected payout only counts balanced pairs
         assert_eq!(positions.expected_payout(), dec!(10));
     }
+
+    #[test]
+    fn imbalanced_remainder_valued_at_assumed_probability() {
+        let positions = MarketPositions {
+            up: Position {
+                token_id: "up".to_string(),
+                size: dec!(15),
+                avg_price: dec!(0.50),
+                unrealized_pnl: None,
+            },
+            down: Position {
+                token_id: "down".to_string(),
+                size: dec!(10),
+                avg_price: dec!(0.48),
+                unrealized_pnl: None,
+            },
+        };
+
+        // 10 pairs cost 9.80 and pay 10; the extra 5 UP cost 2.50
+        assert_eq!(positions.expected_profit(), dec!(0.2));
+        assert_eq!(positions.total_cost_basis(), dec!(12.3));
+
+        assert_eq!(positions.directional_value(dec!(0.5)), dec!(2.5));
+        assert_eq!(positions.expected_value(dec!(0.5)), dec!(12.5));
+        assert_eq!(positions.expected_total_profit(dec!(0.5)), dec!(0.2));
+
+        assert_eq!(positions.directional_value(dec!(0.7)), dec!(3.5));
+        assert_eq!(positions.expected_value(dec!(0.7)), dec!(13.5));
+        assert_eq!(positions.expected_total_profit(dec!(0.7)), dec!(1.2));
+
+        // A DOWN-heavy position pays on the complement
+        let flipped = MarketPositions {
+            up: positions.down.clone(),
+            down: positions.up.clone(),
+        };
+        assert_eq!(flipped.directional_value(dec!(0.7)), dec!(1.5));
+    }
 }
@@ -0,0 +1,227 @@
+//! On-chain redemption of resolved positions through the CTF contract.
+//!
+//! Once a market resolves, its winning shares are worth 1 USDC each but stay
+//! as conditional tokens until redeemed. Redemption burns both outcomes of the
+//! condition from the signing wallet and pays out the winners, so it is sent
+//! from the EOA that holds the shares and costs gas in POL.
+
+use std::time::Duration;
+
+use alloy::primitives::{address, Address, B256, U256};
+use alloy::providers::ProviderBuilder;
+use alloy::sol;
+use rust_decimal::Decimal;
+use tracing::{info, instrument};
+
+use crate::config::Config;
+use crate::error::TradingError;
+use crate::market::client::wei_to_units;
+use crate::market::Market;
+use crate::signing;
+
+/// Conditional Tokens Framework contract on Polygon.
+pub const CTF_ADDRESS: Address = address!("4D97DCd97eC945f40cF65F87097ACe5EA0476045");
+
+/// USDC.e on Polygon, the collateral of every Polymarket condition.
+pub const COLLATERAL_ADDRESS: Address = address!("2791Bca1f2de4661ED88A30C99A7a9449Aa84174");
+
+/// Index sets of the two outcomes of a binary condition.
+const BINARY_INDEX_SETS: [u64; 2] = [1, 2];
+
+/// Decimals of POL, the gas token.
+const GAS_TOKEN_DECIMALS: u32 = 18;
+
+/// How long to wait for the redemption transaction to be mined.
+const RECEIPT_TIMEOUT: Duration = Duration::from_secs(120);
+
+sol! {
+    #[sol(rpc)]
+    interface IConditionalTokens {
+        function redeemPositions(
+            address collateralToken,
+            bytes32 parentCollectionId,
+            bytes32 conditionId,
+            uint256[] indexSets
+        ) external;
+
+        event PayoutRedemption(
+            address indexed redeemer,
+            address indexed collateralToken,
+            bytes32 indexed parentCollectionId,
+            bytes32 conditionId,
+            uint256[] indexSets,
+            uint256 payout
+        );
+    }
+}
+
+/// Outcome of a mined redemption.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Redemption {
+    /// Redemption transaction hash.
+    pub tx_hash: String,
+    /// USDC paid out.
+    pub usdc_received: Decimal,
+    /// Gas paid for the transaction, in POL.
+    pub gas_cost: Decimal,
+}
+
+impl Redemption {
+    /// Build from the payout event and the receipt's gas figures.
+    fn from_receipt_parts(
+        tx_hash: B256,
+        payout: U256,
+        gas_used: u64,
+        effective_gas_price: u128,
+        usdc_decimals: u32,
+    ) -> Self {
+        let gas_wei = u128::from(gas_used).saturating_mul(effective_gas_price);
+        Self {
+            tx_hash: tx_hash.to_string(),
+            usdc_received: wei_to_units(&payout.to_string(), usdc_decimals),
+            gas_cost: wei_to_units(&gas_wei.to_string(), GAS_TOKEN_DECIMALS),
+        }
+    }
+}
+
+/// Check that `market` can be redeemed straight through the CTF contract.
+fn redeemable_condition(config: &Config, market: &Market) -> Result<B256, TradingError> {
+    if config.polymarket_signature_type != 0 {
+        return Err(TradingError::RedemptionUnsupported(
+            "shares are held by the proxy wallet; redeem through the Polymarket relayer".into(),
+        ));
+    }
+    if market.neg_risk_or(config.neg_risk_default) {
+        return Err(TradingError::RedemptionUnsupported(format!(
+            "{} is a neg-risk market; redeem through the NegRiskAdapter",
+            market.slug
+        )));
+    }
+    let condition_id = market.condition_id.as_deref().ok_or_else(|| {
+        TradingError::RedemptionUnsupported(format!("No condition ID for {}", market.slug))
+    })?;
+    condition_id.parse().map_err(|e| {
+        TradingError::RedemptionUnsupported(format!("Invalid condition ID {}: {}", condition_id, e))
+    })
+}
+
+/// Redeem both outcomes of a resolved market, returning the USDC received and gas paid.
+///
+/// Reverts (e.g. the condition has not been reported yet) surface as
+/// [`TradingError::RedemptionFailed`]; markets or wallets that can never be
+/// redeemed here as [`TradingError::RedemptionUnsupported`].
+#[instrument(skip(config, market), fields(market = %market.slug))]
+pub async fn redeem_positions(config: &Config, market: &Market) -> Result<Redemption, TradingError> {
+    let condition_id = redeemable_condition(config, market)?;
+    let rpc_url = config.polygon_rpc_url.parse().map_err(|e| {
+        TradingError::RedemptionFailed(format!("Invalid POLYGON_RPC_URL: {}", e))
+    })?;
+    let signer = signing::get_or_create_signer(&config.polymarket_private_key)?;
+    let provider = ProviderBuilder::new().wallet(signer).connect_http(rpc_url);
+
+    let ctf = IConditionalTokens::new(CTF_ADDRESS, &provider);
+    let index_sets = BINARY_INDEX_SETS.iter().map(|&set| U256::from(set)).collect();
+    let receipt = ctf
+        .redeemPositions(COLLATERAL_ADDRESS, B256::ZERO, condition_id, index_sets)
+        .send()
+        .await
+        .map_err(|e| TradingError::RedemptionFailed(format!("Failed to send: {}", e)))?
+        .with_timeout(Some(RECEIPT_TIMEOUT))
+        .get_receipt()
+        .await
+        .map_err(|e| TradingError::RedemptionFailed(format!("No receipt: {}", e)))?;
+
+    if !receipt.status() {
+        return Err(TradingError::RedemptionFailed(format!(
+            "Transaction {} reverted",
+            receipt.transaction_hash
+        )));
+    }
+
+    let payout = receipt
+        .decoded_log::<IConditionalTokens::PayoutRedemption>()
+        .map(|log| log.data.payout)
+        .unwrap_or_default();
+    let redemption = Redemption::from_receipt_parts(
+        receipt.transaction_hash,
+        payout,
+        receipt.gas_used,
+        receipt.effective_gas_price,
+        config.usdc_decimals,
+    );
+
+    info!(
+        tx = %redemption.tx_hash,
+        received = %redemption.usdc_received,
+        gas_pol = %redemption.gas_cost,
+        "Redeemed resolved positions"
+    );
+
+    Ok(redemption)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    fn market(neg_risk: Option<bool>, condition_id: Option<&str>) -> Market {
+        Market {
+            slug: "btc-updown-15m-0".to_string(),
+            id: "market-id".to_string(),
+            up_token_id: "up-token".to_string(),
+            down_token_id: "down-token".to_string(),
+            start_timestamp: 0,
+            end_timestamp: 900,
+            question: None,
+            neg_risk,
+            condition_id: condition_id.map(str::to_string),
+            accepting_orders_until: None,
+            tick_size: None,
+        }
+    }
+
+    #[test]
+    fn redemption_converts_payout_and_gas_from_wei() {
+        // 10.5 USDC paid out; 150k gas at 40 gwei
+        let redemption = Redemption::from_receipt_parts(
+            B256::ZERO,
+            U256::from(10_500_000u64),
+            150_000,
+            40_000_000_000,
+            6,
+        );
+
+        assert_eq!(redemption.usdc_received, dec!(10.5));
+        assert_eq!(redemption.gas_cost, dec!(0.006));
+    }
+
+    #[test]
+    fn only_standard_markets_held_by_an_eoa_are_redeemable() {
+        let condition = format!("0x{}", "ab".repeat(32));
+        let config = Config::test_default();
+
+        let id = redeemable_condition(&config, &market(Some(false), Some(&condition))).unwrap();
+        assert_eq!(id.to_string(), condition);
+
+        for market in [
+            market(Some(true), Some(&condition)),
+            market(Some(false), None),
+            market(Some(false), Some("0x1234")),
+        ] {
+            assert!(matches!(
+                redeemable_condition(&config, &market),
+                Err(TradingError::RedemptionUnsupported(_))
+            ));
+        }
+
+        let proxy = Config {
+            polymarket_signature_type: 1,
+            ..Config::test_default()
+        };
+        assert!(matches!(
+            redeemable_condition(&proxy, &market(Some(false), Some(&condition))),
+            Err(TradingError::RedemptionUnsupported(_))
+        ));
+    }
+}
@@ -0,0 +1,400 @@
+//! Authenticated user-channel WebSocket for real-time order updates.
+//!
+//! Replaces REST polling of order status: order events pushed on the `user`
+//! channel are folded into an `OrderState` per order ID, and waiters are
+//! woken on every update.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use dashmap::DashMap;
+use futures::{SinkExt, StreamExt};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use tokio::sync::Notify;
+use tokio_tungstenite::{connect_async, tungstenite::Message};
+use tracing::{debug, error, info, warn};
+
+use super::order::{OrderState, OrderStatus};
+use crate::error::WsError;
+use crate::orderbook::websocket::{ReconnectConfig, WsAuthFrame, WsAuthResponse, WsCredentials};
+use crate::signing;
+
+/// How long an order's state is kept after its last update when nobody reads it.
+const ORDER_STATE_TTL: Duration = Duration::from_secs(600);
+
+/// Subscription message for the user channel.
+#[derive(Debug, Serialize)]
+struct UserSubscribeMessage {
+    /// Message type ("USER").
+    #[serde(rename = "type")]
+    msg_type: String,
+}
+
+/// Order event from the user channel.
+#[derive(Debug, Deserialize)]
+struct WsOrderEvent {
+    /// Event type ("order" or "trade").
+    event_type: Option<String>,
+    /// Order ID.
+    id: Option<String>,
+    /// Order event kind (PLACEMENT, UPDATE, CANCELLATION).
+    #[serde(rename = "type")]
+    kind: Option<String>,
+    /// Order status, if present.
+    status: Option<String>,
+    /// Original order size.
+    original_size: Option<String>,
+    /// Cumulative matched size.
+    size_matched: Option<String>,
+}
+
+impl WsOrderEvent {
+    /// Convert an order event into an order state.
+    fn into_order_state(self) -> Option<OrderState> {
+        if self.event_type.as_deref() != Some("order") {
+            return None;
+        }
+
+        let order_id = self.id?;
+        let original_size = self.original_size.and_then(|s| s.parse::<Decimal>().ok());
+        let filled_size = self.size_matched.and_then(|s| s.parse::<Decimal>().ok());
+        let remaining_size = match (original_size, filled_size) {
+            (Some(original), Some(filled)) => Some((original - filled).max(Decimal::ZERO)),
+            _ => None,
+        };
+
+        let fully_matched = matches!(
+            (original_size, filled_size),
+            (Some(original), Some(filled)) if filled >= original
+        );
+
+        let status = if self.kind.as_deref() == Some("CANCELLATION") {
+            OrderStatus::Canceled
+        } else if fully_matched {
+            OrderStatus::Filled
+        } else {
+            self.status
+                .and_then(|s| s.parse::<OrderStatus>().ok())
+                .unwrap_or(OrderStatus::Live)
+        };
+
+        Some(OrderState {
+            order_id,
+            status: Some(status),
+            filled_size,
+            remaining_size,
+            original_size,
+            is_terminal: status.is_terminal(),
+            is_filled: status.is_filled(),
+            fee: None,
+        })
+    }
+}
+
+/// Parse a user-channel message into order state updates.
+///
+/// Messages may be single objects or arrays; non-order events are ignored.
+pub fn parse_user_message(text: &str) -> Vec<OrderState> {
+    let events: Vec<WsOrderEvent> = if text.starts_with('[') {
+        serde_json::from_str(text).unwrap_or_default()
+    } else {
+        serde_json::from_str(text)
+            .map(|e| vec![e])
+            .unwrap_or_default()
+    };
+
+    events
+        .into_iter()
+        .filter_map(WsOrderEvent::into_order_state)
+        .collect()
+}
+
+/// User-channel WebSocket tracking order state by order ID.
+pub struct UserWebSocket {
+    /// WebSocket base URL.
+    ws_url: String,
+    /// API credentials for the auth frame.
+    credentials: WsCredentials,
+    /// Private key for signing auth headers.
+    private_key: String,
+    /// Reconnection configuration.
+    reconnect_config: ReconnectConfig,
+    /// Connection state.
+    connected: AtomicBool,
+    /// Latest state per order ID, with when it was last updated.
+    orders: DashMap<String, (OrderState, Instant)>,
+    /// Woken on every order update.
+    updated: Notify,
+}
+
+impl std::fmt::Debug for UserWebSocket {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("UserWebSocket")
+            .field("ws_url", &self.ws_url)
+            .field("credentials", &self.credentials)
+            .field("connected", &self.is_connected())
+            .field("orders", &self.orders.len())
+            .finish()
+    }
+}
+
+impl UserWebSocket {
+    /// Create a new user-channel client.
+    pub fn new(
+        ws_url: String,
+        credentials: WsCredentials,
+        private_key: String,
+        reconnect_config: ReconnectConfig,
+    ) -> Self {
+        Self {
+            ws_url,
+            credentials,
+            private_key,
+            reconnect_config,
+            connected: AtomicBool::new(false),
+            orders: DashMap::new(),
+            updated: Notify::new(),
+        }
+    }
+
+    /// Check if currently connected.
+    pub fn is_connected(&self) -> bool {
+        self.connected.load(Ordering::SeqCst)
+    }
+
+    /// Get the latest known state for an order.
+    pub fn order_state(&self, order_id: &str) -> Option<OrderState> {
+        self.orders.get(order_id).map(|entry| entry.0.clone())
+    }
+
+    /// Apply an order update and wake waiters.
+    ///
+    /// Entries not updated within [`ORDER_STATE_TTL`] are dropped, so orders
+    /// nobody waits on do not accumulate.
+    pub fn apply_update(&self, state: OrderState) {
+        debug!(order_id = %state.order_id, status = ?state.status, "Order update");
+        let now = Instant::now();
+        self.orders
+            .retain(|_, (_, updated)| now.duration_since(*updated) < ORDER_STATE_TTL);
+        self.orders.insert(state.order_id.clone(), (state, now));
+        self.updated.notify_waiters();
+    }
+
+    /// Wait until an order is filled to `requested_size` or reaches a terminal state.
+    ///
+    /// The order's entry is removed once its terminal state is returned.
+    /// Returns `None` if no such update arrives before the timeout.
+    pub async fn wait_for_terminal(
+        &self,
+        order_id: &str,
+        requested_size: Decimal,
+        timeout: Duration,
+    ) -> Option<OrderState> {
+        let deadline = tokio::time::Instant::now() + timeout;
+
+        loop {
+            // Register for wakeups before checking to avoid missing an update
+            let notified = self.updated.notified();
+            tokio::pin!(notified);
+            notified.as_mut().enable();
+
+            if let Some(state) = self.order_state(order_id) {
+                if state.filled_size.is_some_and(|f| f >= requested_size) {
+                    self.orders.remove(order_id);
+                    return Some(OrderState {
+                        is_terminal: true,
+                        is_filled: true,
+                        ..state
+                    });
+                }
+                if state.is_terminal {
+                    self.orders.remove(order_id);
+                    return Some(state);
+                }
+            }
+
+            if tokio::time::timeout_at(deadline, notified).await.is_err() {
+                return None;
+            }
+        }
+    }
+
+    /// Connect, authenticate, and process messages until the connection drops.
+    async fn run_once(&self) -> Result<(), WsError> {
+        let url = format!("{}/ws/user", self.ws_url.trim_end_matches('/'));
+        info!(url = %url, "Connecting to user WebSocket");
+
+        let (ws_stream, _) = connect_async(&url)
+            .await
+            .map_err(|e| WsError::ConnectionFailed(e.to_string()))?;
+        let (mut write, mut read) = ws_stream.split();
+
+        // Auth frame must be the first message
+        let headers = signing::generate_auth_headers(&self.private_key, 0)
+            .await
+            .map_err(|e| WsError::AuthFailed(e.to_string()))?;
+        let auth_json = serde_json::to_string(&WsAuthFrame::new(&self.credentials, headers))
+            .map_err(|e| WsError::SendFailed(e.to_string()))?;
+        write
+            .send(Message::Text(auth_json))
+            .await
+            .map_err(|e| WsError::SendFailed(e.to_string()))?;
+
+        let subscribe_json = serde_json::to_string(&UserSubscribeMessage {
+            msg_type: "USER".to_string(),
+        })
+        .map_err(|e| WsError::SendFailed(e.to_string()))?;
+        write
+            .send(Message::Text(subscribe_json))
+            .await
+            .map_err(|e| WsError::SendFailed(e.to_string()))?;
+
+        self.connected.store(true, Ordering::SeqCst);
+        info!("Subscribed to user channel");
+
+        while let Some(msg) = read.next().await {
+            match msg {
+                Ok(Message::Text(text)) => {
+                    match WsAuthResponse::parse(&text) {
+                        Some(WsAuthResponse::Ack) => {
+                            info!("User WebSocket authenticated");
+                            continue;
+                        }
+                        Some(WsAuthResponse::Error(reason)) => {
+                            self.connected.store(false, Ordering::SeqCst);
+                            return Err(WsError::AuthFailed(reason));
+                        }
+                        None => {}
+                    }
+
+                    for state in parse_user_message(&text) {
+                        self.apply_update(state);
+                    }
+                }
+                Ok(Message::Close(frame)) => {
+                    warn!(frame = ?frame, "User WebSocket closed");
+                    break;
+                }
+                Ok(_) => {}
+                Err(e) => {
+                    error!(error = %e, "User WebSocket error");
+                    break;
+                }
+            }
+        }
+
+        self.connected.store(false, Ordering::SeqCst);
+        Ok(())
+    }
+
+    /// Run with automatic reconnection until the task is dropped.
+    pub fn spawn(self: Arc<Self>) -> tokio::task::JoinHandle<()> {
+        tokio::spawn(async move {
+            let mut attempt = 0u32;
+
+            loop {
+                match self.run_once().await {
+                    Ok(()) => {
+                        attempt = 0;
+                        warn!("User WebSocket stream ended, will reconnect");
+                    }
+                    Err(e) => {
+                        error!(error = %e, attempt = attempt, "User WebSocket connection failed");
+                    }
+                }
+
+                let delay = self.reconnect_config.next_delay(attempt);
+                tokio::time::sleep(delay).await;
+                attempt = attempt.saturating_add(1);
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    fn test_ws() -> UserWebSocket {
+        UserWebSocket::new(
+            "wss://test".to_string(),
+            WsCredentials {
+                api_key: "key".to_string(),
+                secret: "secret".to_string(),
+                passphrase: "pass".to_string(),
+            },
+            "0x0123456789abcdef0123456789abcdef0123456789abcdef0123456789abcdef".to_string(),
+            ReconnectConfig::default(),
+        )
+    }
+
+    #[test]
+    fn parse_user_fill_message() {
+        let text = r#"{"event_type":"order","id":"0xorder1","type":"UPDATE","status":"MATCHED","original_size":"10","size_matched":"10","asset_id":"up-token"}"#;
+
+        let states = parse_user_message(text);
+
+        assert_eq!(states.len(), 1);
+        let state = &states[0];
+        assert_eq!(state.order_id, "0xorder1");
+        assert_eq!(state.status, Some(OrderStatus::Filled));
+        assert_eq!(state.filled_size, Some(dec!(10)));
+        assert_eq!(state.remaining_size, Some(dec!(0)));
+        assert!(state.is_terminal);
+        assert!(state.is_filled);
+    }
+
+    #[test]
+    fn parse_user_cancellation_and_ignores_trades() {
+        let text = r#"[
+            {"event_type":"order","id":"0xorder2","type":"CANCELLATION","original_size":"10","size_matched":"4"},
+            {"event_type":"trade","taker_order_id":"0xorder2","size":"4"}
+        ]"#;
+
+        let states = parse_user_message(text);
+
+        assert_eq!(states.len(), 1);
+        assert_eq!(states[0].status, Some(OrderStatus::Canceled));
+        assert!(states[0].is_terminal);
+        assert!(!states[0].is_filled);
+    }
+
+    #[tokio::test]
+    async fn wait_for_terminal_wakes_on_update() {
+        let ws = Arc::new(test_ws());
+        let waiter = {
+            let ws = ws.clone();
+            tokio::spawn(async move {
+                ws.wait_for_terminal("0xorder1", dec!(10), Duration::from_secs(5))
+                    .await
+            })
+        };
+
+        tokio::task::yield_now().await;
+        for state in parse_user_message(
+            r#"{"event_type":"order","id":"0xorder1","type":"UPDATE","original_size":"10","size_matched":"10"}"#,
+        ) {
+            ws.apply_update(state);
+        }
+
+        let state = waiter.await.unwrap().unwrap();
+        assert!(state.is_filled);
+        // The terminal state was handed out, so it is no longer tracked
+        assert!(ws.orders.is_empty());
+        assert!(ws.order_state("0xorder1").is_none());
+    }
+
+    #[tokio::test]
+    async fn wait_for_terminal_times_out() {
+        let ws = test_ws();
+
+        let state = ws
+            .wait_for_terminal("0xmissing", dec!(10), Duration::from_millis(10))
+            .await;
+
+        assert!(state.is_none());
+    }
+}
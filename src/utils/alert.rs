@@ -0,0 +1,111 @@
+//! Best-effort webhook alerts for events operators care about.
+//!
+//! Alerts are POSTed from a spawned task with a short timeout, so a slow or
+//! unreachable webhook never delays an execution.
+
+use std::time::Duration;
+
+use rust_decimal::Decimal;
+use serde::Serialize;
+use tokio::task::JoinHandle;
+use tracing::{debug, warn};
+
+use crate::market::Outcome;
+
+/// How long a webhook POST may take before it is abandoned.
+const ALERT_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Event POSTed to the alert webhook, tagged by `event`.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum AlertEvent {
+    /// A live trade filled both legs.
+    BothFilled {
+        /// Market slug.
+        market: String,
+        /// Execution ID.
+        exec_id: u64,
+        /// UP shares filled.
+        up_filled_size: Decimal,
+        /// DOWN shares filled.
+        down_filled_size: Decimal,
+        /// USDC spent on both legs.
+        investment: Decimal,
+        /// Profit locked in by the matched pairs, net of the execution's fees.
+        expected_profit: Decimal,
+    },
+    /// Only one leg filled and an unwind was attempted.
+    PartialFill {
+        /// Market slug.
+        market: String,
+        /// Execution ID.
+        exec_id: u64,
+        /// Leg that filled.
+        filled_leg: Outcome,
+        /// Shares filled on that leg.
+        filled_size: Decimal,
+        /// Unwind result, if any.
+        unwind_result: Option<String>,
+    },
+    /// Order submission failed for one or both legs.
+    ExecutionFailed {
+        /// Market slug.
+        market: String,
+        /// Execution ID.
+        exec_id: u64,
+        /// Submission error.
+        error: String,
+    },
+    /// Consecutive failures tripped the circuit breaker.
+    CircuitBreakerTripped {
+        /// Consecutive failures that tripped it.
+        consecutive_failures: u32,
+        /// Seconds until executions resume.
+        cooldown_seconds: u64,
+    },
+}
+
+/// Sends [`AlertEvent`]s to a webhook.
+#[derive(Debug, Clone)]
+pub struct AlertNotifier {
+    /// HTTP client for webhook requests.
+    http: reqwest::Client,
+    /// Webhook URL.
+    url: String,
+}
+
+impl AlertNotifier {
+    /// Create a notifier POSTing to `url`.
+    pub fn new(url: impl Into<String>) -> Self {
+        Self {
+            http: reqwest::Client::builder()
+                .timeout(ALERT_TIMEOUT)
+                .build()
+                .unwrap_or_default(),
+            url: url.into(),
+        }
+    }
+
+    /// Notifier for the configured webhook; `None` when unset or empty.
+    pub fn from_url(url: Option<&str>) -> Option<Self> {
+        url.filter(|url| !url.is_empty()).map(Self::new)
+    }
+
+    /// POST `event` to the webhook in the background; failures are only logged.
+    pub fn notify(&self, event: AlertEvent) -> JoinHandle<()> {
+        let http = self.http.clone();
+        let url = self.url.clone();
+        tokio::spawn(async move {
+            match http.post(&url).json(&event).send().await {
+                Ok(response) if response.status().is_success() => {
+                    debug!(event = ?event, "Alert sent");
+                }
+                Ok(response) => {
+                    let status = response.status();
+                    warn!(status = %status, event = ?event, "Alert webhook rejected event");
+                }
+                Err(e) => warn!(error = %e, event = ?event, "Failed to send alert"),
+            }
+        })
+    }
+}
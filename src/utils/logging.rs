@@ -0,0 +1,45 @@
+//! Tracing subscriber setup.
+
+use serde::Deserialize;
+use tracing::Subscriber;
+use tracing_subscriber::{fmt, prelude::*, EnvFilter};
+
+/// Log output format.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, clap::ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum LogFormat {
+    /// Human-readable output.
+    #[default]
+    Text,
+    /// One JSON object per line, for log aggregators.
+    Json,
+}
+
+/// Build a subscriber for the given format and filter.
+pub fn build_subscriber(format: LogFormat, filter: EnvFilter) -> Box<dyn Subscriber + Send + Sync> {
+    let registry = tracing_subscriber::registry().with(filter);
+    match format {
+        LogFormat::Text => Box::new(registry.with(fmt::layer())),
+        LogFormat::Json => Box::new(registry.with(fmt::layer().json())),
+    }
+}
+
+/// Install the global subscriber. Call before any logging.
+pub fn init_logging(format: LogFormat, filter: EnvFilter) {
+    build_subscriber(format, filter).init();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn builds_subscriber_for_both_formats() {
+        for format in [LogFormat::Text, LogFormat::Json] {
+            let subscriber = build_subscriber(format, EnvFilter::new("info"));
+            tracing::subscriber::with_default(subscriber, || {
+                tracing::info!(order_id = "abc", profit_pct = 1.5, "smoke test");
+            });
+        }
+    }
+}
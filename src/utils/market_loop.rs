@@ -0,0 +1,151 @@
+//! Control of the per-market session loop.
+
+use std::future::Future;
+
+use tokio::task::JoinSet;
+use tracing::{info, info_span, Instrument};
+
+use crate::config::MarketSpec;
+
+/// Spawn one task per market spec, each running `run` for its spec.
+///
+/// Each task's logs carry the spec name. Dropping the returned set aborts
+/// every task.
+pub fn spawn_market_tasks<F, Fut>(specs: Vec<MarketSpec>, run: F) -> JoinSet<()>
+where
+    F: Fn(MarketSpec) -> Fut,
+    Fut: Future<Output = ()> + Send + 'static,
+{
+    let mut tasks = JoinSet::new();
+    for spec in specs {
+        info!(
+            name = %spec.name,
+            window_offset_seconds = spec.window_offset_seconds,
+            "Starting market task"
+        );
+        let span = info_span!("market_task", name = %spec.name);
+        tasks.spawn(run(spec).instrument(span));
+    }
+    tasks
+}
+
+/// Decides whether the bot moves on to the next market after one closes.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct MarketLoop {
+    /// Exit after the first market instead of looping forever.
+    once: bool,
+    /// Markets traded through to close.
+    completed: u64,
+}
+
+impl MarketLoop {
+    /// Create a loop controller; `once` stops after a single market.
+    pub fn new(once: bool) -> Self {
+        Self { once, completed: 0 }
+    }
+
+    /// Markets traded through to close so far.
+    pub fn completed(&self) -> u64 {
+        self.completed
+    }
+
+    /// Record a closed market and return whether to continue with the next one.
+    pub fn finish_market(&mut self) -> bool {
+        self.completed += 1;
+        if self.once {
+            info!("--once set, exiting after {} market", self.completed);
+            return false;
+        }
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::market::Market;
+    use time::OffsetDateTime;
+
+    fn market_closing_in(slug: &str, seconds: i64) -> Market {
+        let now = OffsetDateTime::now_utc().unix_timestamp();
+        Market {
+            slug: slug.to_string(),
+            id: "market-id".to_string(),
+            up_token_id: "up-token".to_string(),
+            down_token_id: "down-token".to_string(),
+            start_timestamp: now - Market::WINDOW_SECONDS + seconds,
+            end_timestamp: now + seconds,
+            question: None,
+            neg_risk: None,
+            condition_id: None,
+            accepting_orders_until: None,
+            tick_size: None,
+        }
+    }
+
+    /// Drive markets the way the bot loop does, returning the slugs traded.
+    async fn run(once: bool, markets: Vec<Market>) -> Vec<String> {
+        let mut control = MarketLoop::new(once);
+        let mut traded = Vec::new();
+
+        for market in markets {
+            while !market.is_closed() {
+                tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+            }
+            traded.push(market.slug);
+            if !control.finish_market() {
+                break;
+            }
+        }
+
+        assert_eq!(control.completed(), traded.len() as u64);
+        traded
+    }
+
+    #[tokio::test]
+    async fn once_stops_after_first_market() {
+        let markets = vec![market_closing_in("first", 0), market_closing_in("second", 0)];
+
+        let traded = run(true, markets).await;
+
+        assert_eq!(traded, vec!["first"]);
+    }
+
+    #[tokio::test]
+    async fn each_spec_runs_in_its_own_task() {
+        let specs = crate::config::parse_market_specs("current:0,next:900").unwrap();
+        let (sender, mut receiver) = tokio::sync::mpsc::unbounded_channel();
+
+        let mut tasks = spawn_market_tasks(specs, |spec| {
+            let sender = sender.clone();
+            async move {
+                sender.send((spec.name, tokio::task::id())).unwrap();
+            }
+        });
+        let mut finished = 0;
+        while let Some(result) = tasks.join_next().await {
+            result.unwrap();
+            finished += 1;
+        }
+        drop(sender);
+
+        let mut runs = Vec::new();
+        while let Some(run) = receiver.recv().await {
+            runs.push(run);
+        }
+        runs.sort_by(|a, b| a.0.cmp(&b.0));
+        assert_eq!(finished, 2);
+        assert_eq!(runs.len(), 2);
+        assert_eq!((runs[0].0.as_str(), runs[1].0.as_str()), ("current", "next"));
+        assert_ne!(runs[0].1, runs[1].1);
+    }
+
+    #[tokio::test]
+    async fn default_continues_to_next_market() {
+        let markets = vec![market_closing_in("first", 0), market_closing_in("second", 0)];
+
+        let traded = run(false, markets).await;
+
+        assert_eq!(traded, vec!["first", "second"]);
+    }
+}
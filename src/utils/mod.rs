@@ -1,5 +1,14 @@
 //! Utility modules.
 
+pub mod alert;
+pub mod logging;
+pub mod market_loop;
 pub mod shutdown;
 
-pub use shutdown::shutdown_signal;
+pub use alert::{AlertEvent, AlertNotifier};
+pub use logging::{init_logging, LogFormat};
+pub use market_loop::{spawn_market_tasks, MarketLoop};
+pub use shutdown::{
+    cancel_all_on_shutdown, shutdown_signal, Shutdown, SHUTDOWN_CANCEL_TIMEOUT,
+    SHUTDOWN_DRAIN_TIMEOUT,
+};
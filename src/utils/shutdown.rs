@@ -1,7 +1,21 @@
 //! Graceful shutdown utilities.
 
+use std::future::Future;
+use std::sync::Arc;
+use std::time::Duration;
+
 use tokio::signal;
-use tracing::info;
+use tokio::sync::{Mutex, MutexGuard};
+use tokio_util::sync::CancellationToken;
+use tracing::{info, warn};
+
+use crate::error::TradingError;
+
+/// How long shutdown waits for cancel-all before giving up.
+pub const SHUTDOWN_CANCEL_TIMEOUT: Duration = Duration::from_secs(3);
+
+/// How long shutdown waits for an in-flight execution, including any unwind.
+pub const SHUTDOWN_DRAIN_TIMEOUT: Duration = Duration::from_secs(10);
 
 /// Wait for shutdown signal (Ctrl+C or SIGTERM).
 pub async fn shutdown_signal() {
@@ -31,3 +45,294 @@ pub async fn shutdown_signal() {
         }
     }
 }
+
+/// Shutdown request shared with the bot loop, plus a lock held by in-flight executions.
+///
+/// Exiting mid-execution can leave one leg filled and unmanaged, so shutdown
+/// waits for the lock before dropping the loop; see [`Shutdown::drained`].
+///
+/// A soft stop only blocks new executions: the loop keeps managing the current
+/// market until it closes, then exits instead of moving on to the next one.
+#[derive(Debug, Clone, Default)]
+pub struct Shutdown {
+    /// Cancelled once shutdown is requested.
+    token: CancellationToken,
+    /// Cancelled once a soft stop (or shutdown) is requested.
+    soft_stop: CancellationToken,
+    /// Held for the duration of each execution.
+    in_flight: Arc<Mutex<()>>,
+}
+
+impl Shutdown {
+    /// Create a shutdown handle with no request pending.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Handle Ctrl+C or SIGTERM until shutdown is requested; see [`Shutdown::on_signal`].
+    pub fn listen_for_signals(&self, soft_stop: bool) {
+        let shutdown = self.clone();
+        tokio::spawn(async move {
+            while !shutdown.is_requested() {
+                shutdown_signal().await;
+                shutdown.on_signal(soft_stop);
+            }
+        });
+    }
+
+    /// React to a signal: with `soft_stop`, the first one soft-stops and a second
+    /// requests shutdown; otherwise every signal requests shutdown.
+    pub fn on_signal(&self, soft_stop: bool) {
+        if soft_stop && !self.is_soft_stop_requested() {
+            info!("Soft stop: no new entries, exiting after the current market closes");
+            info!("Send the signal again to shut down now");
+            self.request_soft_stop();
+        } else {
+            self.request();
+        }
+    }
+
+    /// Request shutdown.
+    pub fn request(&self) {
+        self.soft_stop.cancel();
+        self.token.cancel();
+    }
+
+    /// Stop new executions and exit once the current market closes.
+    pub fn request_soft_stop(&self) {
+        self.soft_stop.cancel();
+    }
+
+    /// Whether shutdown has been requested.
+    pub fn is_requested(&self) -> bool {
+        self.token.is_cancelled()
+    }
+
+    /// Whether a soft stop or shutdown has been requested.
+    pub fn is_soft_stop_requested(&self) -> bool {
+        self.soft_stop.is_cancelled()
+    }
+
+    /// Wait for a shutdown request.
+    pub async fn requested(&self) {
+        self.token.cancelled().await;
+    }
+
+    /// Mark an execution as in flight until the guard drops.
+    ///
+    /// Returns `None` once a soft stop or shutdown is requested, so no new
+    /// execution starts.
+    pub async fn begin_execution(&self) -> Option<MutexGuard<'_, ()>> {
+        if self.is_soft_stop_requested() {
+            return None;
+        }
+        let guard = self.in_flight.lock().await;
+        (!self.is_soft_stop_requested()).then_some(guard)
+    }
+
+    /// Wait for a shutdown request, then for any in-flight execution to finish.
+    ///
+    /// Poll alongside the bot loop so the execution keeps running while it drains.
+    /// Returns false if it was still running after `drain_timeout`.
+    pub async fn drained(&self, drain_timeout: Duration) -> bool {
+        self.token.cancelled().await;
+
+        match tokio::time::timeout(drain_timeout, self.in_flight.lock()).await {
+            Ok(_guard) => true,
+            Err(_) => {
+                warn!(
+                    timeout_ms = drain_timeout.as_millis() as u64,
+                    "Execution still in flight at shutdown deadline, abandoning it"
+                );
+                false
+            }
+        }
+    }
+}
+
+/// Cancel all open orders on shutdown, best-effort.
+///
+/// Skipped in dry-run. Gives up after `timeout` so shutdown is never blocked;
+/// returns the number of orders cancelled if the call completed.
+pub async fn cancel_all_on_shutdown<F, Fut>(
+    dry_run: bool,
+    timeout: Duration,
+    cancel_all: F,
+) -> Option<u32>
+where
+    F: FnOnce() -> Fut,
+    Fut: Future<Output = Result<u32, TradingError>>,
+{
+    if dry_run {
+        return None;
+    }
+
+    info!("Cancelling open orders before exit");
+    match tokio::time::timeout(timeout, cancel_all()).await {
+        Ok(Ok(count)) => {
+            info!(count = count, "Cancelled open orders on shutdown");
+            Some(count)
+        }
+        Ok(Err(e)) => {
+            warn!(error = %e, "Failed to cancel open orders on shutdown");
+            None
+        }
+        Err(_) => {
+            warn!(
+                timeout_ms = timeout.as_millis() as u64,
+                "Timed out cancelling open orders on shutdown"
+            );
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicBool, Ordering};
+
+    use crate::market::mock::{MockConfig, MockPolymarketClient};
+    use crate::trading::execution::OrderRequest;
+
+    fn order(token_id: &str) -> OrderRequest {
+        OrderRequest {
+            token_id: token_id.to_string(),
+            side: "BUY".to_string(),
+            price: "0.48".to_string(),
+            size: "10".to_string(),
+            fee_rate_bps: "0".to_string(),
+            nonce: "0".to_string(),
+            expiration: "0".to_string(),
+            taker: String::new(),
+            maker: String::new(),
+            signer: String::new(),
+            signature_type: 0,
+            signature: String::new(),
+            order_type: "FOK".to_string(),
+            neg_risk: false,
+            post_only: false,
+        }
+    }
+
+    #[tokio::test]
+    async fn shutdown_mid_loop_lets_started_execution_finish() {
+        let client = MockPolymarketClient::with_config(MockConfig {
+            latency_ms: 50,
+            ..Default::default()
+        });
+        let shutdown = Shutdown::new();
+        let completed = AtomicBool::new(false);
+
+        let bot_loop = async {
+            loop {
+                let Some(_execution) = shutdown.begin_execution().await else {
+                    break;
+                };
+                client.submit_order(&order("up-token")).await.unwrap();
+                // Shutdown arrives between the legs
+                shutdown.request();
+                client.submit_order(&order("down-token")).await.unwrap();
+                completed.store(true, Ordering::SeqCst);
+            }
+        };
+        // The loop may see the request first and exit on its own
+        tokio::select! {
+            _ = bot_loop => {}
+            drained = shutdown.drained(SHUTDOWN_DRAIN_TIMEOUT) => assert!(drained),
+        }
+
+        assert!(completed.load(Ordering::SeqCst));
+        assert_eq!(client.orders_submitted(), 2);
+        assert!(shutdown.begin_execution().await.is_none());
+    }
+
+    #[tokio::test]
+    async fn first_signal_soft_stops_and_second_shuts_down() {
+        let shutdown = Shutdown::new();
+
+        shutdown.on_signal(true);
+
+        assert!(shutdown.is_soft_stop_requested());
+        assert!(!shutdown.is_requested());
+        assert!(shutdown.begin_execution().await.is_none());
+
+        shutdown.on_signal(true);
+
+        assert!(shutdown.is_requested());
+        assert!(shutdown.drained(SHUTDOWN_DRAIN_TIMEOUT).await);
+    }
+
+    #[tokio::test]
+    async fn signal_without_soft_stop_shuts_down() {
+        let shutdown = Shutdown::new();
+
+        shutdown.on_signal(false);
+
+        assert!(shutdown.is_requested());
+        assert!(shutdown.begin_execution().await.is_none());
+    }
+
+    #[tokio::test]
+    async fn shutdown_drain_gives_up_after_timeout() {
+        let client = MockPolymarketClient::with_config(MockConfig {
+            latency_ms: 1000,
+            ..Default::default()
+        });
+        let shutdown = Shutdown::new();
+
+        let execution = async {
+            let _execution = shutdown.begin_execution().await.unwrap();
+            shutdown.request();
+            client.submit_order(&order("up-token")).await
+        };
+        let drained = tokio::select! {
+            _ = execution => panic!("execution should outlast the drain timeout"),
+            drained = shutdown.drained(Duration::from_millis(10)) => drained,
+        };
+
+        assert!(!drained);
+    }
+
+    #[tokio::test]
+    async fn shutdown_cancels_open_orders() {
+        let client = MockPolymarketClient::with_config(MockConfig {
+            open_orders: 2,
+            ..Default::default()
+        });
+
+        let cancelled =
+            cancel_all_on_shutdown(false, SHUTDOWN_CANCEL_TIMEOUT, || client.cancel_all_orders())
+                .await;
+
+        assert_eq!(cancelled, Some(2));
+        assert_eq!(client.cancel_all_calls(), 1);
+    }
+
+    #[tokio::test]
+    async fn shutdown_skips_cancel_in_dry_run() {
+        let client = MockPolymarketClient::new();
+
+        let cancelled =
+            cancel_all_on_shutdown(true, SHUTDOWN_CANCEL_TIMEOUT, || client.cancel_all_orders())
+                .await;
+
+        assert_eq!(cancelled, None);
+        assert_eq!(client.cancel_all_calls(), 0);
+    }
+
+    #[tokio::test]
+    async fn shutdown_cancel_gives_up_after_timeout() {
+        let client = MockPolymarketClient::with_config(MockConfig {
+            latency_ms: 1000,
+            ..Default::default()
+        });
+
+        let cancelled =
+            cancel_all_on_shutdown(false, Duration::from_millis(10), || client.cancel_all_orders())
+                .await;
+
+        assert_eq!(cancelled, None);
+        assert_eq!(client.cancel_all_calls(), 1);
+    }
+}
@@ -0,0 +1,380 @@
+//! Shared test harness: a mock Polymarket CLOB served on localhost.
+//!
+//! Serves the REST endpoints the bot calls (`/time`, `/book`, `/price`, `/midpoint`,
+//! `/order`, `/order/{id}`, `/balance-allowance`, `/positions`), an alert `/webhook` sink, and
+//! a `/ws/market` WebSocket that answers a subscription with canned book
+//! snapshots, so request/response paths run in CI without credentials or network
+//! access. A proxy in front of the REST server can drop connections to exercise
+//! retry paths. [`LogCapture`] collects log lines so tests can assert on fields.
+
+#![allow(dead_code)]
+
+use std::net::SocketAddr;
+use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
+use std::sync::{Arc, Mutex};
+
+use axum::extract::{Path, Query, State};
+use axum::http::{header, HeaderMap, StatusCode};
+use axum::routing::{get, post};
+use axum::{Json, Router};
+use futures::{SinkExt, StreamExt};
+use polymarket_arb::config::Config;
+use polymarket_arb::market::PolymarketClient;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use tokio::net::{TcpListener, TcpStream};
+use tokio_tungstenite::tungstenite::Message;
+
+/// Test-only private key (not a funded wallet).
+pub const TEST_PRIVATE_KEY: &str =
+    "0x0123456789abcdef0123456789abcdef0123456789abcdef0123456789abcdef";
+
+/// Order ID returned for accepted orders.
+pub const MOCK_ORDER_ID: &str = "0xmockorder1";
+
+/// Shared state behind the mock endpoints.
+#[derive(Debug, Default)]
+pub struct MockState {
+    /// Reject `POST /order` with HTTP 400 when set.
+    pub reject_orders: AtomicBool,
+    /// Reject post-only orders as crossing the book when set.
+    pub cross_post_only: AtomicBool,
+    /// Reject orders for this token with HTTP 400.
+    pub reject_token: Mutex<Option<String>>,
+    /// Reject SELL orders with HTTP 400 when set.
+    pub reject_sells: AtomicBool,
+    /// Orders still to reject as unfillable at their price.
+    pub price_rejections: AtomicU32,
+    /// Price of a single 100-share ask level `/book` serves in place of the canned asks.
+    pub book_ask: Mutex<Option<String>>,
+    /// Orders received on `POST /order`.
+    pub orders_posted: AtomicU32,
+    /// Bodies received on `POST /order`, oldest first.
+    pub orders: Mutex<Vec<Value>>,
+    /// `POLY_SIGNATURE` auth headers received on `POST /order`, oldest first.
+    pub order_signatures: Mutex<Vec<String>>,
+    /// Status polls answered on `GET /order/{id}`.
+    pub status_polls: AtomicU32,
+    /// Polls answered with HTTP 404 before an order becomes visible.
+    pub not_found_polls: AtomicU32,
+    /// Polls answered as `live` before an order reports `filled`.
+    pub polls_until_filled: AtomicU32,
+    /// Fee reported on filled orders, in USDC.
+    pub fill_fee: Mutex<Option<String>>,
+    /// Report orders as `live` once a SELL has been posted.
+    pub rest_sells: AtomicBool,
+    /// Balance requests answered on `GET /balance-allowance`.
+    pub balance_requests: AtomicU32,
+    /// Delay before answering `GET /balance-allowance`, in milliseconds.
+    pub balance_delay_ms: AtomicU32,
+    /// Balance requests currently being answered.
+    pub balance_in_flight: AtomicU32,
+    /// Most balance requests seen in flight at once.
+    pub max_balance_in_flight: AtomicU32,
+    /// User-Agent of the last `GET /book` request.
+    pub last_user_agent: Mutex<Option<String>>,
+    /// Connections to `flaky_url` still to be closed without a response.
+    pub drop_connections: AtomicU32,
+    /// Connections accepted on `flaky_url`.
+    pub proxy_connections: AtomicU32,
+    /// JSON payloads received on `POST /webhook`, oldest first.
+    pub alerts: Mutex<Vec<Value>>,
+    /// Positions served on `GET /positions`.
+    pub positions: Mutex<Vec<Value>>,
+}
+
+/// Running mock CLOB.
+pub struct MockClob {
+    /// REST base URL (`http://127.0.0.1:port`).
+    pub base_url: String,
+    /// WebSocket base URL (`ws://127.0.0.1:port`).
+    pub ws_url: String,
+    /// REST base URL behind a proxy that closes `drop_connections` connections first.
+    pub flaky_url: String,
+    /// Endpoint state, for steering responses and inspecting calls.
+    pub state: Arc<MockState>,
+}
+
+impl MockClob {
+    /// Start the REST and WebSocket servers on ephemeral ports.
+    pub async fn start() -> Self {
+        let state = Arc::new(MockState::default());
+
+        let router = Router::new()
+            .route("/time", get(server_time))
+            .route("/book", get(book))
+            .route("/price", get(price))
+            .route("/midpoint", get(midpoint))
+            .route("/order", post(submit_order))
+            .route("/order/:id", get(order_status))
+            .route("/balance-allowance", get(balance_allowance))
+            .route("/positions", get(positions))
+            .route("/webhook", post(webhook))
+            .with_state(state.clone());
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let rest_addr = listener.local_addr().unwrap();
+        let base_url = format!("http://{}", rest_addr);
+        tokio::spawn(async move { axum::serve(listener, router).await });
+
+        let proxy_listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let flaky_url = format!("http://{}", proxy_listener.local_addr().unwrap());
+        tokio::spawn(serve_flaky_proxy(proxy_listener, rest_addr, state.clone()));
+
+        let ws_listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let ws_url = format!("ws://{}", ws_listener.local_addr().unwrap());
+        tokio::spawn(serve_market_ws(ws_listener));
+
+        Self {
+            base_url,
+            ws_url,
+            flaky_url,
+            state,
+        }
+    }
+
+    /// Client pointing at this mock with the test key, for tests that need no `Config`.
+    pub fn client(&self) -> PolymarketClient {
+        PolymarketClient::with_base_url(&self.base_url, TEST_PRIVATE_KEY).unwrap()
+    }
+
+    /// Config pointing the client at this mock, with defaults for everything else.
+    pub fn config(&self) -> Config {
+        serde_json::from_value(json!({
+            "polymarket_private_key": TEST_PRIVATE_KEY,
+            "polymarket_clob_url": self.base_url,
+            "polymarket_ws_url": self.ws_url,
+            "dry_run": false,
+        }))
+        .unwrap()
+    }
+}
+
+/// Canned book for a token: bid 0.47 x 100, asks 0.48 x 50 and 0.49 x 100.
+pub fn canned_book(token_id: &str) -> Value {
+    json!({
+        "market": "mock-market",
+        "asset_id": token_id,
+        "bids": [{"price": "0.47", "size": "100"}],
+        "asks": [{"price": "0.48", "size": "50"}, {"price": "0.49", "size": "100"}],
+    })
+}
+
+/// Current Unix time in seconds, as the CLOB's `/time` returns it.
+async fn server_time() -> String {
+    time::OffsetDateTime::now_utc().unix_timestamp().to_string()
+}
+
+#[derive(Debug, Deserialize)]
+struct BookQuery {
+    token_id: String,
+}
+
+async fn book(
+    State(state): State<Arc<MockState>>,
+    headers: HeaderMap,
+    Query(query): Query<BookQuery>,
+) -> Json<Value> {
+    let user_agent = headers
+        .get(header::USER_AGENT)
+        .and_then(|v| v.to_str().ok())
+        .map(str::to_string);
+    *state.last_user_agent.lock().unwrap() = user_agent;
+
+    let mut book = canned_book(&query.token_id);
+    if let Some(ask) = state.book_ask.lock().unwrap().clone() {
+        book["asks"] = json!([{"price": ask, "size": "100"}]);
+    }
+    Json(book)
+}
+
+/// Best ask of the canned book, as `/price?side=buy` reports it.
+async fn price(Query(_query): Query<BookQuery>) -> Json<Value> {
+    Json(json!({"price": "0.48"}))
+}
+
+/// Midpoint of the canned book's 0.47 bid and 0.48 ask.
+async fn midpoint(Query(_query): Query<BookQuery>) -> Json<Value> {
+    Json(json!({"mid": "0.475"}))
+}
+
+async fn webhook(State(state): State<Arc<MockState>>, Json(alert): Json<Value>) -> StatusCode {
+    state.alerts.lock().unwrap().push(alert);
+    StatusCode::NO_CONTENT
+}
+
+async fn submit_order(
+    State(state): State<Arc<MockState>>,
+    headers: HeaderMap,
+    Json(order): Json<Value>,
+) -> (StatusCode, Json<Value>) {
+    state.orders_posted.fetch_add(1, Ordering::SeqCst);
+    state.orders.lock().unwrap().push(order.clone());
+    if let Some(signature) = headers.get("POLY_SIGNATURE").and_then(|v| v.to_str().ok()) {
+        state.order_signatures.lock().unwrap().push(signature.to_string());
+    }
+
+    let rejected_token = state.reject_token.lock().unwrap().as_deref().is_some_and(|token| {
+        order["token_id"] == token
+    });
+    let rejected_sell = order["side"] == "SELL" && state.reject_sells.load(Ordering::SeqCst);
+    if order["side"] == "SELL" && state.rest_sells.load(Ordering::SeqCst) {
+        state.polls_until_filled.store(u32::MAX, Ordering::SeqCst);
+    }
+    if rejected_token || rejected_sell {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(json!({"error": "order couldn't be fully filled"})),
+        );
+    }
+
+    let price_rejected = state
+        .price_rejections
+        .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |n| n.checked_sub(1))
+        .is_ok();
+    if price_rejected {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(json!({
+                "error": "order couldn't be fully filled. FOK orders are fully filled or killed.",
+                "error_code": "FOK_ORDER_NOT_FILLED_ERROR",
+            })),
+        );
+    }
+
+    if order["post_only"] == true && state.cross_post_only.load(Ordering::SeqCst) {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(json!({"error": "invalid post-only order: order crosses book"})),
+        );
+    }
+
+    if state.reject_orders.load(Ordering::SeqCst) {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(json!({"error": "not enough balance / allowance"})),
+        );
+    }
+
+    (
+        StatusCode::OK,
+        Json(json!({"orderID": MOCK_ORDER_ID, "success": true})),
+    )
+}
+
+async fn order_status(
+    State(state): State<Arc<MockState>>,
+    Path(id): Path<String>,
+) -> Result<Json<Value>, StatusCode> {
+    if id != MOCK_ORDER_ID {
+        return Err(StatusCode::NOT_FOUND);
+    }
+
+    let polls = state.status_polls.fetch_add(1, Ordering::SeqCst);
+    if polls < state.not_found_polls.load(Ordering::SeqCst) {
+        return Err(StatusCode::NOT_FOUND);
+    }
+    if polls < state.polls_until_filled.load(Ordering::SeqCst) {
+        return Ok(Json(json!({"status": "live", "size": "10", "filled": "0"})));
+    }
+    let mut filled = json!({"status": "filled", "size": "10", "filled": "10"});
+    if let Some(fee) = state.fill_fee.lock().unwrap().clone() {
+        filled["fee"] = json!(fee);
+    }
+    Ok(Json(filled))
+}
+
+async fn balance_allowance(State(state): State<Arc<MockState>>) -> Json<Value> {
+    state.balance_requests.fetch_add(1, Ordering::SeqCst);
+    let in_flight = state.balance_in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+    state.max_balance_in_flight.fetch_max(in_flight, Ordering::SeqCst);
+
+    let delay = state.balance_delay_ms.load(Ordering::SeqCst);
+    tokio::time::sleep(std::time::Duration::from_millis(delay.into())).await;
+    state.balance_in_flight.fetch_sub(1, Ordering::SeqCst);
+
+    // 25 USDC in 6-decimal units
+    Json(json!({"balance": "25000000", "allowance": "25000000"}))
+}
+
+async fn positions(State(state): State<Arc<MockState>>) -> Json<Value> {
+    Json(Value::Array(state.positions.lock().unwrap().clone()))
+}
+
+/// Close the first `drop_connections` connections unanswered, then proxy to the REST server.
+async fn serve_flaky_proxy(listener: TcpListener, target: SocketAddr, state: Arc<MockState>) {
+    while let Ok((mut inbound, _)) = listener.accept().await {
+        state.proxy_connections.fetch_add(1, Ordering::SeqCst);
+        let drop_connection = state
+            .drop_connections
+            .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |n| n.checked_sub(1))
+            .is_ok();
+        if drop_connection {
+            continue;
+        }
+
+        tokio::spawn(async move {
+            if let Ok(mut outbound) = TcpStream::connect(target).await {
+                let _ = tokio::io::copy_bidirectional(&mut inbound, &mut outbound).await;
+            }
+        });
+    }
+}
+
+/// Answer each market subscription with a book snapshot per asset, then idle.
+async fn serve_market_ws(listener: TcpListener) {
+    while let Ok((stream, _)) = listener.accept().await {
+        tokio::spawn(async move {
+            let Ok(mut ws) = tokio_tungstenite::accept_async(stream).await else {
+                return;
+            };
+
+            while let Some(Ok(msg)) = ws.next().await {
+                let Message::Text(text) = msg else {
+                    continue;
+                };
+                let Ok(subscribe) = serde_json::from_str::<Value>(&text) else {
+                    continue;
+                };
+                let assets = subscribe["assets_ids"].as_array().cloned().unwrap_or_default();
+
+                for asset in assets.iter().filter_map(Value::as_str) {
+                    let mut frame = canned_book(asset);
+                    frame["event_type"] = json!("book");
+                    if ws.send(Message::Text(frame.to_string())).await.is_err() {
+                        return;
+                    }
+                }
+            }
+        });
+    }
+}
+
+/// In-memory log sink for a `tracing_subscriber` fmt layer.
+#[derive(Debug, Clone, Default)]
+pub struct LogCapture(Arc<Mutex<Vec<u8>>>);
+
+impl LogCapture {
+    /// Everything logged so far.
+    pub fn contents(&self) -> String {
+        String::from_utf8_lossy(&self.0.lock().unwrap()).into_owned()
+    }
+}
+
+impl std::io::Write for LogCapture {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.0.lock().unwrap().extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+impl<'a> tracing_subscriber::fmt::MakeWriter<'a> for LogCapture {
+    type Writer = Self;
+
+    fn make_writer(&'a self) -> Self::Writer {
+        self.clone()
+    }
+}
@@ -0,0 +1,978 @@
+//! Request/response tests against the local mock CLOB in `common`.
+
+mod common;
+
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+use std::time::Duration;
+
+use common::{LogCapture, MockClob, MOCK_ORDER_ID};
+use polymarket_arb::arbitrage::{
+    calculate_opportunity, ArbitrageExecutor, ArbitrageOpportunity, ExecutionResult, JournalEvent,
+    JournalRow,
+};
+use polymarket_arb::config::{DryRunMode, LegMode, MakerLeg};
+use polymarket_arb::error::{MarketError, TradingError};
+use polymarket_arb::market::{Market, Outcome, PolymarketClient};
+use polymarket_arb::orderbook::websocket::MarketWebSocket;
+use polymarket_arb::trading::execution::{
+    get_order_status, submit_order, wait_for_terminal_order,
+};
+use polymarket_arb::trading::{OrderParams, OrderStatus};
+use rust_decimal_macros::dec;
+
+/// Opportunity on the mock books (0.48 + 0.48) for the window starting at `start_timestamp`.
+async fn mock_opportunity(
+    client: &PolymarketClient,
+    start_timestamp: i64,
+) -> ArbitrageOpportunity {
+    let market = Market {
+        slug: format!("btc-updown-15m-{}", start_timestamp),
+        id: "mock-market".to_string(),
+        up_token_id: "up-token".to_string(),
+        down_token_id: "down-token".to_string(),
+        start_timestamp,
+        end_timestamp: start_timestamp + Market::WINDOW_SECONDS,
+        question: None,
+        neg_risk: None,
+        condition_id: None,
+        accepting_orders_until: None,
+        tick_size: None,
+    };
+    let up = client.get_order_book("up-token").await.unwrap();
+    let down = client.get_order_book("down-token").await.unwrap();
+
+    calculate_opportunity(&market, &up, &down, dec!(10), dec!(0.991)).unwrap()
+}
+
+#[tokio::test]
+async fn get_order_book_parses_levels() {
+    let mock = MockClob::start().await;
+    let client = PolymarketClient::new(&mock.config());
+
+    let book = client.get_order_book("up-token").await.unwrap();
+
+    assert_eq!(book.token_id, "up-token");
+    assert_eq!(book.best_ask(), Some(dec!(0.48)));
+    assert_eq!(book.best_bid(), Some(dec!(0.47)));
+    assert_eq!(book.total_ask_liquidity(), dec!(150));
+}
+
+#[tokio::test]
+async fn base_url_client_reaches_the_mock() {
+    let mock = MockClob::start().await;
+    let client = mock.client();
+
+    let book = client.get_order_book("up-token").await.unwrap();
+
+    assert_eq!(client.clob_url(), mock.base_url);
+    assert_eq!(book.best_ask(), Some(dec!(0.48)));
+}
+
+#[tokio::test]
+async fn single_price_endpoints_parse() {
+    let mock = MockClob::start().await;
+    let client = PolymarketClient::new(&mock.config());
+
+    assert_eq!(client.get_buy_price("up-token").await.unwrap(), dec!(0.48));
+    assert_eq!(client.get_midpoint("up-token").await.unwrap(), dec!(0.475));
+}
+
+#[tokio::test]
+async fn get_order_book_retries_dropped_connection() {
+    for (retries, expect_book) in [(1, true), (0, false)] {
+        let mock = MockClob::start().await;
+        mock.state.drop_connections.store(1, Ordering::SeqCst);
+        let mut config = mock.config();
+        config.polymarket_clob_url = mock.flaky_url.clone();
+        config.book_fetch_retries = retries;
+        let client = PolymarketClient::new(&config);
+
+        let result = client.get_order_book("up-token").await;
+
+        if expect_book {
+            assert_eq!(result.unwrap().best_ask(), Some(dec!(0.48)));
+        } else {
+            assert!(matches!(result, Err(MarketError::FetchFailed { .. })), "got {:?}", result);
+        }
+        assert_eq!(mock.state.drop_connections.load(Ordering::SeqCst), 0);
+    }
+}
+
+#[tokio::test]
+async fn warmup_opens_connection_reused_by_next_request() {
+    let mock = MockClob::start().await;
+    let mut config = mock.config();
+    config.polymarket_clob_url = mock.flaky_url.clone();
+    let client = PolymarketClient::new(&config);
+
+    client.warmup().await.unwrap();
+    assert_eq!(mock.state.proxy_connections.load(Ordering::SeqCst), 1);
+
+    client.get_order_book("up-token").await.unwrap();
+    assert_eq!(mock.state.proxy_connections.load(Ordering::SeqCst), 1);
+}
+
+#[tokio::test]
+async fn requests_send_configured_user_agent() {
+    let mock = MockClob::start().await;
+    let mut config = mock.config();
+    config.http_user_agent = "arb-bot/test".to_string();
+    let client = PolymarketClient::new(&config);
+
+    client.get_order_book("up-token").await.unwrap();
+
+    let user_agent = mock.state.last_user_agent.lock().unwrap().clone();
+    assert_eq!(user_agent.as_deref(), Some("arb-bot/test"));
+}
+
+#[tokio::test]
+async fn get_balance_converts_from_base_units() {
+    let mock = MockClob::start().await;
+    let client = PolymarketClient::new(&mock.config());
+
+    assert_eq!(client.get_balance().await.unwrap(), dec!(25));
+}
+
+#[tokio::test]
+async fn stale_balance_cache_refetches() {
+    let mock = MockClob::start().await;
+    let mut config = mock.config();
+    config.balance_cache_ttl_ms = 20;
+    let client = PolymarketClient::new(&config);
+
+    client.get_balance_cached().await.unwrap();
+    client.get_balance_cached().await.unwrap();
+    assert_eq!(mock.state.balance_requests.load(Ordering::SeqCst), 1);
+
+    tokio::time::sleep(Duration::from_millis(30)).await;
+    client.get_balance_cached().await.unwrap();
+    assert_eq!(mock.state.balance_requests.load(Ordering::SeqCst), 2);
+}
+
+#[tokio::test]
+async fn request_limiter_caps_concurrent_requests() {
+    let mock = MockClob::start().await;
+    mock.state.balance_delay_ms.store(50, Ordering::SeqCst);
+    let mut config = mock.config();
+    config.max_concurrent_requests = 2;
+    let client = PolymarketClient::new(&config);
+
+    let requests = (0..8).map(|_| client.get_balance());
+    for balance in futures::future::join_all(requests).await {
+        assert_eq!(balance.unwrap(), dec!(25));
+    }
+
+    assert_eq!(mock.state.balance_requests.load(Ordering::SeqCst), 8);
+    assert_eq!(mock.state.max_balance_in_flight.load(Ordering::SeqCst), 2);
+}
+
+#[tokio::test]
+async fn live_executions_within_ttl_share_one_balance_fetch() {
+    let mock = MockClob::start().await;
+    let mut config = mock.config();
+    config.cooldown_seconds = 0;
+    config.state_file = String::new();
+    config.balance_cache_ttl_ms = 60_000;
+    let client = PolymarketClient::new(&config);
+    let mut executor = ArbitrageExecutor::new(&config);
+
+    // Consecutive windows, so the second trade isn't deduplicated
+    for start_timestamp in [0, Market::WINDOW_SECONDS] {
+        let opportunity = mock_opportunity(&client, start_timestamp).await;
+
+        let result = executor.execute(&client, &opportunity, &config).await.unwrap();
+        assert!(matches!(result, ExecutionResult::BothFilled { .. }), "got {:?}", result);
+    }
+
+    assert_eq!(mock.state.balance_requests.load(Ordering::SeqCst), 1);
+    // 25 USDC less two fills of 10 pairs at 0.48 + 0.48
+    assert_eq!(client.cached_balance().unwrap().value, dec!(5.80));
+}
+
+/// Report `size` shares of each of the mock market's tokens as held.
+fn hold_pairs(mock: &MockClob, size: &str) {
+    *mock.state.positions.lock().unwrap() = ["up-token", "down-token"]
+        .iter()
+        .map(|token_id| serde_json::json!({"token_id": token_id, "size": size}))
+        .collect();
+}
+
+#[tokio::test]
+async fn positioned_market_is_skipped_without_orders() {
+    let mock = MockClob::start().await;
+    hold_pairs(&mock, "10");
+    let mut config = mock.config();
+    config.state_file = String::new();
+    config.skip_if_already_positioned = true;
+    let client = PolymarketClient::new(&config);
+    let mut executor = ArbitrageExecutor::new(&config);
+    let opportunity = mock_opportunity(&client, 0).await;
+
+    let result = executor.execute(&client, &opportunity, &config).await.unwrap();
+
+    assert!(
+        matches!(result, ExecutionResult::AlreadyPositioned { held_pairs } if held_pairs == dec!(10)),
+        "got {:?}",
+        result
+    );
+    assert_eq!(mock.state.orders_posted.load(Ordering::SeqCst), 0);
+}
+
+#[tokio::test]
+async fn second_opportunity_after_restart_is_skipped_once_positioned() {
+    let mock = MockClob::start().await;
+    let mut config = mock.config();
+    config.state_file = String::new();
+    config.skip_if_already_positioned = true;
+    let client = PolymarketClient::new(&config);
+    let opportunity = mock_opportunity(&client, 0).await;
+
+    let mut executor = ArbitrageExecutor::new(&config);
+    let result = executor.execute(&client, &opportunity, &config).await.unwrap();
+    assert!(matches!(result, ExecutionResult::BothFilled { .. }), "got {:?}", result);
+    assert_eq!(mock.state.orders_posted.load(Ordering::SeqCst), 2);
+
+    // A restarted executor has no trade intents, but the exchange holds the pairs
+    hold_pairs(&mock, "10");
+    let mut restarted = ArbitrageExecutor::new(&config);
+    let mut second = opportunity.clone();
+    second.up_price = dec!(0.47);
+    let result = restarted.execute(&client, &second, &config).await.unwrap();
+
+    assert!(matches!(result, ExecutionResult::AlreadyPositioned { .. }), "got {:?}", result);
+    assert_eq!(mock.state.orders_posted.load(Ordering::SeqCst), 2);
+    assert_eq!(restarted.stats().trades_executed, 0);
+}
+
+/// Report 10 shares of each token of the mock market at the given prices.
+fn priced_positions(mock: &MockClob, up_price: &str, down_price: &str) {
+    *mock.state.positions.lock().unwrap() = vec![
+        serde_json::json!({"token_id": "up-token", "size": "10", "cur_price": up_price}),
+        serde_json::json!({"token_id": "down-token", "size": "10", "cur_price": down_price}),
+    ];
+}
+
+#[tokio::test]
+async fn closed_market_settles_once_resolved() {
+    let mock = MockClob::start().await;
+    priced_positions(&mock, "1", "0");
+    let mut config = mock.config();
+    config.state_file = String::new();
+    let client = PolymarketClient::new(&config);
+    let mut executor = ArbitrageExecutor::new(&config);
+    let opportunity = mock_opportunity(&client, 0).await;
+
+    executor.queue_settlement(&opportunity.market, dec!(9.6));
+    assert_eq!(executor.settle_pending(&client).await.len(), 1);
+
+    assert_eq!(executor.stats().realized_profit, Some(dec!(0.4)));
+    assert_eq!(executor.pending_settlements(), 0);
+}
+
+#[tokio::test]
+async fn market_without_positions_settles_at_zero() {
+    let mock = MockClob::start().await;
+    let mut config = mock.config();
+    config.state_file = String::new();
+    let client = PolymarketClient::new(&config);
+    let mut executor = ArbitrageExecutor::new(&config);
+    let opportunity = mock_opportunity(&client, 0).await;
+
+    // Fully unwound: nothing held, so nothing to redeem
+    executor.queue_settlement(&opportunity.market, dec!(9.6));
+    assert!(executor.settle_pending(&client).await.is_empty());
+
+    assert_eq!(executor.stats().realized_profit, Some(dec!(-9.6)));
+    assert_eq!(executor.pending_settlements(), 0);
+    assert!(executor.capital_at_risk().is_zero());
+}
+
+#[tokio::test]
+async fn unresolved_market_stays_pending() {
+    let mock = MockClob::start().await;
+    priced_positions(&mock, "0.62", "0.38");
+    let mut config = mock.config();
+    config.state_file = String::new();
+    let client = PolymarketClient::new(&config);
+    let mut executor = ArbitrageExecutor::new(&config);
+    let opportunity = mock_opportunity(&client, 0).await;
+
+    executor.queue_settlement(&opportunity.market, dec!(9.6));
+    assert!(executor.settle_pending(&client).await.is_empty());
+
+    // Backed off: not re-checked on the very next scan
+    assert!(executor.settle_pending(&client).await.is_empty());
+    assert_eq!(executor.stats().realized_profit, None);
+    assert_eq!(executor.pending_settlements(), 1);
+}
+
+#[tokio::test]
+async fn filled_execution_appends_journal_row() {
+    let mock = MockClob::start().await;
+    let path = std::env::temp_dir().join(format!("journal-{}.csv", std::process::id()));
+    let _ = std::fs::remove_file(&path);
+    let mut config = mock.config();
+    config.state_file = String::new();
+    config.journal_csv = path.to_str().unwrap().to_string();
+    let client = PolymarketClient::new(&config);
+    let mut executor = ArbitrageExecutor::new(&config);
+
+    let opportunity = mock_opportunity(&client, 0).await;
+    let result = executor.execute(&client, &opportunity, &config).await.unwrap();
+    let ExecutionResult::BothFilled { exec_id, .. } = result else {
+        panic!("expected both legs filled, got {:?}", result);
+    };
+
+    let text = std::fs::read_to_string(&path).unwrap();
+    let _ = std::fs::remove_file(&path);
+    let mut reader = csv::Reader::from_reader(text.as_bytes());
+    assert_eq!(
+        reader.headers().unwrap().iter().collect::<Vec<_>>(),
+        [
+            "timestamp", "event", "exec_id", "market", "up_price", "down_price", "up_size",
+            "down_size", "investment", "fees", "settled_value", "realized_profit",
+        ]
+    );
+    let rows: Vec<JournalRow> = reader.deserialize().collect::<Result<_, _>>().unwrap();
+    assert_eq!(rows.len(), 1);
+    let row = &rows[0];
+    assert_eq!(row.event, JournalEvent::BothFilled);
+    assert_eq!(row.exec_id, Some(exec_id));
+    assert_eq!(row.market, opportunity.market.slug);
+    assert_eq!((row.up_price, row.down_price), (Some(dec!(0.48)), Some(dec!(0.48))));
+    assert_eq!((row.up_size, row.down_size), (Some(dec!(10)), Some(dec!(10))));
+    assert_eq!(row.investment, dec!(9.60));
+    assert_eq!(row.fees, Some(dec!(0)));
+    assert_eq!(row.realized_profit, None);
+    assert!(time::OffsetDateTime::parse(
+        &row.timestamp,
+        &time::format_description::well_known::Rfc3339
+    )
+    .is_ok());
+}
+
+#[tokio::test]
+async fn maker_leg_is_journaled_at_its_resting_price() {
+    let mock = MockClob::start().await;
+    let path = std::env::temp_dir().join(format!("maker-journal-{}.csv", std::process::id()));
+    let _ = std::fs::remove_file(&path);
+    let mut config = mock.config();
+    config.state_file = String::new();
+    config.journal_csv = path.to_str().unwrap().to_string();
+    config.maker_leg = MakerLeg::Up;
+    let client = PolymarketClient::new(&config);
+    let mut executor = ArbitrageExecutor::new(&config);
+
+    let opportunity = mock_opportunity(&client, 0).await;
+    let result = executor.execute(&client, &opportunity, &config).await.unwrap();
+    let ExecutionResult::BothFilled { actual_investment, .. } = result else {
+        panic!("expected both legs filled, got {:?}", result);
+    };
+
+    let text = std::fs::read_to_string(&path).unwrap();
+    let _ = std::fs::remove_file(&path);
+    let rows: Vec<JournalRow> = csv::Reader::from_reader(text.as_bytes())
+        .deserialize()
+        .collect::<Result<_, _>>()
+        .unwrap();
+    // UP rests one tick inside the 0.48 ask
+    assert_eq!((rows[0].up_price, rows[0].down_price), (Some(dec!(0.47)), Some(dec!(0.48))));
+    assert_eq!(rows[0].investment, actual_investment);
+    assert_eq!(rows[0].investment, dec!(9.50));
+}
+
+#[tokio::test]
+async fn live_executions_carry_distinct_exec_ids() {
+    let mock = MockClob::start().await;
+    let mut config = mock.config();
+    config.cooldown_seconds = 0;
+    config.state_file = String::new();
+    let client = PolymarketClient::new(&config);
+    let mut executor = ArbitrageExecutor::new(&config);
+
+    let mut exec_ids = Vec::new();
+    for start_timestamp in [0, Market::WINDOW_SECONDS] {
+        let opportunity = mock_opportunity(&client, start_timestamp).await;
+
+        let result = executor.execute(&client, &opportunity, &config).await.unwrap();
+        let ExecutionResult::BothFilled { exec_id, .. } = result else {
+            panic!("expected both legs filled, got {:?}", result);
+        };
+        assert_eq!(result.exec_id(), Some(exec_id));
+        exec_ids.push(exec_id);
+    }
+
+    assert_ne!(exec_ids[0], exec_ids[1]);
+}
+
+#[tokio::test]
+async fn sign_dry_run_posts_no_orders() {
+    let mock = MockClob::start().await;
+    let mut config = mock.config();
+    config.state_file = String::new();
+    config.dry_run = true;
+    config.dry_run_mode = DryRunMode::Sign;
+    let client = PolymarketClient::new(&config);
+    let mut executor = ArbitrageExecutor::new(&config);
+
+    let opportunity = mock_opportunity(&client, 0).await;
+    let result = executor.execute(&client, &opportunity, &config).await.unwrap();
+
+    assert!(matches!(result, ExecutionResult::Signed { .. }), "got {:?}", result);
+    assert_eq!(mock.state.orders_posted.load(Ordering::SeqCst), 0);
+}
+
+#[tokio::test]
+async fn paired_execution_shares_one_auth_signature() {
+    let mock = MockClob::start().await;
+    let mut config = mock.config();
+    config.state_file = String::new();
+    let client = PolymarketClient::new(&config);
+    let mut executor = ArbitrageExecutor::new(&config);
+
+    let opportunity = mock_opportunity(&client, 0).await;
+    // Signatures cover a seconds timestamp, so a fresh one would differ from this
+    let cached = client.auth_headers().await.unwrap();
+    let cached = cached.iter().find(|(key, _)| key == "POLY_SIGNATURE").unwrap().1.clone();
+    tokio::time::sleep(Duration::from_millis(1100)).await;
+    let result = executor.execute(&client, &opportunity, &config).await.unwrap();
+
+    assert!(matches!(result, ExecutionResult::BothFilled { .. }), "got {:?}", result);
+    let signatures = mock.state.order_signatures.lock().unwrap().clone();
+    assert_eq!(signatures, [cached.clone(), cached]);
+}
+
+#[tokio::test]
+async fn both_filled_posts_alert_to_webhook() {
+    let mock = MockClob::start().await;
+    *mock.state.fill_fee.lock().unwrap() = Some("0.02".to_string());
+    let mut config = mock.config();
+    config.state_file = String::new();
+    config.alert_webhook_url = Some(format!("{}/webhook", mock.base_url));
+    let client = PolymarketClient::new(&config);
+    let mut executor = ArbitrageExecutor::new(&config);
+
+    let opportunity = mock_opportunity(&client, 0).await;
+    let result = executor.execute(&client, &opportunity, &config).await.unwrap();
+    let exec_id = result.exec_id().unwrap();
+
+    // Alerts are sent in the background
+    let alert = tokio::time::timeout(Duration::from_secs(5), async {
+        loop {
+            if let Some(alert) = mock.state.alerts.lock().unwrap().first().cloned() {
+                return alert;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+    })
+    .await
+    .expect("alert before timeout");
+
+    assert_eq!(alert["event"], "both_filled");
+    assert_eq!(alert["market"], "btc-updown-15m-0");
+    assert_eq!(alert["exec_id"], exec_id);
+    assert_eq!(alert["up_filled_size"], "10");
+    assert_eq!(alert["down_filled_size"], "10");
+    // 10 pairs at 0.48 + 0.48, less 0.02 in fees on each leg
+    assert_eq!(alert["investment"], "9.60");
+    assert_eq!(alert["expected_profit"], "0.36");
+}
+
+#[tokio::test]
+async fn sequential_legs_skip_down_when_up_is_rejected() {
+    for (leg_mode, expected_posts) in [(LegMode::Sequential, 1), (LegMode::Parallel, 2)] {
+        let mock = MockClob::start().await;
+        mock.state.reject_orders.store(true, Ordering::SeqCst);
+        let mut config = mock.config();
+        config.leg_mode = leg_mode;
+        config.state_file = String::new();
+        let client = PolymarketClient::new(&config);
+        let mut executor = ArbitrageExecutor::new(&config);
+
+        let opportunity = mock_opportunity(&client, 0).await;
+        let result = executor.execute(&client, &opportunity, &config).await.unwrap();
+
+        assert!(matches!(result, ExecutionResult::NeitherFilled { .. }), "got {:?}", result);
+        assert_eq!(mock.state.orders_posted.load(Ordering::SeqCst), expected_posts);
+    }
+}
+
+#[tokio::test]
+async fn balance_must_fund_each_leg_at_its_limit_price() {
+    let mock = MockClob::start().await;
+    let mut config = mock.config();
+    config.state_file = String::new();
+    // Aggressive entry lifts both limits to 0.99 on the tick, within the 0.991 target:
+    // 9.90 locked for 10 pairs
+    config.entry_price_offset_bps = dec!(-1000);
+    config.balance_margin = dec!(2.55);
+    let client = PolymarketClient::new(&config);
+    let mut executor = ArbitrageExecutor::new(&config);
+
+    let opportunity = mock_opportunity(&client, 0).await;
+    // 9.60 * 2.55 = 24.48 fits the 25 USDC balance as a total
+    assert!(opportunity.total_investment * config.balance_margin <= dec!(25));
+    let result = executor.execute(&client, &opportunity, &config).await.unwrap();
+
+    // UP at 0.49 locks 12.495, leaving 12.505 for DOWN's 12.75 at 0.50
+    match result {
+        ExecutionResult::InsufficientBalance { required, available } => {
+            assert_eq!(required, dec!(9.90) * dec!(2.55));
+            assert_eq!(available, dec!(25));
+        }
+        other => panic!("expected InsufficientBalance, got {:?}", other),
+    }
+    assert_eq!(mock.state.orders_posted.load(Ordering::SeqCst), 0);
+}
+
+#[tokio::test]
+async fn sequential_legs_fill_both_when_up_is_accepted() {
+    let mock = MockClob::start().await;
+    let mut config = mock.config();
+    config.leg_mode = LegMode::Sequential;
+    config.state_file = String::new();
+    let client = PolymarketClient::new(&config);
+    let mut executor = ArbitrageExecutor::new(&config);
+
+    let opportunity = mock_opportunity(&client, 0).await;
+    let result = executor.execute(&client, &opportunity, &config).await.unwrap();
+
+    assert!(matches!(result, ExecutionResult::BothFilled { .. }), "got {:?}", result);
+    assert_eq!(mock.state.orders_posted.load(Ordering::SeqCst), 2);
+}
+
+#[tokio::test]
+async fn crossing_post_only_leg_is_unfilled_not_failed() {
+    let mock = MockClob::start().await;
+    mock.state.cross_post_only.store(true, Ordering::SeqCst);
+    let mut config = mock.config();
+    config.maker_leg = MakerLeg::Up;
+    config.state_file = String::new();
+    let client = PolymarketClient::new(&config);
+    let mut executor = ArbitrageExecutor::new(&config);
+
+    let opportunity = mock_opportunity(&client, 0).await;
+    let result = executor.execute(&client, &opportunity, &config).await.unwrap();
+
+    // The taker DOWN leg filled on its own and is unwound like any partial fill
+    assert!(
+        matches!(result, ExecutionResult::PartialFill { filled_leg: Outcome::Down, .. }),
+        "got {:?}",
+        result
+    );
+    assert!(!executor.is_circuit_open());
+}
+
+#[tokio::test]
+async fn price_rejected_leg_retries_once_when_pair_still_pays() {
+    let mock = MockClob::start().await;
+    let path = std::env::temp_dir().join(format!("reprice-journal-{}.csv", std::process::id()));
+    let _ = std::fs::remove_file(&path);
+    let mut config = mock.config();
+    config.state_file = String::new();
+    config.journal_csv = path.to_str().unwrap().to_string();
+    config.leg_mode = LegMode::Sequential;
+    let client = PolymarketClient::new(&config);
+    let mut executor = ArbitrageExecutor::new(&config);
+    let opportunity = mock_opportunity(&client, 0).await;
+
+    // The first leg's 0.48 level is gone; 0.50 + 0.48 still clears 0.991
+    *mock.state.book_ask.lock().unwrap() = Some("0.50".to_string());
+    mock.state.price_rejections.store(1, Ordering::SeqCst);
+    let result = executor.execute(&client, &opportunity, &config).await.unwrap();
+
+    // Investment is counted at the repriced 0.50, not the detected 0.48
+    match result {
+        ExecutionResult::BothFilled { actual_investment, .. } => {
+            assert_eq!(actual_investment, dec!(9.80));
+        }
+        other => panic!("expected BothFilled, got {:?}", other),
+    }
+    let orders = mock.state.orders.lock().unwrap().clone();
+    assert_eq!(orders.len(), 3);
+    assert_eq!(orders[0]["token_id"], orders[1]["token_id"]);
+    assert_eq!(orders[1]["price"].as_str().unwrap().parse::<f64>().unwrap(), 0.5);
+
+    // The journal agrees with the repriced investment
+    let text = std::fs::read_to_string(&path).unwrap();
+    let _ = std::fs::remove_file(&path);
+    let rows: Vec<JournalRow> = csv::Reader::from_reader(text.as_bytes())
+        .deserialize()
+        .collect::<Result<_, _>>()
+        .unwrap();
+    assert_eq!((rows[0].up_price, rows[0].down_price), (Some(dec!(0.50)), Some(dec!(0.48))));
+    assert_eq!(rows[0].investment, dec!(9.80));
+}
+
+#[tokio::test]
+async fn parallel_legs_are_not_repriced() {
+    let mock = MockClob::start().await;
+    let mut config = mock.config();
+    config.state_file = String::new();
+    config.leg_mode = LegMode::Parallel;
+    let client = PolymarketClient::new(&config);
+    let mut executor = ArbitrageExecutor::new(&config);
+    let opportunity = mock_opportunity(&client, 0).await;
+
+    // 0.50 + 0.48 would still clear 0.991, but a parallel leg is not retried
+    *mock.state.book_ask.lock().unwrap() = Some("0.50".to_string());
+    mock.state.price_rejections.store(1, Ordering::SeqCst);
+    let result = executor.execute(&client, &opportunity, &config).await.unwrap();
+
+    // The rejection stands and the surviving leg is cancelled
+    assert!(matches!(result, ExecutionResult::NeitherFilled { .. }), "got {:?}", result);
+    let orders = mock.state.orders.lock().unwrap().clone();
+    let buys: Vec<_> = orders.iter().filter(|order| order["side"] == "BUY").collect();
+    assert_eq!(buys.len(), 2);
+    assert!(buys.iter().all(|order| order["price"] == "0.48"));
+}
+
+#[tokio::test]
+async fn price_rejected_leg_aborts_when_pair_no_longer_pays() {
+    let mock = MockClob::start().await;
+    let mut config = mock.config();
+    config.state_file = String::new();
+    config.leg_mode = LegMode::Sequential;
+    let client = PolymarketClient::new(&config);
+    let mut executor = ArbitrageExecutor::new(&config);
+    let opportunity = mock_opportunity(&client, 0).await;
+
+    // 0.52 + 0.48 is over the 0.991 target, so the rejection stands
+    *mock.state.book_ask.lock().unwrap() = Some("0.52".to_string());
+    mock.state.price_rejections.store(1, Ordering::SeqCst);
+    let result = executor.execute(&client, &opportunity, &config).await.unwrap();
+
+    assert!(matches!(result, ExecutionResult::NeitherFilled { .. }), "got {:?}", result);
+    assert_eq!(mock.state.orders_posted.load(Ordering::SeqCst), 1);
+}
+
+#[tokio::test]
+async fn price_rejected_leg_aborts_when_edge_falls_below_floor() {
+    let mock = MockClob::start().await;
+    let mut config = mock.config();
+    config.state_file = String::new();
+    config.leg_mode = LegMode::Sequential;
+    config.min_edge_bps = dec!(250);
+    let client = PolymarketClient::new(&config);
+    let mut executor = ArbitrageExecutor::new(&config);
+    let opportunity = mock_opportunity(&client, 0).await;
+
+    // 0.50 + 0.48 clears the 0.991 target but leaves 200 bps, under the 250 floor
+    *mock.state.book_ask.lock().unwrap() = Some("0.50".to_string());
+    mock.state.price_rejections.store(1, Ordering::SeqCst);
+    let result = executor.execute(&client, &opportunity, &config).await.unwrap();
+
+    assert!(matches!(result, ExecutionResult::NeitherFilled { .. }), "got {:?}", result);
+    assert_eq!(mock.state.orders_posted.load(Ordering::SeqCst), 1);
+}
+
+#[tokio::test]
+async fn strict_hedge_liquidates_leg_filled_without_its_pair() {
+    let mock = MockClob::start().await;
+    *mock.state.reject_token.lock().unwrap() = Some("down-token".to_string());
+    let mut config = mock.config();
+    config.state_file = String::new();
+    config.strict_hedge = true;
+    let client = PolymarketClient::new(&config);
+    let mut executor = ArbitrageExecutor::new(&config);
+
+    let opportunity = mock_opportunity(&client, 0).await;
+    let result = executor.execute(&client, &opportunity, &config).await.unwrap();
+
+    // The UP leg filled before DOWN was refused, so it is sold at once
+    assert!(
+        matches!(
+            result,
+            ExecutionResult::PartialFill { filled_leg: Outcome::Up, filled_size, .. }
+                if filled_size == dec!(10)
+        ),
+        "got {:?}",
+        result
+    );
+    let orders = mock.state.orders.lock().unwrap().clone();
+    assert_eq!(orders.len(), 3);
+    let liquidation = &orders[2];
+    assert_eq!(liquidation["token_id"], "up-token");
+    assert_eq!(liquidation["side"], "SELL");
+    assert_eq!(liquidation["order_type"], "FAK");
+    assert_eq!(liquidation["price"], "0.01");
+    assert!(executor.open_exposure.is_zero());
+    assert!(!executor.is_circuit_open());
+}
+
+#[tokio::test]
+async fn resting_unwind_is_watched_only_until_the_order_timeout() {
+    let mock = MockClob::start().await;
+    mock.state.cross_post_only.store(true, Ordering::SeqCst);
+    mock.state.rest_sells.store(true, Ordering::SeqCst);
+    let mut config = mock.config();
+    config.maker_leg = MakerLeg::Up;
+    config.state_file = String::new();
+    config.order_timeout_ms = 300;
+    config.strict_hedge_deadline_ms = 10_000;
+    let client = PolymarketClient::new(&config);
+    let mut executor = ArbitrageExecutor::new(&config);
+
+    let opportunity = mock_opportunity(&client, 0).await;
+    let started = std::time::Instant::now();
+    let result = executor.execute(&client, &opportunity, &config).await.unwrap();
+
+    // The unwind sell rests unmatched; the strict-hedge deadline does not apply to it
+    assert!(started.elapsed() < Duration::from_secs(5), "took {:?}", started.elapsed());
+    assert!(
+        matches!(
+            &result,
+            ExecutionResult::PartialFill { unwind_result: Some(unwind), .. }
+                if unwind.starts_with("Unwind order submitted")
+        ),
+        "got {:?}",
+        result
+    );
+    let orders = mock.state.orders.lock().unwrap().clone();
+    assert_eq!(orders.last().unwrap()["side"], "SELL");
+}
+
+#[tokio::test]
+async fn partial_fill_charges_fees_on_entry_and_liquidation() {
+    let mock = MockClob::start().await;
+    *mock.state.reject_token.lock().unwrap() = Some("down-token".to_string());
+    *mock.state.fill_fee.lock().unwrap() = Some("0.02".to_string());
+    let mut config = mock.config();
+    config.state_file = String::new();
+    config.strict_hedge = true;
+    let client = PolymarketClient::new(&config);
+    let mut executor = ArbitrageExecutor::new(&config);
+
+    let opportunity = mock_opportunity(&client, 0).await;
+    let result = executor.execute(&client, &opportunity, &config).await.unwrap();
+
+    assert!(matches!(result, ExecutionResult::PartialFill { .. }), "got {:?}", result);
+    // The filled UP leg and the sale that liquidated it both paid fees
+    assert_eq!(executor.stats().total_fees, dec!(0.04));
+}
+
+#[tokio::test]
+async fn failed_strict_hedge_liquidation_trips_circuit_breaker() {
+    let mock = MockClob::start().await;
+    *mock.state.reject_token.lock().unwrap() = Some("down-token".to_string());
+    mock.state.reject_sells.store(true, Ordering::SeqCst);
+    let mut config = mock.config();
+    config.state_file = String::new();
+    config.strict_hedge = true;
+    config.alert_webhook_url = Some(format!("{}/webhook", mock.base_url));
+    let client = PolymarketClient::new(&config);
+    let mut executor = ArbitrageExecutor::new(&config);
+
+    let opportunity = mock_opportunity(&client, 0).await;
+    let result = executor.execute(&client, &opportunity, &config).await.unwrap();
+
+    assert!(matches!(result, ExecutionResult::PartialFill { .. }), "got {:?}", result);
+    assert!(executor.is_circuit_open());
+    assert_eq!(executor.open_exposure, dec!(10));
+
+    // Alerts are sent in the background
+    tokio::time::timeout(Duration::from_secs(5), async {
+        loop {
+            let alerts = mock.state.alerts.lock().unwrap().clone();
+            if alerts.iter().any(|alert| alert["event"] == "circuit_breaker_tripped") {
+                return;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+    })
+    .await
+    .expect("circuit breaker alert before timeout");
+}
+
+#[tokio::test]
+async fn submit_order_returns_order_id() {
+    let mock = MockClob::start().await;
+    let client = PolymarketClient::new(&mock.config());
+
+    let order_id = submit_order(&client, &OrderParams::buy("up-token", dec!(0.48), dec!(10)))
+        .await
+        .unwrap();
+
+    assert_eq!(order_id, MOCK_ORDER_ID);
+    assert_eq!(mock.state.orders_posted.load(Ordering::SeqCst), 1);
+}
+
+#[tokio::test]
+async fn rejected_order_can_be_retried_within_dedup_window() {
+    let mock = MockClob::start().await;
+    mock.state.price_rejections.store(1, Ordering::SeqCst);
+    let mut config = mock.config();
+    config.order_dedup_window_ms = 60_000;
+    let client = PolymarketClient::new(&config);
+    let params = OrderParams::buy("up-token", dec!(0.48), dec!(10));
+
+    let rejected = submit_order(&client, &params).await;
+    assert!(matches!(rejected, Err(TradingError::PriceMoved { .. })), "got {:?}", rejected);
+    assert_eq!(submit_order(&client, &params).await.unwrap(), MOCK_ORDER_ID);
+
+    // The accepted order now blocks an identical one
+    let duplicate = submit_order(&client, &params).await;
+    assert!(matches!(duplicate, Err(TradingError::DuplicateOrder(_))), "got {:?}", duplicate);
+    assert_eq!(mock.state.orders_posted.load(Ordering::SeqCst), 2);
+}
+
+#[tokio::test]
+async fn submit_order_maps_balance_rejection() {
+    let mock = MockClob::start().await;
+    mock.state.reject_orders.store(true, Ordering::SeqCst);
+    let client = PolymarketClient::new(&mock.config());
+
+    let result = submit_order(&client, &OrderParams::buy("up-token", dec!(0.48), dec!(10))).await;
+
+    match result {
+        Err(TradingError::InsufficientFunds { reason }) => {
+            assert!(reason.contains("not enough balance"), "unexpected reason: {}", reason);
+        }
+        other => panic!("expected InsufficientFunds, got {:?}", other),
+    }
+}
+
+#[tokio::test]
+async fn rejected_order_logs_request_fields() {
+    let mock = MockClob::start().await;
+    mock.state.reject_orders.store(true, Ordering::SeqCst);
+    let client = PolymarketClient::new(&mock.config());
+    let logs = LogCapture::default();
+    let subscriber = tracing_subscriber::fmt().json().with_writer(logs.clone()).finish();
+    let _guard = tracing::subscriber::set_default(subscriber);
+
+    let params = OrderParams::buy("up-token", dec!(0.48), dec!(10));
+    assert!(submit_order(&client, &params).await.is_err());
+
+    let line = logs
+        .contents()
+        .lines()
+        .find(|line| line.contains("Order rejected by CLOB"))
+        .map(str::to_string)
+        .expect("rejection logged");
+    let entry: serde_json::Value = serde_json::from_str(&line).unwrap();
+    let fields = &entry["fields"];
+    assert_eq!(entry["level"], "ERROR");
+    assert_eq!(fields["status"], "400 Bad Request");
+    assert_eq!(fields["token_id"], "up-token");
+    assert_eq!(fields["side"], "BUY");
+    assert_eq!(fields["price"], "0.48");
+    assert_eq!(fields["size"], "10");
+    assert_eq!(fields["order_type"], "FOK");
+    assert_eq!(fields["neg_risk"], params.neg_risk);
+    assert!(fields["nonce"].is_string());
+    assert!(!line.contains("signature"), "signature leaked: {}", line);
+}
+
+#[tokio::test]
+async fn order_status_polling_waits_for_fill() {
+    let mock = MockClob::start().await;
+    mock.state.polls_until_filled.store(2, Ordering::SeqCst);
+    let client = PolymarketClient::new(&mock.config());
+
+    let state = wait_for_terminal_order(
+        &client,
+        MOCK_ORDER_ID,
+        dec!(10),
+        Duration::from_secs(5),
+        Duration::from_millis(10),
+        Duration::from_millis(250),
+    )
+    .await;
+
+    assert!(state.is_terminal);
+    assert!(state.is_filled);
+    assert_eq!(state.status, Some(OrderStatus::Filled));
+    assert_eq!(state.filled_size, Some(dec!(10)));
+    assert_eq!(mock.state.status_polls.load(Ordering::SeqCst), 3);
+}
+
+#[tokio::test]
+async fn order_status_polling_waits_out_not_found() {
+    let mock = MockClob::start().await;
+    mock.state.not_found_polls.store(2, Ordering::SeqCst);
+    let client = PolymarketClient::new(&mock.config());
+
+    assert!(matches!(
+        get_order_status(&client, MOCK_ORDER_ID).await,
+        Err(TradingError::OrderNotFound(id)) if id == MOCK_ORDER_ID
+    ));
+
+    let state = wait_for_terminal_order(
+        &client,
+        MOCK_ORDER_ID,
+        dec!(10),
+        Duration::from_secs(5),
+        Duration::from_millis(10),
+        Duration::from_millis(250),
+    )
+    .await;
+
+    assert!(state.is_filled);
+    assert_eq!(state.status, Some(OrderStatus::Filled));
+    assert_eq!(mock.state.status_polls.load(Ordering::SeqCst), 3);
+}
+
+#[tokio::test]
+async fn order_status_polling_rejects_order_that_never_appears() {
+    let mock = MockClob::start().await;
+    mock.state.not_found_polls.store(u32::MAX, Ordering::SeqCst);
+    let client = PolymarketClient::new(&mock.config());
+
+    let state = wait_for_terminal_order(
+        &client,
+        MOCK_ORDER_ID,
+        dec!(10),
+        Duration::from_secs(5),
+        Duration::from_millis(10),
+        Duration::from_millis(250),
+    )
+    .await;
+
+    assert!(state.is_terminal);
+    assert!(!state.is_filled);
+    assert_eq!(state.status, Some(OrderStatus::Rejected));
+}
+
+#[tokio::test]
+async fn order_status_polling_times_out_on_live_order() {
+    let mock = MockClob::start().await;
+    mock.state.polls_until_filled.store(u32::MAX, Ordering::SeqCst);
+    let client = PolymarketClient::new(&mock.config());
+
+    let state = wait_for_terminal_order(
+        &client,
+        MOCK_ORDER_ID,
+        dec!(10),
+        Duration::from_millis(100),
+        Duration::from_millis(10),
+        Duration::from_millis(250),
+    )
+    .await;
+
+    assert!(!state.is_terminal);
+    assert!(!state.is_filled);
+}
+
+#[tokio::test]
+async fn market_websocket_applies_canned_book_frames() {
+    let mock = MockClob::start().await;
+    let ws = Arc::new(MarketWebSocket::new(mock.ws_url.clone()));
+    let assets = vec!["up-token".to_string(), "down-token".to_string()];
+
+    let mut updates = ws.clone().run_with_reconnect(assets).await;
+    for _ in 0..2 {
+        tokio::time::timeout(Duration::from_secs(5), updates.recv())
+            .await
+            .expect("book update before timeout")
+            .expect("update channel open");
+    }
+
+    for asset in ["up-token", "down-token"] {
+        let (bids, asks) = ws.get_book(asset).unwrap().to_levels();
+        assert_eq!(bids.len(), 1);
+        assert_eq!(asks.len(), 2);
+        assert_eq!(asks[0].price, dec!(0.48));
+    }
+}